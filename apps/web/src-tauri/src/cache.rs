@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::commands::buckets::BucketStatus;
+use crate::commands::objects::ListObjectsResponse;
+use crate::db::DbManager;
+
+/// How long a cached listing page is served before S3 is asked again
+const LISTING_CACHE_TTL: Duration = Duration::from_secs(30);
+/// Max cached pages across all accounts; the least recently used page is evicted
+const LISTING_CACHE_MAX_ENTRIES: usize = 256;
+
+/// Identifies one listing page. `options` folds in every other request
+/// parameter (sort, delimiter, page size, ...) so differently shaped pages of
+/// the same prefix never share an entry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ListingCacheKey {
+    pub account_id: String,
+    pub bucket: String,
+    pub prefix: String,
+    pub continuation_token: Option<String>,
+    pub options: String,
+}
+
+#[derive(Debug)]
+struct ListingCacheEntry {
+    response: ListObjectsResponse,
+    stored_at: Instant,
+    last_used: Instant,
+}
+
+/// In-memory cache of recent `list_objects` pages so navigating back into a
+/// folder doesn't re-list it. Entries expire after a short TTL and are dropped
+/// explicitly when an upload, delete or rename touches their prefix.
+#[derive(Debug, Default)]
+pub struct ListingCache {
+    entries: Mutex<HashMap<ListingCacheKey, ListingCacheEntry>>,
+}
+
+impl ListingCache {
+    /// Return a fresh cached page, dropping it if it has expired
+    pub fn get(&self, key: &ListingCacheKey) -> Option<ListObjectsResponse> {
+        let mut entries = self.entries.lock().ok()?;
+        match entries.get_mut(key) {
+            Some(entry) if entry.stored_at.elapsed() < LISTING_CACHE_TTL => {
+                entry.last_used = Instant::now();
+                Some(entry.response.clone())
+            }
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn insert(&self, key: ListingCacheKey, response: ListObjectsResponse) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+
+        entries.retain(|_, entry| entry.stored_at.elapsed() < LISTING_CACHE_TTL);
+        if entries.len() >= LISTING_CACHE_MAX_ENTRIES && !entries.contains_key(&key) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+
+        let now = Instant::now();
+        entries.insert(
+            key,
+            ListingCacheEntry {
+                response,
+                stored_at: now,
+                last_used: now,
+            },
+        );
+    }
+
+    /// Drop every cached page that could show `path` (a key or prefix): listings
+    /// of its parent prefixes, and listings inside it when it is a folder.
+    /// Returns the number of pages removed.
+    pub fn invalidate(&self, account_id: &str, bucket: &str, path: &str) -> usize {
+        let Ok(mut entries) = self.entries.lock() else {
+            return 0;
+        };
+
+        let before = entries.len();
+        entries.retain(|key, _| {
+            !(key.account_id == account_id
+                && key.bucket == bucket
+                && (path.starts_with(&key.prefix) || key.prefix.starts_with(path)))
+        });
+        before - entries.len()
+    }
+}
+
+/// How long a bucket check from `head_bucket` is trusted
+const BUCKET_STATUS_TTL: Duration = Duration::from_secs(60);
+
+/// Recent bucket checks keyed by (account_id, bucket), so expensive commands
+/// can fail fast on a missing or forbidden bucket without a HeadBucket each time
+#[derive(Debug, Default)]
+pub struct BucketStatusCache {
+    entries: Mutex<HashMap<(String, String), (BucketStatus, Instant)>>,
+}
+
+impl BucketStatusCache {
+    pub fn get(&self, account_id: &str, bucket: &str) -> Option<BucketStatus> {
+        let entries = self.entries.lock().ok()?;
+        entries
+            .get(&(account_id.to_string(), bucket.to_string()))
+            .filter(|(_, checked_at)| checked_at.elapsed() < BUCKET_STATUS_TTL)
+            .map(|(status, _)| status.clone())
+    }
+
+    pub fn insert(&self, account_id: &str, bucket: &str, status: BucketStatus) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.retain(|_, (_, checked_at)| checked_at.elapsed() < BUCKET_STATUS_TTL);
+            entries.insert(
+                (account_id.to_string(), bucket.to_string()),
+                (status, Instant::now()),
+            );
+        }
+    }
+}
+
+/// How long a public base URL check is trusted
+const PUBLIC_URL_STATUS_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Whether an account's public base URL serves objects anonymously, keyed by
+/// (account_id, base_url), so previews and thumbnails don't probe it each time
+#[derive(Debug, Default)]
+pub struct PublicUrlCache {
+    entries: Mutex<HashMap<(String, String), (bool, Instant)>>,
+}
+
+impl PublicUrlCache {
+    pub fn get(&self, account_id: &str, base_url: &str) -> Option<bool> {
+        let entries = self.entries.lock().ok()?;
+        entries
+            .get(&(account_id.to_string(), base_url.to_string()))
+            .filter(|(_, checked_at)| checked_at.elapsed() < PUBLIC_URL_STATUS_TTL)
+            .map(|(readable, _)| *readable)
+    }
+
+    pub fn insert(&self, account_id: &str, base_url: &str, readable: bool) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.retain(|_, (_, checked_at)| checked_at.elapsed() < PUBLIC_URL_STATUS_TTL);
+            entries.insert(
+                (account_id.to_string(), base_url.to_string()),
+                (readable, Instant::now()),
+            );
+        }
+    }
+}
+
+/// Drop cached data a mutating command may have made stale: listings showing
+/// any of `prefixes` or `keys`, and thumbnails of the affected objects.
+/// Every command that writes, moves or deletes objects should call this.
+pub fn invalidate_for(
+    listing_cache: &ListingCache,
+    db: &DbManager,
+    account_id: &str,
+    bucket: &str,
+    prefixes: &[String],
+    keys: &[String],
+) {
+    for path in prefixes.iter().chain(keys) {
+        listing_cache.invalidate(account_id, bucket, path);
+    }
+
+    if let Err(e) = db.invalidate_cached_thumbnails(account_id, bucket, prefixes, keys) {
+        log::warn!("Failed to invalidate thumbnails in {}: {}", bucket, e);
+    }
+}
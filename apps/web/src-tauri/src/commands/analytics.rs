@@ -1,11 +1,31 @@
+use crate::cache::BucketStatusCache;
+use crate::commands::buckets::ensure_bucket_accessible;
+use crate::commands::objects::{flat_listing, list_page};
 use crate::credentials::CredentialsManager;
+use crate::db::analytics::{AnalyticsCachedObject, StorageClassTotal};
+use crate::db::DbManager;
 use crate::error::AppError;
+use crate::progress::ProgressThrottle;
 use crate::s3::client::S3ClientManager;
-use chrono::Utc;
-use serde::Serialize;
-use std::collections::HashMap;
+use chrono::{TimeZone, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::atomic::{self, AtomicUsize};
+use std::sync::Arc;
+use std::time::Instant;
 use tauri::{AppHandle, Emitter, State};
 
+/// Sub-prefixes listed at once by a parallel scan
+const ANALYTICS_FANOUT_CONCURRENCY: usize = 8;
+/// Below this many top-level folders a parallel scan lists sequentially, since
+/// the extra delimiter listing would cost more than it saves
+const ANALYTICS_FANOUT_MIN_PREFIXES: usize = 4;
+/// Listings with more objects than this aren't cached, to bound memory and
+/// database size on very large buckets
+const ANALYTICS_CACHE_MAX_OBJECTS: usize = 200_000;
+
 /// Analytics progress event sent to frontend
 #[derive(Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -63,6 +83,10 @@ pub struct BucketAnalytics {
     pub by_storage_class: Vec<StorageClassStats>,
     pub largest_files: Vec<LargeFile>,
     pub calculated_at: String,
+    /// Whether the figures were computed from a cached listing instead of a fresh one
+    pub from_cache: bool,
+    /// Time spent listing (and tallying) objects; None when served from the cache
+    pub listing_ms: Option<i64>,
 }
 
 /// Categorize a file extension into a content type category
@@ -109,205 +133,818 @@ fn extract_top_folder(key: &str, base_prefix: Option<&str>) -> Option<String> {
     None
 }
 
-/// Maintains a sorted list of the N largest files
+/// Orders large files by size, then key, so ties break deterministically
+struct BySize(LargeFile);
+
+impl PartialEq for BySize {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for BySize {}
+
+impl PartialOrd for BySize {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BySize {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .size
+            .cmp(&other.0.size)
+            .then_with(|| other.0.key.cmp(&self.0.key))
+    }
+}
+
+/// Keeps the N largest files in a bounded min-heap: the smallest kept file
+/// sits on top, so each add is O(log n). Trackers filled independently (e.g.
+/// one per worker) can be combined with `merge`.
 struct TopNTracker {
-    files: Vec<LargeFile>,
+    heap: BinaryHeap<Reverse<BySize>>,
     capacity: usize,
 }
 
 impl TopNTracker {
     fn new(capacity: usize) -> Self {
         Self {
-            files: Vec::with_capacity(capacity + 1),
+            heap: BinaryHeap::with_capacity(capacity + 1),
             capacity,
         }
     }
 
     fn add(&mut self, file: LargeFile) {
-        // Find insertion position (sorted by size descending)
-        let pos = self.files.iter().position(|f| f.size < file.size).unwrap_or(self.files.len());
-
-        // Only insert if within capacity or larger than smallest
-        if pos < self.capacity {
-            self.files.insert(pos, file);
-            if self.files.len() > self.capacity {
-                self.files.pop();
-            }
+        if self.capacity == 0 {
+            return;
+        }
+        let entry = Reverse(BySize(file));
+        if self.heap.len() < self.capacity {
+            self.heap.push(entry);
+        } else if self.heap.peek().map_or(false, |smallest| entry < *smallest) {
+            // Reverse flips the order: `entry < smallest` means the new file is larger
+            self.heap.pop();
+            self.heap.push(entry);
+        }
+    }
+
+    /// Fold another tracker's files into this one, keeping this tracker's capacity
+    fn merge(&mut self, other: TopNTracker) {
+        for Reverse(BySize(file)) in other.heap {
+            self.add(file);
         }
     }
 
+    /// The kept files, largest first
     fn into_vec(self) -> Vec<LargeFile> {
-        self.files
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(BySize(file))| file)
+            .collect()
     }
 }
 
-#[tauri::command(rename_all = "camelCase")]
-pub async fn get_bucket_analytics(
-    app: AppHandle,
-    credentials: State<'_, CredentialsManager>,
-    s3_clients: State<'_, S3ClientManager>,
-    account_id: String,
-    bucket: String,
-    prefix: Option<String>,
-    top_n_largest: Option<usize>,
-    top_n_folders: Option<usize>,
-) -> Result<BucketAnalytics, AppError> {
-    let account = credentials.get_account(&account_id)?;
-    let secret = credentials.get_secret_key(&account_id)?;
+/// Running totals for an analytics pass over a prefix. Progress events are
+/// only sent when an app handle is attached.
+struct AnalyticsAccumulator<'a> {
+    app: Option<&'a AppHandle>,
+    base_prefix: &'a str,
+    total_size: i64,
+    total_objects: usize,
+    folder_stats: HashMap<String, (i64, usize)>, // prefix -> (size, count)
+    content_type_stats: HashMap<&'static str, (i64, usize)>,
+    storage_class_stats: HashMap<String, (i64, usize)>,
+    largest_tracker: TopNTracker,
+    /// Objects seen across this accumulator and any forked from it, for
+    /// progress and the cache size limit
+    processed: Arc<AtomicUsize>,
+    progress: ProgressThrottle,
+}
 
-    let client = s3_clients
-        .get_or_create_client(
-            &account_id,
-            &account.endpoint,
-            &account.access_key_id,
-            &secret,
-            account.provider_type,
-            account.region.as_deref(),
-        )
-        .await?;
+impl<'a> AnalyticsAccumulator<'a> {
+    fn new(app: Option<&'a AppHandle>, base_prefix: &'a str, top_n_largest: usize) -> Self {
+        Self {
+            app,
+            base_prefix,
+            total_size: 0,
+            total_objects: 0,
+            folder_stats: HashMap::new(),
+            content_type_stats: HashMap::new(),
+            storage_class_stats: HashMap::new(),
+            largest_tracker: TopNTracker::new(top_n_largest),
+            processed: Arc::new(AtomicUsize::new(0)),
+            progress: ProgressThrottle::new(),
+        }
+    }
 
-    let top_n_largest = top_n_largest.unwrap_or(20);
-    let top_n_folders = top_n_folders.unwrap_or(10);
+    /// An empty accumulator for one sub-prefix of a parallel scan, sharing this
+    /// one's base prefix, top-N size and progress count
+    fn fork(&self) -> Self {
+        Self {
+            app: self.app,
+            base_prefix: self.base_prefix,
+            total_size: 0,
+            total_objects: 0,
+            folder_stats: HashMap::new(),
+            content_type_stats: HashMap::new(),
+            storage_class_stats: HashMap::new(),
+            largest_tracker: TopNTracker::new(self.largest_tracker.capacity),
+            processed: Arc::clone(&self.processed),
+            progress: ProgressThrottle::new(),
+        }
+    }
+
+    /// Fold a forked accumulator's figures into this one
+    fn merge(&mut self, other: AnalyticsAccumulator<'_>) {
+        self.total_size += other.total_size;
+        self.total_objects += other.total_objects;
+        for (prefix, (size, count)) in other.folder_stats {
+            let entry = self.folder_stats.entry(prefix).or_insert((0, 0));
+            entry.0 += size;
+            entry.1 += count;
+        }
+        for (category, (size, count)) in other.content_type_stats {
+            let entry = self.content_type_stats.entry(category).or_insert((0, 0));
+            entry.0 += size;
+            entry.1 += count;
+        }
+        for (storage_class, (size, count)) in other.storage_class_stats {
+            let entry = self.storage_class_stats.entry(storage_class).or_insert((0, 0));
+            entry.0 += size;
+            entry.1 += count;
+        }
+        self.largest_tracker.merge(other.largest_tracker);
+    }
 
-    // Accumulators
-    let mut total_size: i64 = 0;
-    let mut total_objects: usize = 0;
-    let mut folder_stats: HashMap<String, (i64, usize)> = HashMap::new(); // prefix -> (size, count)
-    let mut content_type_stats: HashMap<&str, (i64, usize)> = HashMap::new();
-    let mut storage_class_stats: HashMap<String, (i64, usize)> = HashMap::new();
-    let mut largest_tracker = TopNTracker::new(top_n_largest);
+    fn add(&mut self, object: &AnalyticsCachedObject) {
+        let key = object.key.as_str();
+        let size = object.size;
 
-    let mut continuation_token: Option<String> = None;
-    let prefix_ref = prefix.as_deref();
+        // Update totals
+        self.total_size += size;
+        self.total_objects += 1;
 
-    loop {
-        let mut request = client
-            .list_objects_v2()
-            .bucket(&bucket);
+        // Update folder stats
+        if let Some(folder_prefix) = extract_top_folder(key, Some(self.base_prefix)) {
+            let entry = self.folder_stats.entry(folder_prefix).or_insert((0, 0));
+            entry.0 += size;
+            entry.1 += 1;
+        }
+
+        // Update content type stats
+        let category = categorize_by_extension(key);
+        let entry = self.content_type_stats.entry(category).or_insert((0, 0));
+        entry.0 += size;
+        entry.1 += 1;
+
+        // Update storage class stats
+        let storage_class_key = object
+            .storage_class
+            .clone()
+            .unwrap_or_else(|| "STANDARD".to_string());
+        let entry = self.storage_class_stats.entry(storage_class_key).or_insert((0, 0));
+        entry.0 += size;
+        entry.1 += 1;
+
+        // Track large files
+        self.largest_tracker.add(LargeFile {
+            key: key.to_string(),
+            size,
+            last_modified: object.last_modified.clone(),
+            storage_class: object.storage_class.clone(),
+        });
+
+        // Emit progress, coalesced to a steady cadence
+        let processed = self.processed.fetch_add(1, atomic::Ordering::Relaxed) + 1;
+        if let Some(app) = self.app.filter(|_| self.progress.ready(false)) {
+            let _ = app.emit(
+                "analytics-progress",
+                AnalyticsProgress {
+                    objects_processed: processed,
+                    current_prefix: key.rsplit('/').nth(1).unwrap_or("").to_string(),
+                },
+            );
+        }
+    }
 
-        // No delimiter - flat listing to get all objects
-        if let Some(ref p) = prefix {
-            request = request.prefix(p);
+    fn finish(
+        mut self,
+        top_n_folders: usize,
+        calculated_at: String,
+        from_cache: bool,
+        listing_ms: Option<i64>,
+    ) -> BucketAnalytics {
+        // Always report the final count, even if the last object fell inside
+        // the throttle window
+        if let Some(app) = self.app.filter(|_| self.progress.ready(true)) {
+            let _ = app.emit(
+                "analytics-progress",
+                AnalyticsProgress {
+                    objects_processed: self.processed.load(atomic::Ordering::Relaxed),
+                    current_prefix: String::new(),
+                },
+            );
         }
 
-        if let Some(token) = &continuation_token {
-            request = request.continuation_token(token);
+        // Convert folder stats to sorted vec (top N by size)
+        let mut folders: Vec<FolderStats> = self
+            .folder_stats
+            .into_iter()
+            .map(|(prefix, (size, count))| {
+                let name = prefix
+                    .trim_end_matches('/')
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or(&prefix)
+                    .to_string();
+                FolderStats {
+                    prefix,
+                    name,
+                    size,
+                    object_count: count,
+                }
+            })
+            .collect();
+        folders.sort_by(|a, b| b.size.cmp(&a.size));
+        folders.truncate(top_n_folders);
+
+        // Convert content type stats to sorted vec
+        let mut by_content_type: Vec<ContentTypeStats> = self
+            .content_type_stats
+            .into_iter()
+            .map(|(content_type, (size, count))| ContentTypeStats {
+                content_type: content_type.to_string(),
+                size,
+                object_count: count,
+            })
+            .collect();
+        by_content_type.sort_by(|a, b| b.size.cmp(&a.size));
+
+        // Convert storage class stats to sorted vec
+        let mut by_storage_class: Vec<StorageClassStats> = self
+            .storage_class_stats
+            .into_iter()
+            .map(|(storage_class, (size, count))| StorageClassStats {
+                storage_class,
+                size,
+                object_count: count,
+            })
+            .collect();
+        by_storage_class.sort_by(|a, b| b.size.cmp(&a.size));
+
+        BucketAnalytics {
+            total_size: self.total_size,
+            total_objects: self.total_objects,
+            folders,
+            by_content_type,
+            by_storage_class,
+            largest_files: self.largest_tracker.into_vec(),
+            calculated_at,
+            from_cache,
+            listing_ms,
         }
+    }
+}
 
-        let response = request.send().await?;
+/// Convert a listed object, skipping folder placeholders (keys ending with /)
+fn listed_object(obj: &aws_sdk_s3::types::Object) -> Option<AnalyticsCachedObject> {
+    let key = obj.key().filter(|k| !k.ends_with('/'))?;
+    Some(AnalyticsCachedObject {
+        key: key.to_string(),
+        size: obj.size().unwrap_or(0),
+        last_modified: obj.last_modified().map(|d| d.to_string()),
+        storage_class: obj.storage_class().map(|s| s.as_str().to_string()),
+    })
+}
 
-        for obj in response.contents() {
-            let key = match obj.key() {
-                Some(k) => k,
-                None => continue,
-            };
+/// Flat-list every object under `prefix` into the accumulator. The objects are
+/// also returned for the cache, or `None` once the scan (counting any parallel
+/// workers) has passed `ANALYTICS_CACHE_MAX_OBJECTS`.
+async fn list_into(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    prefix: &str,
+    accumulator: &mut AnalyticsAccumulator<'_>,
+) -> Result<Option<Vec<AnalyticsCachedObject>>, AppError> {
+    let mut objects = Some(Vec::new());
+    let mut continuation_token: Option<String> = None;
 
-            // Skip folder placeholders (keys ending with /)
-            if key.ends_with('/') {
-                continue;
+    // No delimiter - flat listing to get all objects
+    let listing = flat_listing(client, bucket, Some(prefix));
+    loop {
+        let (response, next_token) = list_page(&listing, continuation_token.as_deref()).await?;
+
+        for object in response.contents().iter().filter_map(listed_object) {
+            accumulator.add(&object);
+            if accumulator.processed.load(atomic::Ordering::Relaxed) > ANALYTICS_CACHE_MAX_OBJECTS {
+                objects = None;
+            } else if let Some(objects) = objects.as_mut() {
+                objects.push(object);
             }
+        }
 
-            let size = obj.size().unwrap_or(0);
-            let storage_class = obj.storage_class().map(|s| s.as_str().to_string());
+        // Check for more pages
+        match next_token {
+            Some(token) => continuation_token = Some(token),
+            None => break,
+        }
+    }
 
-            // Update totals
-            total_size += size;
-            total_objects += 1;
+    Ok(objects)
+}
 
-            // Update folder stats
-            if let Some(folder_prefix) = extract_top_folder(key, prefix_ref) {
-                let entry = folder_stats.entry(folder_prefix).or_insert((0, 0));
-                entry.0 += size;
-                entry.1 += 1;
-            }
+/// List the folders and loose objects directly under `prefix`
+async fn list_top_level(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    prefix: &str,
+) -> Result<(Vec<String>, Vec<AnalyticsCachedObject>), AppError> {
+    let mut sub_prefixes = Vec::new();
+    let mut objects = Vec::new();
+    let mut continuation_token: Option<String> = None;
 
-            // Update content type stats
-            let category = categorize_by_extension(key);
-            let entry = content_type_stats.entry(category).or_insert((0, 0));
-            entry.0 += size;
-            entry.1 += 1;
+    let listing = flat_listing(client, bucket, Some(prefix)).delimiter("/");
+    loop {
+        let (response, next_token) = list_page(&listing, continuation_token.as_deref()).await?;
+
+        sub_prefixes.extend(
+            response
+                .common_prefixes()
+                .iter()
+                .filter_map(|p| p.prefix().map(str::to_string)),
+        );
+        objects.extend(response.contents().iter().filter_map(listed_object));
+
+        match next_token {
+            Some(token) => continuation_token = Some(token),
+            None => break,
+        }
+    }
 
-            // Update storage class stats
-            let storage_class_key = storage_class.clone().unwrap_or_else(|| "STANDARD".to_string());
-            let entry = storage_class_stats.entry(storage_class_key).or_insert((0, 0));
-            entry.0 += size;
-            entry.1 += 1;
+    Ok((sub_prefixes, objects))
+}
 
-            // Track large files
-            largest_tracker.add(LargeFile {
-                key: key.to_string(),
-                size,
-                last_modified: obj.last_modified().map(|d| d.to_string()),
-                storage_class,
-            });
+/// List every object under `prefix`, feeding it to the accumulator, and cache
+/// the listing (unless it's very large) so drill-downs into its sub-folders
+/// can skip S3.
+///
+/// With `parallel`, the top-level folders are listed first and then scanned
+/// concurrently, each into its own accumulator merged back at the end. A
+/// prefix with only a few folders is still listed sequentially.
+///
+/// Returns the time spent listing, in milliseconds.
+async fn scan_and_cache(
+    client: &aws_sdk_s3::Client,
+    db: &DbManager,
+    account_id: &str,
+    bucket: &str,
+    prefix: &str,
+    parallel: bool,
+    accumulator: &mut AnalyticsAccumulator<'_>,
+) -> Result<i64, AppError> {
+    let listing_started = Instant::now();
+    let top_level = if parallel {
+        Some(list_top_level(client, bucket, prefix).await?)
+    } else {
+        None
+    };
 
-            // Emit progress every 1000 objects
-            if total_objects % 1000 == 0 {
-                let _ = app.emit("analytics-progress", AnalyticsProgress {
-                    objects_processed: total_objects,
-                    current_prefix: key.rsplit('/').nth(1).unwrap_or("").to_string(),
+    let objects = match top_level {
+        Some((sub_prefixes, loose_objects))
+            if sub_prefixes.len() >= ANALYTICS_FANOUT_MIN_PREFIXES =>
+        {
+            for object in &loose_objects {
+                accumulator.add(object);
+            }
+
+            // try_collect stops at the first failed sub-prefix instead of
+            // waiting for the rest of the scan
+            let forked = &*accumulator;
+            let scanned: Vec<_> = stream::iter(sub_prefixes)
+                .map(|sub_prefix| {
+                    let mut sub_accumulator = forked.fork();
+                    async move {
+                        let objects =
+                            list_into(client, bucket, &sub_prefix, &mut sub_accumulator).await?;
+                        Ok::<_, AppError>((sub_accumulator, objects))
+                    }
+                })
+                .buffer_unordered(ANALYTICS_FANOUT_CONCURRENCY)
+                .try_collect()
+                .await?;
+
+            let mut objects = Some(loose_objects);
+            for (sub_accumulator, sub_objects) in scanned {
+                accumulator.merge(sub_accumulator);
+                objects = objects.zip(sub_objects).map(|(mut objects, sub_objects)| {
+                    objects.extend(sub_objects);
+                    objects
                 });
             }
+            objects
         }
+        _ => list_into(client, bucket, prefix, accumulator).await?,
+    };
+    let listing_ms = listing_started.elapsed().as_millis() as i64;
 
-        // Check for more pages
-        if response.is_truncated() == Some(true) {
-            continuation_token = response.next_continuation_token().map(|s| s.to_string());
-        } else {
-            break;
+    // The figures are already computed; a cache write failure only costs a re-list later
+    match objects.filter(|objects| objects.len() <= ANALYTICS_CACHE_MAX_OBJECTS) {
+        Some(objects) => {
+            if let Err(e) = db.save_analytics_cache(account_id, bucket, prefix, &objects) {
+                log::warn!("Failed to cache analytics listing for {}/{}: {}", bucket, prefix, e);
+            }
         }
+        None => log::info!(
+            "Not caching analytics listing for {}/{}: over {} objects",
+            bucket,
+            prefix,
+            ANALYTICS_CACHE_MAX_OBJECTS
+        ),
     }
 
-    // Convert folder stats to sorted vec (top N by size)
-    let mut folders: Vec<FolderStats> = folder_stats
-        .into_iter()
-        .map(|(prefix, (size, count))| {
-            let name = prefix
-                .trim_end_matches('/')
-                .rsplit('/')
-                .next()
-                .unwrap_or(&prefix)
-                .to_string();
-            FolderStats {
-                prefix,
-                name,
-                size,
-                object_count: count,
-            }
-        })
-        .collect();
-    folders.sort_by(|a, b| b.size.cmp(&a.size));
-    folders.truncate(top_n_folders);
+    // Whole-bucket scans also keep their storage class breakdown for cost estimates
+    if prefix.is_empty() {
+        let totals: Vec<StorageClassTotal> = accumulator
+            .storage_class_stats
+            .iter()
+            .map(|(storage_class, (size, count))| StorageClassTotal {
+                storage_class: storage_class.clone(),
+                size: *size,
+                object_count: *count as i64,
+            })
+            .collect();
+        if let Err(e) = db.save_storage_class_totals(account_id, bucket, &totals) {
+            log::warn!("Failed to save storage class totals for {}: {}", bucket, e);
+        }
+    }
 
-    // Convert content type stats to sorted vec
-    let mut by_content_type: Vec<ContentTypeStats> = content_type_stats
-        .into_iter()
-        .map(|(content_type, (size, count))| ContentTypeStats {
-            content_type: content_type.to_string(),
-            size,
-            object_count: count,
-        })
+    Ok(listing_ms)
+}
+
+/// Get an S3 client for analytics, failing fast on a missing or forbidden bucket
+async fn analytics_client(
+    credentials: &CredentialsManager,
+    s3_clients: &S3ClientManager,
+    bucket_status_cache: &BucketStatusCache,
+    account_id: &str,
+    bucket: &str,
+) -> Result<std::sync::Arc<aws_sdk_s3::Client>, AppError> {
+    let account = credentials.get_account(account_id)?;
+    let secret = credentials.get_secret_key(account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    // Fail fast on a missing or forbidden bucket instead of mid-listing
+    ensure_bucket_accessible(&client, bucket_status_cache, account_id, bucket).await?;
+
+    Ok(client)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_bucket_analytics(
+    app: AppHandle,
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    bucket_status_cache: State<'_, BucketStatusCache>,
+    db: State<'_, DbManager>,
+    account_id: String,
+    bucket: String,
+    prefix: Option<String>,
+    top_n_largest: Option<usize>,
+    top_n_folders: Option<usize>,
+    parallel: Option<bool>,
+) -> Result<BucketAnalytics, AppError> {
+    let client =
+        analytics_client(&credentials, &s3_clients, &bucket_status_cache, &account_id, &bucket)
+            .await?;
+
+    let prefix = prefix.unwrap_or_default();
+    let mut accumulator = AnalyticsAccumulator::new(Some(&app), &prefix, top_n_largest.unwrap_or(20));
+    let listing_ms = scan_and_cache(
+        &client,
+        &db,
+        &account_id,
+        &bucket,
+        &prefix,
+        parallel.unwrap_or(false),
+        &mut accumulator,
+    )
+    .await?;
+
+    Ok(accumulator.finish(
+        top_n_folders.unwrap_or(10),
+        Utc::now().to_rfc3339(),
+        false,
+        Some(listing_ms),
+    ))
+}
+
+/// Analytics for a sub-folder. If this prefix or one of its parents was
+/// scanned recently, the cached listing is filtered in memory instead of
+/// listing the folder again; `refresh` forces a new listing.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_subfolder_analytics(
+    app: AppHandle,
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    bucket_status_cache: State<'_, BucketStatusCache>,
+    db: State<'_, DbManager>,
+    account_id: String,
+    bucket: String,
+    prefix: String,
+    top_n_largest: Option<usize>,
+    top_n_folders: Option<usize>,
+    refresh: Option<bool>,
+    parallel: Option<bool>,
+) -> Result<BucketAnalytics, AppError> {
+    let top_n_largest = top_n_largest.unwrap_or(20);
+    let top_n_folders = top_n_folders.unwrap_or(10);
+
+    let cached = if refresh.unwrap_or(false) {
+        None
+    } else {
+        db.find_analytics_cache(&account_id, &bucket, &prefix)?
+    };
+
+    if let Some(entry) = cached {
+        log::debug!(
+            "Reusing analytics listing of '{}' for '{}' in {}",
+            entry.prefix,
+            prefix,
+            bucket
+        );
+        let mut accumulator = AnalyticsAccumulator::new(Some(&app), &prefix, top_n_largest);
+        for object in db.get_analytics_cache_objects(entry.id, &prefix)? {
+            accumulator.add(&object);
+        }
+        let calculated_at = Utc
+            .timestamp_opt(entry.cached_at, 0)
+            .single()
+            .unwrap_or_else(Utc::now)
+            .to_rfc3339();
+        return Ok(accumulator.finish(top_n_folders, calculated_at, true, None));
+    }
+
+    let client =
+        analytics_client(&credentials, &s3_clients, &bucket_status_cache, &account_id, &bucket)
+            .await?;
+
+    let mut accumulator = AnalyticsAccumulator::new(Some(&app), &prefix, top_n_largest);
+    let listing_ms = scan_and_cache(
+        &client,
+        &db,
+        &account_id,
+        &bucket,
+        &prefix,
+        parallel.unwrap_or(false),
+        &mut accumulator,
+    )
+    .await?;
+
+    Ok(accumulator.finish(
+        top_n_folders,
+        Utc::now().to_rfc3339(),
+        false,
+        Some(listing_ms),
+    ))
+}
+
+/// Bytes per GB as providers bill storage
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Storage prices for a cost estimate
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoragePricing {
+    /// Price per GB-month by storage class; replaces the provider default for that class
+    #[serde(default)]
+    pub per_gb_month: HashMap<String, f64>,
+    /// Price for storage classes with no entry of their own
+    pub fallback_per_gb_month: Option<f64>,
+}
+
+/// Estimated cost of one storage class
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageClassCost {
+    pub storage_class: String,
+    pub size: i64,
+    pub object_count: usize,
+    /// None when no price is known; the class then adds nothing to the total
+    pub price_per_gb_month: Option<f64>,
+    pub monthly_cost: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageCostEstimate {
+    pub total_monthly_cost: f64,
+    pub total_size: i64,
+    pub by_storage_class: Vec<StorageClassCost>,
+    /// Buckets the estimate covers (those with a whole-bucket analytics scan)
+    pub buckets: Vec<String>,
+    /// When the oldest scan used was taken
+    pub calculated_at: String,
+}
+
+/// Estimate the monthly storage cost of a bucket, or of every bucket in an
+/// account, from the storage class breakdown saved by earlier whole-bucket
+/// analytics scans; nothing is listed here. Prices default to the provider's
+/// list prices (USD) and can be overridden per storage class. Request, egress
+/// and minimum-duration charges and free tiers aren't included.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn estimate_storage_cost(
+    credentials: State<'_, CredentialsManager>,
+    db: State<'_, DbManager>,
+    account_id: String,
+    bucket: Option<String>,
+    provider_pricing: Option<StoragePricing>,
+) -> Result<StorageCostEstimate, AppError> {
+    let account = credentials.get_account(&account_id)?;
+    let scans = db.get_storage_class_totals(&account_id, bucket.as_deref())?;
+    if scans.is_empty() {
+        return Err(AppError::NotFound(match &bucket {
+            Some(bucket) => format!("No analytics for {}; run bucket analytics first", bucket),
+            None => "No analytics for this account; run bucket analytics first".to_string(),
+        }));
+    }
+
+    let pricing = provider_pricing.unwrap_or_default();
+    let mut prices: HashMap<String, f64> = account
+        .provider_type
+        .default_storage_pricing()
+        .iter()
+        .map(|(class, price)| (class.to_string(), *price))
         .collect();
-    by_content_type.sort_by(|a, b| b.size.cmp(&a.size));
+    prices.extend(pricing.per_gb_month);
+
+    let mut by_class: HashMap<String, (i64, usize)> = HashMap::new();
+    let mut buckets: Vec<String> = Vec::new();
+    let mut oldest = i64::MAX;
+    for scan in scans {
+        for total in scan.storage_classes {
+            let entry = by_class.entry(total.storage_class).or_insert((0, 0));
+            entry.0 += total.size;
+            entry.1 += total.object_count as usize;
+        }
+        buckets.push(scan.bucket);
+        oldest = oldest.min(scan.calculated_at);
+    }
 
-    // Convert storage class stats to sorted vec
-    let mut by_storage_class: Vec<StorageClassStats> = storage_class_stats
+    let mut by_storage_class: Vec<StorageClassCost> = by_class
         .into_iter()
-        .map(|(storage_class, (size, count))| StorageClassStats {
-            storage_class,
-            size,
-            object_count: count,
+        .map(|(storage_class, (size, object_count))| {
+            let price = prices
+                .get(&storage_class)
+                .copied()
+                .or(pricing.fallback_per_gb_month);
+            StorageClassCost {
+                monthly_cost: price.map_or(0.0, |price| size as f64 / BYTES_PER_GB * price),
+                storage_class,
+                size,
+                object_count,
+                price_per_gb_month: price,
+            }
         })
         .collect();
-    by_storage_class.sort_by(|a, b| b.size.cmp(&a.size));
+    by_storage_class.sort_by(|a, b| b.monthly_cost.total_cmp(&a.monthly_cost));
 
-    Ok(BucketAnalytics {
-        total_size,
-        total_objects,
-        folders,
-        by_content_type,
+    Ok(StorageCostEstimate {
+        total_monthly_cost: by_storage_class.iter().map(|c| c.monthly_cost).sum(),
+        total_size: by_storage_class.iter().map(|c| c.size).sum(),
         by_storage_class,
-        largest_files: largest_tracker.into_vec(),
-        calculated_at: Utc::now().to_rfc3339(),
+        buckets,
+        calculated_at: Utc
+            .timestamp_opt(oldest, 0)
+            .single()
+            .unwrap_or_else(Utc::now)
+            .to_rfc3339(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_objects() -> Vec<AnalyticsCachedObject> {
+        let folders = ["photos", "videos", "logs", "archive", "docs"];
+        let extensions = ["jpg", "mp4", "txt", "zip", "json", "bin"];
+        let storage_classes = [None, Some("STANDARD_IA"), Some("GLACIER")];
+
+        let mut objects: Vec<AnalyticsCachedObject> = (0..300)
+            .map(|i| AnalyticsCachedObject {
+                key: format!(
+                    "data/{}/nested/file{}.{}",
+                    folders[i % folders.len()],
+                    i,
+                    extensions[i % extensions.len()]
+                ),
+                // Distinct sizes keep the top-N and sort orders unambiguous
+                size: ((i * 37) % 101 + 1) as i64 * 1000 + i as i64,
+                last_modified: None,
+                storage_class: storage_classes[i % storage_classes.len()].map(str::to_string),
+            })
+            .collect();
+        objects.push(AnalyticsCachedObject {
+            key: "data/loose.md".to_string(),
+            size: 5,
+            last_modified: None,
+            storage_class: None,
+        });
+        objects
+    }
+
+    type Summary = (
+        i64,
+        usize,
+        Vec<(String, i64, usize)>,
+        Vec<(String, i64, usize)>,
+        Vec<(String, i64, usize)>,
+        Vec<(String, i64)>,
+    );
+
+    fn summarize(analytics: BucketAnalytics) -> Summary {
+        (
+            analytics.total_size,
+            analytics.total_objects,
+            analytics
+                .folders
+                .into_iter()
+                .map(|f| (f.prefix, f.size, f.object_count))
+                .collect(),
+            analytics
+                .by_content_type
+                .into_iter()
+                .map(|c| (c.content_type, c.size, c.object_count))
+                .collect(),
+            analytics
+                .by_storage_class
+                .into_iter()
+                .map(|c| (c.storage_class, c.size, c.object_count))
+                .collect(),
+            analytics
+                .largest_files
+                .into_iter()
+                .map(|f| (f.key, f.size))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn forked_and_merged_totals_match_a_sequential_pass() {
+        let prefix = "data/";
+        let objects = sample_objects();
+
+        let mut sequential = AnalyticsAccumulator::new(None, prefix, 7);
+        for object in &objects {
+            sequential.add(object);
+        }
+
+        // Mirror a parallel scan: loose objects go to the root accumulator and
+        // each top-level folder to its own fork
+        let mut parallel = AnalyticsAccumulator::new(None, prefix, 7);
+        let mut by_folder: HashMap<String, Vec<&AnalyticsCachedObject>> = HashMap::new();
+        for object in &objects {
+            match extract_top_folder(&object.key, Some(prefix)) {
+                Some(folder) => by_folder.entry(folder).or_default().push(object),
+                None => parallel.add(object),
+            }
+        }
+        let forks: Vec<_> = by_folder
+            .into_values()
+            .map(|folder_objects| {
+                let mut fork = parallel.fork();
+                for object in folder_objects {
+                    fork.add(object);
+                }
+                fork
+            })
+            .collect();
+        for fork in forks {
+            parallel.merge(fork);
+        }
+
+        assert_eq!(
+            parallel.processed.load(atomic::Ordering::Relaxed),
+            objects.len()
+        );
+        let sequential = summarize(sequential.finish(3, String::new(), false, None));
+        let parallel = summarize(parallel.finish(3, String::new(), false, None));
+        assert_eq!(sequential.1, objects.len());
+        assert_eq!(sequential.2.len(), 3);
+        assert_eq!(sequential.5.len(), 7);
+        assert_eq!(parallel, sequential);
+    }
+}
@@ -1,14 +1,28 @@
+use crate::cache::BucketStatusCache;
+use crate::commands::objects::ObjectTag;
 use crate::credentials::CredentialsManager;
 use crate::error::AppError;
 use crate::provider::ProviderType;
 use crate::s3::client::S3ClientManager;
+use aws_sdk_s3::error::ProvideErrorMetadata;
 use aws_sdk_s3::types::{
     BucketLocationConstraint, BucketVersioningStatus, CorsConfiguration, CorsRule,
     CreateBucketConfiguration, MfaDeleteStatus, ObjectIdentifier, VersioningConfiguration,
 };
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
 use tauri::State;
 
+/// Max number of accounts queried at once by `list_all_buckets`
+const LIST_ALL_BUCKETS_CONCURRENCY: usize = 4;
+
+/// Location hints accepted by R2 when creating a bucket
+const R2_LOCATION_HINTS: [&str; 5] = ["wnam", "enam", "weur", "eeur", "apac"];
+/// Header R2 reads the location hint from on CreateBucket
+const R2_LOCATION_HINT_HEADER: &str = "cf-create-bucket-region";
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Bucket {
@@ -16,6 +30,16 @@ pub struct Bucket {
     pub creation_date: Option<String>,
 }
 
+/// Buckets for one account in the cross-account listing
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBuckets {
+    pub account_id: String,
+    pub account_name: String,
+    pub buckets: Vec<Bucket>,
+    pub error: Option<String>,
+}
+
 /// Validates S3 bucket name according to AWS naming rules
 fn validate_bucket_name(name: &str) -> Result<(), AppError> {
     if name.len() < 3 || name.len() > 63 {
@@ -74,9 +98,14 @@ pub async fn list_buckets(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
+    fetch_buckets(&client).await
+}
+
+async fn fetch_buckets(client: &aws_sdk_s3::Client) -> Result<Vec<Bucket>, AppError> {
     let response = client.list_buckets().send().await?;
 
     let buckets = response
@@ -93,6 +122,57 @@ pub async fn list_buckets(
     Ok(buckets)
 }
 
+/// List buckets for every stored account. Failures are reported per account
+/// so one broken account doesn't hide the others.
+#[tauri::command]
+pub async fn list_all_buckets(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+) -> Result<Vec<AccountBuckets>, AppError> {
+    let accounts = credentials.list_accounts()?;
+    let credentials = &*credentials;
+    let s3_clients = &*s3_clients;
+
+    let mut results: Vec<AccountBuckets> = stream::iter(accounts)
+        .map(|account| async move {
+            let result = async {
+                let secret = credentials.get_secret_key(&account.id)?;
+                let client = s3_clients
+                    .get_or_create_client(
+                        &account.id,
+                        &account.endpoint,
+                        &account.access_key_id,
+                        &secret,
+                        account.provider_type,
+                        account.region.as_deref(),
+                        &account.client_options(),
+                    )
+                    .await?;
+                fetch_buckets(&client).await
+            }
+            .await;
+
+            let (buckets, error) = match result {
+                Ok(buckets) => (buckets, None),
+                Err(e) => (Vec::new(), Some(e.to_string())),
+            };
+
+            AccountBuckets {
+                account_id: account.id,
+                account_name: account.name,
+                buckets,
+                error,
+            }
+        })
+        .buffer_unordered(LIST_ALL_BUCKETS_CONCURRENCY)
+        .collect()
+        .await;
+
+    results.sort_by(|a, b| a.account_name.cmp(&b.account_name));
+
+    Ok(results)
+}
+
 #[tauri::command(rename_all = "camelCase")]
 pub async fn create_bucket(
     credentials: State<'_, CredentialsManager>,
@@ -115,6 +195,7 @@ pub async fn create_bucket(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
@@ -143,10 +224,27 @@ pub async fn create_bucket(
             }
         }
         ProviderType::CloudflareR2 => {
-            // R2 uses location hints via custom header
-            // Unfortunately, AWS SDK doesn't support custom headers easily here
-            // R2 will use automatic location if not specified
-            // For now, location hint would need to be set via Cloudflare API
+            // R2 takes the location hint as a custom header on CreateBucket
+            if let Some(hint) = location.as_deref().map(str::trim).filter(|h| !h.is_empty()) {
+                let hint = hint.to_ascii_lowercase();
+                if !R2_LOCATION_HINTS.contains(&hint.as_str()) {
+                    return Err(AppError::InvalidInput(format!(
+                        "Unknown R2 location hint: {} (expected one of {})",
+                        hint,
+                        R2_LOCATION_HINTS.join(", ")
+                    )));
+                }
+
+                request
+                    .customize()
+                    .mutate_request(move |req| {
+                        req.headers_mut()
+                            .insert(R2_LOCATION_HINT_HEADER, hint.clone());
+                    })
+                    .send()
+                    .await?;
+                return Ok(());
+            }
         }
     }
 
@@ -174,6 +272,7 @@ pub async fn delete_bucket(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
@@ -272,6 +371,7 @@ pub async fn get_bucket_versioning(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
@@ -318,6 +418,7 @@ pub async fn put_bucket_versioning(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
@@ -373,19 +474,17 @@ pub async fn get_bucket_cors(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
     let response = match client.get_bucket_cors().bucket(&bucket).send().await {
         Ok(resp) => resp,
-        Err(e) => {
-            // NoSuchCORSConfiguration means CORS is not configured
-            let error_str = format!("{:?}", e);
-            if error_str.contains("NoSuchCORSConfiguration") || error_str.contains("NoSuchCors") {
-                return Ok(BucketCorsConfig { rules: vec![] });
-            }
-            return Err(e.into());
+        // NoSuchCORSConfiguration means CORS is not configured
+        Err(e) if matches!(e.code(), Some("NoSuchCORSConfiguration") | Some("NoSuchCors")) => {
+            return Ok(BucketCorsConfig { rules: vec![] });
         }
+        Err(e) => return Err(e.into()),
     };
 
     let rules = response
@@ -403,6 +502,125 @@ pub async fn get_bucket_cors(
     Ok(BucketCorsConfig { rules })
 }
 
+/// A change made to a CORS rule so the provider will accept it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorsAdjustment {
+    /// Index of the rule in the submitted list
+    pub rule_index: usize,
+    /// "allowedOrigins", "allowedMethods", "allowedHeaders" or "exposeHeaders"
+    pub field: String,
+    pub message: String,
+}
+
+/// The CORS rules that will actually be applied, and what was changed to get there
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorsValidationReport {
+    pub rules: Vec<CorsRuleConfig>,
+    pub adjustments: Vec<CorsAdjustment>,
+}
+
+/// Normalize CORS rules for the provider: trim values, drop empty ones and,
+/// for R2, drop the wildcard AllowedHeader it rejects. Every change is recorded.
+/// Fails if a rule is left without an origin or method.
+fn validate_cors_rules(
+    rules: Vec<CorsRuleConfig>,
+    provider_type: ProviderType,
+) -> Result<CorsValidationReport, AppError> {
+    if rules.is_empty() {
+        return Err(AppError::InvalidInput(
+            "CORS configuration needs at least one rule".into(),
+        ));
+    }
+
+    let mut adjustments = Vec::new();
+    let mut cleaned_rules = Vec::with_capacity(rules.len());
+
+    for (rule_index, rule) in rules.into_iter().enumerate() {
+        // R2 doesn't like empty arrays or empty strings in XML
+        let mut clean = |values: Vec<String>, field: &str, drop_wildcard: bool| {
+            let mut empty = 0;
+            let mut wildcard = false;
+            let cleaned: Vec<String> = values
+                .into_iter()
+                .map(|s| s.trim().to_string())
+                .filter(|s| {
+                    if s.is_empty() {
+                        empty += 1;
+                        false
+                    } else if drop_wildcard && s == "*" {
+                        wildcard = true;
+                        false
+                    } else {
+                        true
+                    }
+                })
+                .collect();
+
+            if empty > 0 {
+                adjustments.push(CorsAdjustment {
+                    rule_index,
+                    field: field.to_string(),
+                    message: format!("Dropped {} empty value(s)", empty),
+                });
+            }
+            if wildcard {
+                adjustments.push(CorsAdjustment {
+                    rule_index,
+                    field: field.to_string(),
+                    message: "Removed wildcard \"*\", which R2 does not support".into(),
+                });
+            }
+            cleaned
+        };
+
+        // R2 does NOT support wildcard "*" in AllowedHeaders - filter it out
+        // See: https://community.cloudflare.com/t/problem-with-settings-cors-policies-on-r2/432339
+        let is_r2 = provider_type == ProviderType::CloudflareR2;
+        let cleaned = CorsRuleConfig {
+            allowed_headers: clean(rule.allowed_headers, "allowedHeaders", is_r2),
+            allowed_methods: clean(rule.allowed_methods, "allowedMethods", false),
+            allowed_origins: clean(rule.allowed_origins, "allowedOrigins", false),
+            expose_headers: clean(rule.expose_headers, "exposeHeaders", false),
+            max_age_seconds: rule.max_age_seconds,
+        };
+
+        if cleaned.allowed_origins.is_empty() {
+            return Err(AppError::InvalidInput(format!(
+                "CORS rule {} needs at least one allowed origin",
+                rule_index + 1
+            )));
+        }
+        if cleaned.allowed_methods.is_empty() {
+            return Err(AppError::InvalidInput(format!(
+                "CORS rule {} needs at least one allowed method",
+                rule_index + 1
+            )));
+        }
+
+        cleaned_rules.push(cleaned);
+    }
+
+    Ok(CorsValidationReport {
+        rules: cleaned_rules,
+        adjustments,
+    })
+}
+
+/// Preview how `put_bucket_cors` would adjust the rules for this account's provider
+#[tauri::command(rename_all = "camelCase")]
+pub async fn validate_bucket_cors(
+    credentials: State<'_, CredentialsManager>,
+    account_id: String,
+    rules: Vec<CorsRuleConfig>,
+) -> Result<CorsValidationReport, AppError> {
+    let account = credentials.get_account(&account_id)?;
+    validate_cors_rules(rules, account.provider_type)
+}
+
+/// Apply CORS rules after validating them. Returns the rules as applied along
+/// with any adjustments made for the provider.
 #[tauri::command(rename_all = "camelCase")]
 pub async fn put_bucket_cors(
     credentials: State<'_, CredentialsManager>,
@@ -410,8 +628,9 @@ pub async fn put_bucket_cors(
     account_id: String,
     bucket: String,
     rules: Vec<CorsRuleConfig>,
-) -> Result<(), AppError> {
+) -> Result<CorsValidationReport, AppError> {
     let account = credentials.get_account(&account_id)?;
+    let report = validate_cors_rules(rules, account.provider_type)?;
     let secret = credentials.get_secret_key(&account_id)?;
 
     let client = s3_clients
@@ -422,74 +641,26 @@ pub async fn put_bucket_cors(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    let cors_rules: Vec<CorsRule> = rules
-        .into_iter()
+    // Only set optional fields if they have values; R2 is strict about XML format
+    let non_empty = |v: &[String]| (!v.is_empty()).then(|| v.to_vec());
+    let cors_rules: Vec<CorsRule> = report
+        .rules
+        .iter()
         .map(|rule| {
-            // Helper to filter empty strings and convert empty Vec to None
-            // R2 doesn't like empty arrays or empty strings in XML
-            let clean_vec = |v: Vec<String>| -> Option<Vec<String>> {
-                let filtered: Vec<String> = v
-                    .into_iter()
-                    .map(|s| s.trim().to_string())
-                    .filter(|s| !s.is_empty())
-                    .collect();
-                if filtered.is_empty() {
-                    None
-                } else {
-                    Some(filtered)
-                }
-            };
-
-            // R2 does NOT support wildcard "*" in AllowedHeaders - filter it out
-            // See: https://community.cloudflare.com/t/problem-with-settings-cors-policies-on-r2/432339
-            let clean_headers = |v: Vec<String>| -> Option<Vec<String>> {
-                let filtered: Vec<String> = v
-                    .into_iter()
-                    .map(|s| s.trim().to_string())
-                    .filter(|s| !s.is_empty() && s != "*")
-                    .collect();
-                if filtered.is_empty() {
-                    None
-                } else {
-                    Some(filtered)
-                }
-            };
-
-            let allowed_headers = clean_headers(rule.allowed_headers);
-            let allowed_methods = clean_vec(rule.allowed_methods);
-            let allowed_origins = clean_vec(rule.allowed_origins);
-            let expose_headers = clean_vec(rule.expose_headers);
-            let max_age = rule.max_age_seconds;
-
-            // Build CORS rule - only set optional fields if they have values
-            // R2 is strict about XML format
-            let mut builder = CorsRule::builder();
-
-            // Required fields - must be set
-            if let Some(origins) = allowed_origins {
-                builder = builder.set_allowed_origins(Some(origins));
-            }
-            if let Some(methods) = allowed_methods {
-                builder = builder.set_allowed_methods(Some(methods));
-            }
-
-            // Optional fields - only set if provided and non-empty
-            if let Some(headers) = allowed_headers {
-                builder = builder.set_allowed_headers(Some(headers));
-            }
-            if let Some(headers) = expose_headers {
-                builder = builder.set_expose_headers(Some(headers));
-            }
-            if let Some(age) = max_age {
-                builder = builder.set_max_age_seconds(Some(age));
-            }
-
-            builder.build().expect("CORS rule build should succeed")
+            CorsRule::builder()
+                .set_allowed_origins(Some(rule.allowed_origins.clone()))
+                .set_allowed_methods(Some(rule.allowed_methods.clone()))
+                .set_allowed_headers(non_empty(&rule.allowed_headers))
+                .set_expose_headers(non_empty(&rule.expose_headers))
+                .set_max_age_seconds(rule.max_age_seconds)
+                .build()
+                .map_err(|e| AppError::InvalidInput(format!("Invalid CORS rule: {}", e)))
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
     let config = CorsConfiguration::builder()
         .set_cors_rules(Some(cors_rules))
@@ -503,7 +674,7 @@ pub async fn put_bucket_cors(
         .send()
         .await?;
 
-    Ok(())
+    Ok(report)
 }
 
 #[tauri::command(rename_all = "camelCase")]
@@ -524,6 +695,7 @@ pub async fn delete_bucket_cors(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
@@ -543,6 +715,151 @@ pub struct LifecycleRuleConfig {
     pub abort_incomplete_multipart_upload_days: Option<i32>,
     #[serde(default)]
     pub transitions: Vec<LifecycleTransition>,
+    /// Tiering for previous versions in versioned buckets
+    #[serde(default)]
+    pub noncurrent_version_transitions: Vec<NoncurrentVersionTransitionConfig>,
+    /// Objects must carry all of these tags
+    #[serde(default)]
+    pub tags: Vec<ObjectTag>,
+    /// Size bounds in bytes (exclusive)
+    pub object_size_greater_than: Option<i64>,
+    pub object_size_less_than: Option<i64>,
+}
+
+impl LifecycleRuleConfig {
+    fn from_rule(rule: &aws_sdk_s3::types::LifecycleRule) -> Self {
+        let transitions = rule
+            .transitions()
+            .iter()
+            .map(|t| LifecycleTransition {
+                days: t.days(),
+                storage_class: t.storage_class().map(|s| s.as_str().to_string()),
+            })
+            .collect();
+        let noncurrent_version_transitions = rule
+            .noncurrent_version_transitions()
+            .iter()
+            .map(|t| NoncurrentVersionTransitionConfig {
+                noncurrent_days: t.noncurrent_days(),
+                storage_class: t.storage_class().map(|s| s.as_str().to_string()),
+            })
+            .collect();
+
+        let to_tag = |t: &aws_sdk_s3::types::Tag| ObjectTag {
+            key: t.key().to_string(),
+            value: t.value().to_string(),
+        };
+
+        // Multiple conditions are nested under an And operator
+        let filter = rule.filter();
+        let and = filter.and_then(|f| f.and());
+        let (prefix, tags, greater_than, less_than) = match and {
+            Some(and) => (
+                and.prefix(),
+                and.tags().iter().map(to_tag).collect(),
+                and.object_size_greater_than(),
+                and.object_size_less_than(),
+            ),
+            None => (
+                filter.and_then(|f| f.prefix()),
+                filter.and_then(|f| f.tag()).map(to_tag).into_iter().collect(),
+                filter.and_then(|f| f.object_size_greater_than()),
+                filter.and_then(|f| f.object_size_less_than()),
+            ),
+        };
+
+        LifecycleRuleConfig {
+            id: rule.id().map(|s| s.to_string()),
+            status: rule.status().as_str().to_string(),
+            prefix: prefix.map(|p| p.to_string()),
+            expiration_days: rule.expiration().and_then(|e| e.days()),
+            noncurrent_version_expiration_days: rule
+                .noncurrent_version_expiration()
+                .and_then(|e| e.noncurrent_days()),
+            abort_incomplete_multipart_upload_days: rule
+                .abort_incomplete_multipart_upload()
+                .and_then(|a| a.days_after_initiation()),
+            transitions,
+            noncurrent_version_transitions,
+            tags,
+            object_size_greater_than: greater_than,
+            object_size_less_than: less_than,
+        }
+    }
+
+    /// Build the rule filter. S3 accepts a single condition directly on the
+    /// filter but requires an And operator to combine prefix, tags and sizes.
+    fn filter(&self) -> Result<Option<aws_sdk_s3::types::LifecycleRuleFilter>, AppError> {
+        use aws_sdk_s3::types::{LifecycleRuleAndOperator, LifecycleRuleFilter, Tag};
+
+        for size in [self.object_size_greater_than, self.object_size_less_than]
+            .into_iter()
+            .flatten()
+        {
+            if size < 0 {
+                return Err(AppError::InvalidInput(
+                    "Object size filters cannot be negative".into(),
+                ));
+            }
+        }
+        if let (Some(greater), Some(less)) =
+            (self.object_size_greater_than, self.object_size_less_than)
+        {
+            if greater >= less {
+                return Err(AppError::InvalidInput(
+                    "objectSizeGreaterThan must be less than objectSizeLessThan".into(),
+                ));
+            }
+        }
+
+        let tags = self
+            .tags
+            .iter()
+            .map(|t| {
+                Tag::builder()
+                    .key(&t.key)
+                    .value(&t.value)
+                    .build()
+                    .map_err(|e| AppError::InvalidInput(format!("Invalid tag: {}", e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // An empty prefix on its own still yields a filter matching every object
+        let prefix = self.prefix.as_deref().filter(|p| !p.is_empty());
+        let conditions = prefix.is_some() as usize
+            + tags.len()
+            + self.object_size_greater_than.is_some() as usize
+            + self.object_size_less_than.is_some() as usize;
+
+        let filter = match conditions {
+            0 => self
+                .prefix
+                .as_ref()
+                .map(|p| LifecycleRuleFilter::builder().prefix(p).build()),
+            1 => Some(
+                LifecycleRuleFilter::builder()
+                    .set_prefix(prefix.map(|p| p.to_string()))
+                    .set_tag(tags.into_iter().next())
+                    .set_object_size_greater_than(self.object_size_greater_than)
+                    .set_object_size_less_than(self.object_size_less_than)
+                    .build(),
+            ),
+            _ => Some(
+                LifecycleRuleFilter::builder()
+                    .and(
+                        LifecycleRuleAndOperator::builder()
+                            .set_prefix(prefix.map(|p| p.to_string()))
+                            .set_tags(Some(tags).filter(|t| !t.is_empty()))
+                            .set_object_size_greater_than(self.object_size_greater_than)
+                            .set_object_size_less_than(self.object_size_less_than)
+                            .build(),
+                    )
+                    .build(),
+            ),
+        };
+
+        Ok(filter)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -552,6 +869,28 @@ pub struct LifecycleTransition {
     pub storage_class: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoncurrentVersionTransitionConfig {
+    /// Days after an object becomes noncurrent
+    pub noncurrent_days: Option<i32>,
+    pub storage_class: Option<String>,
+}
+
+/// Parse a transition storage class such as "GLACIER" or "STANDARD_IA"
+fn transition_storage_class(
+    storage_class: Option<&str>,
+) -> Result<aws_sdk_s3::types::TransitionStorageClass, AppError> {
+    let storage_class = storage_class
+        .ok_or_else(|| AppError::InvalidInput("Transition storage class is required".into()))?;
+    aws_sdk_s3::types::TransitionStorageClass::try_parse(storage_class).map_err(|_| {
+        AppError::InvalidInput(format!(
+            "Unknown transition storage class: {}",
+            storage_class
+        ))
+    })
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BucketLifecycleConfig {
@@ -576,6 +915,7 @@ pub async fn get_bucket_lifecycle(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
@@ -586,45 +926,17 @@ pub async fn get_bucket_lifecycle(
         .await
     {
         Ok(resp) => resp,
-        Err(e) => {
-            // NoSuchLifecycleConfiguration means no lifecycle rules
-            let error_str = format!("{:?}", e);
-            if error_str.contains("NoSuchLifecycleConfiguration") {
-                return Ok(BucketLifecycleConfig { rules: vec![] });
-            }
-            return Err(e.into());
+        // NoSuchLifecycleConfiguration means no lifecycle rules
+        Err(e) if e.code() == Some("NoSuchLifecycleConfiguration") => {
+            return Ok(BucketLifecycleConfig { rules: vec![] });
         }
+        Err(e) => return Err(e.into()),
     };
 
     let rules = response
         .rules()
         .iter()
-        .map(|rule| {
-            let transitions = rule
-                .transitions()
-                .iter()
-                .map(|t| LifecycleTransition {
-                    days: t.days(),
-                    storage_class: t.storage_class().map(|s| s.as_str().to_string()),
-                })
-                .collect();
-
-            LifecycleRuleConfig {
-                id: rule.id().map(|s| s.to_string()),
-                status: rule.status().as_str().to_string(),
-                prefix: rule
-                    .filter()
-                    .and_then(|f| f.prefix().map(|p| p.to_string())),
-                expiration_days: rule.expiration().and_then(|e| e.days()),
-                noncurrent_version_expiration_days: rule
-                    .noncurrent_version_expiration()
-                    .and_then(|e| e.noncurrent_days()),
-                abort_incomplete_multipart_upload_days: rule
-                    .abort_incomplete_multipart_upload()
-                    .and_then(|a| a.days_after_initiation()),
-                transitions,
-            }
-        })
+        .map(LifecycleRuleConfig::from_rule)
         .collect();
 
     Ok(BucketLifecycleConfig { rules })
@@ -640,7 +952,8 @@ pub async fn put_bucket_lifecycle(
 ) -> Result<(), AppError> {
     use aws_sdk_s3::types::{
         AbortIncompleteMultipartUpload, BucketLifecycleConfiguration, ExpirationStatus,
-        LifecycleExpiration, LifecycleRule, LifecycleRuleFilter, NoncurrentVersionExpiration,
+        LifecycleExpiration, LifecycleRule, NoncurrentVersionExpiration,
+        NoncurrentVersionTransition, Transition,
     };
 
     let account = credentials.get_account(&account_id)?;
@@ -654,30 +967,27 @@ pub async fn put_bucket_lifecycle(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
     let lifecycle_rules: Vec<LifecycleRule> = rules
         .into_iter()
-        .map(|rule| {
+        .map(|rule| -> Result<LifecycleRule, AppError> {
             let status = if rule.status == "Enabled" {
                 ExpirationStatus::Enabled
             } else {
                 ExpirationStatus::Disabled
             };
 
-            let mut builder = LifecycleRule::builder().status(status);
+            let mut builder = LifecycleRule::builder()
+                .status(status)
+                .set_filter(rule.filter()?);
 
             if let Some(id) = rule.id {
                 builder = builder.id(id);
             }
 
-            // Set filter (prefix)
-            if let Some(prefix) = rule.prefix {
-                let filter = LifecycleRuleFilter::builder().prefix(prefix).build();
-                builder = builder.filter(filter);
-            }
-
             // Set expiration
             if let Some(days) = rule.expiration_days {
                 builder = builder.expiration(
@@ -703,9 +1013,31 @@ pub async fn put_bucket_lifecycle(
                 );
             }
 
-            builder.build().expect("LifecycleRule build should succeed")
+            // Set storage class transitions for current and noncurrent versions
+            for transition in &rule.transitions {
+                builder = builder.transitions(
+                    Transition::builder()
+                        .set_days(transition.days)
+                        .storage_class(transition_storage_class(
+                            transition.storage_class.as_deref(),
+                        )?)
+                        .build(),
+                );
+            }
+            for transition in &rule.noncurrent_version_transitions {
+                builder = builder.noncurrent_version_transitions(
+                    NoncurrentVersionTransition::builder()
+                        .set_noncurrent_days(transition.noncurrent_days)
+                        .storage_class(transition_storage_class(
+                            transition.storage_class.as_deref(),
+                        )?)
+                        .build(),
+                );
+            }
+
+            Ok(builder.build().expect("LifecycleRule build should succeed"))
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
     let config = BucketLifecycleConfiguration::builder()
         .set_rules(Some(lifecycle_rules))
@@ -740,6 +1072,7 @@ pub async fn delete_bucket_lifecycle(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
@@ -778,25 +1111,26 @@ pub async fn get_bucket_encryption(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
     let response = match client.get_bucket_encryption().bucket(&bucket).send().await {
         Ok(resp) => resp,
-        Err(e) => {
-            // ServerSideEncryptionConfigurationNotFoundError means no encryption config
-            let error_str = format!("{:?}", e);
-            if error_str.contains("ServerSideEncryptionConfigurationNotFoundError")
-                || error_str.contains("NoSuchEncryption")
-            {
-                return Ok(BucketEncryptionConfig {
-                    sse_algorithm: None,
-                    kms_master_key_id: None,
-                    bucket_key_enabled: None,
-                });
-            }
-            return Err(e.into());
+        // ServerSideEncryptionConfigurationNotFoundError means no encryption config
+        Err(e)
+            if matches!(
+                e.code(),
+                Some("ServerSideEncryptionConfigurationNotFoundError") | Some("NoSuchEncryption")
+            ) =>
+        {
+            return Ok(BucketEncryptionConfig {
+                sse_algorithm: None,
+                kms_master_key_id: None,
+                bucket_key_enabled: None,
+            });
         }
+        Err(e) => return Err(e.into()),
     };
 
     // Get the first rule (typically there's only one)
@@ -847,6 +1181,7 @@ pub async fn get_bucket_logging(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
@@ -868,6 +1203,209 @@ pub async fn get_bucket_logging(
     Ok(config)
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BucketLocation {
+    pub region: String,
+    /// Location constraint exactly as returned by the provider
+    pub location_constraint: Option<String>,
+}
+
+/// Map a GetBucketLocation constraint to a region name. Buckets in us-east-1
+/// report an empty constraint and legacy Ireland buckets report "EU".
+fn normalize_location_constraint(constraint: Option<&str>) -> String {
+    match constraint {
+        None | Some("") => "us-east-1".to_string(),
+        Some("EU") => "eu-west-1".to_string(),
+        Some(region) => region.to_string(),
+    }
+}
+
+/// Whether a bucket can be used with the account's credentials
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BucketAccess {
+    Accessible,
+    /// The bucket exists but the credentials may not use it
+    Forbidden,
+    NotFound,
+    /// The bucket exists in a different region than the client targets
+    WrongRegion,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BucketStatus {
+    pub bucket: String,
+    pub exists: bool,
+    pub access: BucketAccess,
+    /// Region reported by the provider, when known
+    pub region: Option<String>,
+}
+
+impl BucketStatus {
+    /// Error to show before starting work on the bucket, or `None` if it is usable
+    pub(crate) fn to_error(&self) -> Option<AppError> {
+        match self.access {
+            BucketAccess::Accessible => None,
+            BucketAccess::Forbidden => Some(AppError::AccessDenied(format!(
+                "Access to bucket '{}' is denied for this account",
+                self.bucket
+            ))),
+            BucketAccess::NotFound => Some(AppError::NotFound(format!(
+                "Bucket '{}' does not exist",
+                self.bucket
+            ))),
+            BucketAccess::WrongRegion => Some(AppError::InvalidInput(format!(
+                "Bucket '{}' is in region {}; update the account's region to use it",
+                self.bucket,
+                self.region.as_deref().unwrap_or("unknown")
+            ))),
+        }
+    }
+}
+
+/// Classify a HeadBucket failure. Returns `None` for errors without an HTTP
+/// response (network, timeouts), which say nothing about the bucket itself.
+pub(crate) fn bucket_status_from_error<E>(
+    bucket: &str,
+    error: &aws_sdk_s3::error::SdkError<E, aws_sdk_s3::config::http::HttpResponse>,
+) -> Option<BucketStatus> {
+    let response = error.raw_response()?;
+    let region = response
+        .headers()
+        .get("x-amz-bucket-region")
+        .map(|r| r.to_string());
+    let (exists, access) = match response.status().as_u16() {
+        403 => (true, BucketAccess::Forbidden),
+        404 => (false, BucketAccess::NotFound),
+        301 | 307 | 400 if region.is_some() => (true, BucketAccess::WrongRegion),
+        _ => return None,
+    };
+
+    Some(BucketStatus {
+        bucket: bucket.to_string(),
+        exists,
+        access,
+        region,
+    })
+}
+
+/// Check a bucket with HeadBucket
+pub(crate) async fn check_bucket(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+) -> Result<BucketStatus, AppError> {
+    match client.head_bucket().bucket(bucket).send().await {
+        Ok(response) => Ok(BucketStatus {
+            bucket: bucket.to_string(),
+            exists: true,
+            access: BucketAccess::Accessible,
+            region: response.bucket_region().map(|r| r.to_string()),
+        }),
+        Err(e) => bucket_status_from_error(bucket, &e).ok_or_else(|| e.into()),
+    }
+}
+
+/// Fail with a clear message if the bucket is missing, forbidden or in another
+/// region. Results are cached briefly so repeated operations don't re-check.
+pub(crate) async fn ensure_bucket_accessible(
+    client: &aws_sdk_s3::Client,
+    cache: &BucketStatusCache,
+    account_id: &str,
+    bucket: &str,
+) -> Result<(), AppError> {
+    let status = match cache.get(account_id, bucket) {
+        Some(status) => status,
+        None => {
+            let status = check_bucket(client, bucket).await?;
+            cache.insert(account_id, bucket, status.clone());
+            status
+        }
+    };
+
+    match status.to_error() {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Check whether a bucket exists and is accessible, distinguishing a missing
+/// bucket from a permission problem or a region mismatch
+#[tauri::command(rename_all = "camelCase")]
+pub async fn head_bucket(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    bucket_status_cache: State<'_, BucketStatusCache>,
+    account_id: String,
+    bucket: String,
+) -> Result<BucketStatus, AppError> {
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    let status = check_bucket(&client, &bucket).await?;
+    bucket_status_cache.insert(&account_id, &bucket, status.clone());
+
+    Ok(status)
+}
+
+/// Look up the region a bucket lives in and remember it for bucket-specific clients
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_bucket_location(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    account_id: String,
+    bucket: String,
+) -> Result<BucketLocation, AppError> {
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    let response = client
+        .get_bucket_location()
+        .bucket(&bucket)
+        .send()
+        .await?;
+
+    let location_constraint = response.location_constraint().map(|c| c.as_str().to_string());
+    let region = normalize_location_constraint(location_constraint.as_deref());
+
+    // Pre-populate the region cache so the first bucket access skips the redirect
+    if account.provider_type == ProviderType::AwsS3 {
+        s3_clients
+            .cache_bucket_region(&account_id, &bucket, &region)
+            .await;
+    }
+
+    Ok(BucketLocation {
+        region,
+        location_constraint,
+    })
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BucketConfigSummary {
@@ -876,6 +1414,41 @@ pub struct BucketConfigSummary {
     pub lifecycle: BucketLifecycleConfig,
     pub encryption: BucketEncryptionConfig,
     pub logging: BucketLoggingConfig,
+    /// Sections that could not be fetched; their fields above hold placeholders
+    pub errors: Vec<BucketConfigError>,
+}
+
+/// A bucket configuration section that failed or timed out
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BucketConfigError {
+    pub section: String,
+    pub message: String,
+}
+
+/// How long each configuration section may take before it is reported as failed
+const BUCKET_CONFIG_SECTION_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Run one section fetch of `get_bucket_config` under a timeout, turning any
+/// failure into an entry for the summary's `errors`
+async fn fetch_config_section<T>(
+    section: &str,
+    fetch: impl Future<Output = Result<T, AppError>>,
+) -> Result<T, BucketConfigError> {
+    let message = match tokio::time::timeout(BUCKET_CONFIG_SECTION_TIMEOUT, fetch).await {
+        Ok(Ok(config)) => return Ok(config),
+        Ok(Err(e)) => e.to_string(),
+        Err(_) => format!(
+            "Timed out after {} seconds",
+            BUCKET_CONFIG_SECTION_TIMEOUT.as_secs()
+        ),
+    };
+    log::warn!("Failed to fetch bucket {} config: {}", section, message);
+
+    Err(BucketConfigError {
+        section: section.to_string(),
+        message,
+    })
 }
 
 #[tauri::command(rename_all = "camelCase")]
@@ -896,14 +1469,16 @@ pub async fn get_bucket_config(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
     // Fetch all configurations in parallel using tokio::join!
-    // Handle "NotImplemented" errors gracefully for providers like R2
+    // Handle "NotImplemented" errors gracefully for providers like R2; other
+    // failures are collected into `errors` so the rest of the summary still loads
     let (versioning_result, cors_result, lifecycle_result, encryption_result, logging_result) =
         tokio::join!(
-            async {
+            fetch_config_section("versioning", async {
                 match client.get_bucket_versioning().bucket(&bucket).send().await {
                     Ok(resp) => {
                         let status = match resp.status() {
@@ -921,21 +1496,17 @@ pub async fn get_bucket_config(
                             mfa_delete,
                         })
                     }
-                    Err(e) => {
-                        let error_str = format!("{:?}", e);
-                        if error_str.contains("NotImplemented") {
-                            // R2 and some providers don't support versioning API
-                            Ok(BucketVersioningConfig {
-                                status: "Unsupported".to_string(),
-                                mfa_delete: None,
-                            })
-                        } else {
-                            Err(e.into())
-                        }
-                    }
+                    Err(e) => match AppError::from(e) {
+                        // R2 and some providers don't support versioning API
+                        AppError::NotImplemented(_) => Ok(BucketVersioningConfig {
+                            status: "Unsupported".to_string(),
+                            mfa_delete: None,
+                        }),
+                        e => Err(e),
+                    },
                 }
-            },
-            async {
+            }),
+            fetch_config_section("cors", async {
                 match client.get_bucket_cors().bucket(&bucket).send().await {
                     Ok(resp) => {
                         let rules = resp
@@ -967,20 +1538,21 @@ pub async fn get_bucket_config(
                             .collect();
                         Ok::<_, AppError>(BucketCorsConfig { rules })
                     }
-                    Err(e) => {
-                        let error_str = format!("{:?}", e);
-                        if error_str.contains("NoSuchCORSConfiguration")
-                            || error_str.contains("NoSuchCors")
-                            || error_str.contains("NotImplemented")
-                        {
-                            Ok(BucketCorsConfig { rules: vec![] })
-                        } else {
-                            Err(e.into())
-                        }
+                    Err(e)
+                        if matches!(
+                            e.code(),
+                            Some("NoSuchCORSConfiguration") | Some("NoSuchCors")
+                        ) =>
+                    {
+                        Ok(BucketCorsConfig { rules: vec![] })
                     }
+                    Err(e) => match AppError::from(e) {
+                        AppError::NotImplemented(_) => Ok(BucketCorsConfig { rules: vec![] }),
+                        e => Err(e),
+                    },
                 }
-            },
-            async {
+            }),
+            fetch_config_section("lifecycle", async {
                 match client
                     .get_bucket_lifecycle_configuration()
                     .bucket(&bucket)
@@ -991,49 +1563,20 @@ pub async fn get_bucket_config(
                         let rules = resp
                             .rules()
                             .iter()
-                            .map(|rule| {
-                                let transitions = rule
-                                    .transitions()
-                                    .iter()
-                                    .map(|t| LifecycleTransition {
-                                        days: t.days(),
-                                        storage_class: t
-                                            .storage_class()
-                                            .map(|s| s.as_str().to_string()),
-                                    })
-                                    .collect();
-                                LifecycleRuleConfig {
-                                    id: rule.id().map(|s| s.to_string()),
-                                    status: rule.status().as_str().to_string(),
-                                    prefix: rule
-                                        .filter()
-                                        .and_then(|f| f.prefix().map(|p| p.to_string())),
-                                    expiration_days: rule.expiration().and_then(|e| e.days()),
-                                    noncurrent_version_expiration_days: rule
-                                        .noncurrent_version_expiration()
-                                        .and_then(|e| e.noncurrent_days()),
-                                    abort_incomplete_multipart_upload_days: rule
-                                        .abort_incomplete_multipart_upload()
-                                        .and_then(|a| a.days_after_initiation()),
-                                    transitions,
-                                }
-                            })
+                            .map(LifecycleRuleConfig::from_rule)
                             .collect();
                         Ok::<_, AppError>(BucketLifecycleConfig { rules })
                     }
-                    Err(e) => {
-                        let error_str = format!("{:?}", e);
-                        if error_str.contains("NoSuchLifecycleConfiguration")
-                            || error_str.contains("NotImplemented")
-                        {
-                            Ok(BucketLifecycleConfig { rules: vec![] })
-                        } else {
-                            Err(e.into())
-                        }
+                    Err(e) if e.code() == Some("NoSuchLifecycleConfiguration") => {
+                        Ok(BucketLifecycleConfig { rules: vec![] })
                     }
+                    Err(e) => match AppError::from(e) {
+                        AppError::NotImplemented(_) => Ok(BucketLifecycleConfig { rules: vec![] }),
+                        e => Err(e),
+                    },
                 }
-            },
-            async {
+            }),
+            fetch_config_section("encryption", async {
                 match client.get_bucket_encryption().bucket(&bucket).send().await {
                     Ok(resp) => {
                         let config = resp
@@ -1057,24 +1600,30 @@ pub async fn get_bucket_config(
                             });
                         Ok::<_, AppError>(config)
                     }
-                    Err(e) => {
-                        let error_str = format!("{:?}", e);
-                        if error_str.contains("ServerSideEncryptionConfigurationNotFoundError")
-                            || error_str.contains("NoSuchEncryption")
-                            || error_str.contains("NotImplemented")
-                        {
-                            Ok(BucketEncryptionConfig {
-                                sse_algorithm: None,
-                                kms_master_key_id: None,
-                                bucket_key_enabled: None,
-                            })
-                        } else {
-                            Err(e.into())
-                        }
+                    Err(e)
+                        if matches!(
+                            e.code(),
+                            Some("ServerSideEncryptionConfigurationNotFoundError")
+                                | Some("NoSuchEncryption")
+                        ) =>
+                    {
+                        Ok(BucketEncryptionConfig {
+                            sse_algorithm: None,
+                            kms_master_key_id: None,
+                            bucket_key_enabled: None,
+                        })
                     }
+                    Err(e) => match AppError::from(e) {
+                        AppError::NotImplemented(_) => Ok(BucketEncryptionConfig {
+                            sse_algorithm: None,
+                            kms_master_key_id: None,
+                            bucket_key_enabled: None,
+                        }),
+                        e => Err(e),
+                    },
                 }
-            },
-            async {
+            }),
+            fetch_config_section("logging", async {
                 match client.get_bucket_logging().bucket(&bucket).send().await {
                     Ok(resp) => {
                         let config = match resp.logging_enabled() {
@@ -1091,27 +1640,57 @@ pub async fn get_bucket_config(
                         };
                         Ok::<_, AppError>(config)
                     }
-                    Err(e) => {
-                        let error_str = format!("{:?}", e);
-                        if error_str.contains("NotImplemented") {
-                            Ok(BucketLoggingConfig {
-                                logging_enabled: false,
-                                target_bucket: None,
-                                target_prefix: None,
-                            })
-                        } else {
-                            Err(e.into())
-                        }
-                    }
+                    Err(e) => match AppError::from(e) {
+                        AppError::NotImplemented(_) => Ok(BucketLoggingConfig {
+                            logging_enabled: false,
+                            target_bucket: None,
+                            target_prefix: None,
+                        }),
+                        e => Err(e),
+                    },
                 }
-            }
+            })
         );
 
+    let mut errors = Vec::new();
+    let versioning = versioning_result.unwrap_or_else(|e| {
+        errors.push(e);
+        BucketVersioningConfig {
+            status: "Unknown".to_string(),
+            mfa_delete: None,
+        }
+    });
+    let cors = cors_result.unwrap_or_else(|e| {
+        errors.push(e);
+        BucketCorsConfig { rules: vec![] }
+    });
+    let lifecycle = lifecycle_result.unwrap_or_else(|e| {
+        errors.push(e);
+        BucketLifecycleConfig { rules: vec![] }
+    });
+    let encryption = encryption_result.unwrap_or_else(|e| {
+        errors.push(e);
+        BucketEncryptionConfig {
+            sse_algorithm: None,
+            kms_master_key_id: None,
+            bucket_key_enabled: None,
+        }
+    });
+    let logging = logging_result.unwrap_or_else(|e| {
+        errors.push(e);
+        BucketLoggingConfig {
+            logging_enabled: false,
+            target_bucket: None,
+            target_prefix: None,
+        }
+    });
+
     Ok(BucketConfigSummary {
-        versioning: versioning_result?,
-        cors: cors_result?,
-        lifecycle: lifecycle_result?,
-        encryption: encryption_result?,
-        logging: logging_result?,
+        versioning,
+        cors,
+        lifecycle,
+        encryption,
+        logging,
+        errors,
     })
 }
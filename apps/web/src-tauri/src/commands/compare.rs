@@ -0,0 +1,241 @@
+use crate::commands::objects::{flat_listing, list_page};
+use crate::credentials::CredentialsManager;
+use crate::error::AppError;
+use crate::s3::client::S3ClientManager;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::State;
+
+/// Default cap on the keys returned per category; counts and byte totals always cover everything
+const COMPARE_DEFAULT_MAX_RESULTS: usize = 1000;
+
+/// One side of a comparison
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareScope {
+    pub account_id: String,
+    pub bucket: String,
+    #[serde(default)]
+    pub prefix: String,
+}
+
+/// Why an object present on both sides counts as different
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffReason {
+    Size,
+    Etag,
+}
+
+/// An object in a comparison, keyed relative to each scope's prefix
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparedObject {
+    pub key: String,
+    pub size_a: Option<i64>,
+    pub size_b: Option<i64>,
+    pub etag_a: Option<String>,
+    pub etag_b: Option<String>,
+    pub reason: Option<DiffReason>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BucketComparison {
+    pub only_in_a: Vec<ComparedObject>,
+    pub only_in_b: Vec<ComparedObject>,
+    pub different: Vec<ComparedObject>,
+    pub only_in_a_count: usize,
+    pub only_in_b_count: usize,
+    pub different_count: usize,
+    pub identical_count: usize,
+    /// Same size but multipart ETags that differ, which can't prove the
+    /// content differs (the part sizes may just be different)
+    pub unverified_count: usize,
+    pub only_in_a_bytes: i64,
+    pub only_in_b_bytes: i64,
+    /// Total of `size_b - size_a` over the differing objects
+    pub different_bytes_delta: i64,
+    /// True if any category holds more keys than were returned
+    pub truncated: bool,
+}
+
+struct ListedObject {
+    size: i64,
+    etag: Option<String>,
+}
+
+/// List every object under a scope, keyed relative to its prefix
+async fn list_scope(
+    client: &aws_sdk_s3::Client,
+    scope: &CompareScope,
+) -> Result<HashMap<String, ListedObject>, AppError> {
+    let mut objects = HashMap::new();
+    let mut continuation_token: Option<String> = None;
+    let listing = flat_listing(client, &scope.bucket, Some(&scope.prefix));
+
+    loop {
+        let (response, next_token) = list_page(&listing, continuation_token.as_deref()).await?;
+
+        for obj in response.contents() {
+            let Some(key) = obj.key() else { continue };
+            // Folder markers hold no content worth comparing
+            if key.ends_with('/') {
+                continue;
+            }
+            let relative = key.strip_prefix(scope.prefix.as_str()).unwrap_or(key);
+            objects.insert(
+                relative.to_string(),
+                ListedObject {
+                    size: obj.size().unwrap_or(0),
+                    etag: obj.e_tag().map(|e| e.trim_matches('"').to_string()),
+                },
+            );
+        }
+
+        match next_token {
+            Some(token) => continuation_token = Some(token),
+            None => break,
+        }
+    }
+
+    Ok(objects)
+}
+
+/// Compare the objects under two bucket/prefix scopes, which may belong to
+/// different accounts. Objects are matched by key relative to each prefix and
+/// compared by size, then by ETag when both are single-part (plain MD5) ETags.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn compare_buckets(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    scope_a: CompareScope,
+    scope_b: CompareScope,
+    max_results: Option<usize>,
+) -> Result<BucketComparison, AppError> {
+    let account_a = credentials.get_account(&scope_a.account_id)?;
+    let secret_a = credentials.get_secret_key(&scope_a.account_id)?;
+    let client_a = s3_clients
+        .get_or_create_client(
+            &scope_a.account_id,
+            &account_a.endpoint,
+            &account_a.access_key_id,
+            &secret_a,
+            account_a.provider_type,
+            account_a.region.as_deref(),
+            &account_a.client_options(),
+        )
+        .await?;
+
+    let account_b = credentials.get_account(&scope_b.account_id)?;
+    let secret_b = credentials.get_secret_key(&scope_b.account_id)?;
+    let client_b = s3_clients
+        .get_or_create_client(
+            &scope_b.account_id,
+            &account_b.endpoint,
+            &account_b.access_key_id,
+            &secret_b,
+            account_b.provider_type,
+            account_b.region.as_deref(),
+            &account_b.client_options(),
+        )
+        .await?;
+
+    let (listed_a, listed_b) = tokio::join!(
+        list_scope(&client_a, &scope_a),
+        list_scope(&client_b, &scope_b)
+    );
+    let listed_a = listed_a?;
+    let mut listed_b = listed_b?;
+
+    let max = max_results.unwrap_or(COMPARE_DEFAULT_MAX_RESULTS);
+    let mut comparison = BucketComparison {
+        only_in_a: Vec::new(),
+        only_in_b: Vec::new(),
+        different: Vec::new(),
+        only_in_a_count: 0,
+        only_in_b_count: 0,
+        different_count: 0,
+        identical_count: 0,
+        unverified_count: 0,
+        only_in_a_bytes: 0,
+        only_in_b_bytes: 0,
+        different_bytes_delta: 0,
+        truncated: false,
+    };
+
+    for (key, a) in listed_a {
+        let Some(b) = listed_b.remove(&key) else {
+            comparison.only_in_a_count += 1;
+            comparison.only_in_a_bytes += a.size;
+            comparison.only_in_a.push(ComparedObject {
+                key,
+                size_a: Some(a.size),
+                size_b: None,
+                etag_a: a.etag,
+                etag_b: None,
+                reason: None,
+            });
+            continue;
+        };
+
+        let reason = if a.size != b.size {
+            Some(DiffReason::Size)
+        } else if a.etag == b.etag {
+            None
+        } else if is_multipart_etag(a.etag.as_deref()) || is_multipart_etag(b.etag.as_deref()) {
+            comparison.unverified_count += 1;
+            continue;
+        } else {
+            Some(DiffReason::Etag)
+        };
+
+        match reason {
+            Some(reason) => {
+                comparison.different_count += 1;
+                comparison.different_bytes_delta += b.size - a.size;
+                comparison.different.push(ComparedObject {
+                    key,
+                    size_a: Some(a.size),
+                    size_b: Some(b.size),
+                    etag_a: a.etag,
+                    etag_b: b.etag,
+                    reason: Some(reason),
+                });
+            }
+            None => comparison.identical_count += 1,
+        }
+    }
+
+    for (key, b) in listed_b {
+        comparison.only_in_b_count += 1;
+        comparison.only_in_b_bytes += b.size;
+        comparison.only_in_b.push(ComparedObject {
+            key,
+            size_a: None,
+            size_b: Some(b.size),
+            etag_a: None,
+            etag_b: b.etag,
+            reason: None,
+        });
+    }
+
+    for list in [
+        &mut comparison.only_in_a,
+        &mut comparison.only_in_b,
+        &mut comparison.different,
+    ] {
+        list.sort_by(|x, y| x.key.cmp(&y.key));
+        if list.len() > max {
+            list.truncate(max);
+            comparison.truncated = true;
+        }
+    }
+
+    Ok(comparison)
+}
+
+/// Multipart ETags end in `-<part count>` and depend on the part size used
+fn is_multipart_etag(etag: Option<&str>) -> bool {
+    etag.map_or(false, |e| e.contains('-'))
+}
@@ -1,8 +1,67 @@
-use crate::credentials::{Account, CredentialsManager};
+use crate::credentials::{Account, CredentialBackend, CredentialBackendStatus, CredentialsManager};
 use crate::error::AppError;
 use crate::provider::ProviderType;
 use crate::s3::client::S3ClientManager;
-use tauri::State;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::net::{lookup_host, TcpStream};
+use tokio::sync::RwLock;
+use tokio::time::timeout;
+
+/// Timeout applied to each network stage of `diagnose_connection`
+const DIAGNOSTIC_STAGE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Timeout for the authenticated call made by account health checks
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Global state holding the latest health check result per account
+#[derive(Default)]
+pub struct AccountHealthState {
+    /// Map of account_id -> last health check result
+    pub results: RwLock<HashMap<String, AccountHealth>>,
+}
+
+/// Outcome category of an account health check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Healthy,
+    InvalidCredentials,
+    AccessDenied,
+    NetworkError,
+    Error,
+}
+
+/// Result of an account health check
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountHealth {
+    pub account_id: String,
+    pub status: HealthStatus,
+    pub message: Option<String>,
+    /// Age of the secret key in days, when the account records when it was set
+    pub key_age_days: Option<i64>,
+    pub checked_at: String,
+}
+
+/// Public base URLs end up in `src` attributes, so only http(s) is accepted.
+/// An empty string is allowed: it clears the URL on update.
+fn validate_public_base_url(public_base_url: Option<&str>) -> Result<(), AppError> {
+    match public_base_url.map(str::trim) {
+        Some(url)
+            if !url.is_empty() && !url.starts_with("https://") && !url.starts_with("http://") =>
+        {
+            Err(AppError::InvalidInput(format!(
+                "Public base URL must start with http:// or https://: {}",
+                url
+            )))
+        }
+        _ => Ok(()),
+    }
+}
 
 #[tauri::command(rename_all = "camelCase")]
 pub async fn add_account(
@@ -14,7 +73,15 @@ pub async fn add_account(
     provider_type: ProviderType,
     cloudflare_account_id: Option<String>,
     region: Option<String>,
+    operation_timeout_ms: Option<u64>,
+    connect_timeout_ms: Option<u64>,
+    http_proxy: Option<String>,
+    https_proxy: Option<String>,
+    no_proxy: Option<String>,
+    public_base_url: Option<String>,
 ) -> Result<Account, AppError> {
+    validate_public_base_url(public_base_url.as_deref())?;
+
     credentials.add_account(
         name,
         endpoint,
@@ -23,6 +90,12 @@ pub async fn add_account(
         provider_type,
         cloudflare_account_id,
         region,
+        operation_timeout_ms,
+        connect_timeout_ms,
+        http_proxy,
+        https_proxy,
+        no_proxy,
+        public_base_url,
     )
 }
 
@@ -65,13 +138,26 @@ pub async fn update_account(
     provider_type: Option<ProviderType>,
     cloudflare_account_id: Option<String>,
     region: Option<String>,
+    operation_timeout_ms: Option<u64>,
+    connect_timeout_ms: Option<u64>,
+    http_proxy: Option<String>,
+    https_proxy: Option<String>,
+    no_proxy: Option<String>,
+    public_base_url: Option<String>,
 ) -> Result<Account, AppError> {
-    // Invalidate cached S3 client if credentials or provider config changed
+    validate_public_base_url(public_base_url.as_deref())?;
+
+    // Invalidate cached S3 client if credentials, provider or client config changed
     if access_key_id.is_some()
         || secret_access_key.is_some()
         || endpoint.is_some()
         || provider_type.is_some()
         || region.is_some()
+        || operation_timeout_ms.is_some()
+        || connect_timeout_ms.is_some()
+        || http_proxy.is_some()
+        || https_proxy.is_some()
+        || no_proxy.is_some()
     {
         s3_clients.remove_client(&id);
     }
@@ -85,6 +171,12 @@ pub async fn update_account(
         provider_type,
         cloudflare_account_id,
         region,
+        operation_timeout_ms,
+        connect_timeout_ms,
+        http_proxy,
+        https_proxy,
+        no_proxy,
+        public_base_url,
     )
 }
 
@@ -105,12 +197,610 @@ pub async fn test_connection(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
     // Try to list buckets as a connection test
     match client.list_buckets().send().await {
         Ok(_) => Ok(true),
-        Err(e) => Err(AppError::S3(format!("Connection test failed: {:?}", e))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Result of a single diagnostic stage
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticStage {
+    /// One of "dns", "tcp", "tls", "auth"
+    pub stage: String,
+    /// One of "passed", "failed", "skipped"
+    pub status: String,
+    pub duration_ms: u64,
+    pub detail: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Staged connection diagnostics for an account
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionDiagnostics {
+    pub endpoint: String,
+    pub host: String,
+    pub port: u16,
+    pub success: bool,
+    pub failed_stage: Option<String>,
+    pub stages: Vec<DiagnosticStage>,
+}
+
+impl ConnectionDiagnostics {
+    fn record(&mut self, stage: &str, started: Instant, result: Result<String, String>) -> bool {
+        let duration_ms = started.elapsed().as_millis() as u64;
+        let passed = result.is_ok();
+        let (detail, error) = match result {
+            Ok(detail) => (Some(detail), None),
+            Err(error) => (None, Some(error)),
+        };
+        self.stages.push(DiagnosticStage {
+            stage: stage.to_string(),
+            status: if passed { "passed" } else { "failed" }.to_string(),
+            duration_ms,
+            detail,
+            error,
+        });
+        if !passed {
+            self.success = false;
+            self.failed_stage = Some(stage.to_string());
+        }
+        passed
+    }
+
+    fn skip(&mut self, stage: &str, reason: &str) {
+        self.stages.push(DiagnosticStage {
+            stage: stage.to_string(),
+            status: "skipped".to_string(),
+            duration_ms: 0,
+            detail: Some(reason.to_string()),
+            error: None,
+        });
+    }
+}
+
+/// Split an endpoint URL into (use_tls, host, port)
+fn parse_endpoint(endpoint: &str) -> Result<(bool, String, u16), AppError> {
+    let (use_tls, rest) = if let Some(rest) = endpoint.strip_prefix("https://") {
+        (true, rest)
+    } else if let Some(rest) = endpoint.strip_prefix("http://") {
+        (false, rest)
+    } else {
+        (true, endpoint)
+    };
+
+    let authority = rest.split('/').next().unwrap_or_default();
+    if authority.is_empty() {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid endpoint: {}",
+            endpoint
+        )));
+    }
+
+    let default_port = if use_tls { 443 } else { 80 };
+    let (host, port) = match authority.rsplit_once(':') {
+        // Ignore colons inside bracketed IPv6 literals
+        Some((host, port)) if !port.contains(']') => {
+            let port = port.parse::<u16>().map_err(|_| {
+                AppError::InvalidInput(format!("Invalid port in endpoint: {}", endpoint))
+            })?;
+            (host, port)
+        }
+        _ => (authority, default_port),
+    };
+
+    Ok((
+        use_tls,
+        host.trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string(),
+        port,
+    ))
+}
+
+/// Run staged connectivity checks (DNS, TCP, TLS, authenticated request) so a
+/// failed connection can be narrowed down to the stage that broke
+#[tauri::command(rename_all = "camelCase")]
+pub async fn diagnose_connection(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    account_id: String,
+    bucket: Option<String>,
+) -> Result<ConnectionDiagnostics, AppError> {
+    let account = credentials.get_account(&account_id)?;
+
+    // AWS accounts usually leave the endpoint empty and rely on the regional default
+    let endpoint = if account.endpoint.is_empty() {
+        let region = account
+            .region
+            .clone()
+            .unwrap_or_else(|| account.provider_type.default_region().to_string());
+        format!("https://s3.{}.amazonaws.com", region)
+    } else {
+        account.endpoint.clone()
+    };
+
+    let (use_tls, host, port) = parse_endpoint(&endpoint)?;
+    let mut report = ConnectionDiagnostics {
+        endpoint,
+        host: host.clone(),
+        port,
+        success: true,
+        failed_stage: None,
+        stages: Vec::new(),
+    };
+
+    // Stage 1: DNS resolution
+    let started = Instant::now();
+    let dns = match timeout(DIAGNOSTIC_STAGE_TIMEOUT, lookup_host((host.as_str(), port))).await {
+        Ok(Ok(addrs)) => {
+            let addrs: Vec<_> = addrs.collect();
+            if addrs.is_empty() {
+                Err(format!("No addresses found for {}", host))
+            } else {
+                Ok(addrs)
+            }
+        }
+        Ok(Err(e)) => Err(format!("DNS lookup failed: {}", e)),
+        Err(_) => Err("DNS lookup timed out".to_string()),
+    };
+    let addrs = match dns {
+        Ok(addrs) => {
+            let detail = addrs
+                .iter()
+                .map(|a| a.ip().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            report.record("dns", started, Ok(detail));
+            addrs
+        }
+        Err(e) => {
+            report.record("dns", started, Err(e));
+            return Ok(report);
+        }
+    };
+
+    // Stage 2: TCP connect
+    let started = Instant::now();
+    let tcp = match timeout(DIAGNOSTIC_STAGE_TIMEOUT, TcpStream::connect(&addrs[..])).await {
+        Ok(Ok(stream)) => Ok(stream),
+        Ok(Err(e)) => Err(format!("TCP connect failed: {}", e)),
+        Err(_) => Err("TCP connect timed out".to_string()),
+    };
+    let stream = match tcp {
+        Ok(stream) => {
+            let peer = stream
+                .peer_addr()
+                .map(|a| a.to_string())
+                .unwrap_or_default();
+            report.record("tcp", started, Ok(format!("Connected to {}", peer)));
+            stream
+        }
+        Err(e) => {
+            report.record("tcp", started, Err(e));
+            return Ok(report);
+        }
+    };
+
+    // Stage 3: TLS handshake
+    if use_tls {
+        use tokio_rustls::rustls::{pki_types::ServerName, ClientConfig, RootCertStore};
+
+        let started = Instant::now();
+        let mut roots = RootCertStore::empty();
+        roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        let config = ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+
+        let tls = match ServerName::try_from(host.clone()) {
+            Ok(server_name) => {
+                match timeout(
+                    DIAGNOSTIC_STAGE_TIMEOUT,
+                    connector.connect(server_name, stream),
+                )
+                .await
+                {
+                    Ok(Ok(_)) => Ok("TLS handshake succeeded".to_string()),
+                    Ok(Err(e)) => Err(format!("TLS handshake failed: {}", e)),
+                    Err(_) => Err("TLS handshake timed out".to_string()),
+                }
+            }
+            Err(e) => Err(format!("Invalid TLS server name: {}", e)),
+        };
+        if !report.record("tls", started, tls) {
+            return Ok(report);
+        }
+    } else {
+        report.skip("tls", "Endpoint uses plain HTTP");
     }
+
+    // Stage 4: authenticated request
+    let started = Instant::now();
+    let auth = async {
+        let secret = credentials
+            .get_secret_key(&account_id)
+            .map_err(|e| e.to_string())?;
+        let client = s3_clients
+            .get_or_create_client(
+                &account_id,
+                &account.endpoint,
+                &account.access_key_id,
+                &secret,
+                account.provider_type,
+                account.region.as_deref(),
+                &account.client_options(),
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let request = async {
+            match &bucket {
+                Some(bucket) => client
+                    .head_bucket()
+                    .bucket(bucket)
+                    .send()
+                    .await
+                    .map(|_| format!("HeadBucket on '{}' succeeded", bucket))
+                    .map_err(|e| format!("HeadBucket failed: {:?}", e)),
+                None => client
+                    .list_buckets()
+                    .send()
+                    .await
+                    .map(|r| format!("ListBuckets returned {} bucket(s)", r.buckets().len()))
+                    .map_err(|e| format!("ListBuckets failed: {:?}", e)),
+            }
+        };
+
+        match timeout(DIAGNOSTIC_STAGE_TIMEOUT, request).await {
+            Ok(result) => result,
+            Err(_) => Err("Authenticated request timed out".to_string()),
+        }
+    }
+    .await;
+    report.record("auth", started, auth);
+
+    Ok(report)
+}
+
+/// Prefix for the throwaway object written by the opt-in write probe
+const PERMISSION_PROBE_PREFIX: &str = ".probe/";
+
+/// What the account's credentials may do in a bucket. `None` means the
+/// capability was not probed (write and delete need `probe_write`).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionProbe {
+    pub can_list: bool,
+    pub can_read: bool,
+    pub can_write: Option<bool>,
+    pub can_delete: Option<bool>,
+    pub can_configure: bool,
+    /// Capability -> error for checks that did not pass
+    pub errors: HashMap<String, String>,
+}
+
+/// Turn a probe request into allowed/denied, recording why it was denied
+fn probe_outcome<T, E: std::fmt::Debug>(
+    errors: &mut HashMap<String, String>,
+    capability: &str,
+    result: Result<T, E>,
+) -> bool {
+    match result {
+        Ok(_) => true,
+        Err(e) => {
+            errors.insert(capability.to_string(), format!("{:?}", e));
+            false
+        }
+    }
+}
+
+/// Check what the account can do in a bucket with lightweight, non-destructive
+/// requests: a one-key listing, a HEAD on a missing key and a versioning read.
+/// With `probe_write`, an empty object is written under `.probe/` and deleted again.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn probe_permissions(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    account_id: String,
+    bucket: String,
+    probe_write: Option<bool>,
+) -> Result<PermissionProbe, AppError> {
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    let mut errors = HashMap::new();
+    let probe_key = format!("{}{}", PERMISSION_PROBE_PREFIX, uuid::Uuid::new_v4());
+
+    let list = client
+        .list_objects_v2()
+        .bucket(&bucket)
+        .max_keys(1)
+        .send()
+        .await;
+    let can_list = probe_outcome(&mut errors, "list", list);
+
+    // A missing key answers 404 when reads are allowed and 403 when they aren't
+    let can_read = match client
+        .head_object()
+        .bucket(&bucket)
+        .key(&probe_key)
+        .send()
+        .await
+    {
+        Ok(_) => true,
+        Err(e) if e.raw_response().map(|r| r.status().as_u16()) == Some(404) => true,
+        Err(e) => probe_outcome(&mut errors, "read", Err::<(), _>(e)),
+    };
+
+    let versioning = client.get_bucket_versioning().bucket(&bucket).send().await;
+    let can_configure = probe_outcome(&mut errors, "configure", versioning);
+
+    let (can_write, can_delete) = if probe_write.unwrap_or(false) {
+        let put = client
+            .put_object()
+            .bucket(&bucket)
+            .key(&probe_key)
+            .body(aws_sdk_s3::primitives::ByteStream::from(Vec::new()))
+            .send()
+            .await;
+        let can_write = probe_outcome(&mut errors, "write", put);
+
+        // Deleting a missing key succeeds, so only a written probe tells us anything
+        let can_delete = if can_write {
+            let delete = client
+                .delete_object()
+                .bucket(&bucket)
+                .key(&probe_key)
+                .send()
+                .await;
+            let can_delete = probe_outcome(&mut errors, "delete", delete);
+            if !can_delete {
+                log::warn!("Permission probe left {} in bucket {}", probe_key, bucket);
+            }
+            Some(can_delete)
+        } else {
+            None
+        };
+        (Some(can_write), can_delete)
+    } else {
+        (None, None)
+    };
+
+    Ok(PermissionProbe {
+        can_list,
+        can_read,
+        can_write,
+        can_delete,
+        can_configure,
+        errors,
+    })
+}
+
+/// Classify an S3 error string into a health status
+fn classify_health_error(error: &str) -> HealthStatus {
+    if error.contains("InvalidAccessKeyId")
+        || error.contains("SignatureDoesNotMatch")
+        || error.contains("InvalidToken")
+        || error.contains("ExpiredToken")
+        || error.contains("Unauthorized")
+    {
+        HealthStatus::InvalidCredentials
+    } else if error.contains("AccessDenied") || error.contains("Forbidden") {
+        HealthStatus::AccessDenied
+    } else if error.contains("DispatchFailure")
+        || error.contains("TimeoutError")
+        || error.contains("timed out")
+        || error.contains("dns error")
+        || error.contains("Connection refused")
+    {
+        HealthStatus::NetworkError
+    } else {
+        HealthStatus::Error
+    }
+}
+
+/// Run a lightweight authenticated call for an account and classify the outcome
+pub(crate) async fn run_account_health_check(
+    credentials: &CredentialsManager,
+    s3_clients: &S3ClientManager,
+    account_id: &str,
+) -> AccountHealth {
+    let checked_at = chrono::Utc::now().to_rfc3339();
+
+    let account = match credentials.get_account(account_id) {
+        Ok(account) => account,
+        Err(e) => {
+            return AccountHealth {
+                account_id: account_id.to_string(),
+                status: HealthStatus::Error,
+                message: Some(e.to_string()),
+                key_age_days: None,
+                checked_at,
+            }
+        }
+    };
+
+    let key_age_days = account
+        .secret_updated_at
+        .map(|ts| (chrono::Utc::now().timestamp_millis() - ts) / (24 * 60 * 60 * 1000));
+
+    let result = async {
+        let secret = credentials.get_secret_key(account_id)?;
+        let client = s3_clients
+            .get_or_create_client(
+                account_id,
+                &account.endpoint,
+                &account.access_key_id,
+                &secret,
+                account.provider_type,
+                account.region.as_deref(),
+                &account.client_options(),
+            )
+            .await?;
+
+        match timeout(HEALTH_CHECK_TIMEOUT, client.list_buckets().send()).await {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(e)) => Err(e.into()),
+            Err(_) => Err(AppError::S3("Health check timed out".to_string())),
+        }
+    }
+    .await;
+
+    let (status, message) = match result {
+        Ok(()) => (HealthStatus::Healthy, None),
+        Err(AppError::S3(e)) => (classify_health_error(&e), Some(e)),
+        Err(e @ AppError::AccessDenied(_)) => (HealthStatus::AccessDenied, Some(e.to_string())),
+        Err(e) => (HealthStatus::Error, Some(e.to_string())),
+    };
+
+    AccountHealth {
+        account_id: account_id.to_string(),
+        status,
+        message,
+        key_age_days,
+        checked_at,
+    }
+}
+
+/// Check all accounts in the background and publish results as they complete.
+/// Called once on startup so the UI can flag accounts whose credentials stopped working.
+pub(crate) async fn check_all_accounts_health(app: AppHandle) {
+    let credentials = app.state::<CredentialsManager>();
+    let s3_clients = app.state::<S3ClientManager>();
+    let health_state = app.state::<AccountHealthState>();
+
+    let accounts = match credentials.list_accounts() {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            log::warn!("Skipping startup account health check: {}", e);
+            return;
+        }
+    };
+
+    for account in accounts {
+        let health = run_account_health_check(&credentials, &s3_clients, &account.id).await;
+        if health.status != HealthStatus::Healthy {
+            log::warn!(
+                "Account '{}' failed health check: {:?}",
+                account.name,
+                health.status
+            );
+        }
+
+        health_state
+            .results
+            .write()
+            .await
+            .insert(account.id.clone(), health.clone());
+        let _ = app.emit("account-health", health);
+    }
+}
+
+/// Check that an account's credentials still work
+#[tauri::command(rename_all = "camelCase")]
+pub async fn check_account_health(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    health_state: State<'_, AccountHealthState>,
+    account_id: String,
+) -> Result<AccountHealth, AppError> {
+    let health = run_account_health_check(&credentials, &s3_clients, &account_id).await;
+    health_state
+        .results
+        .write()
+        .await
+        .insert(account_id, health.clone());
+    Ok(health)
+}
+
+/// Get the latest known health check result for every account
+#[tauri::command]
+pub async fn get_account_health_statuses(
+    health_state: State<'_, AccountHealthState>,
+) -> Result<Vec<AccountHealth>, AppError> {
+    Ok(health_state
+        .results
+        .read()
+        .await
+        .values()
+        .cloned()
+        .collect())
+}
+
+/// Enable or disable wire-level logging of S3 requests
+#[tauri::command]
+pub async fn set_debug_logging(
+    s3_clients: State<'_, S3ClientManager>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    s3_clients.set_debug_logging(enabled).await;
+    log::info!(
+        "S3 debug logging {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+    Ok(())
+}
+
+/// Set how long secrets are cached in memory (0 disables the cache)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_secret_cache_ttl(
+    credentials: State<'_, CredentialsManager>,
+    ttl_seconds: u64,
+) -> Result<(), AppError> {
+    credentials.set_secret_cache_ttl(Duration::from_secs(ttl_seconds));
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_credential_backend(
+    credentials: State<'_, CredentialsManager>,
+) -> Result<CredentialBackendStatus, AppError> {
+    credentials.backend_status()
+}
+
+#[tauri::command]
+pub async fn set_credential_backend(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    backend: CredentialBackend,
+    passphrase: Option<String>,
+) -> Result<CredentialBackendStatus, AppError> {
+    credentials.set_backend(backend, passphrase.as_deref())?;
+
+    // Drop cached clients so they are rebuilt from the new backend's secrets
+    for account in credentials.list_accounts()? {
+        s3_clients.remove_client(&account.id);
+    }
+
+    credentials.backend_status()
+}
+
+#[tauri::command]
+pub async fn unlock_credential_store(
+    credentials: State<'_, CredentialsManager>,
+    passphrase: String,
+) -> Result<CredentialBackendStatus, AppError> {
+    credentials.unlock_file_store(&passphrase)?;
+    credentials.backend_status()
 }
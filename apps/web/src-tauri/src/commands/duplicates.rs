@@ -1,16 +1,27 @@
+use crate::cache::{self, BucketStatusCache, ListingCache};
+use crate::commands::buckets::ensure_bucket_accessible;
+use crate::commands::objects::{glob_match, hash_object, HashAlgorithm};
 use crate::credentials::CredentialsManager;
 use crate::db::duplicates::{
-    DuplicateGroup, DuplicateScan, HashType, NewScan, ScanSummary, ScannedFile,
+    DuplicateFile, DuplicateGroup, DuplicateScan, HashType, NewScan, ScanFilters, ScanObject,
+    ScanSummary, ScanTarget, ScannedFile,
 };
+use crate::db::settings::UploadSettings;
 use crate::db::DbManager;
 use crate::error::AppError;
+use crate::progress::ProgressThrottle;
 use crate::s3::client::S3ClientManager;
+use crate::s3::retry::{with_retry, RetryPolicy};
+use futures::stream::{self, StreamExt};
+use md5::Md5;
 use serde::Serialize;
-use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use sha2::Digest;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 use tauri::{AppHandle, Emitter, State};
+use tokio::io::AsyncReadExt;
 use tokio::sync::RwLock;
 
 /// Global state for tracking active scans
@@ -35,8 +46,14 @@ pub struct ScanProgressEvent {
     pub phase: String,
     pub files_scanned: i64,
     pub total_files: i64,
+    /// Bucket of `current_file`, or the bucket being listed
+    pub current_bucket: Option<String>,
     pub current_file: Option<String>,
     pub bytes_processed: i64,
+    /// Combined size of the files that need hashing (hashing phase only)
+    pub total_bytes_to_hash: Option<i64>,
+    /// Estimated seconds until hashing finishes (hashing phase only)
+    pub eta_secs: Option<u64>,
 }
 
 /// Completion event for scan
@@ -47,6 +64,8 @@ pub struct ScanCompleteEvent {
     pub duplicate_groups: i64,
     pub duplicate_files: i64,
     pub reclaimable_bytes: i64,
+    pub listing_ms: i64,
+    pub hashing_ms: i64,
 }
 
 /// Error event for scan
@@ -65,29 +84,130 @@ pub async fn start_duplicate_scan(
     s3_clients: State<'_, S3ClientManager>,
     db: State<'_, DbManager>,
     scan_state: State<'_, ScanState>,
+    bucket_status_cache: State<'_, BucketStatusCache>,
     account_id: String,
     bucket: String,
     prefix: Option<String>,
     hash_type: String,
     min_file_size: Option<i64>,
+    max_depth: Option<u32>,
+    include_extensions: Option<Vec<String>>,
+    exclude_extensions: Option<Vec<String>>,
+    exclude_patterns: Option<Vec<String>>,
+) -> Result<i64, AppError> {
+    start_scan(
+        app,
+        &credentials,
+        &s3_clients,
+        &db,
+        &scan_state,
+        &bucket_status_cache,
+        account_id,
+        vec![ScanTarget {
+            bucket,
+            prefix: prefix.unwrap_or_default(),
+        }],
+        hash_type,
+        min_file_size,
+        max_depth,
+        include_extensions,
+        exclude_extensions,
+        exclude_patterns,
+    )
+    .await
+}
+
+/// Start a duplicate scan across several buckets and prefixes of one account.
+/// Files are grouped by content regardless of which bucket holds them.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn start_cross_bucket_duplicate_scan(
+    app: AppHandle,
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    scan_state: State<'_, ScanState>,
+    bucket_status_cache: State<'_, BucketStatusCache>,
+    account_id: String,
+    targets: Vec<ScanTarget>,
+    hash_type: String,
+    min_file_size: Option<i64>,
+    max_depth: Option<u32>,
+    include_extensions: Option<Vec<String>>,
+    exclude_extensions: Option<Vec<String>>,
+    exclude_patterns: Option<Vec<String>>,
+) -> Result<i64, AppError> {
+    let mut unique_targets: Vec<ScanTarget> = Vec::new();
+    for target in targets {
+        if target.bucket.trim().is_empty() {
+            return Err(AppError::InvalidInput("Scan targets need a bucket".into()));
+        }
+        if !unique_targets.contains(&target) {
+            unique_targets.push(target);
+        }
+    }
+    if unique_targets.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Select at least one bucket to scan".into(),
+        ));
+    }
+
+    start_scan(
+        app,
+        &credentials,
+        &s3_clients,
+        &db,
+        &scan_state,
+        &bucket_status_cache,
+        account_id,
+        unique_targets,
+        hash_type,
+        min_file_size,
+        max_depth,
+        include_extensions,
+        exclude_extensions,
+        exclude_patterns,
+    )
+    .await
+}
+
+/// Record a scan over `targets` and run it in the background
+async fn start_scan(
+    app: AppHandle,
+    credentials: &CredentialsManager,
+    s3_clients: &S3ClientManager,
+    db: &DbManager,
+    scan_state: &ScanState,
+    bucket_status_cache: &BucketStatusCache,
+    account_id: String,
+    targets: Vec<ScanTarget>,
+    hash_type: String,
+    min_file_size: Option<i64>,
+    max_depth: Option<u32>,
+    include_extensions: Option<Vec<String>>,
+    exclude_extensions: Option<Vec<String>>,
+    exclude_patterns: Option<Vec<String>>,
 ) -> Result<i64, AppError> {
-    let prefix = prefix.unwrap_or_default();
     let hash_type = HashType::try_from(hash_type.as_str())?;
     let min_size = min_file_size.unwrap_or(0);
 
-    // Create scan record
-    let scan_id = db.create_scan(&NewScan {
-        account_id: account_id.clone(),
-        bucket: bucket.clone(),
-        prefix: prefix.clone(),
-    })?;
-
-    // Set up cancellation token
-    let cancel_flag = Arc::new(AtomicBool::new(false));
-    {
-        let mut scans = scan_state.active_scans.write().await;
-        scans.insert(scan_id, cancel_flag.clone());
-    }
+    // Extensions are compared lowercase and without the leading dot
+    let normalize = |extensions: Option<Vec<String>>| -> Vec<String> {
+        extensions
+            .unwrap_or_default()
+            .iter()
+            .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+            .filter(|ext| !ext.is_empty())
+            .collect()
+    };
+    let filters = ScanFilters {
+        include_extensions: normalize(include_extensions),
+        exclude_extensions: normalize(exclude_extensions),
+        exclude_patterns: exclude_patterns
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|pattern| !pattern.trim().is_empty())
+            .collect(),
+    };
 
     // Get S3 client
     let account = credentials.get_account(&account_id)?;
@@ -100,14 +220,37 @@ pub async fn start_duplicate_scan(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
+    // Check the buckets before recording a scan that would fail immediately
+    let mut checked: HashSet<&str> = HashSet::new();
+    for target in &targets {
+        if checked.insert(&target.bucket) {
+            ensure_bucket_accessible(&client, bucket_status_cache, &account_id, &target.bucket)
+                .await?;
+        }
+    }
+
+    // Create scan record
+    let scan_id = db.create_scan(&NewScan {
+        account_id: account_id.clone(),
+        targets: targets.clone(),
+        max_depth: max_depth.map(i64::from),
+        filters: filters.clone(),
+    })?;
+
+    // Set up cancellation token
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    {
+        let mut scans = scan_state.active_scans.write().await;
+        scans.insert(scan_id, cancel_flag.clone());
+    }
+
     // Clone values for the async task
-    let db_clone = (*db).clone();
+    let db_clone = db.clone();
     let app_clone = app.clone();
-    let bucket_clone = bucket.clone();
-    let prefix_clone = prefix.clone();
 
     // Spawn async scan task
     tokio::spawn(async move {
@@ -116,10 +259,11 @@ pub async fn start_duplicate_scan(
             &client,
             &db_clone,
             scan_id,
-            &bucket_clone,
-            &prefix_clone,
+            &targets,
             hash_type,
             min_size,
+            max_depth,
+            &filters,
             cancel_flag.clone(),
         )
         .await;
@@ -145,10 +289,11 @@ async fn run_scan(
     client: &aws_sdk_s3::Client,
     db: &DbManager,
     scan_id: i64,
-    bucket: &str,
-    prefix: &str,
+    targets: &[ScanTarget],
     hash_type: HashType,
     min_size: i64,
+    max_depth: Option<u32>,
+    filters: &ScanFilters,
     cancel_flag: Arc<AtomicBool>,
 ) -> Result<(), AppError> {
     // Phase 1: List all objects
@@ -159,98 +304,137 @@ async fn run_scan(
             phase: "listing".to_string(),
             files_scanned: 0,
             total_files: 0,
+            current_bucket: None,
             current_file: None,
             bytes_processed: 0,
+            total_bytes_to_hash: None,
+            eta_secs: None,
         },
     );
 
     let mut all_files: Vec<ScannedFile> = Vec::new();
-    let mut continuation_token: Option<String> = None;
+    let mut seen: HashSet<(String, String)> = HashSet::new();
     let mut total_size: i64 = 0;
+    let mut progress = ProgressThrottle::new();
+    let listing_started = Instant::now();
 
-    loop {
-        if cancel_flag.load(Ordering::Relaxed) {
-            db.cancel_scan(scan_id)?;
-            return Ok(());
-        }
+    for target in targets {
+        let bucket = target.bucket.as_str();
+        let prefix = target.prefix.as_str();
+        let mut continuation_token: Option<String> = None;
 
-        let mut request = client.list_objects_v2().bucket(bucket);
+        loop {
+            if cancel_flag.load(Ordering::Relaxed) {
+                db.cancel_scan(scan_id)?;
+                return Ok(());
+            }
 
-        if !prefix.is_empty() {
-            request = request.prefix(prefix);
-        }
+            let mut request = client.list_objects_v2().bucket(bucket);
 
-        if let Some(token) = &continuation_token {
-            request = request.continuation_token(token);
-        }
+            if !prefix.is_empty() {
+                request = request.prefix(prefix);
+            }
 
-        let response = request.send().await?;
+            if let Some(token) = &continuation_token {
+                request = request.continuation_token(token);
+            }
 
-        for obj in response.contents() {
-            if let Some(key) = obj.key() {
-                // Skip folder markers
-                if key.ends_with('/') {
-                    continue;
-                }
+            let response = request.send().await?;
 
-                let size = obj.size().unwrap_or(0);
+            for obj in response.contents() {
+                if let Some(key) = obj.key() {
+                    // Skip folder markers
+                    if key.ends_with('/') {
+                        continue;
+                    }
+
+                    // Overlapping targets in one bucket list some keys twice
+                    if !seen.insert((bucket.to_string(), key.to_string())) {
+                        continue;
+                    }
 
-                // Skip files smaller than min_size
-                if size < min_size {
-                    continue;
+                    let size = obj.size().unwrap_or(0);
+
+                    // Skip files smaller than min_size
+                    if size < min_size {
+                        continue;
+                    }
+
+                    // Skip files nested deeper than max_depth
+                    if max_depth.is_some_and(|max| depth_below_prefix(key, prefix) > max) {
+                        continue;
+                    }
+
+                    if !passes_filters(key, filters) {
+                        continue;
+                    }
+
+                    total_size += size;
+                    all_files.push(ScannedFile {
+                        bucket: bucket.to_string(),
+                        key: key.to_string(),
+                        size,
+                        etag: obj.e_tag().map(|e| e.trim_matches('"').to_string()),
+                        last_modified: obj.last_modified().and_then(|d| {
+                            d.secs().try_into().ok()
+                        }),
+                        storage_class: obj.storage_class().map(|s| s.as_str().to_string()),
+                        content_hash: None,
+                    });
                 }
+            }
 
-                total_size += size;
-                all_files.push(ScannedFile {
-                    key: key.to_string(),
-                    size,
-                    etag: obj.e_tag().map(|e| e.trim_matches('"').to_string()),
-                    last_modified: obj.last_modified().and_then(|d| {
-                        d.secs().try_into().ok()
-                    }),
-                    storage_class: obj.storage_class().map(|s| s.as_str().to_string()),
-                    content_hash: None,
-                });
+            // Update progress
+            db.update_scan_progress(scan_id, all_files.len() as i64, total_size)?;
+
+            if progress.ready(false) {
+                let _ = app.emit(
+                    "scan-progress",
+                    ScanProgressEvent {
+                        scan_id,
+                        phase: "listing".to_string(),
+                        files_scanned: all_files.len() as i64,
+                        total_files: all_files.len() as i64,
+                        current_bucket: Some(bucket.to_string()),
+                        current_file: None,
+                        bytes_processed: total_size,
+                        total_bytes_to_hash: None,
+                        eta_secs: None,
+                    },
+                );
             }
-        }
 
-        // Update progress
-        db.update_scan_progress(scan_id, all_files.len() as i64, total_size)?;
-
-        let _ = app.emit(
-            "scan-progress",
-            ScanProgressEvent {
-                scan_id,
-                phase: "listing".to_string(),
-                files_scanned: all_files.len() as i64,
-                total_files: all_files.len() as i64,
-                current_file: None,
-                bytes_processed: total_size,
-            },
-        );
-
-        if response.is_truncated() == Some(true) {
-            continuation_token = response.next_continuation_token().map(|s| s.to_string());
-        } else {
-            break;
+            if response.is_truncated() == Some(true) {
+                continuation_token = response.next_continuation_token().map(|s| s.to_string());
+            } else {
+                break;
+            }
         }
     }
 
-    let total_files = all_files.len() as i64;
-
-    // Phase 2: Group by hash
+    // Always report the final listing counts, even if the last page fell
+    // inside the throttle window
+    progress.ready(true);
     let _ = app.emit(
         "scan-progress",
         ScanProgressEvent {
             scan_id,
-            phase: "hashing".to_string(),
-            files_scanned: 0,
-            total_files,
+            phase: "listing".to_string(),
+            files_scanned: all_files.len() as i64,
+            total_files: all_files.len() as i64,
+            current_bucket: None,
             current_file: None,
-            bytes_processed: 0,
+            bytes_processed: total_size,
+            total_bytes_to_hash: None,
+            eta_secs: None,
         },
     );
 
+    let total_files = all_files.len() as i64;
+    let listing_ms = listing_started.elapsed().as_millis() as i64;
+    db.set_scan_listing_time(scan_id, listing_ms)?;
+
+    // Phase 2: Group by hash
     // Group files by size first (optimization - same size is necessary for duplicates)
     let mut by_size: HashMap<i64, Vec<ScannedFile>> = HashMap::new();
     for file in all_files {
@@ -263,9 +447,32 @@ async fn run_scan(
         .filter(|files| files.len() > 1)
         .collect();
 
+    // Known once grouping is done, so the hashing phase can show real progress
+    let total_bytes_to_hash: i64 = candidate_groups.iter().flatten().map(|f| f.size).sum();
+    let hashing_started = Instant::now();
+
+    let _ = app.emit(
+        "scan-progress",
+        ScanProgressEvent {
+            scan_id,
+            phase: "hashing".to_string(),
+            files_scanned: 0,
+            total_files,
+            current_bucket: None,
+            current_file: None,
+            bytes_processed: 0,
+            total_bytes_to_hash: Some(total_bytes_to_hash),
+            eta_secs: None,
+        },
+    );
+
     let files_processed = Arc::new(AtomicI64::new(0));
     let bytes_processed = Arc::new(AtomicI64::new(0));
 
+    // Composite ETags are compared against the part layout our uploader would use
+    let upload_settings = db.get_upload_settings()?;
+    let retry = RetryPolicy::from_settings(&db.get_app_settings()?);
+
     // Process each size group
     let mut duplicate_groups_count = 0i64;
     let mut duplicate_files_count = 0i64;
@@ -279,6 +486,18 @@ async fn run_scan(
 
         // For each size group, compute hashes and find actual duplicates
         let mut hash_groups: HashMap<String, Vec<ScannedFile>> = HashMap::new();
+        let mut heuristic_hashes: std::collections::HashSet<String> =
+            std::collections::HashSet::new();
+
+        // In composite mode, plain-MD5 ETags only need converting when the same size
+        // group also holds multipart uploads made with our part size
+        let bridge_plain_etags = hash_type == HashType::EtagComposite
+            && size_group.iter().any(|f| {
+                matches!(classify_etag(f, &upload_settings), EtagKind::KnownMultipart(_))
+            })
+            && size_group
+                .iter()
+                .any(|f| matches!(classify_etag(f, &upload_settings), EtagKind::Plain(_)));
 
         for mut file in size_group {
             if cancel_flag.load(Ordering::Relaxed) {
@@ -293,7 +512,7 @@ async fn run_scan(
                 }
                 HashType::Sha256 => {
                     // Download and compute SHA-256 (accurate mode)
-                    match compute_sha256(client, bucket, &file.key).await {
+                    match compute_sha256(client, &file.bucket, &file.key, retry).await {
                         Ok(h) => h,
                         Err(e) => {
                             log::warn!("Failed to hash {}: {}", file.key, e);
@@ -301,6 +520,46 @@ async fn run_scan(
                         }
                     }
                 }
+                HashType::Blake3 => {
+                    // Download and compute BLAKE3 (accurate mode, faster than SHA-256)
+                    match compute_blake3(client, &file.bucket, &file.key, retry).await {
+                        Ok(h) => h,
+                        Err(e) => {
+                            log::warn!("Failed to hash {}: {}", file.key, e);
+                            continue;
+                        }
+                    }
+                }
+                HashType::EtagComposite => match classify_etag(&file, &upload_settings) {
+                    EtagKind::Plain(etag) if bridge_plain_etags => {
+                        // Convert to the composite form our uploader would produce
+                        let part_size =
+                            upload_settings.effective_part_size(file.size.max(0) as u64) as usize;
+                        match compute_composite_md5(
+                            client,
+                            &file.bucket,
+                            &file.key,
+                            part_size,
+                            retry,
+                        )
+                        .await
+                        {
+                            Ok(h) => format!("mpu:{}", h),
+                            Err(e) => {
+                                log::warn!("Failed to hash {}: {}", file.key, e);
+                                format!("md5:{}", etag)
+                            }
+                        }
+                    }
+                    EtagKind::Plain(etag) => format!("md5:{}", etag),
+                    EtagKind::KnownMultipart(etag) => format!("mpu:{}", etag),
+                    EtagKind::UnknownMultipart(etag) => {
+                        let hash = format!("etag:{}", etag);
+                        heuristic_hashes.insert(hash.clone());
+                        hash
+                    }
+                    EtagKind::Missing => String::new(),
+                },
             };
 
             if !hash.is_empty() {
@@ -312,8 +571,8 @@ async fn run_scan(
             let processed = files_processed.fetch_add(1, Ordering::Relaxed) + 1;
             let bytes = bytes_processed.fetch_add(file.size, Ordering::Relaxed) + file.size;
 
-            // Emit progress every 10 files or so
-            if processed % 10 == 0 {
+            // Emit progress, coalesced to a steady cadence
+            if progress.ready(false) {
                 let _ = app.emit(
                     "scan-progress",
                     ScanProgressEvent {
@@ -321,8 +580,11 @@ async fn run_scan(
                         phase: "hashing".to_string(),
                         files_scanned: processed,
                         total_files,
+                        current_bucket: Some(file.bucket.clone()),
                         current_file: Some(file.key.clone()),
                         bytes_processed: bytes,
+                        total_bytes_to_hash: Some(total_bytes_to_hash),
+                        eta_secs: hashing_eta(hashing_started, bytes, total_bytes_to_hash),
                     },
                 );
             }
@@ -334,7 +596,8 @@ async fn run_scan(
                 let file_size = files[0].size;
                 let file_count = files.len() as i64;
 
-                db.save_duplicate_group(scan_id, &hash, hash_type, file_size, &files)?;
+                let heuristic = heuristic_hashes.contains(&hash);
+                db.save_duplicate_group(scan_id, &hash, hash_type, file_size, heuristic, &files)?;
 
                 duplicate_groups_count += 1;
                 duplicate_files_count += file_count;
@@ -343,12 +606,31 @@ async fn run_scan(
         }
     }
 
+    // Always report the final hashing counts before the scan completes
+    progress.ready(true);
+    let _ = app.emit(
+        "scan-progress",
+        ScanProgressEvent {
+            scan_id,
+            phase: "hashing".to_string(),
+            files_scanned: files_processed.load(Ordering::Relaxed),
+            total_files,
+            current_bucket: None,
+            current_file: None,
+            bytes_processed: bytes_processed.load(Ordering::Relaxed),
+            total_bytes_to_hash: Some(total_bytes_to_hash),
+            eta_secs: Some(0),
+        },
+    );
+
     // Complete the scan
+    let hashing_ms = hashing_started.elapsed().as_millis() as i64;
     db.complete_scan(
         scan_id,
         duplicate_groups_count,
         duplicate_files_count,
         reclaimable_bytes,
+        hashing_ms,
     )?;
 
     let _ = app.emit(
@@ -358,31 +640,161 @@ async fn run_scan(
             duplicate_groups: duplicate_groups_count,
             duplicate_files: duplicate_files_count,
             reclaimable_bytes,
+            listing_ms,
+            hashing_ms,
         },
     );
 
     Ok(())
 }
 
+/// Remaining hashing time, extrapolated from the average rate so far
+fn hashing_eta(started: Instant, bytes_done: i64, total_bytes: i64) -> Option<u64> {
+    if bytes_done <= 0 {
+        return None;
+    }
+    let remaining = (total_bytes - bytes_done).max(0) as f64;
+    let elapsed = started.elapsed().as_secs_f64();
+    Some((elapsed * remaining / bytes_done as f64).ceil() as u64)
+}
+
+/// Number of folder levels between a key and the scan prefix: 0 for files directly
+/// in the prefix's folder. A prefix not ending in '/' is measured from its parent
+/// folder, so "photos/2024" treats "photos/2024-01/a.jpg" as depth 1.
+fn depth_below_prefix(key: &str, prefix: &str) -> u32 {
+    let base_len = prefix.rfind('/').map(|i| i + 1).unwrap_or(0);
+    key.get(base_len..)
+        .map(|relative| relative.matches('/').count() as u32)
+        .unwrap_or(0)
+}
+
+/// Whether a key survives the scan's extension and pattern filters
+fn passes_filters(key: &str, filters: &ScanFilters) -> bool {
+    let file_name = key.rsplit('/').next().unwrap_or(key);
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_lowercase())
+        .unwrap_or_default();
+
+    if !filters.include_extensions.is_empty() && !filters.include_extensions.contains(&extension)
+    {
+        return false;
+    }
+    if filters.exclude_extensions.contains(&extension) {
+        return false;
+    }
+
+    !filters.exclude_patterns.iter().any(|pattern| {
+        let target = if pattern.contains('/') { key } else { file_name };
+        glob_match(pattern, target)
+    })
+}
+
 /// Compute SHA-256 hash of an S3 object
 async fn compute_sha256(
     client: &aws_sdk_s3::Client,
     bucket: &str,
     key: &str,
+    retry: RetryPolicy,
 ) -> Result<String, AppError> {
-    let response = client.get_object().bucket(bucket).key(key).send().await?;
+    Ok(hash_object(client, bucket, key, HashAlgorithm::Sha256, None, retry)
+        .await?
+        .hash)
+}
 
-    let body = response
-        .body
-        .collect()
-        .await
-        .map_err(|e| AppError::S3(format!("Failed to read body: {:?}", e)))?;
+/// Compute BLAKE3 hash of an S3 object
+async fn compute_blake3(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+    retry: RetryPolicy,
+) -> Result<String, AppError> {
+    Ok(hash_object(client, bucket, key, HashAlgorithm::Blake3, None, retry)
+        .await?
+        .hash)
+}
 
-    let mut hasher = Sha256::new();
-    hasher.update(body.into_bytes());
-    let result = hasher.finalize();
+/// How an object's ETag relates to its content
+enum EtagKind {
+    /// Single-part upload: the ETag is the MD5 of the content
+    Plain(String),
+    /// Multipart upload whose part count matches our uploader's part size
+    KnownMultipart(String),
+    /// Multipart upload with an unknown part size
+    UnknownMultipart(String),
+    Missing,
+}
 
-    Ok(hex::encode(result))
+fn classify_etag(file: &ScannedFile, upload_settings: &UploadSettings) -> EtagKind {
+    let Some(etag) = file.etag.clone().filter(|e| !e.is_empty()) else {
+        return EtagKind::Missing;
+    };
+
+    let Some((_, parts)) = etag.rsplit_once('-') else {
+        return EtagKind::Plain(etag);
+    };
+
+    let size = file.size.max(0) as u64;
+    let expected_parts = if size > upload_settings.multipart_threshold {
+        size.div_ceil(upload_settings.effective_part_size(size))
+    } else {
+        0
+    };
+
+    match parts.parse::<u64>() {
+        Ok(n) if n == expected_parts => EtagKind::KnownMultipart(etag),
+        _ => EtagKind::UnknownMultipart(etag),
+    }
+}
+
+/// Compute the multipart-style ETag (MD5 of the concatenated part MD5s, with a
+/// `-N` suffix) an object would have if uploaded with the given part size
+async fn compute_composite_md5(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+    part_size: usize,
+    retry: RetryPolicy,
+) -> Result<String, AppError> {
+    let response = with_retry(retry, || client.get_object().bucket(bucket).key(key).send()).await?;
+    let mut body = response.body.into_async_read();
+
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut part_hasher = Md5::new();
+    let mut part_len = 0usize;
+    let mut part_digests: Vec<u8> = Vec::new();
+    let mut part_count = 0usize;
+
+    loop {
+        let bytes_read = body
+            .read(&mut buffer)
+            .await
+            .map_err(|e| AppError::S3(format!("Failed to read body: {}", e)))?;
+        if bytes_read == 0 {
+            break;
+        }
+
+        let mut data = &buffer[..bytes_read];
+        while !data.is_empty() {
+            let take = (part_size - part_len).min(data.len());
+            part_hasher.update(&data[..take]);
+            part_len += take;
+            data = &data[take..];
+
+            if part_len == part_size {
+                part_digests.extend_from_slice(&part_hasher.finalize_reset());
+                part_count += 1;
+                part_len = 0;
+            }
+        }
+    }
+
+    if part_len > 0 {
+        part_digests.extend_from_slice(&part_hasher.finalize_reset());
+        part_count += 1;
+    }
+
+    Ok(format!("{}-{}", hex::encode(Md5::digest(&part_digests)), part_count))
 }
 
 /// Cancel a running scan
@@ -444,6 +856,212 @@ pub async fn delete_scan(db: State<'_, DbManager>, scan_id: i64) -> Result<(), A
     db.delete_scan(scan_id)
 }
 
+/// Concurrent downloads when verifying a duplicate group by content
+const VERIFY_CONCURRENCY: usize = 4;
+
+/// Outcome of re-checking a duplicate group by content hash
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyGroupResult {
+    /// Groups of files confirmed identical by SHA-256
+    pub groups: Vec<DuplicateGroup>,
+    /// Files whose content matched no other file in the group
+    pub unique_keys: Vec<String>,
+    /// Same files as `unique_keys`, with their buckets
+    pub unique_objects: Vec<ScanObject>,
+}
+
+/// Download and SHA-256 hash every file in a duplicate group, replacing the
+/// group with sub-groups of files that are truly identical. Groups found by
+/// ETag can hold false positives; verifying before deleting rules them out.
+/// The group is left unchanged if any file fails to hash.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn verify_duplicate_group(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    account_id: String,
+    bucket: String,
+    group_id: i64,
+) -> Result<VerifyGroupResult, AppError> {
+    let group = db
+        .get_duplicate_group(group_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Duplicate group {} not found", group_id)))?;
+
+    let scan = db
+        .get_scan(group.scan_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Scan {} not found", group.scan_id)))?;
+    if scan.account_id != account_id || !scan.targets.iter().any(|t| t.bucket == bucket) {
+        return Err(AppError::InvalidInput(
+            "Duplicate group does not belong to this bucket".into(),
+        ));
+    }
+
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    let retry = RetryPolicy::from_settings(&db.get_app_settings()?);
+    let client = &client;
+    let hashed: Vec<(DuplicateFile, Result<String, AppError>)> = stream::iter(group.files)
+        .map(|file| async move {
+            let hash = compute_sha256(client, &file.bucket, &file.key, retry).await;
+            (file, hash)
+        })
+        .buffer_unordered(VERIFY_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut by_hash: HashMap<String, Vec<DuplicateFile>> = HashMap::new();
+    for (file, hash) in hashed {
+        let hash = hash
+            .map_err(|e| AppError::S3(format!("Failed to hash {}: {}", file.key, e)))?;
+        by_hash.entry(hash).or_default().push(file);
+    }
+
+    let unique_objects: Vec<ScanObject> = by_hash
+        .values()
+        .filter(|files| files.len() == 1)
+        .map(|files| ScanObject {
+            bucket: files[0].bucket.clone(),
+            key: files[0].key.clone(),
+        })
+        .collect();
+    let unique_keys = unique_objects.iter().map(|o| o.key.clone()).collect();
+
+    let subgroups: Vec<(String, Vec<DuplicateFile>)> = by_hash.into_iter().collect();
+    let group_ids = db.split_duplicate_group(group_id, &subgroups)?;
+
+    let mut groups = Vec::new();
+    for id in group_ids {
+        if let Some(group) = db.get_duplicate_group(id)? {
+            groups.push(group);
+        }
+    }
+
+    Ok(VerifyGroupResult {
+        groups,
+        unique_keys,
+        unique_objects,
+    })
+}
+
+/// How a delete selection affects one duplicate group
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupDeletionPreview {
+    pub group_id: i64,
+    pub content_hash: String,
+    pub file_size: i64,
+    pub delete_count: i64,
+    pub remaining_count: i64,
+}
+
+/// Expected outcome of deleting a selection of duplicates
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateDeletionPreview {
+    /// Selected files found in the scan's groups
+    pub delete_count: usize,
+    pub freed_bytes: i64,
+    /// Only groups the selection touches
+    pub groups: Vec<GroupDeletionPreview>,
+}
+
+/// Combine keys in `bucket` with explicitly bucketed objects into one selection
+fn selected_objects(
+    bucket: &str,
+    keys: Vec<String>,
+    objects: Option<Vec<ScanObject>>,
+) -> HashSet<ScanObject> {
+    keys.into_iter()
+        .map(|key| ScanObject {
+            bucket: bucket.to_string(),
+            key,
+        })
+        .chain(objects.unwrap_or_default())
+        .collect()
+}
+
+/// Work out what deleting `selected` does to each group. Fails if any group
+/// would lose every copy, so no file disappears entirely.
+fn preview_deletion(
+    groups: &[DuplicateGroup],
+    selected: &HashSet<ScanObject>,
+) -> Result<DuplicateDeletionPreview, AppError> {
+    let mut preview = DuplicateDeletionPreview {
+        delete_count: 0,
+        freed_bytes: 0,
+        groups: Vec::new(),
+    };
+
+    for group in groups {
+        let delete_count = group
+            .files
+            .iter()
+            .filter(|f| {
+                selected.contains(&ScanObject {
+                    bucket: f.bucket.clone(),
+                    key: f.key.clone(),
+                })
+            })
+            .count() as i64;
+        if delete_count == 0 {
+            continue;
+        }
+
+        let remaining_count = group.files.len() as i64 - delete_count;
+        if remaining_count == 0 {
+            return Err(AppError::InvalidInput(format!(
+                "Refusing to delete every copy in duplicate group {}; keep at least one file",
+                group.content_hash
+            )));
+        }
+
+        preview.delete_count += delete_count as usize;
+        preview.freed_bytes += delete_count * group.file_size;
+        preview.groups.push(GroupDeletionPreview {
+            group_id: group.id,
+            content_hash: group.content_hash.clone(),
+            file_size: group.file_size,
+            delete_count,
+            remaining_count,
+        });
+    }
+
+    Ok(preview)
+}
+
+/// Preview `delete_duplicates` without deleting anything: the space it would
+/// free and how many copies each group keeps. Fails the same way the delete
+/// would if a group would lose every copy. Keys are in the scan's bucket;
+/// files of a cross-bucket scan are passed as `objects_to_delete`.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn preview_duplicate_deletion(
+    db: State<'_, DbManager>,
+    scan_id: i64,
+    keys_to_delete: Vec<String>,
+    objects_to_delete: Option<Vec<ScanObject>>,
+) -> Result<DuplicateDeletionPreview, AppError> {
+    let scan = db
+        .get_scan(scan_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Scan {} not found", scan_id)))?;
+
+    let selected = selected_objects(&scan.bucket, keys_to_delete, objects_to_delete);
+    preview_deletion(&db.get_duplicate_groups(scan_id)?, &selected)
+}
+
 /// Delete duplicate files result
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -456,21 +1074,53 @@ pub struct DeleteDuplicatesResult {
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteDuplicateError {
+    pub bucket: String,
     pub key: String,
     pub error: String,
 }
 
-/// Delete selected duplicate files (keep one, delete rest)
+/// Delete selected duplicate files (keep one, delete rest). `keys_to_delete`
+/// are in `bucket`; files of a cross-bucket scan are passed as `objects_to_delete`
+/// and each is deleted from its own bucket.
 #[tauri::command(rename_all = "camelCase")]
 pub async fn delete_duplicates(
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
     db: State<'_, DbManager>,
+    listing_cache: State<'_, ListingCache>,
     account_id: String,
     bucket: String,
     scan_id: i64,
     keys_to_delete: Vec<String>,
+    objects_to_delete: Option<Vec<ScanObject>>,
 ) -> Result<DeleteDuplicatesResult, AppError> {
+    let scan = db
+        .get_scan(scan_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Scan {} not found", scan_id)))?;
+    if scan.account_id != account_id {
+        return Err(AppError::InvalidInput(
+            "Scan does not belong to this account".into(),
+        ));
+    }
+
+    let requested = selected_objects(&bucket, keys_to_delete, objects_to_delete);
+
+    // Never delete every copy of a file: each group must keep at least one member
+    let groups = db.get_duplicate_groups(scan_id)?;
+    preview_deletion(&groups, &requested)?;
+
+    // Route each key to its bucket, refusing buckets the scan never covered
+    let mut by_bucket: HashMap<String, Vec<String>> = HashMap::new();
+    for object in requested {
+        if !scan.targets.iter().any(|t| t.bucket == object.bucket) {
+            return Err(AppError::InvalidInput(format!(
+                "Bucket '{}' is not part of scan {}",
+                object.bucket, scan_id
+            )));
+        }
+        by_bucket.entry(object.bucket).or_default().push(object.key);
+    }
+
     let account = credentials.get_account(&account_id)?;
     let secret = credentials.get_secret_key(&account_id)?;
 
@@ -482,72 +1132,80 @@ pub async fn delete_duplicates(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
     let mut deleted_count = 0usize;
-    let mut freed_bytes = 0i64;
     let mut errors = Vec::new();
-    let mut deleted_keys = Vec::new();
+    let mut deleted: Vec<ScanObject> = Vec::new();
 
     // Delete in batches of 1000 (S3 limit)
-    for chunk in keys_to_delete.chunks(1000) {
-        let objects_to_delete: Vec<aws_sdk_s3::types::ObjectIdentifier> = chunk
-            .iter()
-            .filter_map(|key| {
-                aws_sdk_s3::types::ObjectIdentifier::builder()
-                    .key(key)
-                    .build()
-                    .ok()
-            })
-            .collect();
-
-        let delete = aws_sdk_s3::types::Delete::builder()
-            .set_objects(Some(objects_to_delete))
-            .build()
-            .map_err(|e| AppError::S3(format!("Failed to build delete request: {:?}", e)))?;
-
-        let response = client
-            .delete_objects()
-            .bucket(&bucket)
-            .delete(delete)
-            .send()
-            .await?;
-
-        // Track successful deletions
-        for deleted in response.deleted() {
-            if let Some(key) = deleted.key() {
-                deleted_count += 1;
-                deleted_keys.push(key.to_string());
+    for (bucket, keys) in &by_bucket {
+        for chunk in keys.chunks(1000) {
+            let objects_to_delete: Vec<aws_sdk_s3::types::ObjectIdentifier> = chunk
+                .iter()
+                .filter_map(|key| {
+                    aws_sdk_s3::types::ObjectIdentifier::builder()
+                        .key(key)
+                        .build()
+                        .ok()
+                })
+                .collect();
+
+            let delete = aws_sdk_s3::types::Delete::builder()
+                .set_objects(Some(objects_to_delete))
+                .build()
+                .map_err(|e| AppError::S3(format!("Failed to build delete request: {:?}", e)))?;
+
+            let response = client
+                .delete_objects()
+                .bucket(bucket)
+                .delete(delete)
+                .send()
+                .await?;
+
+            // Track successful deletions
+            for object in response.deleted() {
+                if let Some(key) = object.key() {
+                    deleted_count += 1;
+                    deleted.push(ScanObject {
+                        bucket: bucket.clone(),
+                        key: key.to_string(),
+                    });
+                }
             }
-        }
 
-        // Track errors
-        for err in response.errors() {
-            errors.push(DeleteDuplicateError {
-                key: err.key().unwrap_or_default().to_string(),
-                error: err.message().unwrap_or_default().to_string(),
-            });
+            // Track errors
+            for err in response.errors() {
+                errors.push(DeleteDuplicateError {
+                    bucket: bucket.clone(),
+                    key: err.key().unwrap_or_default().to_string(),
+                    error: err.message().unwrap_or_default().to_string(),
+                });
+            }
         }
     }
 
-    // Calculate freed bytes (need to look up sizes)
-    // For simplicity, we'll estimate based on the groups
-    let groups = db.get_duplicate_groups(scan_id)?;
-    for group in &groups {
-        let deleted_in_group = group
-            .files
+    for bucket in by_bucket.keys() {
+        let keys: Vec<String> = deleted
             .iter()
-            .filter(|f| deleted_keys.contains(&f.key))
-            .count();
-        freed_bytes += (deleted_in_group as i64) * group.file_size;
+            .filter(|object| &object.bucket == bucket)
+            .map(|object| object.key.clone())
+            .collect();
+        cache::invalidate_for(&listing_cache, &db, &account_id, bucket, &[], &keys);
     }
 
     // Update database to reflect deleted files
-    if !deleted_keys.is_empty() {
-        db.remove_deleted_files(scan_id, &deleted_keys)?;
+    if !deleted.is_empty() {
+        db.remove_deleted_files(scan_id, &deleted)?;
     }
 
+    // Only count what was actually deleted; a subset of the checked selection
+    // can't empty a group, so this never fails
+    let freed_bytes =
+        preview_deletion(&groups, &deleted.iter().cloned().collect())?.freed_bytes;
+
     Ok(DeleteDuplicatesResult {
         deleted_count,
         freed_bytes,
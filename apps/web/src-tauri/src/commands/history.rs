@@ -173,7 +173,7 @@ pub async fn update_operation(
 }
 
 /// Helper function to escape CSV values
-fn escape_csv(value: &str) -> String {
+pub(crate) fn escape_csv(value: &str) -> String {
     if value.contains(',') || value.contains('"') || value.contains('\n') {
         format!("\"{}\"", value.replace('"', "\"\""))
     } else {
@@ -1,8 +1,10 @@
 pub mod analytics;
 pub mod buckets;
+pub mod compare;
 pub mod credentials;
 pub mod duplicates;
 pub mod history;
 pub mod objects;
 pub mod preview;
+pub mod settings;
 pub mod sync;
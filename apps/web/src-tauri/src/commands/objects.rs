@@ -1,13 +1,28 @@
+use crate::cache::{self, ListingCache, ListingCacheKey};
 use crate::credentials::CredentialsManager;
-use crate::db::operations::OperationType;
+use crate::db::operations::{NewOperation, OperationStatus, OperationType};
+use crate::commands::history::escape_csv;
+use crate::db::batches::{BatchOperation, FailedItem};
+use crate::db::settings::MAX_PART_SIZE;
+use crate::db::shared_links::{NewSharedLink, SharedLink};
 use crate::db::DbManager;
 use crate::error::AppError;
+use crate::progress::ProgressThrottle;
 use crate::s3::client::S3ClientManager;
+use crate::s3::retry::{is_retryable_code, with_retry, RetryPolicy};
+use aws_sdk_s3::operation::head_object::HeadObjectOutput;
+use aws_sdk_s3::operation::list_objects_v2::builders::ListObjectsV2FluentBuilder;
+use aws_sdk_s3::operation::list_objects_v2::ListObjectsV2Output;
 use aws_sdk_s3::presigning::PresigningConfig;
-use aws_sdk_s3::types::ObjectIdentifier;
+use aws_sdk_s3::types::{ChecksumMode, ObjectIdentifier};
+use aws_smithy_checksums::Checksum;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use futures::stream::{self, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, State};
@@ -21,6 +36,15 @@ pub struct S3Object {
     pub last_modified: Option<String>,
     pub etag: Option<String>,
     pub is_folder: bool,
+    /// Only populated when listing with `fetch_owner`
+    pub owner: Option<ObjectOwner>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectOwner {
+    pub id: Option<String>,
+    pub display_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -31,18 +55,275 @@ pub struct ListObjectsResponse {
     pub continuation_token: Option<String>,
     pub is_truncated: bool,
     pub prefix: Option<String>,
+    /// Per-folder object counts and sizes (only when `include_folder_stats` is set)
+    pub folder_stats: Option<Vec<FolderSummary>>,
+    /// True if the requested sort was skipped because the level was too large
+    pub sort_skipped: bool,
+}
+
+/// Prefix marking a continuation token as an offset into a sorted listing
+const SORTED_CURSOR_PREFIX: &str = "sorted:";
+/// Max entries loaded into memory to sort a level; larger levels fall back to key order
+const SORT_MAX_ENTRIES: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortField {
+    Name,
+    Size,
+    Modified,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ListSort {
+    field: SortField,
+    descending: bool,
+}
+
+impl ListSort {
+    /// Parse sort params. Returns `None` for S3's native order (name ascending).
+    fn parse(sort_by: Option<&str>, sort_dir: Option<&str>) -> Result<Option<Self>, AppError> {
+        let field = match sort_by.unwrap_or("name") {
+            "name" => SortField::Name,
+            "size" => SortField::Size,
+            "modified" => SortField::Modified,
+            other => {
+                return Err(AppError::InvalidInput(format!("Unknown sort field: {}", other)))
+            }
+        };
+        let descending = match sort_dir.unwrap_or("asc") {
+            "asc" => false,
+            "desc" => true,
+            other => {
+                return Err(AppError::InvalidInput(format!(
+                    "Unknown sort direction: {}",
+                    other
+                )))
+            }
+        };
+
+        if field == SortField::Name && !descending {
+            return Ok(None);
+        }
+        Ok(Some(Self { field, descending }))
+    }
+}
+
+fn to_s3_object(obj: &aws_sdk_s3::types::Object) -> S3Object {
+    S3Object {
+        key: obj.key().unwrap_or_default().to_string(),
+        size: obj.size().unwrap_or(0),
+        last_modified: obj.last_modified().map(|d| d.to_string()),
+        etag: obj.e_tag().map(|e| e.trim_matches('"').to_string()),
+        is_folder: false,
+        owner: obj.owner().map(|o| ObjectOwner {
+            id: o.id().map(|s| s.to_string()),
+            display_name: o.display_name().map(|s| s.to_string()),
+        }),
+    }
+}
+
+/// Build a flat (non-delimited) listing of everything under `prefix`.
+/// An empty prefix lists the whole bucket.
+pub(crate) fn flat_listing(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    prefix: Option<&str>,
+) -> ListObjectsV2FluentBuilder {
+    let request = client.list_objects_v2().bucket(bucket);
+    match prefix.filter(|p| !p.is_empty()) {
+        Some(p) => request.prefix(p),
+        None => request,
+    }
+}
+
+/// Fetch one page of `base`, resuming from `continuation_token`.
+/// Returns the response along with the token for the next page, or `None` on the last page.
+pub(crate) async fn list_page(
+    base: &ListObjectsV2FluentBuilder,
+    continuation_token: Option<&str>,
+) -> Result<(ListObjectsV2Output, Option<String>), AppError> {
+    let mut request = base.clone();
+    if let Some(token) = continuation_token {
+        request = request.continuation_token(token);
+    }
+
+    let response = request.send().await?;
+    let next_token = if response.is_truncated() == Some(true) {
+        response.next_continuation_token().map(|s| s.to_string())
+    } else {
+        None
+    };
+    Ok((response, next_token))
+}
+
+/// Load a whole folder level, sort it and cut out one page.
+/// Returns `None` if the level holds more than `SORT_MAX_ENTRIES` entries.
+/// `base` carries the bucket, prefix and listing options; pagination is added here.
+async fn list_sorted_page(
+    base: &ListObjectsV2FluentBuilder,
+    prefix: Option<&str>,
+    sort: ListSort,
+    offset: usize,
+    page_size: usize,
+) -> Result<Option<(Vec<S3Object>, Vec<String>, Option<String>, bool)>, AppError> {
+    let mut entries: Vec<(S3Object, Option<i64>)> = Vec::new();
+    let mut folders: Vec<String> = Vec::new();
+    let mut continuation_token: Option<String> = None;
+
+    loop {
+        let (response, next_token) = list_page(base, continuation_token.as_deref()).await?;
+
+        for obj in response.contents() {
+            if obj.key().map_or(true, |k| Some(k) == prefix) {
+                continue;
+            }
+            let modified = obj.last_modified().map(|d| d.secs());
+            entries.push((to_s3_object(obj), modified));
+        }
+        folders.extend(
+            response
+                .common_prefixes()
+                .iter()
+                .filter_map(|cp| cp.prefix().map(|p| p.to_string())),
+        );
+
+        if entries.len() + folders.len() > SORT_MAX_ENTRIES {
+            return Ok(None);
+        }
+
+        match next_token {
+            Some(token) => continuation_token = Some(token),
+            None => break,
+        }
+    }
+
+    entries.sort_by(|(a, a_modified), (b, b_modified)| {
+        let ordering = match sort.field {
+            SortField::Name => a.key.cmp(&b.key),
+            SortField::Size => a.size.cmp(&b.size).then_with(|| a.key.cmp(&b.key)),
+            SortField::Modified => a_modified.cmp(b_modified).then_with(|| a.key.cmp(&b.key)),
+        };
+        if sort.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+
+    // Folders have no size or date; keep them by name and return them with the first page
+    if sort.field == SortField::Name && sort.descending {
+        folders.reverse();
+    }
+    let folders = if offset == 0 { folders } else { Vec::new() };
+
+    let total = entries.len();
+    let objects: Vec<S3Object> = entries
+        .into_iter()
+        .skip(offset)
+        .take(page_size)
+        .map(|(obj, _)| obj)
+        .collect();
+
+    let next_offset = offset + page_size;
+    let is_truncated = next_offset < total;
+    let next_token = is_truncated.then(|| format!("{}{}", SORTED_CURSOR_PREFIX, next_offset));
+
+    Ok(Some((objects, folders, next_token, is_truncated)))
+}
+
+/// Quick object count and size for a folder prefix
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSummary {
+    pub prefix: String,
+    pub object_count: i64,
+    pub total_size: i64,
+    /// True if counting stopped at `FOLDER_STATS_MAX_PAGES` pages
+    pub truncated: bool,
+}
+
+/// Max folders summarized at once when `include_folder_stats` is set
+const FOLDER_STATS_CONCURRENCY: usize = 4;
+/// Max list pages (1000 keys each) read per folder before giving up on an exact count
+const FOLDER_STATS_MAX_PAGES: usize = 10;
+
+/// Count objects and bytes under a prefix, reading at most `FOLDER_STATS_MAX_PAGES` pages
+async fn summarize_folder(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    prefix: &str,
+) -> Result<FolderSummary, AppError> {
+    let mut summary = FolderSummary {
+        prefix: prefix.to_string(),
+        object_count: 0,
+        total_size: 0,
+        truncated: false,
+    };
+    let mut continuation_token: Option<String> = None;
+
+    let listing = flat_listing(client, bucket, Some(prefix));
+    for _ in 0..FOLDER_STATS_MAX_PAGES {
+        let (response, next_token) = list_page(&listing, continuation_token.as_deref()).await?;
+
+        for obj in response.contents() {
+            if obj.key().map_or(false, |k| k.ends_with('/')) {
+                continue;
+            }
+            summary.object_count += 1;
+            summary.total_size += obj.size().unwrap_or(0);
+        }
+
+        match next_token {
+            Some(token) => continuation_token = Some(token),
+            None => return Ok(summary),
+        }
+    }
+
+    summary.truncated = true;
+    Ok(summary)
 }
 
 #[tauri::command(rename_all = "camelCase")]
 pub async fn list_objects(
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
+    listing_cache: State<'_, ListingCache>,
     account_id: String,
     bucket: String,
     prefix: Option<String>,
     continuation_token: Option<String>,
     max_keys: Option<i32>,
+    include_folder_stats: Option<bool>,
+    sort_by: Option<String>,
+    sort_dir: Option<String>,
+    start_after: Option<String>,
+    fetch_owner: Option<bool>,
+    delimiter: Option<String>,
+    recursive: Option<bool>,
 ) -> Result<ListObjectsResponse, AppError> {
+    let cache_key = ListingCacheKey {
+        account_id: account_id.clone(),
+        bucket: bucket.clone(),
+        prefix: prefix.clone().unwrap_or_default(),
+        continuation_token: continuation_token.clone(),
+        options: format!(
+            "{:?}",
+            (
+                max_keys,
+                include_folder_stats,
+                &sort_by,
+                &sort_dir,
+                &start_after,
+                fetch_owner,
+                &delimiter,
+                recursive
+            )
+        ),
+    };
+    if let Some(cached) = listing_cache.get(&cache_key) {
+        return Ok(cached);
+    }
+
     let account = credentials.get_account(&account_id)?;
     let secret = credentials.get_secret_key(&account_id)?;
 
@@ -54,62 +335,144 @@ pub async fn list_objects(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    let mut request = client
-        .list_objects_v2()
-        .bucket(&bucket)
-        .delimiter("/"); // Use delimiter for folder-like browsing
-
-    if let Some(ref p) = prefix {
-        request = request.prefix(p);
+    // Folder-style browsing uses "/" by default; recursive mode or an empty delimiter
+    // gives a flat listing of every object under the prefix, with no folders
+    let delimiter = if recursive.unwrap_or(false) {
+        String::new()
+    } else {
+        delimiter.unwrap_or_else(|| "/".to_string())
+    };
+    let mut base_request = flat_listing(&client, &bucket, prefix.as_deref());
+    if !delimiter.is_empty() {
+        base_request = base_request.delimiter(&delimiter);
     }
-
-    if let Some(token) = continuation_token {
-        request = request.continuation_token(token);
+    if let Some(ref key) = start_after {
+        base_request = base_request.start_after(key);
     }
-
-    if let Some(max) = max_keys {
-        request = request.max_keys(max);
+    if fetch_owner.unwrap_or(false) {
+        base_request = base_request.fetch_owner(true);
     }
 
-    let response = request.send().await?;
+    // Sorting by anything other than ascending key order needs the whole level in memory
+    let sort = ListSort::parse(sort_by.as_deref(), sort_dir.as_deref())?;
+    let sorted_page = match sort {
+        Some(sort) => {
+            let offset = continuation_token
+                .as_deref()
+                .and_then(|t| t.strip_prefix(SORTED_CURSOR_PREFIX))
+                .and_then(|o| o.parse::<usize>().ok())
+                .unwrap_or(0);
+            let page_size = max_keys.filter(|m| *m > 0).unwrap_or(1000) as usize;
+            list_sorted_page(&base_request, prefix.as_deref(), sort, offset, page_size).await?
+        }
+        None => None,
+    };
+    let sort_skipped = sort.is_some() && sorted_page.is_none();
 
-    // Parse regular objects (files)
-    let objects: Vec<S3Object> = response
-        .contents()
-        .iter()
-        .filter_map(|obj| {
-            let key = obj.key()?;
-            // Skip the prefix itself if it's returned
-            if prefix.as_ref().map_or(false, |p| key == p) {
-                return None;
+    let (mut objects, folders, next_continuation_token, is_truncated) = match sorted_page {
+        Some(page) => page,
+        None => {
+            let mut request = base_request;
+
+            // A synthetic sorted cursor means nothing to S3; restart from the beginning
+            if let Some(token) = continuation_token.filter(|t| !t.starts_with(SORTED_CURSOR_PREFIX))
+            {
+                request = request.continuation_token(token);
             }
-            Some(S3Object {
-                key: key.to_string(),
-                size: obj.size().unwrap_or(0),
-                last_modified: obj.last_modified().map(|d| d.to_string()),
-                etag: obj.e_tag().map(|e| e.trim_matches('"').to_string()),
-                is_folder: false,
-            })
-        })
-        .collect();
 
-    // Parse common prefixes (folders)
-    let folders: Vec<String> = response
-        .common_prefixes()
-        .iter()
-        .filter_map(|cp| cp.prefix().map(|p| p.to_string()))
-        .collect();
+            if let Some(max) = max_keys {
+                request = request.max_keys(max);
+            }
 
-    Ok(ListObjectsResponse {
+            let response = request.send().await?;
+
+            // Parse regular objects (files)
+            let objects: Vec<S3Object> = response
+                .contents()
+                .iter()
+                .filter_map(|obj| {
+                    let key = obj.key()?;
+                    // Skip the prefix itself if it's returned
+                    if prefix.as_ref().map_or(false, |p| key == p) {
+                        return None;
+                    }
+                    Some(to_s3_object(obj))
+                })
+                .collect();
+
+            // Parse common prefixes (folders)
+            let folders: Vec<String> = response
+                .common_prefixes()
+                .iter()
+                .filter_map(|cp| cp.prefix().map(|p| p.to_string()))
+                .collect();
+
+            (
+                objects,
+                folders,
+                response.next_continuation_token().map(|s| s.to_string()),
+                response.is_truncated().unwrap_or(false),
+            )
+        }
+    };
+
+    // A flat listing has no folders, so leave out the zero-byte "folder/" markers too
+    if delimiter.is_empty() {
+        objects.retain(|obj| !obj.key.ends_with('/'));
+    }
+
+    // Opt-in: summarize each folder on this page (costs extra list calls)
+    let folder_stats = if include_folder_stats.unwrap_or(false) && !folders.is_empty() {
+        let client = &client;
+        let bucket = &bucket;
+        let stats: Vec<FolderSummary> = stream::iter(folders.iter())
+            .map(|folder| async move {
+                summarize_folder(client, bucket, folder)
+                    .await
+                    .map_err(|e| log::warn!("Failed to summarize folder {}: {}", folder, e))
+                    .ok()
+            })
+            .buffered(FOLDER_STATS_CONCURRENCY)
+            .filter_map(|s| async move { s })
+            .collect()
+            .await;
+        Some(stats)
+    } else {
+        None
+    };
+
+    let response = ListObjectsResponse {
         objects,
         folders,
-        continuation_token: response.next_continuation_token().map(|s| s.to_string()),
-        is_truncated: response.is_truncated().unwrap_or(false),
+        continuation_token: next_continuation_token,
+        is_truncated,
         prefix,
-    })
+        folder_stats,
+        sort_skipped,
+    };
+    listing_cache.insert(cache_key, response.clone());
+
+    Ok(response)
+}
+
+/// Drop cached listings for a prefix so the next `list_objects` call hits S3.
+/// Returns the number of cached pages removed.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn invalidate_listing_cache(
+    listing_cache: State<'_, ListingCache>,
+    account_id: String,
+    bucket: String,
+    prefix: Option<String>,
+) -> Result<usize, AppError> {
+    Ok(listing_cache.invalidate(
+        &account_id,
+        &bucket,
+        prefix.as_deref().unwrap_or_default(),
+    ))
 }
 
 #[tauri::command(rename_all = "camelCase")]
@@ -131,30 +494,20 @@ pub async fn get_object_metadata(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    let response = client.head_object().bucket(&bucket).key(&key).send().await?;
-
-    // Convert user metadata to HashMap
-    let metadata = response.metadata().map(|m| {
-        m.iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect::<std::collections::HashMap<String, String>>()
-    });
+    // Checksum headers are only returned when checksum mode is enabled
+    let response = client
+        .head_object()
+        .bucket(&bucket)
+        .key(&key)
+        .checksum_mode(ChecksumMode::Enabled)
+        .send()
+        .await?;
 
-    Ok(ObjectMetadata {
-        key: key.clone(),
-        size: response.content_length().unwrap_or(0),
-        content_type: response.content_type().map(|s| s.to_string()),
-        last_modified: response.last_modified().map(|d| d.to_string()),
-        etag: response.e_tag().map(|e| e.trim_matches('"').to_string()),
-        storage_class: response.storage_class().map(|s| s.as_str().to_string()),
-        content_encoding: response.content_encoding().map(|s| s.to_string()),
-        cache_control: response.cache_control().map(|s| s.to_string()),
-        version_id: response.version_id().map(|s| s.to_string()),
-        metadata,
-    })
+    Ok(ObjectMetadata::from_head_object(key, &response))
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -170,12 +523,82 @@ pub struct ObjectMetadata {
     pub cache_control: Option<String>,
     pub version_id: Option<String>,
     pub metadata: Option<std::collections::HashMap<String, String>>,
+    pub content_disposition: Option<String>,
+    pub content_language: Option<String>,
+    pub expires: Option<String>,
+    pub website_redirect_location: Option<String>,
+    pub checksum_crc32: Option<String>,
+    pub checksum_crc32c: Option<String>,
+    pub checksum_crc64nvme: Option<String>,
+    pub checksum_sha1: Option<String>,
+    pub checksum_sha256: Option<String>,
+    /// "FULL_OBJECT" or "COMPOSITE" (multipart uploads)
+    pub checksum_type: Option<String>,
+    pub server_side_encryption: Option<String>,
+    pub sse_kms_key_id: Option<String>,
+    pub object_lock_mode: Option<String>,
+    pub object_lock_retain_until_date: Option<String>,
+    pub object_lock_legal_hold_status: Option<String>,
+    pub replication_status: Option<String>,
+    pub parts_count: Option<i32>,
+}
+
+impl ObjectMetadata {
+    fn from_head_object(
+        key: String,
+        response: &aws_sdk_s3::operation::head_object::HeadObjectOutput,
+    ) -> Self {
+        // Convert user metadata to HashMap
+        let metadata = response.metadata().map(|m| {
+            m.iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect::<std::collections::HashMap<String, String>>()
+        });
+
+        ObjectMetadata {
+            key,
+            size: response.content_length().unwrap_or(0),
+            content_type: response.content_type().map(|s| s.to_string()),
+            last_modified: response.last_modified().map(|d| d.to_string()),
+            etag: response.e_tag().map(|e| e.trim_matches('"').to_string()),
+            storage_class: response.storage_class().map(|s| s.as_str().to_string()),
+            content_encoding: response.content_encoding().map(|s| s.to_string()),
+            cache_control: response.cache_control().map(|s| s.to_string()),
+            version_id: response.version_id().map(|s| s.to_string()),
+            metadata,
+            content_disposition: response.content_disposition().map(|s| s.to_string()),
+            content_language: response.content_language().map(|s| s.to_string()),
+            expires: response.expires_string().map(|s| s.to_string()),
+            website_redirect_location: response.website_redirect_location().map(|s| s.to_string()),
+            checksum_crc32: response.checksum_crc32().map(|s| s.to_string()),
+            checksum_crc32c: response.checksum_crc32_c().map(|s| s.to_string()),
+            checksum_crc64nvme: response.checksum_crc64_nvme().map(|s| s.to_string()),
+            checksum_sha1: response.checksum_sha1().map(|s| s.to_string()),
+            checksum_sha256: response.checksum_sha256().map(|s| s.to_string()),
+            checksum_type: response.checksum_type().map(|t| t.as_str().to_string()),
+            server_side_encryption: response
+                .server_side_encryption()
+                .map(|s| s.as_str().to_string()),
+            sse_kms_key_id: response.ssekms_key_id().map(|s| s.to_string()),
+            object_lock_mode: response.object_lock_mode().map(|m| m.as_str().to_string()),
+            object_lock_retain_until_date: response
+                .object_lock_retain_until_date()
+                .map(|d| d.to_string()),
+            object_lock_legal_hold_status: response
+                .object_lock_legal_hold_status()
+                .map(|s| s.as_str().to_string()),
+            replication_status: response.replication_status().map(|s| s.as_str().to_string()),
+            parts_count: response.parts_count(),
+        }
+    }
 }
 
 // Object versioning types
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ObjectVersionInfo {
+    /// Key the version belongs to; differs from the requested key in prefix mode
+    pub key: String,
     pub version_id: String,
     pub is_latest: bool,
     pub is_delete_marker: bool,
@@ -196,11 +619,25 @@ pub struct ListVersionsResponse {
     pub versioning_enabled: bool,
 }
 
+/// Where `restore_object_version` writes the old version
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreVersionMode {
+    /// Copy over the key itself, making the old version current again. In an
+    /// unversioned bucket this replaces the current content for good.
+    #[default]
+    Overwrite,
+    /// Copy to a new key, leaving the current object untouched
+    CopyTo,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RestoreVersionResult {
     pub key: String,
     pub restored_version_id: String,
+    /// Key the version was written to; the same as `key` when overwriting
+    pub destination_key: String,
     pub new_version_id: Option<String>,
 }
 
@@ -211,6 +648,8 @@ pub struct UploadStarted {
     pub upload_id: String,
     pub file_name: String,
     pub total_bytes: u64,
+    /// History entry tracking this upload, if it could be recorded
+    pub history_id: Option<i64>,
 }
 
 #[derive(Clone, Serialize)]
@@ -219,6 +658,77 @@ pub struct UploadProgress {
     pub upload_id: String,
     pub bytes_uploaded: u64,
     pub total_bytes: u64,
+    pub bytes_per_sec: u64,
+    pub eta_secs: Option<u64>,
+}
+
+/// Weight of the newest sample in the moving average of the transfer rate
+const RATE_SMOOTHING: f64 = 0.3;
+
+/// Tracks a smoothed transfer rate and decides when a progress event is due
+struct TransferRate {
+    throttle: ProgressThrottle,
+    last_sample: Instant,
+    last_bytes: u64,
+    bytes_per_sec: Option<f64>,
+}
+
+/// Rate and ETA to attach to a progress event
+struct RateSample {
+    bytes_per_sec: u64,
+    eta_secs: Option<u64>,
+}
+
+impl TransferRate {
+    fn new() -> Self {
+        Self::resumed_from(0)
+    }
+
+    /// Start measuring from `bytes` already transferred earlier, so a resumed
+    /// transfer's rate only counts what moves now
+    fn resumed_from(bytes: u64) -> Self {
+        Self {
+            throttle: ProgressThrottle::new(),
+            last_sample: Instant::now(),
+            last_bytes: bytes,
+            bytes_per_sec: None,
+        }
+    }
+
+    /// Record progress. Returns `None` while events are throttled; the final
+    /// update (`bytes_done >= total_bytes`) is always reported.
+    fn sample(&mut self, bytes_done: u64, total_bytes: u64) -> Option<RateSample> {
+        if !self.throttle.ready(bytes_done >= total_bytes) {
+            return None;
+        }
+
+        let elapsed = self.last_sample.elapsed();
+        let secs = elapsed.as_secs_f64();
+        if secs > 0.0 {
+            let current = bytes_done.saturating_sub(self.last_bytes) as f64 / secs;
+            self.bytes_per_sec = Some(match self.bytes_per_sec {
+                Some(previous) => RATE_SMOOTHING * current + (1.0 - RATE_SMOOTHING) * previous,
+                None => current,
+            });
+        }
+        self.last_sample = Instant::now();
+        self.last_bytes = bytes_done;
+
+        let rate = self.bytes_per_sec.unwrap_or(0.0);
+        let remaining = total_bytes.saturating_sub(bytes_done);
+        let eta_secs = if remaining == 0 {
+            Some(0)
+        } else if rate > 0.0 {
+            Some((remaining as f64 / rate).ceil() as u64)
+        } else {
+            None
+        };
+
+        Some(RateSample {
+            bytes_per_sec: rate.round() as u64,
+            eta_secs,
+        })
+    }
 }
 
 #[derive(Clone, Serialize)]
@@ -236,8 +746,160 @@ pub struct UploadFailed {
     pub error: String,
 }
 
-const MULTIPART_THRESHOLD: u64 = 5 * 1024 * 1024; // 5MB
-const PART_SIZE: usize = 5 * 1024 * 1024; // 5MB per part
+/// Global state for tracking cancellable uploads and downloads
+#[derive(Default)]
+pub struct TransferState {
+    /// Map of transfer_id (upload_id / download_id) -> cancellation flag
+    pub active: tokio::sync::RwLock<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl TransferState {
+    async fn register(&self, transfer_id: &str) -> Arc<AtomicBool> {
+        let flag = Arc::new(AtomicBool::new(false));
+        self.active
+            .write()
+            .await
+            .insert(transfer_id.to_string(), flag.clone());
+        flag
+    }
+
+    async fn finish(&self, transfer_id: &str) {
+        self.active.write().await.remove(transfer_id);
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferCancelled {
+    pub transfer_id: String,
+}
+
+/// Resolves once the cancel flag is set
+async fn wait_for_cancel(cancel_flag: &AtomicBool) {
+    while !cancel_flag.load(Ordering::Relaxed) {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Record an operation as in progress so the history shows it while it runs.
+/// History is best-effort: if the row can't be written the transfer goes on.
+fn start_operation(
+    db: &DbManager,
+    account_id: &str,
+    bucket: &str,
+    operation: OperationType,
+    source_key: Option<&str>,
+    dest_key: Option<&str>,
+    size: Option<i64>,
+    metadata: Option<serde_json::Value>,
+) -> Option<i64> {
+    let logged = db.log_operation(&NewOperation {
+        account_id: account_id.to_string(),
+        bucket: bucket.to_string(),
+        operation,
+        source_key: source_key.map(str::to_string),
+        dest_key: dest_key.map(str::to_string),
+        size,
+        status: OperationStatus::InProgress,
+        metadata,
+    });
+    match logged {
+        Ok(id) => Some(id),
+        Err(e) => {
+            log::warn!("Failed to record operation in {}: {}", bucket, e);
+            None
+        }
+    }
+}
+
+/// Finish an operation from `start_operation` with its outcome and duration
+fn finish_operation(
+    db: &DbManager,
+    history_id: Option<i64>,
+    size: Option<i64>,
+    start_time: Instant,
+    error: Option<&AppError>,
+) {
+    let Some(id) = history_id else { return };
+    let status = match error {
+        None => OperationStatus::Completed,
+        Some(AppError::Cancelled) => OperationStatus::Cancelled,
+        Some(_) => OperationStatus::Failed,
+    };
+    let error = error.map(|e| e.to_string());
+    let duration_ms = start_time.elapsed().as_millis() as i64;
+    if let Err(e) = db.finish_operation(id, status, size, duration_ms, error.as_deref()) {
+        log::warn!("Failed to finish operation {}: {}", id, e);
+    }
+}
+
+/// Cancel a running upload or download. Partial files are removed and
+/// in-progress multipart uploads are aborted.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn cancel_transfer(
+    transfer_state: State<'_, TransferState>,
+    transfer_id: String,
+) -> Result<(), AppError> {
+    if let Some(flag) = transfer_state.active.read().await.get(&transfer_id) {
+        flag.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+/// Additional checksum requested for an upload, verified by S3 on top of the ETag
+#[derive(Debug, Clone, Copy)]
+enum UploadChecksum {
+    Crc32c,
+    Sha256,
+    Sha1,
+}
+
+impl UploadChecksum {
+    fn parse(value: &str) -> Result<Self, AppError> {
+        match value.to_ascii_uppercase().as_str() {
+            "CRC32C" => Ok(UploadChecksum::Crc32c),
+            "SHA256" => Ok(UploadChecksum::Sha256),
+            "SHA1" => Ok(UploadChecksum::Sha1),
+            other => Err(AppError::InvalidInput(format!(
+                "Unsupported checksum algorithm: {} (expected CRC32C, SHA256 or SHA1)",
+                other
+            ))),
+        }
+    }
+
+    fn algorithm(self) -> aws_sdk_s3::types::ChecksumAlgorithm {
+        match self {
+            UploadChecksum::Crc32c => aws_sdk_s3::types::ChecksumAlgorithm::Crc32C,
+            UploadChecksum::Sha256 => aws_sdk_s3::types::ChecksumAlgorithm::Sha256,
+            UploadChecksum::Sha1 => aws_sdk_s3::types::ChecksumAlgorithm::Sha1,
+        }
+    }
+
+    /// Raw (not base64-encoded) checksum of `data`
+    fn compute(self, data: &[u8]) -> Vec<u8> {
+        let algorithm = match self {
+            UploadChecksum::Crc32c => aws_smithy_checksums::ChecksumAlgorithm::Crc32c,
+            UploadChecksum::Sha256 => aws_smithy_checksums::ChecksumAlgorithm::Sha256,
+            UploadChecksum::Sha1 => aws_smithy_checksums::ChecksumAlgorithm::Sha1,
+        };
+        let mut hasher = algorithm.into_impl();
+        hasher.update(data);
+        hasher.finalize().to_vec()
+    }
+
+    /// The checksum S3 reports for a completed multipart upload, if any
+    fn reported_checksum(
+        self,
+        response: &aws_sdk_s3::operation::complete_multipart_upload::CompleteMultipartUploadOutput,
+    ) -> Option<String> {
+        match self {
+            UploadChecksum::Crc32c => response.checksum_crc32_c(),
+            UploadChecksum::Sha256 => response.checksum_sha256(),
+            UploadChecksum::Sha1 => response.checksum_sha1(),
+        }
+        .map(|s| s.to_string())
+    }
+}
 
 #[tauri::command(rename_all = "camelCase")]
 pub async fn upload_object(
@@ -245,14 +907,21 @@ pub async fn upload_object(
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
     db: State<'_, DbManager>,
+    transfer_state: State<'_, TransferState>,
+    listing_cache: State<'_, ListingCache>,
     account_id: String,
     bucket: String,
     file_path: PathBuf,
     key: String,
     content_type: Option<String>,
     upload_id: String,
+    checksum_algorithm: Option<String>,
 ) -> Result<(), AppError> {
     let start_time = Instant::now();
+    let checksum = checksum_algorithm
+        .as_deref()
+        .map(UploadChecksum::parse)
+        .transpose()?;
 
     // Read file metadata
     let metadata = tokio::fs::metadata(&file_path)
@@ -265,16 +934,6 @@ pub async fn upload_object(
         .unwrap_or("unknown")
         .to_string();
 
-    // Emit started event
-    let _ = app.emit(
-        "upload-started",
-        UploadStarted {
-            upload_id: upload_id.clone(),
-            file_name: file_name.clone(),
-            total_bytes,
-        },
-    );
-
     let account = credentials.get_account(&account_id)?;
     let secret = credentials.get_secret_key(&account_id)?;
     let client = s3_clients
@@ -285,6 +944,7 @@ pub async fn upload_object(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
@@ -295,29 +955,235 @@ pub async fn upload_object(
             .to_string()
     });
 
-    let result = if total_bytes > MULTIPART_THRESHOLD {
-        upload_multipart(&client, &bucket, &key, &file_path, &mime, total_bytes, &upload_id, &app)
-            .await
-    } else {
-        upload_single(&client, &bucket, &key, &file_path, &mime, total_bytes, &upload_id, &app)
-            .await
-    };
-
-    let duration_ms = start_time.elapsed().as_millis() as i64;
+    run_upload(
+        &app,
+        &client,
+        &db,
+        &transfer_state,
+        &listing_cache,
+        account_id,
+        bucket,
+        key,
+        UploadSource::File(file_path),
+        file_name,
+        mime,
+        total_bytes,
+        upload_id,
+        checksum,
+        start_time,
+    )
+    .await
+}
 
-    match result {
-        Ok(etag) => {
-            // Log successful upload to history
-            let _ = db.log_completed_operation(
-                &account_id,
-                &bucket,
-                OperationType::Upload,
-                Some(&key),
-                None,
-                Some(total_bytes as i64),
-                duration_ms,
-                None,
-            );
+/// Upload pasted or generated content (text, screenshots) without writing a
+/// temp file first. Emits the same events as `upload_object`, using the last
+/// key segment as the file name.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn upload_bytes(
+    app: AppHandle,
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    transfer_state: State<'_, TransferState>,
+    listing_cache: State<'_, ListingCache>,
+    account_id: String,
+    bucket: String,
+    key: String,
+    data: Vec<u8>,
+    content_type: Option<String>,
+    upload_id: String,
+    checksum_algorithm: Option<String>,
+) -> Result<(), AppError> {
+    let start_time = Instant::now();
+    let checksum = checksum_algorithm
+        .as_deref()
+        .map(UploadChecksum::parse)
+        .transpose()?;
+
+    if key.is_empty() || key.ends_with('/') {
+        return Err(AppError::InvalidInput(
+            "Key must name an object, not a folder".into(),
+        ));
+    }
+
+    let total_bytes = data.len() as u64;
+    let file_name = key.rsplit('/').next().unwrap_or(&key).to_string();
+
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    // Determine content type from the key when not given
+    let mime = content_type.unwrap_or_else(|| {
+        mime_guess::from_path(&key)
+            .first_or_octet_stream()
+            .to_string()
+    });
+
+    run_upload(
+        &app,
+        &client,
+        &db,
+        &transfer_state,
+        &listing_cache,
+        account_id,
+        bucket,
+        key,
+        UploadSource::Bytes(data),
+        file_name,
+        mime,
+        total_bytes,
+        upload_id,
+        checksum,
+        start_time,
+    )
+    .await
+}
+
+/// Content for an upload
+enum UploadSource {
+    File(PathBuf),
+    Bytes(Vec<u8>),
+}
+
+/// Upload with a single PUT or in parts depending on size. The operation is
+/// recorded as in progress when `upload-started` is emitted and finished
+/// before the completed/failed/cancelled event.
+async fn run_upload(
+    app: &AppHandle,
+    client: &aws_sdk_s3::Client,
+    db: &DbManager,
+    transfer_state: &TransferState,
+    listing_cache: &ListingCache,
+    account_id: String,
+    bucket: String,
+    key: String,
+    source: UploadSource,
+    file_name: String,
+    mime: String,
+    total_bytes: u64,
+    upload_id: String,
+    checksum: Option<UploadChecksum>,
+    start_time: Instant,
+) -> Result<(), AppError> {
+    let upload_settings = db.get_upload_settings()?;
+    let history_id = start_operation(
+        db,
+        &account_id,
+        &bucket,
+        OperationType::Upload,
+        Some(&key),
+        None,
+        Some(total_bytes as i64),
+        None,
+    );
+
+    // Emit started event
+    let _ = app.emit(
+        "upload-started",
+        UploadStarted {
+            upload_id: upload_id.clone(),
+            file_name,
+            total_bytes,
+            history_id,
+        },
+    );
+
+    let cancel_flag = transfer_state.register(&upload_id).await;
+
+    let result = if total_bytes > upload_settings.multipart_threshold {
+        let part_size = upload_settings.effective_part_size(total_bytes);
+        // Only reachable past S3's 5TB object limit; fail before uploading anything
+        if part_size > MAX_PART_SIZE {
+            Err(AppError::InvalidInput(format!(
+                "File is too large to upload: {} bytes would need {} byte parts (max {})",
+                total_bytes, part_size, MAX_PART_SIZE
+            )))
+        } else {
+            match source {
+                UploadSource::File(file_path) => match tokio::fs::File::open(&file_path).await {
+                    Ok(file) => {
+                        upload_multipart(
+                            client,
+                            &bucket,
+                            &key,
+                            file,
+                            &mime,
+                            total_bytes,
+                            part_size,
+                            &upload_id,
+                            app,
+                            &cancel_flag,
+                            checksum,
+                        )
+                        .await
+                    }
+                    Err(e) => Err(AppError::InvalidInput(format!("Cannot open file: {}", e))),
+                },
+                UploadSource::Bytes(data) => {
+                    upload_multipart(
+                        client,
+                        &bucket,
+                        &key,
+                        data.as_slice(),
+                        &mime,
+                        total_bytes,
+                        part_size,
+                        &upload_id,
+                        app,
+                        &cancel_flag,
+                        checksum,
+                    )
+                    .await
+                }
+            }
+        }
+    } else {
+        let upload = async {
+            let body = match source {
+                UploadSource::File(file_path) => tokio::fs::read(&file_path)
+                    .await
+                    .map_err(|e| AppError::InvalidInput(format!("Failed to read file: {}", e)))?,
+                UploadSource::Bytes(data) => data,
+            };
+            upload_single(
+                client, &bucket, &key, body, &mime, total_bytes, &upload_id, app, checksum,
+            )
+            .await
+        };
+
+        // A single PUT has no checkpoints, so race it against the cancel flag
+        tokio::select! {
+            result = upload => result,
+            _ = wait_for_cancel(&cancel_flag) => {
+                Err(AppError::Cancelled)
+            }
+        }
+    };
+
+    transfer_state.finish(&upload_id).await;
+    finish_operation(db, history_id, None, start_time, result.as_ref().err());
+
+    match result {
+        Ok(etag) => {
+            cache::invalidate_for(
+                listing_cache,
+                db,
+                &account_id,
+                &bucket,
+                &[],
+                std::slice::from_ref(&key),
+            );
 
             let _ = app.emit(
                 "upload-completed",
@@ -330,25 +1196,22 @@ pub async fn upload_object(
             Ok(())
         }
         Err(e) => {
-            // Log failed upload to history
-            let _ = db.log_completed_operation(
-                &account_id,
-                &bucket,
-                OperationType::Upload,
-                Some(&key),
-                None,
-                Some(total_bytes as i64),
-                duration_ms,
-                Some(&e.to_string()),
-            );
-
-            let _ = app.emit(
-                "upload-failed",
-                UploadFailed {
-                    upload_id,
-                    error: e.to_string(),
-                },
-            );
+            if cancel_flag.load(Ordering::Relaxed) {
+                let _ = app.emit(
+                    "transfer-cancelled",
+                    TransferCancelled {
+                        transfer_id: upload_id,
+                    },
+                );
+            } else {
+                let _ = app.emit(
+                    "upload-failed",
+                    UploadFailed {
+                        upload_id,
+                        error: e.to_string(),
+                    },
+                );
+            }
             Err(e)
         }
     }
@@ -358,34 +1221,39 @@ async fn upload_single(
     client: &aws_sdk_s3::Client,
     bucket: &str,
     key: &str,
-    file_path: &PathBuf,
+    body: Vec<u8>,
     content_type: &str,
     total_bytes: u64,
     upload_id: &str,
     app: &AppHandle,
+    checksum: Option<UploadChecksum>,
 ) -> Result<Option<String>, AppError> {
-    let body = tokio::fs::read(file_path)
-        .await
-        .map_err(|e| AppError::InvalidInput(format!("Failed to read file: {}", e)))?;
+    let mut rate = TransferRate::new();
 
+    // The SDK computes the checksum and S3 rejects the PUT if it doesn't match
     let response = client
         .put_object()
         .bucket(bucket)
         .key(key)
         .body(aws_sdk_s3::primitives::ByteStream::from(body))
         .content_type(content_type)
+        .set_checksum_algorithm(checksum.map(UploadChecksum::algorithm))
         .send()
         .await?;
 
     // Emit 100% progress after successful upload
-    let _ = app.emit(
-        "upload-progress",
-        UploadProgress {
-            upload_id: upload_id.to_string(),
-            bytes_uploaded: total_bytes,
-            total_bytes,
-        },
-    );
+    if let Some(sample) = rate.sample(total_bytes, total_bytes) {
+        let _ = app.emit(
+            "upload-progress",
+            UploadProgress {
+                upload_id: upload_id.to_string(),
+                bytes_uploaded: total_bytes,
+                total_bytes,
+                bytes_per_sec: sample.bytes_per_sec,
+                eta_secs: sample.eta_secs,
+            },
+        );
+    }
 
     Ok(response.e_tag().map(|s| s.trim_matches('"').to_string()))
 }
@@ -394,7 +1262,11 @@ async fn upload_single(
 #[serde(rename_all = "camelCase")]
 pub struct DeleteResult {
     pub deleted: usize,
+    /// Objects moved to the trash prefix instead of being deleted
+    pub trashed: usize,
     pub errors: Vec<DeleteError>,
+    /// Set when some items failed; pass to `retry_failed_items` to re-attempt them
+    pub operation_batch_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -404,11 +1276,15 @@ pub struct DeleteError {
     pub error: String,
 }
 
+/// Max folders listed at once when expanding a delete selection
+const DELETE_EXPAND_CONCURRENCY: usize = 4;
+
 #[tauri::command(rename_all = "camelCase")]
 pub async fn delete_objects(
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
     db: State<'_, DbManager>,
+    listing_cache: State<'_, ListingCache>,
     account_id: String,
     bucket: String,
     keys: Vec<String>,
@@ -425,99 +1301,134 @@ pub async fn delete_objects(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    let mut all_keys_to_delete: Vec<String> = Vec::new();
+    let client_ref = &client;
+    let bucket_ref = &bucket;
+    let all_keys_to_delete = expand_delete_selection(&keys, |prefix| async move {
+        list_keys_under_prefix(client_ref, bucket_ref, &prefix).await
+    })
+    .await?;
 
-    // For each key, if it's a folder (ends with /), list all objects with that prefix
-    for key in &keys {
-        if key.ends_with('/') {
-            // It's a folder - recursively list all objects
-            let mut continuation_token: Option<String> = None;
-            loop {
-                let mut request = client
-                    .list_objects_v2()
-                    .bucket(&bucket)
-                    .prefix(key);
+    let mut result = delete_or_trash_keys(
+        &client,
+        &db,
+        &account_id,
+        &bucket,
+        all_keys_to_delete,
+        start_time,
+        |_| {},
+    )
+    .await?;
+    result.operation_batch_id =
+        save_delete_failures(&db, None, &account_id, &bucket, &result.errors);
+
+    let (mut prefixes, files): (Vec<String>, Vec<String>) =
+        keys.into_iter().partition(|k| k.ends_with('/'));
+    if result.trashed > 0 {
+        prefixes.push(db.get_app_settings()?.trash_prefix());
+    }
+    cache::invalidate_for(&listing_cache, &db, &account_id, &bucket, &prefixes, &files);
 
-                if let Some(token) = &continuation_token {
-                    request = request.continuation_token(token);
-                }
+    Ok(result)
+}
 
-                let response = request.send().await?;
+/// Expand a delete selection into the distinct keys it covers. Folders (keys
+/// ending with /) become every object under them via `list_folder`, several
+/// folders at once. Overlapping selections (a folder and a file or subfolder
+/// inside it) expand to the same keys, which are kept once so counts reflect
+/// distinct objects.
+async fn expand_delete_selection<F, Fut>(
+    keys: &[String],
+    list_folder: F,
+) -> Result<Vec<String>, AppError>
+where
+    F: Fn(String) -> Fut,
+    Fut: std::future::Future<Output = Result<Vec<String>, AppError>>,
+{
+    let list_folder = &list_folder;
+    let expanded: Vec<Vec<String>> = stream::iter(keys.iter())
+        .map(|key| async move {
+            if key.ends_with('/') {
+                list_folder(key.clone()).await
+            } else {
+                Ok(vec![key.clone()])
+            }
+        })
+        .buffered(DELETE_EXPAND_CONCURRENCY)
+        .try_collect()
+        .await?;
 
-                for obj in response.contents() {
-                    if let Some(obj_key) = obj.key() {
-                        all_keys_to_delete.push(obj_key.to_string());
-                    }
-                }
+    let mut seen = std::collections::HashSet::new();
+    Ok(expanded
+        .into_iter()
+        .flatten()
+        .filter(|key| seen.insert(key.clone()))
+        .collect())
+}
 
-                if response.is_truncated() == Some(true) {
-                    continuation_token = response.next_continuation_token().map(|s| s.to_string());
-                } else {
-                    break;
-                }
-            }
-        } else {
-            all_keys_to_delete.push(key.clone());
-        }
-    }
+/// Delete distinct expanded keys, moving them to the trash instead when it is enabled.
+/// `on_batch` receives the number of keys processed after each permanent delete batch.
+async fn delete_or_trash_keys(
+    client: &aws_sdk_s3::Client,
+    db: &DbManager,
+    account_id: &str,
+    bucket: &str,
+    all_keys_to_delete: Vec<String>,
+    start_time: Instant,
+    on_batch: impl FnMut(usize),
+) -> Result<DeleteResult, AppError> {
+    // With trash enabled, objects are moved under the trash prefix instead.
+    // Anything already in the trash is deleted for real.
+    let settings = db.get_app_settings()?;
+    let retry = RetryPolicy::from_settings(&settings);
+    let mut trashed = 0;
+    let mut all_errors: Vec<DeleteError> = Vec::new();
+    let all_keys_to_delete = if settings.trash_enabled {
+        let trash_prefix = settings.trash_prefix();
+        let (to_trash, already_trashed): (Vec<String>, Vec<String>) = all_keys_to_delete
+            .into_iter()
+            .partition(|k| !k.starts_with(&trash_prefix));
+
+        let (moved, errors) = move_keys_to_trash(
+            client,
+            db,
+            account_id,
+            bucket,
+            &to_trash,
+            &trash_prefix,
+            retry,
+        )
+        .await?;
+        trashed = moved;
+        all_errors.extend(errors);
+        already_trashed
+    } else {
+        all_keys_to_delete
+    };
 
     if all_keys_to_delete.is_empty() {
         return Ok(DeleteResult {
             deleted: 0,
-            errors: vec![],
+            trashed,
+            errors: all_errors,
+            operation_batch_id: None,
         });
     }
 
-    let mut total_deleted = 0;
-    let mut all_errors: Vec<DeleteError> = Vec::new();
-
-    // S3 delete_objects can handle up to 1000 objects per call
-    for chunk in all_keys_to_delete.chunks(1000) {
-        let objects_to_delete: Vec<ObjectIdentifier> = chunk
-            .iter()
-            .filter_map(|key| {
-                ObjectIdentifier::builder()
-                    .key(key)
-                    .build()
-                    .ok()
-            })
-            .collect();
-
-        let delete = aws_sdk_s3::types::Delete::builder()
-            .set_objects(Some(objects_to_delete))
-            .build()
-            .map_err(|e| AppError::S3(format!("Failed to build delete request: {:?}", e)))?;
-
-        let response = client
-            .delete_objects()
-            .bucket(&bucket)
-            .delete(delete)
-            .send()
-            .await?;
-
-        // Count successful deletions
-        total_deleted += response.deleted().len();
-
-        // Collect errors
-        for err in response.errors() {
-            all_errors.push(DeleteError {
-                key: err.key().unwrap_or_default().to_string(),
-                error: err.message().unwrap_or_default().to_string(),
-            });
-        }
-    }
+    let (total_deleted, delete_errors) =
+        delete_keys_in_batches(client, bucket, &all_keys_to_delete, retry, on_batch).await?;
 
     let duration_ms = start_time.elapsed().as_millis() as i64;
 
     // Log each deletion to history
     for key in &all_keys_to_delete {
-        let error = all_errors.iter().find(|e| &e.key == key);
+        let error = delete_errors.iter().find(|e| &e.key == key);
         let _ = db.log_completed_operation(
-            &account_id,
-            &bucket,
+            account_id,
+            bucket,
             OperationType::Delete,
             Some(key),
             None,
@@ -526,152 +1437,173 @@ pub async fn delete_objects(
             error.map(|e| e.error.as_str()),
         );
     }
+    all_errors.extend(delete_errors);
 
     Ok(DeleteResult {
         deleted: total_deleted,
+        trashed,
         errors: all_errors,
+        operation_batch_id: None,
     })
 }
 
-async fn upload_multipart(
-    client: &aws_sdk_s3::Client,
+/// Persist failed deletes for `retry_failed_items`. Returns the batch id if anything failed.
+fn save_delete_failures(
+    db: &DbManager,
+    batch_id: Option<String>,
+    account_id: &str,
     bucket: &str,
-    key: &str,
-    file_path: &PathBuf,
-    content_type: &str,
-    total_bytes: u64,
-    upload_id: &str,
-    app: &AppHandle,
-) -> Result<Option<String>, AppError> {
-    // Initiate multipart upload
-    let create_response = client
-        .create_multipart_upload()
-        .bucket(bucket)
-        .key(key)
-        .content_type(content_type)
-        .send()
-        .await?;
+    errors: &[DeleteError],
+) -> Option<String> {
+    if batch_id.is_none() && errors.is_empty() {
+        return None;
+    }
+    let batch_id = batch_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let items: Vec<FailedItem> = errors
+        .iter()
+        .map(|e| FailedItem {
+            key: e.key.clone(),
+            dest_key: None,
+            error: e.error.clone(),
+        })
+        .collect();
 
-    let s3_upload_id = create_response
-        .upload_id()
-        .ok_or_else(|| AppError::S3("No upload ID returned".into()))?
-        .to_string();
+    save_failures(db, &batch_id, BatchOperation::Delete, account_id, bucket, "{}", &items)
+}
 
-    let mut file = tokio::fs::File::open(file_path)
-        .await
-        .map_err(|e| AppError::InvalidInput(format!("Cannot open file: {}", e)))?;
+/// Store (or clear) the failures of a batch, returning its id if any items failed
+fn save_failures(
+    db: &DbManager,
+    batch_id: &str,
+    operation: BatchOperation,
+    account_id: &str,
+    bucket: &str,
+    params: &str,
+    items: &[FailedItem],
+) -> Option<String> {
+    if let Err(e) = db.save_failed_batch(batch_id, operation, account_id, bucket, params, items) {
+        log::warn!("Failed to save failed items for batch {}: {}", batch_id, e);
+        return None;
+    }
+    (!items.is_empty()).then(|| batch_id.to_string())
+}
 
-    let mut part_number = 1;
-    let mut completed_parts = Vec::new();
-    let mut bytes_uploaded: u64 = 0;
+/// Move keys to `<trash_prefix><timestamp>/<original-key>` (copy, then batched delete)
+async fn move_keys_to_trash(
+    client: &aws_sdk_s3::Client,
+    db: &DbManager,
+    account_id: &str,
+    bucket: &str,
+    keys: &[String],
+    trash_prefix: &str,
+    retry: RetryPolicy,
+) -> Result<(usize, Vec<DeleteError>), AppError> {
+    if keys.is_empty() {
+        return Ok((0, vec![]));
+    }
 
-    // Clone values needed for abort
-    let client = Arc::new(client.clone());
-    let bucket_clone = bucket.to_string();
-    let key_clone = key.to_string();
-    let s3_upload_id_clone = s3_upload_id.clone();
+    // A random suffix keeps batches started in the same second apart
+    let batch_prefix = format!(
+        "{}{}-{}/",
+        trash_prefix,
+        chrono::Utc::now().format(TRASH_BATCH_TIME_FORMAT),
+        &uuid::Uuid::new_v4().simple().to_string()[..8]
+    );
+    let mut errors: Vec<DeleteError> = Vec::new();
+    let mut copied: Vec<(String, String, u128)> = Vec::new();
 
-    loop {
-        let mut buffer = vec![0u8; PART_SIZE];
-        let bytes_read = file
-            .read(&mut buffer)
-            .await
-            .map_err(|e| AppError::InvalidInput(format!("Read error: {}", e)))?;
+    for key in keys {
+        let start_time = Instant::now();
+        let trash_key = format!("{}{}", batch_prefix, key);
 
-        if bytes_read == 0 {
-            break;
+        match copy_object_server_side(client, bucket, key, None, bucket, &trash_key, retry).await {
+            Ok(()) => copied.push((key.clone(), trash_key, start_time.elapsed().as_millis())),
+            Err(e) => {
+                let error = format!("Failed to move to trash: {}", e);
+                let _ = db.log_completed_operation(
+                    account_id,
+                    bucket,
+                    OperationType::Trash,
+                    Some(key),
+                    Some(&trash_key),
+                    None,
+                    start_time.elapsed().as_millis() as i64,
+                    Some(&error),
+                );
+                errors.push(DeleteError {
+                    key: key.clone(),
+                    error,
+                });
+            }
         }
+    }
 
-        buffer.truncate(bytes_read);
+    // Only remove originals that made it into the trash
+    let originals: Vec<String> = copied.iter().map(|(k, _, _)| k.clone()).collect();
+    let (_, delete_errors) =
+        delete_keys_in_batches(client, bucket, &originals, retry, |_| {}).await?;
 
-        let upload_part_response = match client
-            .upload_part()
-            .bucket(bucket)
-            .key(key)
-            .upload_id(&s3_upload_id)
-            .part_number(part_number)
-            .body(aws_sdk_s3::primitives::ByteStream::from(buffer))
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => {
-                // Attempt to abort on failure
-                let _ = client
-                    .abort_multipart_upload()
-                    .bucket(&bucket_clone)
-                    .key(&key_clone)
-                    .upload_id(&s3_upload_id_clone)
-                    .send()
-                    .await;
-                return Err(AppError::S3(format!("{:?}", e)));
-            }
-        };
-
-        bytes_uploaded += bytes_read as u64;
-
-        // Emit progress
-        let _ = app.emit(
-            "upload-progress",
-            UploadProgress {
-                upload_id: upload_id.to_string(),
-                bytes_uploaded,
-                total_bytes,
-            },
+    let mut moved = 0;
+    for (key, trash_key, duration_ms) in &copied {
+        let error = delete_errors.iter().find(|e| &e.key == key);
+        if error.is_none() {
+            moved += 1;
+        }
+        let _ = db.log_completed_operation(
+            account_id,
+            bucket,
+            OperationType::Trash,
+            Some(key),
+            Some(trash_key),
+            None,
+            *duration_ms as i64,
+            error.map(|e| e.error.as_str()),
         );
+    }
+    errors.extend(delete_errors);
 
-        completed_parts.push(
-            aws_sdk_s3::types::CompletedPart::builder()
-                .e_tag(upload_part_response.e_tag().unwrap_or_default())
-                .part_number(part_number)
-                .build(),
-        );
+    Ok((moved, errors))
+}
 
-        part_number += 1;
-    }
+/// Timestamp at the start of a trash batch folder name
+const TRASH_BATCH_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
 
-    // Complete multipart upload
-    let completed_upload = aws_sdk_s3::types::CompletedMultipartUpload::builder()
-        .set_parts(Some(completed_parts))
-        .build();
+/// The timestamp of the trash batch a key was moved in, from a batch folder
+/// named `<timestamp>-<suffix>` (or just `<timestamp>` for older batches)
+fn trash_batch_time<'a>(trash_key: &'a str, trash_prefix: &str) -> Option<&'a str> {
+    let batch = trash_key.strip_prefix(trash_prefix)?.split('/').next()?;
+    batch.split('-').next()
+}
 
-    let complete_response = client
-        .complete_multipart_upload()
-        .bucket(bucket)
-        .key(key)
-        .upload_id(&s3_upload_id)
-        .multipart_upload(completed_upload)
-        .send()
-        .await?;
+/// Split a trash key into its original key (`<trash_prefix><batch>/<original-key>`)
+fn original_key_from_trash(trash_key: &str, trash_prefix: &str) -> Option<String> {
+    let rest = trash_key.strip_prefix(trash_prefix)?;
+    let (_, original) = rest.split_once('/')?;
+    if original.is_empty() {
+        None
+    } else {
+        Some(original.to_string())
+    }
+}
 
-    Ok(complete_response
-        .e_tag()
-        .map(|s| s.trim_matches('"').to_string()))
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreFromTrashResult {
+    pub restored: usize,
+    pub errors: Vec<DeleteError>,
 }
 
-/// Create a folder in S3 by creating a zero-byte object with a trailing slash
+/// Restore trashed objects (or whole trash folders) to their original keys
 #[tauri::command(rename_all = "camelCase")]
-pub async fn create_folder(
+pub async fn restore_from_trash(
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
     db: State<'_, DbManager>,
+    listing_cache: State<'_, ListingCache>,
     account_id: String,
     bucket: String,
-    prefix: String,
-    folder_name: String,
-) -> Result<String, AppError> {
-    let start_time = Instant::now();
-
-    // Validate folder name
-    if folder_name.is_empty() {
-        return Err(AppError::InvalidInput("Folder name cannot be empty".into()));
-    }
-    if folder_name.contains('/') || folder_name.contains('\\') {
-        return Err(AppError::InvalidInput(
-            "Folder name cannot contain slashes".into(),
-        ));
-    }
-
+    trash_keys: Vec<String>,
+) -> Result<RestoreFromTrashResult, AppError> {
     let account = credentials.get_account(&account_id)?;
     let secret = credentials.get_secret_key(&account_id)?;
 
@@ -683,103 +1615,108 @@ pub async fn create_folder(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    // Construct the full key with trailing slash
-    let key = format!("{}{}/", prefix, folder_name);
+    let settings = db.get_app_settings()?;
+    let trash_prefix = settings.trash_prefix();
+    let retry = RetryPolicy::from_settings(&settings);
 
-    // Create a zero-byte object to represent the folder
-    let result = client
-        .put_object()
-        .bucket(&bucket)
-        .key(&key)
-        .body(aws_sdk_s3::primitives::ByteStream::from(Vec::new()))
-        .send()
+    // Expand trash folders into the objects they contain
+    let mut keys: Vec<String> = Vec::new();
+    for key in &trash_keys {
+        if !key.starts_with(&trash_prefix) {
+            return Err(AppError::InvalidInput(format!("Not a trash key: {}", key)));
+        }
+        if key.ends_with('/') {
+            let (matches, _) =
+                list_matching_objects(&client, &bucket, key, &ObjectFilter::default(), None, None)
+                    .await?;
+            keys.extend(matches.into_iter().map(|m| m.key));
+        } else {
+            keys.push(key.clone());
+        }
+    }
+
+    let mut restored_keys: Vec<String> = Vec::new();
+    let mut errors: Vec<DeleteError> = Vec::new();
+
+    for trash_key in &keys {
+        let start_time = Instant::now();
+        let Some(original_key) = original_key_from_trash(trash_key, &trash_prefix) else {
+            errors.push(DeleteError {
+                key: trash_key.clone(),
+                error: "Could not determine original key".to_string(),
+            });
+            continue;
+        };
+
+        // Trashed objects can be over 5GB, which a single CopyObject can't copy
+        let result = copy_object_server_side(
+            &client,
+            &bucket,
+            trash_key,
+            None,
+            &bucket,
+            &original_key,
+            retry,
+        )
         .await;
 
-    let duration_ms = start_time.elapsed().as_millis() as i64;
+        let error = result.err().map(|e| format!("Failed to restore: {}", e));
+        let _ = db.log_completed_operation(
+            &account_id,
+            &bucket,
+            OperationType::RestoreFromTrash,
+            Some(trash_key),
+            Some(&original_key),
+            None,
+            start_time.elapsed().as_millis() as i64,
+            error.as_deref(),
+        );
 
-    match result {
-        Ok(_) => {
-            let _ = db.log_completed_operation(
-                &account_id,
-                &bucket,
-                OperationType::CreateFolder,
-                Some(&key),
-                None,
-                Some(0),
-                duration_ms,
-                None,
-            );
-            Ok(key)
-        }
-        Err(e) => {
-            let _ = db.log_completed_operation(
-                &account_id,
-                &bucket,
-                OperationType::CreateFolder,
-                Some(&key),
-                None,
-                Some(0),
-                duration_ms,
-                Some(&e.to_string()),
-            );
-            Err(AppError::S3(format!("{:?}", e)))
+        match error {
+            None => restored_keys.push(trash_key.clone()),
+            Some(error) => errors.push(DeleteError {
+                key: trash_key.clone(),
+                error,
+            }),
         }
     }
-}
-
-// Download event types for progress tracking
-#[derive(Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DownloadStarted {
-    pub download_id: String,
-    pub file_name: String,
-    pub total_bytes: u64,
-}
 
-#[derive(Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DownloadProgress {
-    pub download_id: String,
-    pub bytes_downloaded: u64,
-    pub total_bytes: u64,
-}
+    // Remove the trash copies of everything restored
+    let retry = RetryPolicy::from_settings(&db.get_app_settings()?);
+    let (_, delete_errors) =
+        delete_keys_in_batches(&client, &bucket, &restored_keys, retry, |_| {}).await?;
+    errors.extend(delete_errors);
 
-#[derive(Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DownloadCompleted {
-    pub download_id: String,
-    pub key: String,
-    pub path: String,
-}
+    // Both the restored originals and the removed trash copies
+    let mut touched: Vec<String> = restored_keys
+        .iter()
+        .filter_map(|key| original_key_from_trash(key, &trash_prefix))
+        .collect();
+    touched.extend(restored_keys.iter().cloned());
+    cache::invalidate_for(&listing_cache, &db, &account_id, &bucket, &[], &touched);
 
-#[derive(Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DownloadFailed {
-    pub download_id: String,
-    pub error: String,
+    Ok(RestoreFromTrashResult {
+        restored: restored_keys.len(),
+        errors,
+    })
 }
 
-const DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024; // 64KB chunks
-
-/// Download an object from S3 to local filesystem
+/// Permanently delete trashed objects, optionally only those trashed more than `older_than_days` ago
 #[tauri::command(rename_all = "camelCase")]
-pub async fn download_object(
-    app: AppHandle,
+pub async fn empty_trash(
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
     db: State<'_, DbManager>,
+    listing_cache: State<'_, ListingCache>,
     account_id: String,
     bucket: String,
-    key: String,
-    destination: String,
-    download_id: String,
-) -> Result<String, AppError> {
+    older_than_days: Option<i64>,
+) -> Result<DeleteResult, AppError> {
     let start_time = Instant::now();
-    let file_name = key.rsplit('/').next().unwrap_or(&key).to_string();
-
     let account = credentials.get_account(&account_id)?;
     let secret = credentials.get_secret_key(&account_id)?;
 
@@ -791,313 +1728,413 @@ pub async fn download_object(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    // Get the object
-    let response = match client.get_object().bucket(&bucket).key(&key).send().await {
-        Ok(resp) => resp,
-        Err(e) => {
-            let duration_ms = start_time.elapsed().as_millis() as i64;
-            let _ = db.log_completed_operation(
-                &account_id,
-                &bucket,
-                OperationType::Download,
-                Some(&key),
-                None,
-                None,
-                duration_ms,
-                Some(&e.to_string()),
-            );
-            let _ = app.emit(
-                "download-failed",
-                DownloadFailed {
-                    download_id,
-                    error: format!("{:?}", e),
-                },
-            );
-            return Err(AppError::S3(format!("{:?}", e)));
-        }
-    };
+    let trash_prefix = db.get_app_settings()?.trash_prefix();
+    let (matches, _) = list_matching_objects(
+        &client,
+        &bucket,
+        &trash_prefix,
+        &ObjectFilter::default(),
+        None,
+        None,
+    )
+    .await?;
 
-    let total_bytes = response.content_length().unwrap_or(0) as u64;
+    // The batch folder name starts with the trash timestamp
+    let cutoff = older_than_days.map(|days| {
+        (chrono::Utc::now() - chrono::Duration::days(days))
+            .format(TRASH_BATCH_TIME_FORMAT)
+            .to_string()
+    });
+    let keys: Vec<String> = matches
+        .into_iter()
+        .map(|m| m.key)
+        .filter(|key| match &cutoff {
+            Some(cutoff) => trash_batch_time(key, &trash_prefix)
+                .map_or(false, |ts| ts < cutoff.as_str()),
+            None => true,
+        })
+        .collect();
 
-    // Emit started event
-    let _ = app.emit(
-        "download-started",
-        DownloadStarted {
-            download_id: download_id.clone(),
-            file_name: file_name.clone(),
-            total_bytes,
-        },
-    );
+    if keys.is_empty() {
+        return Ok(DeleteResult {
+            deleted: 0,
+            trashed: 0,
+            errors: vec![],
+            operation_batch_id: None,
+        });
+    }
 
-    // Create destination path
-    let dest_path = PathBuf::from(&destination).join(&file_name);
+    let retry = RetryPolicy::from_settings(&db.get_app_settings()?);
+    let (deleted, errors) =
+        delete_keys_in_batches(&client, &bucket, &keys, retry, |_| {}).await?;
+    let duration_ms = start_time.elapsed().as_millis() as i64;
+    cache::invalidate_for(&listing_cache, &db, &account_id, &bucket, &[], &keys);
 
-    // Create parent directories if needed
-    if let Some(parent) = dest_path.parent() {
-        if let Err(e) = tokio::fs::create_dir_all(parent).await {
-            let _ = app.emit(
-                "download-failed",
-                DownloadFailed {
-                    download_id,
-                    error: format!("Failed to create directory: {}", e),
-                },
-            );
-            return Err(AppError::InvalidInput(format!(
-                "Failed to create directory: {}",
-                e
-            )));
-        }
+    for key in &keys {
+        let error = errors.iter().find(|e| &e.key == key);
+        let _ = db.log_completed_operation(
+            &account_id,
+            &bucket,
+            OperationType::Delete,
+            Some(key),
+            None,
+            None,
+            duration_ms / keys.len() as i64,
+            error.map(|e| e.error.as_str()),
+        );
     }
 
-    // Create the file
-    let mut file = match tokio::fs::File::create(&dest_path).await {
-        Ok(f) => f,
-        Err(e) => {
-            let _ = app.emit(
-                "download-failed",
-                DownloadFailed {
-                    download_id,
-                    error: format!("Failed to create file: {}", e),
-                },
-            );
-            return Err(AppError::InvalidInput(format!(
-                "Failed to create file: {}",
-                e
-            )));
-        }
-    };
+    Ok(DeleteResult {
+        deleted,
+        trashed: 0,
+        errors,
+        operation_batch_id: None,
+    })
+}
 
-    // Stream the body to file
-    let mut body = response.body.into_async_read();
-    let mut bytes_downloaded: u64 = 0;
-    let mut buffer = vec![0u8; DOWNLOAD_CHUNK_SIZE];
+/// List every key under a prefix across all pages
+async fn list_keys_under_prefix(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    prefix: &str,
+) -> Result<Vec<String>, AppError> {
+    let listing = flat_listing(client, bucket, Some(prefix));
+    collect_paginated_keys(|continuation_token| {
+        let listing = &listing;
+        async move {
+            let (response, next_token) = list_page(listing, continuation_token.as_deref()).await?;
+            let keys = response
+                .contents()
+                .iter()
+                .filter_map(|obj| obj.key().map(str::to_string))
+                .collect();
+            Ok((keys, next_token))
+        }
+    })
+    .await
+}
 
-    use tokio::io::AsyncWriteExt;
+/// Collect the keys of every page of a listing. `fetch_page` gets the
+/// continuation token (`None` for the first page) and returns that page's keys
+/// with the token for the next one, or `None` on the last page.
+async fn collect_paginated_keys<F, Fut>(mut fetch_page: F) -> Result<Vec<String>, AppError>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: std::future::Future<Output = Result<(Vec<String>, Option<String>), AppError>>,
+{
+    let mut keys = Vec::new();
+    let mut continuation_token: Option<String> = None;
 
     loop {
-        let bytes_read = match body.read(&mut buffer).await {
-            Ok(0) => break, // EOF
-            Ok(n) => n,
-            Err(e) => {
-                let _ = app.emit(
-                    "download-failed",
-                    DownloadFailed {
-                        download_id,
-                        error: format!("Read error: {}", e),
-                    },
-                );
-                return Err(AppError::InvalidInput(format!("Read error: {}", e)));
-            }
-        };
+        let (page, next_token) = fetch_page(continuation_token.take()).await?;
+        keys.extend(page);
 
-        if let Err(e) = file.write_all(&buffer[..bytes_read]).await {
-            let _ = app.emit(
-                "download-failed",
-                DownloadFailed {
-                    download_id,
-                    error: format!("Write error: {}", e),
-                },
-            );
-            return Err(AppError::InvalidInput(format!("Write error: {}", e)));
+        match next_token {
+            Some(token) => continuation_token = Some(token),
+            None => break,
         }
-
-        bytes_downloaded += bytes_read as u64;
-
-        // Emit progress
-        let _ = app.emit(
-            "download-progress",
-            DownloadProgress {
-                download_id: download_id.clone(),
-                bytes_downloaded,
-                total_bytes,
-            },
-        );
     }
 
-    // Flush and sync
-    if let Err(e) = file.sync_all().await {
-        let _ = app.emit(
-            "download-failed",
-            DownloadFailed {
-                download_id,
-                error: format!("Sync error: {}", e),
-            },
-        );
-        return Err(AppError::InvalidInput(format!("Sync error: {}", e)));
-    }
+    Ok(keys)
+}
 
-    let final_path = dest_path.to_string_lossy().to_string();
-    let duration_ms = start_time.elapsed().as_millis() as i64;
+/// Delete keys using batched DeleteObjects calls (max 1000 keys per request).
+/// `on_batch` is called with the running number of processed keys after each batch.
+async fn delete_keys_in_batches(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    keys: &[String],
+    retry: RetryPolicy,
+    mut on_batch: impl FnMut(usize),
+) -> Result<(usize, Vec<DeleteError>), AppError> {
+    let mut total_deleted = 0;
+    let mut processed = 0;
+    let mut all_errors: Vec<DeleteError> = Vec::new();
 
-    // Log successful download to history
-    let _ = db.log_completed_operation(
-        &account_id,
-        &bucket,
-        OperationType::Download,
-        Some(&key),
-        None,
-        Some(total_bytes as i64),
-        duration_ms,
-        None,
-    );
+    // S3 delete_objects can handle up to 1000 objects per call
+    for chunk in keys.chunks(1000) {
+        let mut pending: Vec<&String> = chunk.iter().collect();
+        let mut attempt = 0;
 
-    // Emit completed event
-    let _ = app.emit(
-        "download-completed",
-        DownloadCompleted {
-            download_id,
-            key,
-            path: final_path.clone(),
-        },
-    );
+        loop {
+            let objects_to_delete: Vec<ObjectIdentifier> = pending
+                .iter()
+                .filter_map(|key| {
+                    ObjectIdentifier::builder()
+                        .key(key.as_str())
+                        .build()
+                        .ok()
+                })
+                .collect();
+
+            let delete = aws_sdk_s3::types::Delete::builder()
+                .set_objects(Some(objects_to_delete))
+                .build()
+                .map_err(|e| AppError::S3(format!("Failed to build delete request: {:?}", e)))?;
 
-    Ok(final_path)
-}
+            let response = with_retry(retry, || {
+                client
+                    .delete_objects()
+                    .bucket(bucket)
+                    .delete(delete.clone())
+                    .send()
+            })
+            .await?;
 
-/// Search for objects recursively within a prefix
-#[tauri::command(rename_all = "camelCase")]
-pub async fn search_objects(
-    credentials: State<'_, CredentialsManager>,
-    s3_clients: State<'_, S3ClientManager>,
-    account_id: String,
-    bucket: String,
-    prefix: String,
-    query: String,
-    max_results: Option<u32>,
-) -> Result<Vec<S3Object>, AppError> {
-    use std::collections::HashSet;
+            // Count successful deletions
+            total_deleted += response.deleted().len();
+
+            // A throttled or failed key is reported per key inside a successful
+            // response; send those again with backoff and record the rest
+            let mut retry_keys = Vec::new();
+            for err in response.errors() {
+                let key = err.key().unwrap_or_default();
+                let retryable = attempt < retry.max_retries
+                    && err.code().is_some_and(is_retryable_code);
+                match pending.iter().find(|k| k.as_str() == key) {
+                    Some(pending_key) if retryable => retry_keys.push(*pending_key),
+                    _ => all_errors.push(DeleteError {
+                        key: key.to_string(),
+                        error: err.message().unwrap_or_default().to_string(),
+                    }),
+                }
+            }
 
-    if query.is_empty() {
-        return Ok(Vec::new());
+            if retry_keys.is_empty() {
+                break;
+            }
+            tokio::time::sleep(retry.delay(attempt)).await;
+            attempt += 1;
+            pending = retry_keys;
+        }
+
+        processed += chunk.len();
+        on_batch(processed);
     }
 
-    let account = credentials.get_account(&account_id)?;
-    let secret = credentials.get_secret_key(&account_id)?;
+    Ok((total_deleted, all_errors))
+}
 
-    let client = s3_clients
-        .get_or_create_client(
-            &account_id,
-            &account.endpoint,
-            &account.access_key_id,
-            &secret,
-            account.provider_type,
-            account.region.as_deref(),
-        )
-        .await?;
+/// Criteria for selecting objects under a prefix
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectFilter {
+    /// Glob (`*`, `?`) matched against the file name, or the full key if it contains '/'
+    pub name_glob: Option<String>,
+    /// Only objects last modified before this RFC 3339 timestamp
+    pub older_than: Option<String>,
+    /// Only objects last modified after this RFC 3339 timestamp
+    pub newer_than: Option<String>,
+    pub min_size: Option<i64>,
+    pub max_size: Option<i64>,
+    pub storage_class: Option<String>,
+}
 
-    let max = max_results.unwrap_or(100) as usize;
-    let query_lower = query.to_lowercase();
-    let mut file_results: Vec<S3Object> = Vec::new();
-    let mut folder_results: Vec<S3Object> = Vec::new();
-    let mut seen_folders: HashSet<String> = HashSet::new();
-    let mut continuation_token: Option<String> = None;
+/// Filter with parsed timestamps, ready for matching
+struct CompiledFilter<'a> {
+    filter: &'a ObjectFilter,
+    older_than: Option<i64>,
+    newer_than: Option<i64>,
+}
 
-    // List all objects recursively (no delimiter) and filter by query
-    loop {
-        let mut request = client.list_objects_v2().bucket(&bucket);
+impl ObjectFilter {
+    fn compile(&self) -> Result<CompiledFilter<'_>, AppError> {
+        let parse = |value: &Option<String>| -> Result<Option<i64>, AppError> {
+            value
+                .as_deref()
+                .map(|v| {
+                    chrono::DateTime::parse_from_rfc3339(v)
+                        .map(|d| d.timestamp())
+                        .map_err(|e| AppError::InvalidInput(format!("Invalid date '{}': {}", v, e)))
+                })
+                .transpose()
+        };
+
+        Ok(CompiledFilter {
+            filter: self,
+            older_than: parse(&self.older_than)?,
+            newer_than: parse(&self.newer_than)?,
+        })
+    }
+}
+
+impl CompiledFilter<'_> {
+    fn matches(&self, obj: &aws_sdk_s3::types::Object) -> bool {
+        let Some(key) = obj.key() else {
+            return false;
+        };
+        let size = obj.size().unwrap_or(0);
 
-        if !prefix.is_empty() {
-            request = request.prefix(&prefix);
+        if let Some(pattern) = &self.filter.name_glob {
+            let target = if pattern.contains('/') {
+                key
+            } else {
+                key.trim_end_matches('/').rsplit('/').next().unwrap_or(key)
+            };
+            if !glob_match(pattern, target) {
+                return false;
+            }
+        }
+        if self.filter.min_size.map_or(false, |min| size < min) {
+            return false;
+        }
+        if self.filter.max_size.map_or(false, |max| size > max) {
+            return false;
+        }
+        if let Some(class) = &self.filter.storage_class {
+            let obj_class = obj
+                .storage_class()
+                .map(|c| c.as_str())
+                .unwrap_or("STANDARD");
+            if !obj_class.eq_ignore_ascii_case(class) {
+                return false;
+            }
         }
 
-        if let Some(token) = &continuation_token {
-            request = request.continuation_token(token);
+        let modified = obj.last_modified().map(|d| d.secs());
+        if let Some(before) = self.older_than {
+            if modified.map_or(true, |m| m >= before) {
+                return false;
+            }
+        }
+        if let Some(after) = self.newer_than {
+            if modified.map_or(true, |m| m <= after) {
+                return false;
+            }
         }
 
-        let response = request.send().await?;
+        true
+    }
+}
 
-        for obj in response.contents() {
-            if let Some(key) = obj.key() {
-                // Extract all parent folder paths from this key and check for matches
-                // e.g., "a/b/c/file.txt" -> check folders "a/", "a/b/", "a/b/c/"
-                let key_without_prefix = if !prefix.is_empty() && key.starts_with(&prefix) {
-                    &key[prefix.len()..]
-                } else {
-                    key
-                };
+/// Simple glob matching supporting `*` (any run) and `?` (single char)
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<usize> = None;
+    let mut star_t = 0;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some(p);
+            star_t = t;
+            p += 1;
+        } else if let Some(star_p) = star {
+            p = star_p + 1;
+            star_t += 1;
+            t = star_t;
+        } else {
+            return false;
+        }
+    }
 
-                let parts: Vec<&str> = key_without_prefix.split('/').collect();
-                let mut folder_path = prefix.clone();
+    while p < pattern.len() && pattern[p] == '*' {
+        p += 1;
+    }
+    p == pattern.len()
+}
 
-                // Check each folder segment (except the last part which is the file name)
-                for (i, part) in parts.iter().enumerate() {
-                    if i < parts.len() - 1 && !part.is_empty() {
-                        folder_path.push_str(part);
-                        folder_path.push('/');
+/// An object selected by an `ObjectFilter`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilteredObject {
+    pub key: String,
+    pub size: i64,
+    pub last_modified: Option<String>,
+    pub storage_class: Option<String>,
+}
 
-                        // Check if this folder name matches the query
-                        if part.to_lowercase().contains(&query_lower) {
-                            if !seen_folders.contains(&folder_path) {
-                                seen_folders.insert(folder_path.clone());
-                                folder_results.push(S3Object {
-                                    key: folder_path.clone(),
-                                    size: 0,
-                                    last_modified: None,
-                                    etag: None,
-                                    is_folder: true,
-                                });
-                            }
-                        }
-                    }
-                }
+/// List every object under `prefix` that matches `filter`.
+/// Stops after `limit` matches (returning `true` for truncated) or when cancelled.
+async fn list_matching_objects(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    prefix: &str,
+    filter: &ObjectFilter,
+    limit: Option<usize>,
+    cancel_flag: Option<&AtomicBool>,
+) -> Result<(Vec<FilteredObject>, bool), AppError> {
+    let compiled = filter.compile()?;
+    let mut matches: Vec<FilteredObject> = Vec::new();
+    let mut continuation_token: Option<String> = None;
 
-                // Get the file name from the key and check for match
-                let name = key.rsplit('/').next().unwrap_or(key);
+    let listing = flat_listing(client, bucket, Some(prefix));
+    loop {
+        if cancel_flag.map_or(false, |f| f.load(Ordering::Relaxed)) {
+            return Err(AppError::Cancelled);
+        }
 
-                // Case-insensitive search for files
-                if name.to_lowercase().contains(&query_lower) {
-                    file_results.push(S3Object {
-                        key: key.to_string(),
-                        size: obj.size().unwrap_or(0),
-                        last_modified: obj.last_modified().map(|d| d.to_string()),
-                        etag: obj.e_tag().map(|e| e.trim_matches('"').to_string()),
-                        is_folder: key.ends_with('/'),
-                    });
-                }
+        let (response, next_token) = list_page(&listing, continuation_token.as_deref()).await?;
 
-                // Check if we have enough results
-                if file_results.len() + folder_results.len() >= max {
-                    // Combine folders first, then files
-                    folder_results.extend(file_results);
-                    folder_results.truncate(max);
-                    return Ok(folder_results);
-                }
+        for obj in response.contents() {
+            // Skip folder markers
+            if obj.key().map_or(true, |k| k.ends_with('/')) || !compiled.matches(obj) {
+                continue;
             }
+
+            if limit.map_or(false, |l| matches.len() >= l) {
+                return Ok((matches, true));
+            }
+
+            matches.push(FilteredObject {
+                key: obj.key().unwrap_or_default().to_string(),
+                size: obj.size().unwrap_or(0),
+                last_modified: obj.last_modified().map(|d| d.to_string()),
+                storage_class: obj.storage_class().map(|c| c.as_str().to_string()),
+            });
         }
 
-        if response.is_truncated() == Some(true) {
-            continuation_token = response.next_continuation_token().map(|s| s.to_string());
-        } else {
-            break;
+        match next_token {
+            Some(token) => continuation_token = Some(token),
+            None => break,
         }
     }
 
-    // Combine results: folders first, then files
-    folder_results.extend(file_results);
-    Ok(folder_results)
+    Ok((matches, false))
+}
+
+/// Default and maximum number of keys returned by `resolve_keys`
+const RESOLVE_KEYS_DEFAULT_LIMIT: usize = 10_000;
+const RESOLVE_KEYS_MAX_LIMIT: usize = 100_000;
+
+/// Global state for tracking active `resolve_keys` calls
+#[derive(Default)]
+pub struct ResolveKeysState {
+    /// Map of request_id -> cancellation flag
+    pub active: tokio::sync::RwLock<HashMap<String, Arc<AtomicBool>>>,
 }
 
-// Presigned URL types
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct PresignedUrlResult {
-    pub url: String,
-    pub expires_at: String,
+pub struct ResolveKeysResult {
+    pub keys: Vec<String>,
+    pub total_size: i64,
+    /// True if more objects matched than the limit allowed
+    pub truncated: bool,
 }
 
-/// Generate a presigned URL for downloading an object
+/// Resolve every key under a prefix matching an optional filter, for feeding
+/// into bulk operations (delete, copy, tagging) without paginating in the UI
 #[tauri::command(rename_all = "camelCase")]
-pub async fn generate_presigned_url(
+pub async fn resolve_keys(
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
+    resolve_state: State<'_, ResolveKeysState>,
     account_id: String,
     bucket: String,
-    key: String,
-    expires_in_seconds: u64,
-) -> Result<PresignedUrlResult, AppError> {
+    prefix: String,
+    filter: Option<ObjectFilter>,
+    limit: Option<usize>,
+    request_id: String,
+) -> Result<ResolveKeysResult, AppError> {
     let account = credentials.get_account(&account_id)?;
     let secret = credentials.get_secret_key(&account_id)?;
 
@@ -1109,65 +2146,154 @@ pub async fn generate_presigned_url(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    let expires_in = Duration::from_secs(expires_in_seconds);
-    let presigning_config = PresigningConfig::expires_in(expires_in)
-        .map_err(|e| AppError::InvalidInput(format!("Invalid expiry duration: {}", e)))?;
-
-    let presigned_request = client
-        .get_object()
-        .bucket(&bucket)
-        .key(&key)
-        .presigned(presigning_config)
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    resolve_state
+        .active
+        .write()
         .await
-        .map_err(|e| AppError::S3(format!("Failed to generate presigned URL: {:?}", e)))?;
+        .insert(request_id.clone(), cancel_flag.clone());
 
-    let expires_at = chrono::Utc::now() + chrono::Duration::seconds(expires_in_seconds as i64);
-
-    Ok(PresignedUrlResult {
-        url: presigned_request.uri().to_string(),
-        expires_at: expires_at.to_rfc3339(),
+    let limit = limit
+        .unwrap_or(RESOLVE_KEYS_DEFAULT_LIMIT)
+        .min(RESOLVE_KEYS_MAX_LIMIT);
+    let result = list_matching_objects(
+        &client,
+        &bucket,
+        &prefix,
+        &filter.unwrap_or_default(),
+        Some(limit),
+        Some(&cancel_flag),
+    )
+    .await;
+
+    resolve_state.active.write().await.remove(&request_id);
+
+    let (matches, truncated) = result?;
+    Ok(ResolveKeysResult {
+        total_size: matches.iter().map(|m| m.size).sum(),
+        keys: matches.into_iter().map(|m| m.key).collect(),
+        truncated,
     })
 }
 
-// Rename types
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct RenameResult {
-    pub old_key: String,
-    pub new_key: String,
-    pub objects_renamed: usize,
+/// Cancel a running `resolve_keys` call
+#[tauri::command(rename_all = "camelCase")]
+pub async fn cancel_resolve_keys(
+    resolve_state: State<'_, ResolveKeysState>,
+    request_id: String,
+) -> Result<(), AppError> {
+    if let Some(flag) = resolve_state.active.read().await.get(&request_id) {
+        flag.store(true, Ordering::Relaxed);
+    }
+    Ok(())
 }
 
-/// Rename an object or folder by copying to new key and deleting old key
-#[tauri::command(rename_all = "camelCase")]
-pub async fn rename_object(
-    credentials: State<'_, CredentialsManager>,
-    s3_clients: State<'_, S3ClientManager>,
-    db: State<'_, DbManager>,
-    account_id: String,
-    bucket: String,
-    old_key: String,
-    new_name: String,
-) -> Result<RenameResult, AppError> {
-    let start_time = Instant::now();
+/// Objects whose content type is fetched at once during an inventory export
+const INVENTORY_HEAD_CONCURRENCY: usize = 8;
 
-    // Validate new name
-    if new_name.is_empty() {
-        return Err(AppError::InvalidInput("New name cannot be empty".into()));
-    }
-    if new_name.contains('/') || new_name.contains('\\') {
-        return Err(AppError::InvalidInput(
-            "New name cannot contain slashes".into(),
-        ));
-    }
+/// Global state for tracking running inventory exports
+#[derive(Default)]
+pub struct InventoryExportState {
+    /// Map of export_id -> cancellation flag
+    pub active: tokio::sync::RwLock<HashMap<String, Arc<AtomicBool>>>,
+}
 
-    let account = credentials.get_account(&account_id)?;
-    let secret = credentials.get_secret_key(&account_id)?;
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InventoryFormat {
+    Csv,
+    /// A single JSON array
+    Json,
+    /// One JSON object per line
+    Ndjson,
+}
 
-    let client = s3_clients
+/// One object in an inventory export
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InventoryRow {
+    key: String,
+    size: i64,
+    last_modified: Option<String>,
+    etag: Option<String>,
+    storage_class: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_type: Option<String>,
+}
+
+/// Progress event for inventory exports, sent as each listing page is written
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryExportProgress {
+    pub export_id: String,
+    pub objects_written: usize,
+    pub bytes_listed: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryExportResult {
+    pub path: String,
+    pub objects_written: usize,
+    pub total_size: i64,
+    /// True if the export was cancelled; no file is left behind then
+    pub cancelled: bool,
+}
+
+fn render_inventory_row(
+    format: InventoryFormat,
+    row: &InventoryRow,
+    include_content_type: bool,
+) -> Result<String, AppError> {
+    match format {
+        InventoryFormat::Csv => {
+            let mut line = format!(
+                "{},{},{},{},{}",
+                escape_csv(&row.key),
+                row.size,
+                row.last_modified.as_deref().unwrap_or_default(),
+                escape_csv(row.etag.as_deref().unwrap_or_default()),
+                row.storage_class.as_deref().unwrap_or_default(),
+            );
+            if include_content_type {
+                line.push(',');
+                line.push_str(&escape_csv(row.content_type.as_deref().unwrap_or_default()));
+            }
+            line.push('\n');
+            Ok(line)
+        }
+        InventoryFormat::Json | InventoryFormat::Ndjson => serde_json::to_string(row)
+            .map(|json| json + "\n")
+            .map_err(|e| AppError::Storage(format!("Failed to serialize inventory row: {}", e))),
+    }
+}
+
+/// Write every object under a prefix to a CSV, JSON or NDJSON file, page by
+/// page so large buckets never sit in memory. With `include_content_type`,
+/// each object is also HEADed for its content type, which is much slower.
+/// Emits `inventory-export-progress`; cancel with `cancel_inventory_export`.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn export_object_inventory(
+    app: AppHandle,
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    export_state: State<'_, InventoryExportState>,
+    account_id: String,
+    bucket: String,
+    prefix: String,
+    format: InventoryFormat,
+    output_path: String,
+    include_content_type: Option<bool>,
+    export_id: String,
+) -> Result<InventoryExportResult, AppError> {
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
         .get_or_create_client(
             &account_id,
             &account.endpoint,
@@ -1175,169 +2301,243 @@ pub async fn rename_object(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    let is_folder = old_key.ends_with('/');
-
-    // Calculate new key by replacing the last component of the path
-    let new_key = if is_folder {
-        // For folders: replace the folder name
-        let parts: Vec<&str> = old_key.trim_end_matches('/').split('/').collect();
-        if parts.len() == 1 {
-            format!("{}/", new_name)
-        } else {
-            let parent = parts[..parts.len() - 1].join("/");
-            format!("{}/{}/", parent, new_name)
-        }
-    } else {
-        // For files: replace the file name
-        let parts: Vec<&str> = old_key.split('/').collect();
-        if parts.len() == 1 {
-            new_name.clone()
-        } else {
-            let parent = parts[..parts.len() - 1].join("/");
-            format!("{}/{}", parent, new_name)
-        }
-    };
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    export_state
+        .active
+        .write()
+        .await
+        .insert(export_id.clone(), cancel_flag.clone());
 
-    let mut objects_renamed = 0;
+    // Written to a temporary file first so a failed or cancelled export leaves nothing behind
+    let temp_path = format!("{}.part", output_path);
+    let result = write_inventory(
+        &app,
+        &client,
+        &bucket,
+        &prefix,
+        format,
+        &temp_path,
+        include_content_type.unwrap_or(false),
+        &export_id,
+        &cancel_flag,
+    )
+    .await;
 
-    if is_folder {
-        // For folders, we need to copy all objects with the old prefix to the new prefix
-        let mut continuation_token: Option<String> = None;
+    export_state.active.write().await.remove(&export_id);
 
-        loop {
-            let mut request = client.list_objects_v2().bucket(&bucket).prefix(&old_key);
+    match result {
+        Ok((objects_written, total_size)) if !cancel_flag.load(Ordering::Relaxed) => {
+            tokio::fs::rename(&temp_path, &output_path)
+                .await
+                .map_err(|e| AppError::Storage(format!("Failed to save inventory: {}", e)))?;
+            Ok(InventoryExportResult {
+                path: output_path,
+                objects_written,
+                total_size,
+                cancelled: false,
+            })
+        }
+        Ok((objects_written, total_size)) => {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            Ok(InventoryExportResult {
+                path: output_path,
+                objects_written,
+                total_size,
+                cancelled: true,
+            })
+        }
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            Err(e)
+        }
+    }
+}
 
-            if let Some(token) = &continuation_token {
-                request = request.continuation_token(token);
-            }
+/// Stream the listing into `path`, returning the objects written and their total size
+async fn write_inventory(
+    app: &AppHandle,
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    prefix: &str,
+    format: InventoryFormat,
+    path: &str,
+    include_content_type: bool,
+    export_id: &str,
+    cancel_flag: &AtomicBool,
+) -> Result<(usize, i64), AppError> {
+    use tokio::io::AsyncWriteExt;
 
-            let response = request.send().await?;
+    let file = tokio::fs::File::create(path)
+        .await
+        .map_err(|e| AppError::Storage(format!("Failed to create {}: {}", path, e)))?;
+    let mut writer = tokio::io::BufWriter::new(file);
+    let write_err =
+        |e: std::io::Error| AppError::Storage(format!("Failed to write inventory: {}", e));
+
+    let header = match format {
+        InventoryFormat::Csv if include_content_type => {
+            "key,size,last_modified,etag,storage_class,content_type\n"
+        }
+        InventoryFormat::Csv => "key,size,last_modified,etag,storage_class\n",
+        InventoryFormat::Json => "[\n",
+        InventoryFormat::Ndjson => "",
+    };
+    writer.write_all(header.as_bytes()).await.map_err(write_err)?;
 
-            for obj in response.contents() {
-                if let Some(obj_key) = obj.key() {
-                    // Calculate the new key by replacing the old prefix with the new one
-                    let relative_path = obj_key.strip_prefix(&old_key).unwrap_or(obj_key);
-                    let dest_key = format!("{}{}", new_key, relative_path);
+    let mut objects_written = 0usize;
+    let mut total_size = 0i64;
+    let mut continuation_token: Option<String> = None;
+    let listing = flat_listing(client, bucket, Some(prefix));
 
-                    // Copy to new location
-                    let copy_source = format!(
-                        "{}/{}",
-                        bucket,
-                        urlencoding::encode(obj_key)
-                    );
+    loop {
+        if cancel_flag.load(Ordering::Relaxed) {
+            return Ok((objects_written, total_size));
+        }
 
-                    client
-                        .copy_object()
-                        .bucket(&bucket)
-                        .key(&dest_key)
-                        .copy_source(&copy_source)
-                        .send()
-                        .await
-                        .map_err(|e| {
-                            AppError::S3(format!("Failed to copy {}: {:?}", obj_key, e))
-                        })?;
-
-                    // Delete old object
-                    client
-                        .delete_object()
-                        .bucket(&bucket)
-                        .key(obj_key)
-                        .send()
-                        .await
-                        .map_err(|e| {
-                            AppError::S3(format!("Failed to delete {}: {:?}", obj_key, e))
-                        })?;
+        let (response, next_token) = list_page(&listing, continuation_token.as_deref()).await?;
+        let mut rows: Vec<InventoryRow> = response
+            .contents()
+            .iter()
+            .filter_map(|obj| {
+                Some(InventoryRow {
+                    key: obj.key()?.to_string(),
+                    size: obj.size().unwrap_or(0),
+                    last_modified: obj.last_modified().map(|d| d.to_string()),
+                    etag: obj.e_tag().map(|e| e.trim_matches('"').to_string()),
+                    storage_class: obj.storage_class().map(|s| s.as_str().to_string()),
+                    content_type: None,
+                })
+            })
+            .collect();
 
-                    objects_renamed += 1;
-                }
-            }
+        if include_content_type {
+            rows = stream::iter(rows)
+                .map(|mut row| async move {
+                    match client.head_object().bucket(bucket).key(&row.key).send().await {
+                        Ok(head) => row.content_type = head.content_type().map(|s| s.to_string()),
+                        Err(e) => log::warn!("Failed to get content type of {}: {:?}", row.key, e),
+                    }
+                    row
+                })
+                .buffered(INVENTORY_HEAD_CONCURRENCY)
+                .collect()
+                .await;
+        }
 
-            if response.is_truncated() == Some(true) {
-                continuation_token = response.next_continuation_token().map(|s| s.to_string());
-            } else {
-                break;
+        for row in &rows {
+            let mut line = render_inventory_row(format, row, include_content_type)?;
+            if matches!(format, InventoryFormat::Json) {
+                line.insert_str(0, if objects_written == 0 { "  " } else { ",\n  " });
+                line.pop();
             }
+            writer.write_all(line.as_bytes()).await.map_err(write_err)?;
+            objects_written += 1;
+            total_size += row.size;
         }
-    } else {
-        // For single files, just copy and delete
-        let copy_source = format!(
-            "{}/{}",
-            bucket,
-            urlencoding::encode(&old_key)
+
+        let _ = app.emit(
+            "inventory-export-progress",
+            InventoryExportProgress {
+                export_id: export_id.to_string(),
+                objects_written,
+                bytes_listed: total_size,
+            },
         );
 
-        client
-            .copy_object()
-            .bucket(&bucket)
-            .key(&new_key)
-            .copy_source(&copy_source)
-            .send()
-            .await
-            .map_err(|e| AppError::S3(format!("Failed to copy object: {:?}", e)))?;
+        match next_token {
+            Some(token) => continuation_token = Some(token),
+            None => break,
+        }
+    }
 
-        client
-            .delete_object()
-            .bucket(&bucket)
-            .key(&old_key)
-            .send()
-            .await
-            .map_err(|e| AppError::S3(format!("Failed to delete old object: {:?}", e)))?;
+    if matches!(format, InventoryFormat::Json) {
+        let footer = if objects_written == 0 { "]\n" } else { "\n]\n" };
+        writer.write_all(footer.as_bytes()).await.map_err(write_err)?;
+    }
+    writer.flush().await.map_err(write_err)?;
 
-        objects_renamed = 1;
+    Ok((objects_written, total_size))
+}
+
+/// Cancel a running `export_object_inventory` call
+#[tauri::command(rename_all = "camelCase")]
+pub async fn cancel_inventory_export(
+    export_state: State<'_, InventoryExportState>,
+    export_id: String,
+) -> Result<(), AppError> {
+    if let Some(flag) = export_state.active.read().await.get(&export_id) {
+        flag.store(true, Ordering::Relaxed);
     }
+    Ok(())
+}
 
-    let duration_ms = start_time.elapsed().as_millis() as i64;
+/// How long a `delete_objects_by_filter` dry-run token can be confirmed
+const FILTER_DELETE_TOKEN_TTL: Duration = Duration::from_secs(15 * 60);
 
-    // Log successful rename to history
-    let _ = db.log_completed_operation(
-        &account_id,
-        &bucket,
-        OperationType::Rename,
-        Some(&old_key),
-        Some(&new_key),
-        None,
-        duration_ms,
-        None,
-    );
+/// Keys previewed by a `delete_objects_by_filter` dry run
+pub struct PendingFilterDelete {
+    pub account_id: String,
+    pub bucket: String,
+    pub keys: Vec<String>,
+    pub created_at: Instant,
+}
 
-    Ok(RenameResult {
-        old_key,
-        new_key,
-        objects_renamed,
-    })
+/// Holds dry-run results of `delete_objects_by_filter` until they are confirmed
+/// or expire after `FILTER_DELETE_TOKEN_TTL`
+#[derive(Default)]
+pub struct FilterDeleteState {
+    /// Map of preview token -> previewed keys
+    pub pending: tokio::sync::RwLock<HashMap<String, PendingFilterDelete>>,
 }
 
-// Copy/Move types
-#[derive(Debug, Clone, Serialize)]
+/// Progress event for bulk deletes
+#[derive(Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct CopyMoveResult {
-    pub objects_copied: usize,
-    pub objects_deleted: usize,
-    pub errors: Vec<CopyMoveError>,
+pub struct DeleteProgress {
+    pub token: String,
+    pub deleted: usize,
+    pub total: usize,
 }
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct CopyMoveError {
-    pub source_key: String,
-    pub error: String,
+pub struct FilterDeleteResult {
+    pub dry_run: bool,
+    /// Token to pass back to confirm the deletion (dry runs only)
+    pub token: Option<String>,
+    pub matches: Vec<FilteredObject>,
+    pub total_count: usize,
+    pub total_size: i64,
+    pub deleted: usize,
+    /// Objects moved to the trash instead of deleted, when the trash is enabled
+    pub trashed: usize,
+    pub errors: Vec<DeleteError>,
+    /// Set when some deletes failed; pass to `retry_failed_items` to re-attempt them
+    pub operation_batch_id: Option<String>,
 }
 
-/// Copy or move objects to a destination prefix
+/// Delete every object under a prefix matching a filter.
+/// Must be called with `dry_run = true` first; the returned token confirms
+/// exactly the previewed set of keys for the destructive run.
 #[tauri::command(rename_all = "camelCase")]
-pub async fn copy_objects(
+pub async fn delete_objects_by_filter(
+    app: AppHandle,
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
     db: State<'_, DbManager>,
+    listing_cache: State<'_, ListingCache>,
+    pending_state: State<'_, FilterDeleteState>,
     account_id: String,
     bucket: String,
-    source_keys: Vec<String>,
-    destination_prefix: String,
-    delete_source: bool,
-) -> Result<CopyMoveResult, AppError> {
+    prefix: String,
+    filter: ObjectFilter,
+    dry_run: bool,
+    token: Option<String>,
+) -> Result<FilterDeleteResult, AppError> {
     let start_time = Instant::now();
     let account = credentials.get_account(&account_id)?;
     let secret = credentials.get_secret_key(&account_id)?;
@@ -1350,498 +2550,4440 @@ pub async fn copy_objects(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    let mut objects_copied = 0;
-    let mut objects_deleted = 0;
-    let mut errors: Vec<CopyMoveError> = Vec::new();
+    if dry_run {
+        let (matches, _) =
+            list_matching_objects(&client, &bucket, &prefix, &filter, None, None).await?;
+        let total_size = matches.iter().map(|m| m.size).sum();
+        let token = uuid::Uuid::new_v4().to_string();
+
+        let mut pending = pending_state.pending.write().await;
+        // Unconfirmed dry runs hold their whole key list, so drop them once expired
+        pending.retain(|_, entry| entry.created_at.elapsed() < FILTER_DELETE_TOKEN_TTL);
+        pending.insert(
+            token.clone(),
+            PendingFilterDelete {
+                account_id,
+                bucket,
+                keys: matches.iter().map(|m| m.key.clone()).collect(),
+                created_at: Instant::now(),
+            },
+        );
+        drop(pending);
+
+        return Ok(FilterDeleteResult {
+            dry_run: true,
+            token: Some(token),
+            total_count: matches.len(),
+            total_size,
+            matches,
+            deleted: 0,
+            trashed: 0,
+            errors: vec![],
+            operation_batch_id: None,
+        });
+    }
 
-    for source_key in &source_keys {
-        let is_folder = source_key.ends_with('/');
+    let token = token.ok_or_else(|| {
+        AppError::InvalidInput("Run a dry run first and pass its token to delete".to_string())
+    })?;
+    let pending = pending_state
+        .pending
+        .write()
+        .await
+        .remove(&token)
+        .filter(|entry| entry.created_at.elapsed() < FILTER_DELETE_TOKEN_TTL)
+        .ok_or_else(|| AppError::InvalidInput("Unknown or expired delete token".to_string()))?;
 
-        if is_folder {
-            // For folders, copy all objects recursively
-            let mut continuation_token: Option<String> = None;
+    if pending.account_id != account_id || pending.bucket != bucket {
+        return Err(AppError::InvalidInput(
+            "Delete token does not belong to this bucket".to_string(),
+        ));
+    }
 
-            loop {
-                let mut request = client.list_objects_v2().bucket(&bucket).prefix(source_key);
+    let keys = pending.keys;
+    let total = keys.len();
+    let result = delete_or_trash_keys(
+        &client,
+        &db,
+        &account_id,
+        &bucket,
+        keys.clone(),
+        start_time,
+        |processed| {
+            let _ = app.emit(
+                "delete-progress",
+                DeleteProgress {
+                    token: token.clone(),
+                    deleted: processed,
+                    total,
+                },
+            );
+        },
+    )
+    .await?;
+    let operation_batch_id = save_delete_failures(&db, None, &account_id, &bucket, &result.errors);
 
-                if let Some(token) = &continuation_token {
-                    request = request.continuation_token(token);
-                }
+    let trash_prefixes = if result.trashed > 0 {
+        vec![db.get_app_settings()?.trash_prefix()]
+    } else {
+        vec![]
+    };
+    cache::invalidate_for(
+        &listing_cache,
+        &db,
+        &account_id,
+        &bucket,
+        &trash_prefixes,
+        &keys,
+    );
 
-                let response = match request.send().await {
-                    Ok(r) => r,
-                    Err(e) => {
-                        errors.push(CopyMoveError {
-                            source_key: source_key.clone(),
-                            error: format!("Failed to list folder: {:?}", e),
-                        });
-                        break;
-                    }
-                };
+    Ok(FilterDeleteResult {
+        dry_run: false,
+        token: None,
+        matches: vec![],
+        total_count: total,
+        total_size: 0,
+        deleted: result.deleted,
+        trashed: result.trashed,
+        errors: result.errors,
+        operation_batch_id,
+    })
+}
 
-                for obj in response.contents() {
-                    if let Some(obj_key) = obj.key() {
-                        // Get the relative path within the folder
-                        let folder_name = source_key
-                            .trim_end_matches('/')
-                            .split('/')
-                            .last()
-                            .unwrap_or("");
-                        let relative_path = obj_key.strip_prefix(source_key).unwrap_or(obj_key);
-                        let dest_key =
-                            format!("{}{}/{}", destination_prefix, folder_name, relative_path);
+async fn upload_multipart<R: tokio::io::AsyncRead + Unpin>(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+    mut source: R,
+    content_type: &str,
+    total_bytes: u64,
+    part_size: u64,
+    upload_id: &str,
+    app: &AppHandle,
+    cancel_flag: &AtomicBool,
+    checksum: Option<UploadChecksum>,
+) -> Result<Option<String>, AppError> {
+    // Initiate multipart upload
+    let create_response = client
+        .create_multipart_upload()
+        .bucket(bucket)
+        .key(key)
+        .content_type(content_type)
+        .set_checksum_algorithm(checksum.map(UploadChecksum::algorithm))
+        .send()
+        .await?;
 
-                        // Copy the object
-                        let copy_source = format!(
-                            "{}/{}",
-                            bucket,
-                            urlencoding::encode(obj_key)
-                        );
-
-                        match client
-                            .copy_object()
-                            .bucket(&bucket)
-                            .key(&dest_key)
-                            .copy_source(&copy_source)
-                            .send()
-                            .await
-                        {
-                            Ok(_) => {
-                                objects_copied += 1;
-
-                                // Delete if moving
-                                if delete_source {
-                                    match client
-                                        .delete_object()
-                                        .bucket(&bucket)
-                                        .key(obj_key)
-                                        .send()
-                                        .await
-                                    {
-                                        Ok(_) => objects_deleted += 1,
-                                        Err(e) => errors.push(CopyMoveError {
-                                            source_key: obj_key.to_string(),
-                                            error: format!("Failed to delete: {:?}", e),
-                                        }),
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                errors.push(CopyMoveError {
-                                    source_key: obj_key.to_string(),
-                                    error: format!("Failed to copy: {:?}", e),
-                                });
-                            }
-                        }
-                    }
-                }
+    let s3_upload_id = create_response
+        .upload_id()
+        .ok_or_else(|| AppError::S3("No upload ID returned".into()))?
+        .to_string();
 
-                if response.is_truncated() == Some(true) {
-                    continuation_token = response.next_continuation_token().map(|s| s.to_string());
-                } else {
-                    break;
-                }
-            }
-        } else {
-            // For single files
-            let file_name = source_key.split('/').last().unwrap_or(source_key);
-            let dest_key = format!("{}{}", destination_prefix, file_name);
+    let mut part_number = 1;
+    let mut completed_parts = Vec::new();
+    let mut bytes_uploaded: u64 = 0;
+    // Raw part checksums, concatenated to verify the composite checksum at the end
+    let mut part_checksums: Vec<u8> = Vec::new();
+    let mut rate = TransferRate::new();
 
-            let copy_source = format!(
-                "{}/{}",
-                bucket,
-                urlencoding::encode(source_key)
-            );
+    // Clone values needed for abort
+    let client = Arc::new(client.clone());
+    let bucket_clone = bucket.to_string();
+    let key_clone = key.to_string();
+    let s3_upload_id_clone = s3_upload_id.clone();
 
-            match client
-                .copy_object()
-                .bucket(&bucket)
-                .key(&dest_key)
-                .copy_source(&copy_source)
+    loop {
+        if cancel_flag.load(Ordering::Relaxed) {
+            let _ = client
+                .abort_multipart_upload()
+                .bucket(&bucket_clone)
+                .key(&key_clone)
+                .upload_id(&s3_upload_id_clone)
                 .send()
-                .await
-            {
-                Ok(_) => {
-                    objects_copied += 1;
-
-                    // Delete if moving
-                    if delete_source {
-                        match client
-                            .delete_object()
-                            .bucket(&bucket)
-                            .key(source_key)
-                            .send()
-                            .await
-                        {
-                            Ok(_) => objects_deleted += 1,
-                            Err(e) => errors.push(CopyMoveError {
-                                source_key: source_key.clone(),
-                                error: format!("Failed to delete: {:?}", e),
-                            }),
-                        }
-                    }
-                }
-                Err(e) => {
-                    errors.push(CopyMoveError {
-                        source_key: source_key.clone(),
-                        error: format!("Failed to copy: {:?}", e),
-                    });
-                }
-            }
+                .await;
+            return Err(AppError::Cancelled);
         }
-    }
 
-    let duration_ms = start_time.elapsed().as_millis() as i64;
+        // Fill the whole part; a single read may return less, and S3 rejects
+        // undersized parts other than the last
+        let mut buffer = Vec::with_capacity(part_size as usize);
+        let bytes_read = (&mut source)
+            .take(part_size)
+            .read_to_end(&mut buffer)
+            .await
+            .map_err(|e| AppError::InvalidInput(format!("Read error: {}", e)))?;
 
-    // Log copy/move operations to history
-    let operation_type = if delete_source {
-        OperationType::Move
-    } else {
-        OperationType::Copy
-    };
+        if bytes_read == 0 {
+            break;
+        }
 
-    for source_key in &source_keys {
-        let has_error = errors.iter().any(|e| &e.source_key == source_key);
-        let _ = db.log_completed_operation(
-            &account_id,
-            &bucket,
-            operation_type.clone(),
-            Some(source_key),
-            Some(&destination_prefix),
-            None,
-            duration_ms / source_keys.len().max(1) as i64,
-            if has_error { Some("Copy/move failed") } else { None },
+        let part_checksum = checksum.map(|c| {
+            let raw = c.compute(&buffer);
+            part_checksums.extend_from_slice(&raw);
+            (c, BASE64.encode(raw))
+        });
+
+        let mut part_request = client
+            .upload_part()
+            .bucket(bucket)
+            .key(key)
+            .upload_id(&s3_upload_id)
+            .part_number(part_number)
+            .body(aws_sdk_s3::primitives::ByteStream::from(buffer));
+        let mut completed_part = aws_sdk_s3::types::CompletedPart::builder().part_number(part_number);
+        if let Some((c, value)) = &part_checksum {
+            part_request = part_request.checksum_algorithm(c.algorithm());
+            (part_request, completed_part) = match c {
+                UploadChecksum::Crc32c => (
+                    part_request.checksum_crc32_c(value),
+                    completed_part.checksum_crc32_c(value),
+                ),
+                UploadChecksum::Sha256 => (
+                    part_request.checksum_sha256(value),
+                    completed_part.checksum_sha256(value),
+                ),
+                UploadChecksum::Sha1 => (
+                    part_request.checksum_sha1(value),
+                    completed_part.checksum_sha1(value),
+                ),
+            };
+        }
+
+        let upload_part_response = match part_request.send().await {
+            Ok(resp) => resp,
+            Err(e) => {
+                // Attempt to abort on failure
+                let _ = client
+                    .abort_multipart_upload()
+                    .bucket(&bucket_clone)
+                    .key(&key_clone)
+                    .upload_id(&s3_upload_id_clone)
+                    .send()
+                    .await;
+                return Err(e.into());
+            }
+        };
+
+        bytes_uploaded += bytes_read as u64;
+
+        // Emit progress
+        if let Some(sample) = rate.sample(bytes_uploaded, total_bytes) {
+            let _ = app.emit(
+                "upload-progress",
+                UploadProgress {
+                    upload_id: upload_id.to_string(),
+                    bytes_uploaded,
+                    total_bytes,
+                    bytes_per_sec: sample.bytes_per_sec,
+                    eta_secs: sample.eta_secs,
+                },
+            );
+        }
+
+        completed_parts.push(
+            completed_part
+                .e_tag(upload_part_response.e_tag().unwrap_or_default())
+                .build(),
         );
+
+        part_number += 1;
     }
 
-    Ok(CopyMoveResult {
-        objects_copied,
-        objects_deleted,
-        errors,
-    })
+    // Complete multipart upload
+    let completed_upload = aws_sdk_s3::types::CompletedMultipartUpload::builder()
+        .set_parts(Some(completed_parts))
+        .build();
+
+    let complete_response = client
+        .complete_multipart_upload()
+        .bucket(bucket)
+        .key(key)
+        .upload_id(&s3_upload_id)
+        .multipart_upload(completed_upload)
+        .send()
+        .await?;
+
+    // S3 reports a composite checksum: the checksum of the concatenated part
+    // checksums, suffixed with "-<part count>". Providers that don't support
+    // additional checksums simply omit it.
+    if let Some(c) = checksum {
+        if let Some(reported) = c.reported_checksum(&complete_response) {
+            let expected = BASE64.encode(c.compute(&part_checksums));
+            let reported_value = reported.split('-').next().unwrap_or_default();
+            if reported_value != expected {
+                return Err(AppError::S3(format!(
+                    "Checksum mismatch after upload of {}: expected {}, got {}",
+                    key, expected, reported
+                )));
+            }
+        }
+    }
+
+    Ok(complete_response
+        .e_tag()
+        .map(|s| s.trim_matches('"').to_string()))
 }
 
-/// Copy or move objects across buckets (same or different accounts)
+/// Create a folder in S3 by creating a zero-byte object with a trailing slash.
+/// Idempotent: if the folder already exists, as a marker or through objects
+/// under it, its key is returned without writing.
 #[tauri::command(rename_all = "camelCase")]
-pub async fn copy_objects_across_buckets(
+pub async fn create_folder(
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
     db: State<'_, DbManager>,
-    source_account_id: String,
-    source_bucket: String,
-    dest_account_id: String,
-    dest_bucket: String,
-    source_keys: Vec<String>,
-    destination_prefix: String,
-    delete_source: bool,
-) -> Result<CopyMoveResult, AppError> {
+    listing_cache: State<'_, ListingCache>,
+    account_id: String,
+    bucket: String,
+    prefix: String,
+    folder_name: String,
+) -> Result<String, AppError> {
     let start_time = Instant::now();
-    let source_account = credentials.get_account(&source_account_id)?;
-    let source_secret = credentials.get_secret_key(&source_account_id)?;
-    let source_client = s3_clients
+
+    // Validate folder name
+    if folder_name.is_empty() {
+        return Err(AppError::InvalidInput("Folder name cannot be empty".into()));
+    }
+    if folder_name.contains('/') || folder_name.contains('\\') {
+        return Err(AppError::InvalidInput(
+            "Folder name cannot contain slashes".into(),
+        ));
+    }
+
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
         .get_or_create_client(
-            &source_account_id,
-            &source_account.endpoint,
-            &source_account.access_key_id,
-            &source_secret,
-            source_account.provider_type,
-            source_account.region.as_deref(),
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    let dest_account = credentials.get_account(&dest_account_id)?;
-    let dest_secret = credentials.get_secret_key(&dest_account_id)?;
-    let dest_client = s3_clients
-        .get_or_create_client(
-            &dest_account_id,
-            &dest_account.endpoint,
-            &dest_account.access_key_id,
-            &dest_secret,
-            dest_account.provider_type,
-            dest_account.region.as_deref(),
-        )
+    // Construct the full key with trailing slash
+    let key = format!("{}{}/", prefix, folder_name);
+
+    // An existing zero-byte marker means the folder is already there; an object
+    // with content at the marker key would be overwritten by a new marker
+    match client.head_object().bucket(&bucket).key(&key).send().await {
+        Ok(head) if head.content_length().unwrap_or(0) > 0 => {
+            return Err(AppError::Conflict(format!(
+                "{} is an object with content, not a folder",
+                key
+            )));
+        }
+        Ok(_) => return Ok(key),
+        Err(e) if e.as_service_error().map(|se| se.is_not_found()) == Some(true) => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    // Without a marker the folder can still exist through the objects under it
+    let listing = client
+        .list_objects_v2()
+        .bucket(&bucket)
+        .prefix(&key)
+        .max_keys(1)
+        .send()
         .await?;
+    if !listing.contents().is_empty() {
+        return Ok(key);
+    }
 
-    let mut objects_copied = 0;
-    let mut objects_deleted = 0;
-    let mut errors: Vec<CopyMoveError> = Vec::new();
+    // Create a zero-byte object to represent the folder
+    let result = client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .body(aws_sdk_s3::primitives::ByteStream::from(Vec::new()))
+        .send()
+        .await;
 
-    // Check if same account and bucket - can use S3 copy
-    let same_account = source_account_id == dest_account_id;
-    let same_bucket = source_bucket == dest_bucket;
+    let duration_ms = start_time.elapsed().as_millis() as i64;
 
-    for source_key in &source_keys {
-        let is_folder = source_key.ends_with('/');
+    match result {
+        Ok(_) => {
+            cache::invalidate_for(
+                &listing_cache,
+                &db,
+                &account_id,
+                &bucket,
+                &[],
+                std::slice::from_ref(&key),
+            );
+            let _ = db.log_completed_operation(
+                &account_id,
+                &bucket,
+                OperationType::CreateFolder,
+                Some(&key),
+                None,
+                Some(0),
+                duration_ms,
+                None,
+            );
+            Ok(key)
+        }
+        Err(e) => {
+            let _ = db.log_completed_operation(
+                &account_id,
+                &bucket,
+                OperationType::CreateFolder,
+                Some(&key),
+                None,
+                Some(0),
+                duration_ms,
+                Some(&e.to_string()),
+            );
+            Err(e.into())
+        }
+    }
+}
 
-        if is_folder {
-            // For folders, copy all objects recursively
-            let mut continuation_token: Option<String> = None;
+/// Largest content accepted by `create_object`; use `upload_bytes` beyond this
+const CREATE_OBJECT_MAX_SIZE: usize = 5 * 1024 * 1024; // 5MB
 
-            loop {
-                let mut request = source_client
-                    .list_objects_v2()
-                    .bucket(&source_bucket)
-                    .prefix(source_key);
+/// Create a new file, empty or with small initial content (e.g. a `.gitkeep`
+/// or a new text document). Fails if the key already exists unless `overwrite` is set.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn create_object(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    listing_cache: State<'_, ListingCache>,
+    account_id: String,
+    bucket: String,
+    key: String,
+    content_type: Option<String>,
+    content: Option<Vec<u8>>,
+    overwrite: Option<bool>,
+) -> Result<String, AppError> {
+    let start_time = Instant::now();
 
-                if let Some(token) = &continuation_token {
-                    request = request.continuation_token(token);
-                }
+    if key.is_empty() || key.ends_with('/') {
+        return Err(AppError::InvalidInput(
+            "Key must name a file, not a folder".into(),
+        ));
+    }
+    let content = content.unwrap_or_default();
+    if content.len() > CREATE_OBJECT_MAX_SIZE {
+        return Err(AppError::InvalidInput(format!(
+            "Content is too large to create directly (max {} bytes)",
+            CREATE_OBJECT_MAX_SIZE
+        )));
+    }
 
-                let response = match request.send().await {
-                    Ok(r) => r,
-                    Err(e) => {
-                        errors.push(CopyMoveError {
-                            source_key: source_key.clone(),
-                            error: format!("Failed to list folder: {:?}", e),
-                        });
-                        break;
-                    }
-                };
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
 
-                for obj in response.contents() {
-                    if let Some(obj_key) = obj.key() {
-                        // Get the relative path within the folder
-                        let folder_name = source_key
-                            .trim_end_matches('/')
-                            .split('/')
-                            .last()
-                            .unwrap_or("");
-                        let relative_path = obj_key.strip_prefix(source_key).unwrap_or(obj_key);
-                        let dest_key =
-                            format!("{}{}/{}", destination_prefix, folder_name, relative_path);
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
 
-                        let result = if same_account {
-                            // Same account: use S3 copy
-                            let copy_source = format!(
-                                "{}/{}",
-                                source_bucket,
-                                urlencoding::encode(obj_key)
-                            );
-                            dest_client
-                                .copy_object()
-                                .bucket(&dest_bucket)
-                                .key(&dest_key)
-                                .copy_source(&copy_source)
-                                .send()
-                                .await
-                                .map(|_| ())
-                                .map_err(|e| format!("{:?}", e))
-                        } else {
-                            // Different accounts: download and upload
-                            copy_via_download_upload(
-                                &source_client,
-                                &dest_client,
-                                &source_bucket,
-                                &dest_bucket,
-                                obj_key,
-                                &dest_key,
-                            )
-                            .await
-                        };
+    if !overwrite.unwrap_or(false) && destination_exists(&client, &bucket, &key).await? {
+        return Err(AppError::Conflict(key));
+    }
 
-                        match result {
-                            Ok(_) => {
-                                objects_copied += 1;
-
-                                // Delete source if moving
-                                if delete_source {
-                                    match source_client
-                                        .delete_object()
-                                        .bucket(&source_bucket)
-                                        .key(obj_key)
-                                        .send()
-                                        .await
-                                    {
-                                        Ok(_) => objects_deleted += 1,
-                                        Err(e) => errors.push(CopyMoveError {
-                                            source_key: obj_key.to_string(),
-                                            error: format!("Failed to delete: {:?}", e),
-                                        }),
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                errors.push(CopyMoveError {
-                                    source_key: obj_key.to_string(),
-                                    error: format!("Failed to copy: {}", e),
-                                });
-                            }
-                        }
-                    }
-                }
+    let mime = content_type.unwrap_or_else(|| {
+        mime_guess::from_path(&key)
+            .first_or_octet_stream()
+            .to_string()
+    });
+    let size = content.len() as i64;
 
-                if response.is_truncated() == Some(true) {
-                    continuation_token = response.next_continuation_token().map(|s| s.to_string());
-                } else {
-                    break;
+    let result = client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .content_type(mime)
+        .body(aws_sdk_s3::primitives::ByteStream::from(content))
+        .send()
+        .await;
+
+    let duration_ms = start_time.elapsed().as_millis() as i64;
+
+    match result {
+        Ok(_) => {
+            cache::invalidate_for(
+                &listing_cache,
+                &db,
+                &account_id,
+                &bucket,
+                &[],
+                std::slice::from_ref(&key),
+            );
+            let _ = db.log_completed_operation(
+                &account_id,
+                &bucket,
+                OperationType::CreateObject,
+                Some(&key),
+                None,
+                Some(size),
+                duration_ms,
+                None,
+            );
+            Ok(key)
+        }
+        Err(e) => {
+            let _ = db.log_completed_operation(
+                &account_id,
+                &bucket,
+                OperationType::CreateObject,
+                Some(&key),
+                None,
+                Some(size),
+                duration_ms,
+                Some(&e.to_string()),
+            );
+            Err(e.into())
+        }
+    }
+}
+
+/// Fail with a clear message unless the bucket was created with object lock enabled
+async fn ensure_object_lock_enabled(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+) -> Result<(), AppError> {
+    use aws_sdk_s3::error::ProvideErrorMetadata;
+    use aws_sdk_s3::types::ObjectLockEnabled;
+
+    let not_enabled = || {
+        AppError::InvalidInput(format!(
+            "Object lock is not enabled on bucket {}; it can only be enabled when the bucket is created",
+            bucket
+        ))
+    };
+
+    match client
+        .get_object_lock_configuration()
+        .bucket(bucket)
+        .send()
+        .await
+    {
+        Ok(response) => {
+            let enabled = response
+                .object_lock_configuration()
+                .and_then(|c| c.object_lock_enabled())
+                == Some(&ObjectLockEnabled::Enabled);
+            if enabled {
+                Ok(())
+            } else {
+                Err(not_enabled())
+            }
+        }
+        Err(e) if e.code() == Some("ObjectLockConfigurationNotFoundError") => Err(not_enabled()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Set a retention period on an object in an object-lock bucket. `mode` is
+/// GOVERNANCE or COMPLIANCE and `retain_until` an RFC 3339 timestamp in the future.
+/// Shortening or removing governance retention is only attempted with
+/// `bypass_governance` set (and requires s3:BypassGovernanceRetention).
+#[tauri::command(rename_all = "camelCase")]
+pub async fn put_object_retention(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    account_id: String,
+    bucket: String,
+    key: String,
+    version_id: Option<String>,
+    mode: String,
+    retain_until: String,
+    bypass_governance: Option<bool>,
+) -> Result<(), AppError> {
+    use aws_sdk_s3::types::{ObjectLockRetention, ObjectLockRetentionMode};
+
+    let mode = match mode.to_ascii_uppercase().as_str() {
+        "GOVERNANCE" => ObjectLockRetentionMode::Governance,
+        "COMPLIANCE" => ObjectLockRetentionMode::Compliance,
+        other => {
+            return Err(AppError::InvalidInput(format!(
+                "Unknown retention mode: {} (expected GOVERNANCE or COMPLIANCE)",
+                other
+            )))
+        }
+    };
+
+    let retain_until = chrono::DateTime::parse_from_rfc3339(&retain_until)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid date '{}': {}", retain_until, e)))?
+        .timestamp();
+    if retain_until <= chrono::Utc::now().timestamp() {
+        return Err(AppError::InvalidInput(
+            "Retain-until date must be in the future".into(),
+        ));
+    }
+
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    ensure_object_lock_enabled(&client, &bucket).await?;
+
+    let retention = ObjectLockRetention::builder()
+        .mode(mode)
+        .retain_until_date(aws_sdk_s3::primitives::DateTime::from_secs(retain_until))
+        .build();
+
+    let mut request = client
+        .put_object_retention()
+        .bucket(&bucket)
+        .key(&key)
+        .set_version_id(version_id)
+        .retention(retention);
+    if bypass_governance.unwrap_or(false) {
+        request = request.bypass_governance_retention(true);
+    }
+
+    request.send().await?;
+
+    Ok(())
+}
+
+/// Turn the legal hold on an object in an object-lock bucket on or off
+#[tauri::command(rename_all = "camelCase")]
+pub async fn put_object_legal_hold(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    account_id: String,
+    bucket: String,
+    key: String,
+    version_id: Option<String>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    use aws_sdk_s3::types::{ObjectLockLegalHold, ObjectLockLegalHoldStatus};
+
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    ensure_object_lock_enabled(&client, &bucket).await?;
+
+    let status = if enabled {
+        ObjectLockLegalHoldStatus::On
+    } else {
+        ObjectLockLegalHoldStatus::Off
+    };
+
+    client
+        .put_object_legal_hold()
+        .bucket(&bucket)
+        .key(&key)
+        .set_version_id(version_id)
+        .legal_hold(ObjectLockLegalHold::builder().status(status).build())
+        .send()
+        .await?;
+
+    Ok(())
+}
+
+// Download event types for progress tracking
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadStarted {
+    pub download_id: String,
+    pub file_name: String,
+    pub total_bytes: u64,
+    /// History entry tracking this download, if it could be recorded
+    pub history_id: Option<i64>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadProgress {
+    pub download_id: String,
+    pub bytes_downloaded: u64,
+    pub total_bytes: u64,
+    pub bytes_per_sec: u64,
+    pub eta_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadCompleted {
+    pub download_id: String,
+    pub key: String,
+    pub path: String,
+    /// Set for folder downloads where some files failed; pass to `retry_failed_items`
+    pub operation_batch_id: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadFailed {
+    pub download_id: String,
+    pub error: String,
+}
+
+const DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024; // 64KB chunks
+
+/// Download an object from S3 to local filesystem
+#[tauri::command(rename_all = "camelCase")]
+pub async fn download_object(
+    app: AppHandle,
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    transfer_state: State<'_, TransferState>,
+    account_id: String,
+    bucket: String,
+    key: String,
+    destination: String,
+    download_id: String,
+) -> Result<String, AppError> {
+    let start_time = Instant::now();
+    let file_name = key.rsplit('/').next().unwrap_or(&key).to_string();
+
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    let history_id = start_operation(
+        &db,
+        &account_id,
+        &bucket,
+        OperationType::Download,
+        Some(&key),
+        None,
+        None,
+        None,
+    );
+
+    let result = async {
+        // Get the object
+        let response = match client.get_object().bucket(&bucket).key(&key).send().await {
+            Ok(resp) => resp,
+            Err(e) => {
+                let error = AppError::from(e);
+                let _ = app.emit(
+                    "download-failed",
+                    DownloadFailed {
+                        download_id: download_id.clone(),
+                        error: error.to_string(),
+                    },
+                );
+                return Err(error);
+            }
+        };
+
+        let total_bytes = response.content_length().unwrap_or(0) as u64;
+
+        // Emit started event
+        let _ = app.emit(
+            "download-started",
+            DownloadStarted {
+                download_id: download_id.clone(),
+                file_name: file_name.clone(),
+                total_bytes,
+                history_id,
+            },
+        );
+
+        // Create destination path
+        let dest_path = PathBuf::from(&destination).join(&file_name);
+
+        // Create parent directories if needed
+        if let Some(parent) = dest_path.parent() {
+            if let Err(e) = tokio::fs::create_dir_all(parent).await {
+                let _ = app.emit(
+                    "download-failed",
+                    DownloadFailed {
+                        download_id: download_id.clone(),
+                        error: format!("Failed to create directory: {}", e),
+                    },
+                );
+                return Err(AppError::InvalidInput(format!(
+                    "Failed to create directory: {}",
+                    e
+                )));
+            }
+        }
+
+        // Create the file
+        let mut file = match tokio::fs::File::create(&dest_path).await {
+            Ok(f) => f,
+            Err(e) => {
+                let _ = app.emit(
+                    "download-failed",
+                    DownloadFailed {
+                        download_id: download_id.clone(),
+                        error: format!("Failed to create file: {}", e),
+                    },
+                );
+                return Err(AppError::InvalidInput(format!(
+                    "Failed to create file: {}",
+                    e
+                )));
+            }
+        };
+
+        // Stream the body to file
+        let mut body = response.body.into_async_read();
+        let mut bytes_downloaded: u64 = 0;
+        let mut buffer = vec![0u8; DOWNLOAD_CHUNK_SIZE];
+        let mut rate = TransferRate::new();
+        let cancel_flag = transfer_state.register(&download_id).await;
+
+        use tokio::io::AsyncWriteExt;
+
+        loop {
+            if cancel_flag.load(Ordering::Relaxed) {
+                // Remove the incomplete download
+                drop(file);
+                let _ = tokio::fs::remove_file(&dest_path).await;
+                transfer_state.finish(&download_id).await;
+                let _ = app.emit(
+                    "transfer-cancelled",
+                    TransferCancelled {
+                        transfer_id: download_id.clone(),
+                    },
+                );
+                return Err(AppError::Cancelled);
+            }
+
+            let bytes_read = match body.read(&mut buffer).await {
+                Ok(0) => break, // EOF
+                Ok(n) => n,
+                Err(e) => {
+                    transfer_state.finish(&download_id).await;
+                    let _ = app.emit(
+                        "download-failed",
+                        DownloadFailed {
+                            download_id: download_id.clone(),
+                            error: format!("Read error: {}", e),
+                        },
+                    );
+                    return Err(AppError::InvalidInput(format!("Read error: {}", e)));
+                }
+            };
+
+            if let Err(e) = file.write_all(&buffer[..bytes_read]).await {
+                transfer_state.finish(&download_id).await;
+                let _ = app.emit(
+                    "download-failed",
+                    DownloadFailed {
+                        download_id: download_id.clone(),
+                        error: format!("Write error: {}", e),
+                    },
+                );
+                return Err(AppError::InvalidInput(format!("Write error: {}", e)));
+            }
+
+            bytes_downloaded += bytes_read as u64;
+
+            // Emit progress
+            if let Some(sample) = rate.sample(bytes_downloaded, total_bytes) {
+                let _ = app.emit(
+                    "download-progress",
+                    DownloadProgress {
+                        download_id: download_id.clone(),
+                        bytes_downloaded,
+                        total_bytes,
+                        bytes_per_sec: sample.bytes_per_sec,
+                        eta_secs: sample.eta_secs,
+                    },
+                );
+            }
+        }
+
+        transfer_state.finish(&download_id).await;
+
+        // Flush and sync
+        if let Err(e) = file.sync_all().await {
+            let _ = app.emit(
+                "download-failed",
+                DownloadFailed {
+                    download_id: download_id.clone(),
+                    error: format!("Sync error: {}", e),
+                },
+            );
+            return Err(AppError::InvalidInput(format!("Sync error: {}", e)));
+        }
+
+        let final_path = dest_path.to_string_lossy().to_string();
+
+        Ok((final_path, total_bytes))
+    }
+    .await;
+
+    let size = result.as_ref().ok().map(|(_, total_bytes)| *total_bytes as i64);
+    finish_operation(&db, history_id, size, start_time, result.as_ref().err());
+    let (final_path, _) = result?;
+
+    // Emit completed event
+    let _ = app.emit(
+        "download-completed",
+        DownloadCompleted {
+            download_id,
+            key,
+            path: final_path.clone(),
+            operation_batch_id: None,
+        },
+    );
+
+    Ok(final_path)
+}
+
+/// Objects checked at once against a search's attribute filter
+const SEARCH_ATTRIBUTE_CONCURRENCY: usize = 8;
+
+/// Metadata and tag criteria for `search_objects`. Checking them costs a
+/// HeadObject and/or GetObjectTagging per candidate, so they are opt-in.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SearchAttributeFilter {
+    /// Content type to match, ignoring parameters; `image/*` matches any image type
+    pub content_type: Option<String>,
+    /// User metadata that must be present with these exact values
+    pub metadata: HashMap<String, String>,
+    /// Tags that must be present with these exact values
+    pub tags: Vec<ObjectTag>,
+}
+
+impl SearchAttributeFilter {
+    fn is_empty(&self) -> bool {
+        self.content_type.is_none() && self.metadata.is_empty() && self.tags.is_empty()
+    }
+
+    /// Whether an object's metadata and tags satisfy the filter
+    async fn matches(
+        &self,
+        client: &aws_sdk_s3::Client,
+        bucket: &str,
+        key: &str,
+    ) -> Result<bool, AppError> {
+        if self.content_type.is_some() || !self.metadata.is_empty() {
+            let head = client.head_object().bucket(bucket).key(key).send().await?;
+
+            if let Some(expected) = &self.content_type {
+                let actual = head.content_type().unwrap_or_default();
+                let matched = match expected.strip_suffix("/*") {
+                    Some(top_level) => actual
+                        .split('/')
+                        .next()
+                        .map_or(false, |t| t.trim().eq_ignore_ascii_case(top_level)),
+                    None => same_content_type(actual, expected.trim()),
+                };
+                if !matched {
+                    return Ok(false);
+                }
+            }
+
+            // S3 returns user metadata keys lowercased
+            let metadata = head.metadata();
+            let metadata_matches = self.metadata.iter().all(|(name, value)| {
+                metadata
+                    .and_then(|m| m.get(&name.to_lowercase()))
+                    .map_or(false, |actual| actual == value)
+            });
+            if !metadata_matches {
+                return Ok(false);
+            }
+        }
+
+        if !self.tags.is_empty() {
+            let tagging = client
+                .get_object_tagging()
+                .bucket(bucket)
+                .key(key)
+                .send()
+                .await?;
+            let tags_match = self.tags.iter().all(|wanted| {
+                tagging
+                    .tag_set()
+                    .iter()
+                    .any(|t| t.key() == wanted.key && t.value() == wanted.value)
+            });
+            if !tags_match {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+/// Matches found by an attribute search so far, sent as each listing page is checked
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResultsEvent {
+    pub search_id: String,
+    pub objects: Vec<S3Object>,
+    pub objects_checked: usize,
+}
+
+/// Search for objects recursively within a prefix.
+/// With `attributes`, name matches are further filtered by content type,
+/// metadata and tags; folders are then never returned, the query may be
+/// empty, and with a `search_id` matches are also streamed as
+/// `search-results` events while the search runs.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn search_objects(
+    app: AppHandle,
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    account_id: String,
+    bucket: String,
+    prefix: String,
+    query: String,
+    max_results: Option<u32>,
+    attributes: Option<SearchAttributeFilter>,
+    search_id: Option<String>,
+) -> Result<Vec<S3Object>, AppError> {
+    use std::collections::HashSet;
+
+    let attributes = attributes.filter(|a| !a.is_empty());
+    if query.is_empty() && attributes.is_none() {
+        return Ok(Vec::new());
+    }
+
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    let max = max_results.unwrap_or(100) as usize;
+    let query_lower = query.to_lowercase();
+    let mut file_results: Vec<S3Object> = Vec::new();
+    let mut folder_results: Vec<S3Object> = Vec::new();
+    let mut seen_folders: HashSet<String> = HashSet::new();
+    let mut continuation_token: Option<String> = None;
+    let mut objects_checked = 0usize;
+
+    // List all objects recursively (no delimiter) and filter by query
+    let listing = flat_listing(&client, &bucket, Some(&prefix));
+    loop {
+        let (response, next_token) = list_page(&listing, continuation_token.as_deref()).await?;
+        // Name matches awaiting the attribute check
+        let mut candidates: Vec<S3Object> = Vec::new();
+
+        for obj in response.contents() {
+            if let Some(key) = obj.key() {
+                // Folders carry no metadata, so attribute searches only return files
+                if attributes.is_some() {
+                    let name = key.rsplit('/').next().unwrap_or(key);
+                    if !key.ends_with('/') && name.to_lowercase().contains(&query_lower) {
+                        candidates.push(S3Object {
+                            key: key.to_string(),
+                            size: obj.size().unwrap_or(0),
+                            last_modified: obj.last_modified().map(|d| d.to_string()),
+                            etag: obj.e_tag().map(|e| e.trim_matches('"').to_string()),
+                            is_folder: false,
+                            owner: None,
+                        });
+                    }
+                    continue;
+                }
+
+                // Extract all parent folder paths from this key and check for matches
+                // e.g., "a/b/c/file.txt" -> check folders "a/", "a/b/", "a/b/c/"
+                let key_without_prefix = if !prefix.is_empty() && key.starts_with(&prefix) {
+                    &key[prefix.len()..]
+                } else {
+                    key
+                };
+
+                let parts: Vec<&str> = key_without_prefix.split('/').collect();
+                let mut folder_path = prefix.clone();
+
+                // Check each folder segment (except the last part which is the file name)
+                for (i, part) in parts.iter().enumerate() {
+                    if i < parts.len() - 1 && !part.is_empty() {
+                        folder_path.push_str(part);
+                        folder_path.push('/');
+
+                        // Check if this folder name matches the query
+                        if part.to_lowercase().contains(&query_lower) {
+                            if !seen_folders.contains(&folder_path) {
+                                seen_folders.insert(folder_path.clone());
+                                folder_results.push(S3Object {
+                                    key: folder_path.clone(),
+                                    size: 0,
+                                    last_modified: None,
+                                    etag: None,
+                                    is_folder: true,
+                                    owner: None,
+                                });
+                            }
+                        }
+                    }
+                }
+
+                // Get the file name from the key and check for match
+                let name = key.rsplit('/').next().unwrap_or(key);
+
+                // Case-insensitive search for files
+                if name.to_lowercase().contains(&query_lower) {
+                    file_results.push(S3Object {
+                        key: key.to_string(),
+                        size: obj.size().unwrap_or(0),
+                        last_modified: obj.last_modified().map(|d| d.to_string()),
+                        etag: obj.e_tag().map(|e| e.trim_matches('"').to_string()),
+                        is_folder: key.ends_with('/'),
+                        owner: None,
+                    });
+                }
+
+                // Check if we have enough results
+                if file_results.len() + folder_results.len() >= max {
+                    // Combine folders first, then files
+                    folder_results.extend(file_results);
+                    folder_results.truncate(max);
+                    return Ok(folder_results);
+                }
+            }
+        }
+
+        if let Some(attributes) = &attributes {
+            objects_checked += candidates.len();
+            let client = &client;
+            let bucket = &bucket;
+            let checked: Vec<Option<S3Object>> = stream::iter(candidates)
+                .map(|object| async move {
+                    match attributes.matches(client, bucket, &object.key).await {
+                        Ok(true) => Some(object),
+                        Ok(false) => None,
+                        Err(e) => {
+                            log::warn!("Failed to check attributes of {}: {}", object.key, e);
+                            None
+                        }
+                    }
+                })
+                .buffered(SEARCH_ATTRIBUTE_CONCURRENCY)
+                .collect()
+                .await;
+
+            let mut found: Vec<S3Object> = checked.into_iter().flatten().collect();
+            found.truncate(max - file_results.len());
+            if let Some(search_id) = search_id.as_ref().filter(|_| !found.is_empty()) {
+                let _ = app.emit(
+                    "search-results",
+                    SearchResultsEvent {
+                        search_id: search_id.clone(),
+                        objects: found.clone(),
+                        objects_checked,
+                    },
+                );
+            }
+            file_results.extend(found);
+            if file_results.len() >= max {
+                return Ok(file_results);
+            }
+        }
+
+        match next_token {
+            Some(token) => continuation_token = Some(token),
+            None => break,
+        }
+    }
+
+    // Combine results: folders first, then files
+    folder_results.extend(file_results);
+    Ok(folder_results)
+}
+
+// Presigned URL types
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresignedUrlResult {
+    pub url: String,
+    pub expires_at: String,
+    /// Id of the shared link record, when `record` was set
+    pub shared_link_id: Option<i64>,
+}
+
+/// Generate a presigned URL for downloading an object.
+/// With `record` set, the URL is saved as a shared link (see `list_shared_links`).
+#[tauri::command(rename_all = "camelCase")]
+pub async fn generate_presigned_url(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    account_id: String,
+    bucket: String,
+    key: String,
+    expires_in_seconds: u64,
+    record: Option<bool>,
+    label: Option<String>,
+) -> Result<PresignedUrlResult, AppError> {
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    let url = presign_get_url(&client, &bucket, &key, expires_in_seconds).await?;
+    let created_at = chrono::Utc::now();
+    let expires_at = created_at + chrono::Duration::seconds(expires_in_seconds as i64);
+
+    let shared_link_id = if record.unwrap_or(false) {
+        Some(db.save_shared_link(&NewSharedLink {
+            account_id: &account_id,
+            bucket: &bucket,
+            key: &key,
+            url: &url,
+            label: label.as_deref().map(str::trim).filter(|l| !l.is_empty()),
+            created_at: created_at.timestamp(),
+            expires_at: expires_at.timestamp(),
+        })?)
+    } else {
+        None
+    };
+
+    Ok(PresignedUrlResult {
+        url,
+        expires_at: expires_at.to_rfc3339(),
+        shared_link_id,
+    })
+}
+
+/// List recorded shared links, newest first. Expired links are included unless
+/// `include_expired` is false.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_shared_links(
+    db: State<'_, DbManager>,
+    account_id: Option<String>,
+    bucket: Option<String>,
+    include_expired: Option<bool>,
+) -> Result<Vec<SharedLink>, AppError> {
+    db.list_shared_links(
+        account_id.as_deref(),
+        bucket.as_deref(),
+        include_expired.unwrap_or(true),
+    )
+}
+
+/// Forget a shared link. Presigned URLs can't be revoked, so the URL keeps
+/// working until it expires; only the local record is removed.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn delete_shared_link(db: State<'_, DbManager>, id: i64) -> Result<(), AppError> {
+    db.delete_shared_link(id)
+}
+
+/// Presign a GET request for a single object
+pub(crate) async fn presign_get_url(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+    expires_in_seconds: u64,
+) -> Result<String, AppError> {
+    let expires_in = Duration::from_secs(expires_in_seconds);
+    let presigning_config = PresigningConfig::expires_in(expires_in)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid expiry duration: {}", e)))?;
+
+    let presigned_request = client
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .presigned(presigning_config)
+        .await?;
+
+    Ok(presigned_request.uri().to_string())
+}
+
+// Share manifest types
+const SHARE_MANIFEST_CONCURRENCY: usize = 8;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManifestFormat {
+    Json,
+    Csv,
+    Markdown,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareManifestEntry {
+    pub key: String,
+    pub size: Option<i64>,
+    pub url: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareManifest {
+    pub entries: Vec<ShareManifestEntry>,
+    pub expires_at: String,
+    pub content: String,
+    pub output_path: Option<String>,
+}
+
+fn render_share_manifest(
+    format: ManifestFormat,
+    bucket: &str,
+    expires_at: &str,
+    entries: &[ShareManifestEntry],
+) -> Result<String, AppError> {
+    match format {
+        ManifestFormat::Json => serde_json::to_string_pretty(&serde_json::json!({
+            "bucket": bucket,
+            "expiresAt": expires_at,
+            "files": entries,
+        }))
+        .map_err(|e| AppError::Storage(format!("Failed to serialize manifest: {}", e))),
+        ManifestFormat::Csv => {
+            let mut csv = String::from("key,size,url,error\n");
+            for entry in entries {
+                csv.push_str(&format!(
+                    "{},{},{},{}\n",
+                    escape_csv(&entry.key),
+                    entry.size.map(|s| s.to_string()).unwrap_or_default(),
+                    escape_csv(entry.url.as_deref().unwrap_or_default()),
+                    escape_csv(entry.error.as_deref().unwrap_or_default()),
+                ));
+            }
+            Ok(csv)
+        }
+        ManifestFormat::Markdown => {
+            let mut md = format!(
+                "# Shared files from `{}`\n\nLinks expire at {}.\n\n| File | Size | Link |\n| --- | ---: | --- |\n",
+                bucket, expires_at
+            );
+            for entry in entries {
+                let name = entry.key.replace('|', "\\|");
+                let size = entry.size.map(|s| s.to_string()).unwrap_or_default();
+                let link = match (&entry.url, &entry.error) {
+                    (Some(url), _) => format!("[Download]({})", url),
+                    (None, Some(error)) => format!("Error: {}", error.replace('|', "\\|")),
+                    (None, None) => String::new(),
+                };
+                md.push_str(&format!("| `{}` | {} | {} |\n", name, size, link));
+            }
+            Ok(md)
+        }
+    }
+}
+
+/// Generate presigned download URLs for a set of objects and render them as a manifest.
+/// When `output_path` is given the manifest is also written to that file.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn generate_share_manifest(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    account_id: String,
+    bucket: String,
+    keys: Vec<String>,
+    expires_in_seconds: u64,
+    format: ManifestFormat,
+    output_path: Option<String>,
+) -> Result<ShareManifest, AppError> {
+    if keys.is_empty() {
+        return Err(AppError::InvalidInput("No keys to share".into()));
+    }
+    // Validate the expiry once up front rather than failing every entry
+    PresigningConfig::expires_in(Duration::from_secs(expires_in_seconds))
+        .map_err(|e| AppError::InvalidInput(format!("Invalid expiry duration: {}", e)))?;
+
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    let expires_at =
+        (chrono::Utc::now() + chrono::Duration::seconds(expires_in_seconds as i64)).to_rfc3339();
+
+    let entries: Vec<ShareManifestEntry> = stream::iter(keys)
+        .map(|key| {
+            let client = &client;
+            let bucket = &bucket;
+            async move {
+                let head = client.head_object().bucket(bucket).key(&key).send().await;
+                let size = match head {
+                    Ok(head) => head.content_length(),
+                    Err(e) => {
+                        return ShareManifestEntry {
+                            key,
+                            size: None,
+                            url: None,
+                            error: Some(format!("Failed to get object: {:?}", e)),
+                        };
+                    }
+                };
+                match presign_get_url(client, bucket, &key, expires_in_seconds).await {
+                    Ok(url) => ShareManifestEntry {
+                        key,
+                        size,
+                        url: Some(url),
+                        error: None,
+                    },
+                    Err(e) => ShareManifestEntry {
+                        key,
+                        size,
+                        url: None,
+                        error: Some(e.to_string()),
+                    },
+                }
+            }
+        })
+        .buffered(SHARE_MANIFEST_CONCURRENCY)
+        .collect()
+        .await;
+
+    let content = render_share_manifest(format, &bucket, &expires_at, &entries)?;
+
+    if let Some(path) = &output_path {
+        tokio::fs::write(path, &content)
+            .await
+            .map_err(|e| AppError::Storage(format!("Failed to write manifest: {}", e)))?;
+    }
+
+    Ok(ShareManifest {
+        entries,
+        expires_at,
+        content,
+        output_path,
+    })
+}
+
+// Rename types
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameResult {
+    pub old_key: String,
+    pub new_key: String,
+    pub objects_renamed: usize,
+    /// True if a folder rename was stopped via `cancel_copy`; objects already
+    /// renamed stay at the new key
+    pub cancelled: bool,
+}
+
+/// Rename an object or folder by copying to new key and deleting old key.
+/// Folder renames emit `rename-progress` events when `operation_id` is given.
+///
+/// Either `new_name` (replaces the last path component in place) or `new_key`
+/// (full destination key, allowing a move to another prefix) must be given.
+/// A `new_key` destination that already exists is rejected unless `overwrite` is set.
+/// With `verify_before_delete`, an old key is only deleted once its copy checks out.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn rename_object(
+    app: AppHandle,
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    copy_state: State<'_, CopyState>,
+    listing_cache: State<'_, ListingCache>,
+    account_id: String,
+    bucket: String,
+    old_key: String,
+    new_name: Option<String>,
+    new_key: Option<String>,
+    overwrite: Option<bool>,
+    operation_id: Option<String>,
+    verify_before_delete: Option<bool>,
+    source_version_id: Option<String>,
+) -> Result<RenameResult, AppError> {
+    let start_time = Instant::now();
+    let is_folder = old_key.ends_with('/');
+    if is_folder && source_version_id.is_some() {
+        return Err(AppError::InvalidInput(
+            "A source version can only be given for a file".into(),
+        ));
+    }
+
+    let new_key = match (new_name, new_key) {
+        (Some(new_name), None) => {
+            // Validate new name
+            if new_name.is_empty() {
+                return Err(AppError::InvalidInput("New name cannot be empty".into()));
+            }
+            if new_name.contains('/') || new_name.contains('\\') {
+                return Err(AppError::InvalidInput(
+                    "New name cannot contain slashes".into(),
+                ));
+            }
+
+            // Calculate new key by replacing the last component of the path
+            if is_folder {
+                // For folders: replace the folder name
+                let parts: Vec<&str> = old_key.trim_end_matches('/').split('/').collect();
+                if parts.len() == 1 {
+                    format!("{}/", new_name)
+                } else {
+                    let parent = parts[..parts.len() - 1].join("/");
+                    format!("{}/{}/", parent, new_name)
+                }
+            } else {
+                // For files: replace the file name
+                let parts: Vec<&str> = old_key.split('/').collect();
+                if parts.len() == 1 {
+                    new_name
+                } else {
+                    let parent = parts[..parts.len() - 1].join("/");
+                    format!("{}/{}", parent, new_name)
+                }
+            }
+        }
+        (None, Some(new_key)) => {
+            let new_key = new_key.trim_start_matches('/').to_string();
+            if new_key.trim_end_matches('/').is_empty() {
+                return Err(AppError::InvalidInput("New key cannot be empty".into()));
+            }
+            if is_folder != new_key.ends_with('/') {
+                return Err(AppError::InvalidInput(if is_folder {
+                    "New key for a folder must end with a slash".into()
+                } else {
+                    "New key for a file cannot end with a slash".into()
+                }));
+            }
+            if new_key == old_key {
+                return Err(AppError::InvalidInput(
+                    "New key is the same as the current key".into(),
+                ));
+            }
+            if is_folder && new_key.starts_with(&old_key) {
+                return Err(AppError::InvalidInput(
+                    "Cannot move a folder into itself".into(),
+                ));
+            }
+            new_key
+        }
+        _ => {
+            return Err(AppError::InvalidInput(
+                "Exactly one of new name or new key must be provided".into(),
+            ));
+        }
+    };
+
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    if !overwrite.unwrap_or(false) && destination_exists(&client, &bucket, &new_key).await? {
+        return Err(AppError::InvalidInput(format!(
+            "Destination already exists: {}",
+            new_key
+        )));
+    }
+
+    let retry = RetryPolicy::from_settings(&db.get_app_settings()?);
+    let mut objects_renamed = 0;
+    let mut cancelled = false;
+
+    if is_folder {
+        // For folders, we need to copy all objects with the old prefix to the new prefix.
+        // Collect every key first: deleting while paginating can make some providers
+        // skip or revisit entries.
+        let source_keys = list_keys_under_prefix(&client, &bucket, &old_key).await?;
+        let total_objects = source_keys.len();
+        let cancel_flag = copy_state.register(operation_id.as_ref()).await;
+
+        for obj_key in &source_keys {
+            if cancel_flag.load(Ordering::Relaxed) {
+                cancelled = true;
+                break;
+            }
+
+            // Calculate the new key by replacing the old prefix with the new one
+            let relative_path = obj_key.strip_prefix(&old_key).unwrap_or(obj_key);
+            let dest_key = format!("{}{}", new_key, relative_path);
+
+            let moved = async {
+                // Copy to new location
+                let source = head_copy_source(&client, &bucket, obj_key, None, retry).await?;
+                copy_object_with_head(
+                    &client,
+                    &source,
+                    &bucket,
+                    obj_key,
+                    None,
+                    &bucket,
+                    &dest_key,
+                    retry,
+                )
+                .await?;
+
+                if verify_before_delete.unwrap_or(false) {
+                    verify_copy(&source, &client, &bucket, &dest_key)
+                        .await
+                        .map_err(|e| AppError::S3(format!("{}: {}", obj_key, e)))?;
+                }
+
+                // Delete old object
+                client
+                    .delete_object()
+                    .bucket(&bucket)
+                    .key(obj_key)
+                    .send()
+                    .await?;
+
+                Ok::<(), AppError>(())
+            }
+            .await;
+
+            if let Err(e) = moved {
+                copy_state.finish(operation_id.as_ref()).await;
+                // Earlier objects have already moved
+                cache::invalidate_for(
+                    &listing_cache,
+                    &db,
+                    &account_id,
+                    &bucket,
+                    &[old_key.clone(), new_key.clone()],
+                    &[],
+                );
+                return Err(e);
+            }
+
+            objects_renamed += 1;
+
+            if let Some(operation_id) = &operation_id {
+                let _ = app.emit(
+                    "rename-progress",
+                    CopyProgress {
+                        operation_id: operation_id.clone(),
+                        objects_processed: objects_renamed,
+                        total_objects,
+                    },
+                );
+            }
+        }
+
+        copy_state.finish(operation_id.as_ref()).await;
+    } else {
+        // For single files, just copy and delete. Renaming a specific version
+        // moves that version, removing it from the old key's history.
+        let version_id = source_version_id.as_deref();
+        let source = head_copy_source(&client, &bucket, &old_key, version_id, retry).await?;
+        copy_object_with_head(
+            &client,
+            &source,
+            &bucket,
+            &old_key,
+            version_id,
+            &bucket,
+            &new_key,
+            retry,
+        )
+        .await?;
+
+        if verify_before_delete.unwrap_or(false) {
+            verify_copy(&source, &client, &bucket, &new_key)
+                .await
+                .map_err(AppError::S3)?;
+        }
+
+        client
+            .delete_object()
+            .bucket(&bucket)
+            .key(&old_key)
+            .set_version_id(source_version_id)
+            .send()
+            .await?;
+
+        objects_renamed = 1;
+    }
+
+    let renamed = [old_key.clone(), new_key.clone()];
+    if is_folder {
+        cache::invalidate_for(&listing_cache, &db, &account_id, &bucket, &renamed, &[]);
+    } else {
+        cache::invalidate_for(&listing_cache, &db, &account_id, &bucket, &[], &renamed);
+    }
+
+    let duration_ms = start_time.elapsed().as_millis() as i64;
+
+    // Log rename to history
+    let _ = db.log_completed_operation(
+        &account_id,
+        &bucket,
+        OperationType::Rename,
+        Some(&old_key),
+        Some(&new_key),
+        None,
+        duration_ms,
+        cancelled
+            .then(|| format!("Cancelled after {} objects", objects_renamed))
+            .as_deref(),
+    );
+
+    Ok(RenameResult {
+        old_key,
+        new_key,
+        objects_renamed,
+        cancelled,
+    })
+}
+
+/// Check whether a rename destination is already taken. Folder keys count as
+/// taken when any object exists under the prefix.
+async fn destination_exists(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+) -> Result<bool, AppError> {
+    if key.ends_with('/') {
+        let response = client
+            .list_objects_v2()
+            .bucket(bucket)
+            .prefix(key)
+            .max_keys(1)
+            .send()
+            .await?;
+        return Ok(!response.contents().is_empty());
+    }
+
+    match client.head_object().bucket(bucket).key(key).send().await {
+        Ok(_) => Ok(true),
+        Err(e) => {
+            if e.as_service_error().map(|se| se.is_not_found()) == Some(true) {
+                Ok(false)
+            } else {
+                Err(e.into())
+            }
+        }
+    }
+}
+
+// Copy/Move types
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyMoveResult {
+    pub objects_copied: usize,
+    pub objects_deleted: usize,
+    pub errors: Vec<CopyMoveError>,
+    /// Source keys that were copied to the destination
+    pub copied_keys: Vec<String>,
+    /// Objects not transferred because the destination already held an identical copy
+    pub objects_skipped: usize,
+    pub skipped_keys: Vec<String>,
+    /// Source keys that were removed after copying (moves only)
+    pub deleted_keys: Vec<String>,
+    /// True if the operation was stopped via `cancel_copy` before finishing
+    pub cancelled: bool,
+    /// Set when some items failed; pass to `retry_failed_items` to re-attempt them
+    pub operation_batch_id: Option<String>,
+}
+
+/// Global state for tracking cancellable copy, move and rename operations
+#[derive(Default)]
+pub struct CopyState {
+    /// Map of operation_id -> cancellation flag
+    pub active: tokio::sync::RwLock<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl CopyState {
+    /// Register a cancellation flag for an operation. Operations without an id
+    /// get a flag that can never be set.
+    async fn register(&self, operation_id: Option<&String>) -> Arc<AtomicBool> {
+        let flag = Arc::new(AtomicBool::new(false));
+        if let Some(id) = operation_id {
+            self.active.write().await.insert(id.clone(), flag.clone());
+        }
+        flag
+    }
+
+    async fn finish(&self, operation_id: Option<&String>) {
+        if let Some(id) = operation_id {
+            self.active.write().await.remove(id);
+        }
+    }
+}
+
+/// Cancel a running copy, move or rename. Objects already processed stay where they are.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn cancel_copy(
+    copy_state: State<'_, CopyState>,
+    operation_id: String,
+) -> Result<(), AppError> {
+    if let Some(flag) = copy_state.active.read().await.get(&operation_id) {
+        flag.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyMoveError {
+    pub source_key: String,
+    pub error: String,
+}
+
+/// Progress event for copy, move and rename operations
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyProgress {
+    pub operation_id: String,
+    pub objects_processed: usize,
+    pub total_objects: usize,
+}
+
+/// Sent when a copy or move with an `operation_id` starts, with the history
+/// entries recorded for it (one per requested source key)
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyStarted {
+    pub operation_id: String,
+    pub history_ids: Vec<i64>,
+}
+
+/// Where a copy or move went, stored in its history metadata so the copy can
+/// be replayed later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CopyDestinationRecord {
+    dest_account_id: String,
+    dest_bucket: String,
+    destination_prefix: String,
+}
+
+/// Record one in-progress history entry per requested source key and announce
+/// them with `copy-started`
+fn start_copy_operations(
+    app: &AppHandle,
+    db: &DbManager,
+    account_id: &str,
+    bucket: &str,
+    source_keys: &[String],
+    destination: &CopyDestinationRecord,
+    delete_source: bool,
+    operation_id: Option<&String>,
+) -> Vec<Option<i64>> {
+    let operation_type = if delete_source {
+        OperationType::Move
+    } else {
+        OperationType::Copy
+    };
+    let dest_key = if destination.dest_account_id == account_id && destination.dest_bucket == bucket
+    {
+        destination.destination_prefix.clone()
+    } else {
+        format!(
+            "{}/{}",
+            destination.dest_bucket, destination.destination_prefix
+        )
+    };
+    let metadata = serde_json::to_value(destination).ok();
+    let history_ids: Vec<Option<i64>> = source_keys
+        .iter()
+        .map(|source_key| {
+            start_operation(
+                db,
+                account_id,
+                bucket,
+                operation_type.clone(),
+                Some(source_key),
+                Some(&dest_key),
+                None,
+                metadata.clone(),
+            )
+        })
+        .collect();
+
+    if let Some(operation_id) = operation_id {
+        let _ = app.emit(
+            "copy-started",
+            CopyStarted {
+                operation_id: operation_id.clone(),
+                history_ids: history_ids.iter().flatten().copied().collect(),
+            },
+        );
+    }
+    history_ids
+}
+
+/// Finish the entries from `start_copy_operations`. A source key (or folder)
+/// fails if any object under it failed; otherwise a cancelled batch leaves it
+/// cancelled.
+fn finish_copy_operations(
+    db: &DbManager,
+    history_ids: &[Option<i64>],
+    source_keys: &[String],
+    errors: &[CopyMoveError],
+    cancelled: bool,
+    start_time: Instant,
+) {
+    let duration_ms = start_time.elapsed().as_millis() as i64 / source_keys.len().max(1) as i64;
+    for (history_id, source_key) in history_ids.iter().zip(source_keys) {
+        let Some(id) = *history_id else { continue };
+        let failed = errors.iter().any(|e| {
+            &e.source_key == source_key
+                || (source_key.ends_with('/') && e.source_key.starts_with(source_key.as_str()))
+        });
+        let (status, error) = if failed {
+            (OperationStatus::Failed, Some("Copy/move failed"))
+        } else if cancelled {
+            (OperationStatus::Cancelled, None)
+        } else {
+            (OperationStatus::Completed, None)
+        };
+        if let Err(e) = db.finish_operation(id, status, None, duration_ms, error) {
+            log::warn!("Failed to finish operation {}: {}", id, e);
+        }
+    }
+}
+
+/// Copy or move objects to a destination prefix.
+/// When `operation_id` is given, `copy-progress` events are emitted per object
+/// and the operation can be stopped with `cancel_copy`.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn copy_objects(
+    app: AppHandle,
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    copy_state: State<'_, CopyState>,
+    listing_cache: State<'_, ListingCache>,
+    account_id: String,
+    bucket: String,
+    source_keys: Vec<String>,
+    destination_prefix: String,
+    delete_source: bool,
+    operation_id: Option<String>,
+    verify_before_delete: Option<bool>,
+    source_version_id: Option<String>,
+) -> Result<CopyMoveResult, AppError> {
+    let start_time = Instant::now();
+    let verify_before_delete = verify_before_delete.unwrap_or(false);
+    if source_version_id.is_some()
+        && (source_keys.len() != 1 || source_keys.iter().any(|key| key.ends_with('/')))
+    {
+        return Err(AppError::InvalidInput(
+            "A source version can only be given for a single file".into(),
+        ));
+    }
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    let retry = RetryPolicy::from_settings(&db.get_app_settings()?);
+    let cancel_flag = copy_state.register(operation_id.as_ref()).await;
+    let history_ids = start_copy_operations(
+        &app,
+        &db,
+        &account_id,
+        &bucket,
+        &source_keys,
+        &CopyDestinationRecord {
+            dest_account_id: account_id.clone(),
+            dest_bucket: bucket.clone(),
+            destination_prefix: destination_prefix.clone(),
+        },
+        delete_source,
+        operation_id.as_ref(),
+    );
+
+    // Expand folders up front so the total is known before copying starts
+    let (pairs, errors) =
+        expand_copy_sources(&client, &bucket, &source_keys, &destination_prefix).await;
+    let mut result = copy_pairs_in_bucket(
+        &app,
+        &client,
+        &bucket,
+        &pairs,
+        source_version_id.as_deref(),
+        delete_source,
+        verify_before_delete,
+        operation_id.as_ref(),
+        &cancel_flag,
+        errors,
+        retry,
+    )
+    .await;
+
+    copy_state.finish(operation_id.as_ref()).await;
+
+    let mut touched: Vec<String> = pairs.iter().map(|(_, dest)| dest.clone()).collect();
+    touched.extend(result.deleted_keys.iter().cloned());
+    cache::invalidate_for(&listing_cache, &db, &account_id, &bucket, &[], &touched);
+
+    finish_copy_operations(
+        &db,
+        &history_ids,
+        &source_keys,
+        &result.errors,
+        result.cancelled,
+        start_time,
+    );
+
+    result.operation_batch_id = save_copy_failures(
+        &db,
+        None,
+        &account_id,
+        &bucket,
+        &CopyRetryParams {
+            destination_prefix,
+            delete_source,
+            verify_before_delete,
+            source_version_id,
+            dest_account_id: None,
+            dest_bucket: None,
+        },
+        &pairs,
+        &result.errors,
+    );
+
+    Ok(result)
+}
+
+/// Expand source keys (folders recursively) into (source, destination) key pairs.
+/// Folders that fail to list are reported as errors.
+async fn expand_copy_sources(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    source_keys: &[String],
+    destination_prefix: &str,
+) -> (Vec<(String, String)>, Vec<CopyMoveError>) {
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    let mut errors: Vec<CopyMoveError> = Vec::new();
+
+    for source_key in source_keys {
+        if source_key.ends_with('/') {
+            // For folders, copy all objects recursively
+            let folder_name = source_key
+                .trim_end_matches('/')
+                .split('/')
+                .last()
+                .unwrap_or("");
+
+            match list_keys_under_prefix(client, bucket, source_key).await {
+                Ok(keys) => {
+                    for obj_key in keys {
+                        // Get the relative path within the folder
+                        let relative_path =
+                            obj_key.strip_prefix(source_key.as_str()).unwrap_or(&obj_key);
+                        let dest_key =
+                            format!("{}{}/{}", destination_prefix, folder_name, relative_path);
+                        pairs.push((obj_key, dest_key));
+                    }
+                }
+                Err(e) => errors.push(CopyMoveError {
+                    source_key: source_key.clone(),
+                    error: format!("Failed to list folder: {}", e),
+                }),
+            }
+        } else {
+            // For single files
+            let file_name = source_key.split('/').last().unwrap_or(source_key);
+            let dest_key = format!("{}{}", destination_prefix, file_name);
+            pairs.push((source_key.clone(), dest_key));
+        }
+    }
+
+    (pairs, errors)
+}
+
+/// Copy each (source, destination) pair within a bucket, deleting the source for moves.
+/// Pairs whose destination already holds an identical object are skipped.
+/// With `verify_before_delete`, a moved source is only deleted once its copy checks out.
+/// `errors` seeds the result with failures from an earlier stage (e.g. folder listing).
+/// A source version id applies to a single pair: that version is copied, and a
+/// move deletes that version rather than adding a delete marker.
+async fn copy_pairs_in_bucket(
+    app: &AppHandle,
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    pairs: &[(String, String)],
+    source_version_id: Option<&str>,
+    delete_source: bool,
+    verify_before_delete: bool,
+    operation_id: Option<&String>,
+    cancel_flag: &AtomicBool,
+    mut errors: Vec<CopyMoveError>,
+    retry: RetryPolicy,
+) -> CopyMoveResult {
+    let mut objects_copied = 0;
+    let mut objects_deleted = 0;
+    let mut copied_keys: Vec<String> = Vec::new();
+    let mut skipped_keys: Vec<String> = Vec::new();
+    let mut deleted_keys: Vec<String> = Vec::new();
+    let mut cancelled = false;
+    let total_objects = pairs.len();
+
+    for (index, (obj_key, dest_key)) in pairs.iter().enumerate() {
+        // Only checked between objects so a move never stops between copy and delete
+        if cancel_flag.load(Ordering::Relaxed) {
+            cancelled = true;
+            break;
+        }
+
+        let result = async {
+            // One HEAD of the source serves the skip check, the copy and the verification
+            let source = head_copy_source(client, bucket, obj_key, source_version_id, retry).await?;
+
+            // Copying a key onto itself is never skipped, or a move would delete the only
+            // copy; an explicit version is always copied
+            if obj_key != dest_key
+                && source_version_id.is_none()
+                && destination_matches(&source, client, bucket, dest_key).await
+            {
+                return Ok((true, source));
+            }
+
+            copy_object_with_head(
+                client,
+                &source,
+                bucket,
+                obj_key,
+                source_version_id,
+                bucket,
+                dest_key,
+                retry,
+            )
+            .await?;
+            Ok::<_, AppError>((false, source))
+        }
+        .await;
+
+        match result {
+            Ok((skipped, source)) => {
+                if skipped {
+                    skipped_keys.push(obj_key.clone());
+                } else {
+                    objects_copied += 1;
+                    copied_keys.push(obj_key.clone());
+                }
+
+                // A skipped pair was already compared against its source
+                let verified = if delete_source && verify_before_delete && !skipped {
+                    verify_copy(&source, client, bucket, dest_key).await
+                } else {
+                    Ok(())
+                };
+
+                // Delete if moving
+                if let Err(e) = verified {
+                    errors.push(CopyMoveError {
+                        source_key: obj_key.clone(),
+                        error: e,
+                    });
+                } else if delete_source {
+                    match with_retry(retry, || {
+                        client
+                            .delete_object()
+                            .bucket(bucket)
+                            .key(obj_key)
+                            .set_version_id(source_version_id.map(str::to_string))
+                            .send()
+                    })
+                    .await
+                    {
+                        Ok(_) => {
+                            objects_deleted += 1;
+                            deleted_keys.push(obj_key.clone());
+                        }
+                        Err(e) => errors.push(CopyMoveError {
+                            source_key: obj_key.clone(),
+                            error: format!("Failed to delete: {:?}", e),
+                        }),
+                    }
+                }
+            }
+            Err(e) => {
+                errors.push(CopyMoveError {
+                    source_key: obj_key.clone(),
+                    error: format!("Failed to copy: {}", e),
+                });
+            }
+        }
+
+        if let Some(operation_id) = operation_id {
+            let _ = app.emit(
+                "copy-progress",
+                CopyProgress {
+                    operation_id: operation_id.clone(),
+                    objects_processed: index + 1,
+                    total_objects,
+                },
+            );
+        }
+    }
+
+    CopyMoveResult {
+        objects_copied,
+        objects_deleted,
+        errors,
+        copied_keys,
+        objects_skipped: skipped_keys.len(),
+        skipped_keys,
+        deleted_keys,
+        cancelled,
+        operation_batch_id: None,
+    }
+}
+
+/// Parameters needed to re-run failed copies
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CopyRetryParams {
+    destination_prefix: String,
+    delete_source: bool,
+    #[serde(default)]
+    verify_before_delete: bool,
+    /// Version of the single source object, when one was copied explicitly
+    #[serde(default)]
+    source_version_id: Option<String>,
+    /// Set for `copy_objects_across_buckets`; the batch itself records the source
+    #[serde(default)]
+    dest_account_id: Option<String>,
+    #[serde(default)]
+    dest_bucket: Option<String>,
+}
+
+/// Persist failed copies for `retry_failed_items`. Returns the batch id if anything failed.
+fn save_copy_failures(
+    db: &DbManager,
+    batch_id: Option<String>,
+    account_id: &str,
+    bucket: &str,
+    params: &CopyRetryParams,
+    pairs: &[(String, String)],
+    errors: &[CopyMoveError],
+) -> Option<String> {
+    if batch_id.is_none() && errors.is_empty() {
+        return None;
+    }
+    let batch_id = batch_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    // Folder listing failures have no destination yet and are re-expanded on retry
+    let destinations: HashMap<&str, &str> = pairs
+        .iter()
+        .map(|(src, dest)| (src.as_str(), dest.as_str()))
+        .collect();
+    let items: Vec<FailedItem> = errors
+        .iter()
+        .map(|e| FailedItem {
+            key: e.source_key.clone(),
+            dest_key: destinations
+                .get(e.source_key.as_str())
+                .map(|d| d.to_string()),
+            error: e.error.clone(),
+        })
+        .collect();
+
+    let params = serde_json::to_string(params).unwrap_or_else(|_| "{}".to_string());
+
+    save_failures(db, &batch_id, BatchOperation::Copy, account_id, bucket, &params, &items)
+}
+
+/// Copy or move objects across buckets (same or different accounts).
+/// Supports the same `copy-progress` events and `cancel_copy` as `copy_objects`.
+/// `verify_before_delete` defaults to on for moves between accounts, where the
+/// copy goes through a download and re-upload.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn copy_objects_across_buckets(
+    app: AppHandle,
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    listing_cache: State<'_, ListingCache>,
+    copy_state: State<'_, CopyState>,
+    source_account_id: String,
+    source_bucket: String,
+    dest_account_id: String,
+    dest_bucket: String,
+    source_keys: Vec<String>,
+    destination_prefix: String,
+    delete_source: bool,
+    operation_id: Option<String>,
+    verify_before_delete: Option<bool>,
+) -> Result<CopyMoveResult, AppError> {
+    let start_time = Instant::now();
+    let source_account = credentials.get_account(&source_account_id)?;
+    let source_secret = credentials.get_secret_key(&source_account_id)?;
+    let source_client = s3_clients
+        .get_or_create_client(
+            &source_account_id,
+            &source_account.endpoint,
+            &source_account.access_key_id,
+            &source_secret,
+            source_account.provider_type,
+            source_account.region.as_deref(),
+            &source_account.client_options(),
+        )
+        .await?;
+
+    let dest_account = credentials.get_account(&dest_account_id)?;
+    let dest_secret = credentials.get_secret_key(&dest_account_id)?;
+    let dest_client = s3_clients
+        .get_or_create_client(
+            &dest_account_id,
+            &dest_account.endpoint,
+            &dest_account.access_key_id,
+            &dest_secret,
+            dest_account.provider_type,
+            dest_account.region.as_deref(),
+            &dest_account.client_options(),
+        )
+        .await?;
+
+    let retry = RetryPolicy::from_settings(&db.get_app_settings()?);
+    let cancel_flag = copy_state.register(operation_id.as_ref()).await;
+
+    let history_ids = start_copy_operations(
+        &app,
+        &db,
+        &source_account_id,
+        &source_bucket,
+        &source_keys,
+        &CopyDestinationRecord {
+            dest_account_id: dest_account_id.clone(),
+            dest_bucket: dest_bucket.clone(),
+            destination_prefix: destination_prefix.clone(),
+        },
+        delete_source,
+        operation_id.as_ref(),
+    );
+
+    // Check if same account and bucket - can use S3 copy
+    let same_account = source_account_id == dest_account_id;
+    let verify_before_delete = verify_before_delete.unwrap_or(!same_account);
+
+    // Expand folders up front so the total is known before copying starts
+    let (pairs, errors) =
+        expand_copy_sources(&source_client, &source_bucket, &source_keys, &destination_prefix)
+            .await;
+    let mut result = copy_pairs_across_buckets(
+        &app,
+        &source_client,
+        &source_bucket,
+        &dest_client,
+        &dest_bucket,
+        same_account,
+        &pairs,
+        delete_source,
+        verify_before_delete,
+        operation_id.as_ref(),
+        &cancel_flag,
+        errors,
+        retry,
+    )
+    .await;
+
+    copy_state.finish(operation_id.as_ref()).await;
+
+    let dest_keys: Vec<String> = pairs.iter().map(|(_, dest)| dest.clone()).collect();
+    cache::invalidate_for(&listing_cache, &db, &dest_account_id, &dest_bucket, &[], &dest_keys);
+    if !result.deleted_keys.is_empty() {
+        cache::invalidate_for(
+            &listing_cache,
+            &db,
+            &source_account_id,
+            &source_bucket,
+            &[],
+            &result.deleted_keys,
+        );
+    }
+
+    finish_copy_operations(
+        &db,
+        &history_ids,
+        &source_keys,
+        &result.errors,
+        result.cancelled,
+        start_time,
+    );
+
+    // Failures are stored against the source, which is where a retry reads from
+    result.operation_batch_id = save_copy_failures(
+        &db,
+        None,
+        &source_account_id,
+        &source_bucket,
+        &CopyRetryParams {
+            destination_prefix,
+            delete_source,
+            verify_before_delete,
+            source_version_id: None,
+            dest_account_id: Some(dest_account_id),
+            dest_bucket: Some(dest_bucket),
+        },
+        &pairs,
+        &result.errors,
+    );
+
+    Ok(result)
+}
+
+/// Copy each (source, destination) pair from one bucket to another: server-side
+/// within an account, otherwise through a download and re-upload. Pairs whose
+/// destination already matches the source are skipped.
+async fn copy_pairs_across_buckets(
+    app: &AppHandle,
+    source_client: &aws_sdk_s3::Client,
+    source_bucket: &str,
+    dest_client: &aws_sdk_s3::Client,
+    dest_bucket: &str,
+    same_account: bool,
+    pairs: &[(String, String)],
+    delete_source: bool,
+    verify_before_delete: bool,
+    operation_id: Option<&String>,
+    cancel_flag: &AtomicBool,
+    mut errors: Vec<CopyMoveError>,
+    retry: RetryPolicy,
+) -> CopyMoveResult {
+    let mut objects_copied = 0;
+    let mut objects_deleted = 0;
+    let mut copied_keys: Vec<String> = Vec::new();
+    let mut skipped_keys: Vec<String> = Vec::new();
+    let mut deleted_keys: Vec<String> = Vec::new();
+    let mut cancelled = false;
+    let total_objects = pairs.len();
+
+    for (index, (obj_key, dest_key)) in pairs.iter().enumerate() {
+        // Only checked between objects so a move never stops between copy and delete
+        if cancel_flag.load(Ordering::Relaxed) {
+            cancelled = true;
+            break;
+        }
+
+        let same_object = same_account && source_bucket == dest_bucket && obj_key == dest_key;
+        let result = async {
+            // One HEAD of the source serves the skip check, the copy and the verification
+            let source = head_copy_source(source_client, source_bucket, obj_key, None, retry)
+                .await
+                .map_err(|e| e.to_string())?;
+
+            // Re-running an interrupted migration skips objects that already made it across
+            if !same_object
+                && destination_matches(&source, dest_client, dest_bucket, dest_key).await
+            {
+                return Ok((true, source));
+            }
+
+            if same_account {
+                // Same account: use S3 copy
+                copy_object_with_head(
+                    dest_client,
+                    &source,
+                    source_bucket,
+                    obj_key,
+                    None,
+                    dest_bucket,
+                    dest_key,
+                    retry,
+                )
+                .await
+                .map_err(|e| e.to_string())?;
+            } else {
+                // Different accounts: download and upload
+                copy_via_download_upload(
+                    source_client,
+                    dest_client,
+                    source_bucket,
+                    dest_bucket,
+                    obj_key,
+                    dest_key,
+                )
+                .await?;
+            }
+            Ok::<_, String>((false, source))
+        }
+        .await;
+
+        match result {
+            Ok((skipped, source)) => {
+                if skipped {
+                    skipped_keys.push(obj_key.clone());
+                } else {
+                    objects_copied += 1;
+                    copied_keys.push(obj_key.clone());
+                }
+
+                // A skipped pair was already compared against its source
+                let verified = if delete_source && verify_before_delete && !skipped {
+                    verify_copy(&source, dest_client, dest_bucket, dest_key).await
+                } else {
+                    Ok(())
+                };
+
+                // Delete source if moving
+                if let Err(e) = verified {
+                    errors.push(CopyMoveError {
+                        source_key: obj_key.clone(),
+                        error: e,
+                    });
+                } else if delete_source {
+                    match with_retry(retry, || {
+                        source_client
+                            .delete_object()
+                            .bucket(source_bucket)
+                            .key(obj_key)
+                            .send()
+                    })
+                    .await
+                    {
+                        Ok(_) => {
+                            objects_deleted += 1;
+                            deleted_keys.push(obj_key.clone());
+                        }
+                        Err(e) => errors.push(CopyMoveError {
+                            source_key: obj_key.clone(),
+                            error: format!("Failed to delete: {:?}", e),
+                        }),
+                    }
+                }
+            }
+            Err(e) => {
+                errors.push(CopyMoveError {
+                    source_key: obj_key.clone(),
+                    error: format!("Failed to copy: {}", e),
+                });
+            }
+        }
+
+        if let Some(operation_id) = operation_id {
+            let _ = app.emit(
+                "copy-progress",
+                CopyProgress {
+                    operation_id: operation_id.clone(),
+                    objects_processed: index + 1,
+                    total_objects,
+                },
+            );
+        }
+    }
+
+    CopyMoveResult {
+        objects_copied,
+        objects_deleted,
+        errors,
+        copied_keys,
+        objects_skipped: skipped_keys.len(),
+        skipped_keys,
+        deleted_keys,
+        cancelled,
+        operation_batch_id: None,
+    }
+}
+
+/// Objects above this size must be copied with UploadPartCopy (S3's single CopyObject limit)
+const MULTIPART_COPY_THRESHOLD: i64 = 5 * 1024 * 1024 * 1024; // 5GB
+/// Maximum size of a single UploadPartCopy range
+const COPY_PART_SIZE: i64 = 5 * 1024 * 1024 * 1024; // 5GB
+
+/// Percent-encode each path segment of a key, keeping the slashes between
+/// them, for use in a URL path or copy source
+pub(crate) fn encode_key_path(key: &str) -> String {
+    key.split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// The `x-amz-copy-source` value for a key: each path segment is
+/// percent-encoded but the slashes between them are kept, since some providers
+/// reject an encoded `/` there. A version id selects an older version.
+pub(crate) fn copy_source(bucket: &str, key: &str, version_id: Option<&str>) -> String {
+    let path = encode_key_path(key);
+    match version_id {
+        Some(version_id) => format!(
+            "{}/{}?versionId={}",
+            bucket,
+            path,
+            urlencoding::encode(version_id)
+        ),
+        None => format!("{}/{}", bucket, path),
+    }
+}
+
+/// Server-side copy within one account. Objects over 5GB are copied in parts;
+/// smaller objects use a single CopyObject call. A source version id copies
+/// that version instead of the current one.
+async fn copy_object_server_side(
+    client: &aws_sdk_s3::Client,
+    source_bucket: &str,
+    source_key: &str,
+    source_version_id: Option<&str>,
+    dest_bucket: &str,
+    dest_key: &str,
+    retry: RetryPolicy,
+) -> Result<(), AppError> {
+    let head = head_copy_source(client, source_bucket, source_key, source_version_id, retry).await?;
+    copy_object_with_head(
+        client,
+        &head,
+        source_bucket,
+        source_key,
+        source_version_id,
+        dest_bucket,
+        dest_key,
+        retry,
+    )
+    .await
+}
+
+/// HEAD the source of a copy, including its checksums. Callers fetch it once
+/// per object and share it between the skip check, the copy and the verification.
+async fn head_copy_source(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+    version_id: Option<&str>,
+    retry: RetryPolicy,
+) -> Result<HeadObjectOutput, AppError> {
+    let head = with_retry(retry, || {
+        client
+            .head_object()
+            .bucket(bucket)
+            .key(key)
+            .set_version_id(version_id.map(str::to_string))
+            .checksum_mode(ChecksumMode::Enabled)
+            .send()
+    })
+    .await?;
+    Ok(head)
+}
+
+/// `copy_object_server_side` with the source already fetched by `head_copy_source`
+async fn copy_object_with_head(
+    client: &aws_sdk_s3::Client,
+    head: &HeadObjectOutput,
+    source_bucket: &str,
+    source_key: &str,
+    source_version_id: Option<&str>,
+    dest_bucket: &str,
+    dest_key: &str,
+    retry: RetryPolicy,
+) -> Result<(), AppError> {
+    let copy_source = copy_source(source_bucket, source_key, source_version_id);
+    let size = head.content_length().unwrap_or(0);
+
+    if size <= MULTIPART_COPY_THRESHOLD {
+        with_retry(retry, || {
+            client
+                .copy_object()
+                .bucket(dest_bucket)
+                .key(dest_key)
+                .copy_source(&copy_source)
+                .send()
+        })
+        .await?;
+        return Ok(());
+    }
+
+    // CopyObject carries metadata over automatically; a multipart upload does not
+    let create_response = client
+        .create_multipart_upload()
+        .bucket(dest_bucket)
+        .key(dest_key)
+        .set_content_type(head.content_type().map(|s| s.to_string()))
+        .set_cache_control(head.cache_control().map(|s| s.to_string()))
+        .set_content_disposition(head.content_disposition().map(|s| s.to_string()))
+        .set_content_encoding(head.content_encoding().map(|s| s.to_string()))
+        .set_content_language(head.content_language().map(|s| s.to_string()))
+        .set_storage_class(head.storage_class().cloned())
+        .set_metadata(head.metadata().cloned())
+        .send()
+        .await?;
+
+    let s3_upload_id = create_response
+        .upload_id()
+        .ok_or_else(|| AppError::S3("No upload ID returned".into()))?
+        .to_string();
+
+    let mut completed_parts = Vec::new();
+    let mut part_number = 1;
+    let mut start: i64 = 0;
+
+    while start < size {
+        let end = (start + COPY_PART_SIZE).min(size) - 1;
+
+        let part_response = match with_retry(retry, || {
+            client
+                .upload_part_copy()
+                .bucket(dest_bucket)
+                .key(dest_key)
+                .upload_id(&s3_upload_id)
+                .part_number(part_number)
+                .copy_source(&copy_source)
+                .copy_source_range(format!("bytes={}-{}", start, end))
+                .send()
+        })
+        .await
+        {
+            Ok(resp) => resp,
+            Err(e) => {
+                // Attempt to abort on failure
+                let _ = client
+                    .abort_multipart_upload()
+                    .bucket(dest_bucket)
+                    .key(dest_key)
+                    .upload_id(&s3_upload_id)
+                    .send()
+                    .await;
+                log::warn!("Failed to copy part {}: {:?}", part_number, e);
+                return Err(e.into());
+            }
+        };
+
+        let etag = part_response
+            .copy_part_result()
+            .and_then(|r| r.e_tag())
+            .unwrap_or_default();
+
+        completed_parts.push(
+            aws_sdk_s3::types::CompletedPart::builder()
+                .e_tag(etag)
+                .part_number(part_number)
+                .build(),
+        );
+
+        part_number += 1;
+        start = end + 1;
+    }
+
+    let completed_upload = aws_sdk_s3::types::CompletedMultipartUpload::builder()
+        .set_parts(Some(completed_parts))
+        .build();
+
+    if let Err(e) = client
+        .complete_multipart_upload()
+        .bucket(dest_bucket)
+        .key(dest_key)
+        .upload_id(&s3_upload_id)
+        .multipart_upload(completed_upload)
+        .send()
+        .await
+    {
+        let _ = client
+            .abort_multipart_upload()
+            .bucket(dest_bucket)
+            .key(dest_key)
+            .upload_id(&s3_upload_id)
+            .send()
+            .await;
+        log::warn!("Failed to complete multipart copy: {:?}", e);
+        return Err(e.into());
+    }
+
+    Ok(())
+}
+
+/// Check that a copy landed intact before its source is deleted: the
+/// destination must exist and be the same size as the source's HEAD from
+/// before the copy. Guards against gateways that acknowledge a copy but store
+/// an empty or truncated object.
+async fn verify_copy(
+    source: &HeadObjectOutput,
+    dest_client: &aws_sdk_s3::Client,
+    dest_bucket: &str,
+    dest_key: &str,
+) -> Result<(), String> {
+    let dest = dest_client
+        .head_object()
+        .bucket(dest_bucket)
+        .key(dest_key)
+        .send()
+        .await
+        .map_err(|e| format!("Copy could not be verified, source kept: {:?}", e))?;
+
+    if source.content_length() != dest.content_length() {
+        return Err(format!(
+            "Copy is {} bytes but the source is {} bytes; source kept",
+            dest.content_length().unwrap_or(0),
+            source.content_length().unwrap_or(0)
+        ));
+    }
+    Ok(())
+}
+
+/// Whether the destination already holds an identical copy of the source, given
+/// the source's HEAD from `head_copy_source`. Sizes must match, then any checksum
+/// both sides report must agree; without a shared checksum the ETags are compared.
+/// Returns false when the destination can't be read, so the object is simply
+/// transferred.
+async fn destination_matches(
+    source: &HeadObjectOutput,
+    dest_client: &aws_sdk_s3::Client,
+    dest_bucket: &str,
+    dest_key: &str,
+) -> bool {
+    let dest = match dest_client
+        .head_object()
+        .bucket(dest_bucket)
+        .key(dest_key)
+        .checksum_mode(ChecksumMode::Enabled)
+        .send()
+        .await
+    {
+        Ok(head) => head,
+        Err(_) => return false,
+    };
+
+    if source.content_length() != dest.content_length() {
+        return false;
+    }
+
+    let checksums = [
+        (source.checksum_sha256(), dest.checksum_sha256()),
+        (source.checksum_sha1(), dest.checksum_sha1()),
+        (source.checksum_crc64_nvme(), dest.checksum_crc64_nvme()),
+        (source.checksum_crc32_c(), dest.checksum_crc32_c()),
+        (source.checksum_crc32(), dest.checksum_crc32()),
+    ];
+    if let Some((a, b)) = checksums
+        .iter()
+        .find_map(|pair| match pair {
+            (Some(a), Some(b)) => Some((a, b)),
+            _ => None,
+        })
+    {
+        return a == b;
+    }
+
+    match (source.e_tag(), dest.e_tag()) {
+        (Some(a), Some(b)) => a.trim_matches('"') == b.trim_matches('"'),
+        _ => false,
+    }
+}
+
+/// Helper function to copy an object by downloading from source and uploading to destination
+async fn copy_via_download_upload(
+    source_client: &aws_sdk_s3::Client,
+    dest_client: &aws_sdk_s3::Client,
+    source_bucket: &str,
+    dest_bucket: &str,
+    source_key: &str,
+    dest_key: &str,
+) -> Result<(), String> {
+    // Download from source
+    let response = source_client
+        .get_object()
+        .bucket(source_bucket)
+        .key(source_key)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download: {:?}", e))?;
+
+    let content_type = response
+        .content_type()
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+
+    let body = response
+        .body
+        .collect()
+        .await
+        .map_err(|e| format!("Failed to read body: {:?}", e))?;
+
+    // Upload to destination
+    dest_client
+        .put_object()
+        .bucket(dest_bucket)
+        .key(dest_key)
+        .body(aws_sdk_s3::primitives::ByteStream::from(body.into_bytes()))
+        .content_type(&content_type)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to upload: {:?}", e))?;
+
+    Ok(())
+}
+
+// Folder download event types
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderDownloadProgress {
+    pub download_id: String,
+    /// Includes `files_resumed`
+    pub files_processed: usize,
+    pub total_files: usize,
+    /// Files already downloaded by an earlier, interrupted run
+    pub files_resumed: usize,
+    pub bytes_downloaded: u64,
+    pub bytes_per_sec: u64,
+    pub eta_secs: Option<u64>,
+}
+
+/// A file written by a resumable or directory folder download
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderDownloadFile {
+    pub download_id: String,
+    pub key: String,
+    pub path: String,
+    pub bytes: u64,
+}
+
+/// Update object metadata using copy-in-place with REPLACE directive
+#[tauri::command(rename_all = "camelCase")]
+pub async fn update_object_metadata(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    account_id: String,
+    bucket: String,
+    key: String,
+    content_type: Option<String>,
+    cache_control: Option<String>,
+    content_disposition: Option<String>,
+    content_encoding: Option<String>,
+    custom_metadata: Option<std::collections::HashMap<String, String>>,
+) -> Result<ObjectMetadata, AppError> {
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    // First, get current metadata to preserve any fields not being updated
+    let current = client.head_object().bucket(&bucket).key(&key).send().await?;
+
+    // Use copy-in-place with REPLACE metadata directive
+    let copy_source = copy_source(&bucket, &key, None);
+
+    let mut copy_request = client
+        .copy_object()
+        .bucket(&bucket)
+        .key(&key)
+        .copy_source(&copy_source)
+        .metadata_directive(aws_sdk_s3::types::MetadataDirective::Replace);
+
+    // Set content type (use provided or keep existing)
+    if let Some(ct) = content_type {
+        copy_request = copy_request.content_type(ct);
+    } else if let Some(ct) = current.content_type() {
+        copy_request = copy_request.content_type(ct);
+    }
+
+    // Set cache control
+    if let Some(cc) = cache_control {
+        if !cc.is_empty() {
+            copy_request = copy_request.cache_control(cc);
+        }
+    } else if let Some(cc) = current.cache_control() {
+        copy_request = copy_request.cache_control(cc);
+    }
+
+    // Set content disposition
+    if let Some(cd) = content_disposition {
+        if !cd.is_empty() {
+            copy_request = copy_request.content_disposition(cd);
+        }
+    } else if let Some(cd) = current.content_disposition() {
+        copy_request = copy_request.content_disposition(cd);
+    }
+
+    // Set content encoding
+    if let Some(ce) = content_encoding {
+        if !ce.is_empty() {
+            copy_request = copy_request.content_encoding(ce);
+        }
+    } else if let Some(ce) = current.content_encoding() {
+        copy_request = copy_request.content_encoding(ce);
+    }
+
+    // Set custom metadata
+    if let Some(meta) = custom_metadata {
+        copy_request = copy_request.set_metadata(Some(meta));
+    } else if let Some(meta) = current.metadata() {
+        let meta_map: std::collections::HashMap<String, String> = meta
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        copy_request = copy_request.set_metadata(Some(meta_map));
+    }
+
+    // Execute the copy
+    copy_request.send().await?;
+
+    // Fetch and return the updated metadata
+    let updated = client
+        .head_object()
+        .bucket(&bucket)
+        .key(&key)
+        .checksum_mode(ChecksumMode::Enabled)
+        .send()
+        .await?;
+
+    Ok(ObjectMetadata::from_head_object(key, &updated))
+}
+
+const CONTENT_TYPE_FIX_CONCURRENCY: usize = 8;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentTypeFix {
+    pub key: String,
+    pub current_content_type: Option<String>,
+    pub guessed_content_type: String,
+    /// False in dry-run mode or when the copy failed
+    pub applied: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixContentTypesResult {
+    pub dry_run: bool,
+    pub objects_scanned: usize,
+    /// Only objects whose stored type differs from the guessed one
+    pub changes: Vec<ContentTypeFix>,
+}
+
+/// Compare content types by their essence, ignoring parameters like charset
+fn same_content_type(current: &str, guessed: &str) -> bool {
+    let essence = current.split(';').next().unwrap_or_default().trim();
+    essence.eq_ignore_ascii_case(guessed)
+}
+
+/// Copy an object onto itself, refreshing its last-modified time.
+/// S3 rejects a self-copy that changes nothing, so this uses the REPLACE directive and
+/// re-sends the current headers, user metadata, storage class and KMS key. A new
+/// `content_type` may be given; otherwise the current one is kept.
+async fn copy_in_place(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+    head: &aws_sdk_s3::operation::head_object::HeadObjectOutput,
+    content_type: Option<&str>,
+) -> Result<(), String> {
+    if head.content_length().unwrap_or(0) > MULTIPART_COPY_THRESHOLD {
+        return Err("Object is too large to update in place (over 5GB)".into());
+    }
+
+    let copy_source = copy_source(bucket, key, None);
+    client
+        .copy_object()
+        .bucket(bucket)
+        .key(key)
+        .copy_source(&copy_source)
+        .metadata_directive(aws_sdk_s3::types::MetadataDirective::Replace)
+        .set_content_type(content_type.or(head.content_type()).map(|s| s.to_string()))
+        .set_cache_control(head.cache_control().map(|s| s.to_string()))
+        .set_content_disposition(head.content_disposition().map(|s| s.to_string()))
+        .set_content_encoding(head.content_encoding().map(|s| s.to_string()))
+        .set_content_language(head.content_language().map(|s| s.to_string()))
+        .set_website_redirect_location(head.website_redirect_location().map(|s| s.to_string()))
+        .set_storage_class(head.storage_class().cloned())
+        .set_server_side_encryption(head.server_side_encryption().cloned())
+        .set_ssekms_key_id(head.ssekms_key_id().map(|s| s.to_string()))
+        .set_metadata(head.metadata().cloned())
+        .send()
+        .await
+        .map_err(|e| format!("Failed to copy object in place: {:?}", e))?;
+
+    Ok(())
+}
+
+/// Correct content types under a prefix based on each key's extension.
+/// Keys with an unknown extension are left alone. In dry-run mode the proposed
+/// changes are returned without touching any object.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn fix_content_types(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    listing_cache: State<'_, ListingCache>,
+    account_id: String,
+    bucket: String,
+    prefix: String,
+    dry_run: bool,
+) -> Result<FixContentTypesResult, AppError> {
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    let keys: Vec<String> = list_keys_under_prefix(&client, &bucket, &prefix)
+        .await?
+        .into_iter()
+        .filter(|k| !k.ends_with('/'))
+        .collect();
+    let objects_scanned = keys.len();
+
+    let client = &client;
+    let bucket = &bucket;
+    let results: Vec<Option<ContentTypeFix>> = stream::iter(keys)
+        .map(|key| async move {
+            let guessed = mime_guess::from_path(&key).first()?.essence_str().to_string();
+
+            let head = match client.head_object().bucket(bucket).key(&key).send().await {
+                Ok(head) => head,
+                Err(e) => {
+                    return Some(ContentTypeFix {
+                        key,
+                        current_content_type: None,
+                        guessed_content_type: guessed,
+                        applied: false,
+                        error: Some(format!("Failed to get object: {:?}", e)),
+                    })
+                }
+            };
+
+            let current = head.content_type().map(|s| s.to_string());
+            if current.as_deref().map_or(false, |c| same_content_type(c, &guessed)) {
+                return None;
+            }
+
+            let error = if dry_run {
+                None
+            } else {
+                copy_in_place(client, bucket, &key, &head, Some(&guessed))
+                    .await
+                    .err()
+            };
+
+            Some(ContentTypeFix {
+                key,
+                current_content_type: current,
+                guessed_content_type: guessed,
+                applied: !dry_run && error.is_none(),
+                error,
+            })
+        })
+        .buffered(CONTENT_TYPE_FIX_CONCURRENCY)
+        .collect()
+        .await;
+    let changes: Vec<ContentTypeFix> = results.into_iter().flatten().collect();
+
+    let applied: Vec<String> = changes
+        .iter()
+        .filter(|c| c.applied)
+        .map(|c| c.key.clone())
+        .collect();
+    cache::invalidate_for(&listing_cache, &db, &account_id, bucket, &[], &applied);
+
+    Ok(FixContentTypesResult {
+        dry_run,
+        objects_scanned,
+        changes,
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TouchResult {
+    pub touched: Vec<String>,
+    pub errors: Vec<TouchError>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TouchError {
+    pub key: String,
+    pub error: String,
+}
+
+/// Bump an object's last-modified time without changing its content or metadata,
+/// e.g. to reset lifecycle age. Folders (keys ending in `/`) touch every object
+/// beneath them. Objects over 5GB and SSE-C encrypted objects cannot be touched.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn touch_object(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    listing_cache: State<'_, ListingCache>,
+    account_id: String,
+    bucket: String,
+    key: String,
+) -> Result<TouchResult, AppError> {
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    let keys = if key.ends_with('/') {
+        list_keys_under_prefix(&client, &bucket, &key).await?
+    } else {
+        vec![key]
+    };
+
+    let mut touched = Vec::new();
+    let mut errors = Vec::new();
+
+    for key in keys {
+        let result = match client.head_object().bucket(&bucket).key(&key).send().await {
+            Ok(head) => copy_in_place(&client, &bucket, &key, &head, None).await,
+            Err(e) => Err(format!("Failed to get object: {:?}", e)),
+        };
+        match result {
+            Ok(()) => touched.push(key),
+            Err(error) => errors.push(TouchError { key, error }),
+        }
+    }
+
+    cache::invalidate_for(&listing_cache, &db, &account_id, &bucket, &[], &touched);
+
+    Ok(TouchResult { touched, errors })
+}
+
+/// How `download_folder` stores a folder
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FolderDownloadMode {
+    /// Stream every object straight into `<folder>.zip`; an interrupted
+    /// download starts over
+    #[default]
+    Zip,
+    /// Download into a staging directory next to the archive and zip it once
+    /// every object is in. Running it again after an interruption skips
+    /// objects that were already downloaded.
+    ResumableZip,
+    /// Mirror the folder as plain files under `<destination>/<folder>/`,
+    /// resumable the same way
+    Directory,
+}
+
+/// Manifest of finished objects kept in a resumable download's directory, one
+/// JSON entry per line so an interrupted write loses at most the last entry
+const FOLDER_DOWNLOAD_MANIFEST: &str = ".bucketscout-download.ndjson";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FolderManifestEntry {
+    key: String,
+    size: i64,
+    etag: Option<String>,
+}
+
+/// An object to download as part of a folder
+struct FolderObject {
+    key: String,
+    size: i64,
+    etag: Option<String>,
+}
+
+/// Download a folder as a ZIP file, or as a mirrored directory tree (see `FolderDownloadMode`)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn download_folder(
+    app: AppHandle,
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    transfer_state: State<'_, TransferState>,
+    account_id: String,
+    bucket: String,
+    prefix: String,
+    destination: String,
+    download_id: String,
+    mode: Option<FolderDownloadMode>,
+) -> Result<String, AppError> {
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    // List all objects with this prefix
+    let mut all_objects: Vec<FolderObject> = Vec::new();
+    let mut continuation_token: Option<String> = None;
+
+    loop {
+        let mut request = client.list_objects_v2().bucket(&bucket).prefix(&prefix);
+
+        if let Some(token) = &continuation_token {
+            request = request.continuation_token(token);
+        }
+
+        let response = request.send().await?;
+
+        for obj in response.contents() {
+            if let Some(key) = obj.key() {
+                // Skip folder markers (keys ending with /)
+                if !key.ends_with('/') {
+                    all_objects.push(FolderObject {
+                        key: key.to_string(),
+                        size: obj.size().unwrap_or(0),
+                        etag: obj.e_tag().map(|e| e.to_string()),
+                    });
+                }
+            }
+        }
+
+        if response.is_truncated() == Some(true) {
+            continuation_token = response.next_continuation_token().map(|s| s.to_string());
+        } else {
+            break;
+        }
+    }
+
+    if all_objects.is_empty() {
+        return Err(AppError::InvalidInput("Folder is empty".into()));
+    }
+
+    let total_files = all_objects.len();
+
+    // Create ZIP file name from folder name
+    let folder_name = prefix
+        .trim_end_matches('/')
+        .split('/')
+        .last()
+        .unwrap_or("folder");
+    let zip_filename = format!("{}.zip", folder_name);
+    let zip_path = PathBuf::from(&destination).join(&zip_filename);
+
+    let mode = mode.unwrap_or_default();
+    if mode != FolderDownloadMode::Zip {
+        let root = match mode {
+            FolderDownloadMode::Directory => PathBuf::from(&destination).join(folder_name),
+            _ => PathBuf::from(&destination).join(format!(".{}.parts", zip_filename)),
+        };
+        let cancel_flag = transfer_state.register(&download_id).await;
+        let result = download_folder_to_tree(
+            &app,
+            &client,
+            &bucket,
+            &prefix,
+            &all_objects,
+            &root,
+            &download_id,
+            &cancel_flag,
+        )
+        .await;
+        transfer_state.finish(&download_id).await;
+
+        let final_path = match result {
+            Ok(()) if mode == FolderDownloadMode::ResumableZip => {
+                zip_directory_tree(&root, &prefix, &all_objects, &zip_path)?;
+                let _ = std::fs::remove_dir_all(&root);
+                zip_path.to_string_lossy().to_string()
+            }
+            Ok(()) => {
+                let _ = std::fs::remove_file(root.join(FOLDER_DOWNLOAD_MANIFEST));
+                root.to_string_lossy().to_string()
+            }
+            Err(e) => {
+                if matches!(e, AppError::Cancelled) {
+                    let _ = app.emit(
+                        "transfer-cancelled",
+                        TransferCancelled {
+                            transfer_id: download_id,
+                        },
+                    );
+                } else {
+                    let _ = app.emit(
+                        "download-failed",
+                        DownloadFailed {
+                            download_id,
+                            error: e.to_string(),
+                        },
+                    );
+                }
+                return Err(e);
+            }
+        };
+
+        let _ = app.emit(
+            "download-completed",
+            DownloadCompleted {
+                download_id,
+                key: prefix,
+                path: final_path.clone(),
+                operation_batch_id: None,
+            },
+        );
+        return Ok(final_path);
+    }
+
+    // Create the ZIP file
+    let zip_file = std::fs::File::create(&zip_path)
+        .map_err(|e| AppError::InvalidInput(format!("Failed to create ZIP file: {}", e)))?;
+    let mut zip = zip::ZipWriter::new(zip_file);
+
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .compression_level(Some(6));
+
+    let mut files_processed = 0usize;
+    let mut bytes_downloaded = 0u64;
+    let total_bytes: u64 = all_objects.iter().map(|obj| obj.size as u64).sum();
+    let mut rate = TransferRate::new();
+    let mut failed: Vec<FailedItem> = Vec::new();
+    let cancel_flag = transfer_state.register(&download_id).await;
+
+    for FolderObject { key: object_key, .. } in &all_objects {
+        if cancel_flag.load(Ordering::Relaxed) {
+            // Remove the incomplete archive
+            drop(zip);
+            let _ = std::fs::remove_file(&zip_path);
+            transfer_state.finish(&download_id).await;
+            let _ = app.emit(
+                "transfer-cancelled",
+                TransferCancelled {
+                    transfer_id: download_id,
+                },
+            );
+            return Err(AppError::Cancelled);
+        }
+
+        match add_object_to_zip(&client, &bucket, &prefix, object_key, &mut zip, options).await {
+            Ok(bytes) => bytes_downloaded += bytes,
+            Err(e) => {
+                // Log error but continue with other files
+                log::warn!("{}", e);
+                failed.push(FailedItem {
+                    key: object_key.clone(),
+                    dest_key: None,
+                    error: e,
+                });
+                continue;
+            }
+        }
+
+        files_processed += 1;
+
+        // Emit progress
+        if let Some(sample) = rate.sample(bytes_downloaded, total_bytes) {
+            let _ = app.emit(
+                "folder-download-progress",
+                FolderDownloadProgress {
+                    download_id: download_id.clone(),
+                    files_processed,
+                    total_files,
+                    files_resumed: 0,
+                    bytes_downloaded,
+                    bytes_per_sec: sample.bytes_per_sec,
+                    eta_secs: sample.eta_secs,
+                },
+            );
+        }
+    }
+
+    transfer_state.finish(&download_id).await;
+
+    // Finalize ZIP
+    zip.finish()
+        .map_err(|e| AppError::InvalidInput(format!("Failed to finalize ZIP: {}", e)))?;
+
+    let final_path = zip_path.to_string_lossy().to_string();
+
+    // Failed files can be appended to the archive later; the download id doubles as batch id
+    let operation_batch_id = if failed.is_empty() {
+        None
+    } else {
+        let params = serde_json::to_string(&FolderDownloadRetryParams {
+            prefix: prefix.clone(),
+            zip_path: final_path.clone(),
+        })
+        .unwrap_or_else(|_| "{}".to_string());
+        save_failures(
+            &db,
+            &download_id,
+            BatchOperation::DownloadFolder,
+            &account_id,
+            &bucket,
+            &params,
+            &failed,
+        )
+    };
+
+    // Emit completed
+    let _ = app.emit(
+        "download-completed",
+        DownloadCompleted {
+            download_id,
+            key: prefix,
+            path: final_path.clone(),
+            operation_batch_id,
+        },
+    );
+
+    Ok(final_path)
+}
+
+/// Download a folder as plain files under `<destination>/<folder>/`, keeping
+/// the paths relative to `prefix`. Same as `download_folder` in `Directory` mode.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn download_folder_tree(
+    app: AppHandle,
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    transfer_state: State<'_, TransferState>,
+    account_id: String,
+    bucket: String,
+    prefix: String,
+    destination: String,
+    download_id: String,
+) -> Result<String, AppError> {
+    download_folder(
+        app,
+        credentials,
+        s3_clients,
+        db,
+        transfer_state,
+        account_id,
+        bucket,
+        prefix,
+        destination,
+        download_id,
+        Some(FolderDownloadMode::Directory),
+    )
+    .await
+}
+
+/// Download a folder's objects as files under `root`, recording each finished
+/// object in the manifest there. Objects the manifest already lists with the
+/// same size and ETag, whose file is still intact, are skipped. On cancellation
+/// or failure the downloaded files and manifest stay so a re-run resumes.
+async fn download_folder_to_tree(
+    app: &AppHandle,
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    prefix: &str,
+    objects: &[FolderObject],
+    root: &std::path::Path,
+    download_id: &str,
+    cancel_flag: &AtomicBool,
+) -> Result<(), AppError> {
+    std::fs::create_dir_all(root).map_err(|e| {
+        AppError::InvalidInput(format!("Failed to create '{}': {}", root.display(), e))
+    })?;
+
+    let manifest_path = root.join(FOLDER_DOWNLOAD_MANIFEST);
+    let finished: HashMap<String, FolderManifestEntry> = std::fs::read_to_string(&manifest_path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str::<FolderManifestEntry>(line).ok())
+        .map(|entry| (entry.key.clone(), entry))
+        .collect();
+    let mut manifest = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&manifest_path)
+        .map_err(|e| AppError::InvalidInput(format!("Failed to open download manifest: {}", e)))?;
+
+    let total_files = objects.len();
+    let total_bytes: u64 = objects.iter().map(|obj| obj.size as u64).sum();
+    let mut pending = Vec::new();
+    let mut bytes_downloaded = 0u64;
+
+    for object in objects {
+        let path = folder_object_path(root, prefix, &object.key)?;
+        let intact = finished.get(&object.key).map_or(false, |entry| {
+            entry.size == object.size
+                && entry.etag == object.etag
+                && std::fs::metadata(&path).map_or(false, |m| m.len() == object.size as u64)
+        });
+        if intact {
+            bytes_downloaded += object.size as u64;
+        } else {
+            pending.push((object, path));
+        }
+    }
+
+    let files_resumed = total_files - pending.len();
+    let mut files_processed = files_resumed;
+    let mut failed = 0usize;
+    let mut rate = TransferRate::resumed_from(bytes_downloaded);
+    let progress = |files_processed, bytes_downloaded, sample: Option<RateSample>| {
+        let _ = app.emit(
+            "folder-download-progress",
+            FolderDownloadProgress {
+                download_id: download_id.to_string(),
+                files_processed,
+                total_files,
+                files_resumed,
+                bytes_downloaded,
+                bytes_per_sec: sample.as_ref().map_or(0, |s| s.bytes_per_sec),
+                eta_secs: sample.and_then(|s| s.eta_secs),
+            },
+        );
+    };
+    progress(files_processed, bytes_downloaded, None);
+
+    for (object, path) in pending {
+        if cancel_flag.load(Ordering::Relaxed) {
+            return Err(AppError::Cancelled);
+        }
+
+        match download_object_to_file(client, bucket, &object.key, &path).await {
+            Ok(bytes) => {
+                let _ = app.emit(
+                    "folder-download-file",
+                    FolderDownloadFile {
+                        download_id: download_id.to_string(),
+                        key: object.key.clone(),
+                        path: path.to_string_lossy().to_string(),
+                        bytes,
+                    },
+                );
+                let entry = serde_json::to_string(&FolderManifestEntry {
+                    key: object.key.clone(),
+                    size: object.size,
+                    etag: object.etag.clone(),
+                })
+                .unwrap_or_default();
+                writeln!(manifest, "{}", entry).map_err(|e| {
+                    AppError::InvalidInput(format!("Failed to update download manifest: {}", e))
+                })?;
+                bytes_downloaded += bytes;
+                files_processed += 1;
+            }
+            Err(e) => {
+                log::warn!("{}", e);
+                failed += 1;
+                continue;
+            }
+        }
+
+        if let Some(sample) = rate.sample(bytes_downloaded, total_bytes) {
+            progress(files_processed, bytes_downloaded, Some(sample));
+        }
+    }
+
+    if failed > 0 {
+        return Err(AppError::S3(format!(
+            "{} of {} files failed to download; download the folder again to resume",
+            failed, total_files
+        )));
+    }
+    Ok(())
+}
+
+/// Local path for an object in a downloaded folder tree. Keys that would land
+/// outside `root` (absolute or `..` components) are refused.
+fn folder_object_path(
+    root: &std::path::Path,
+    prefix: &str,
+    key: &str,
+) -> Result<PathBuf, AppError> {
+    let relative = std::path::Path::new(key.strip_prefix(prefix).unwrap_or(key));
+    let escapes = relative
+        .components()
+        .any(|c| !matches!(c, std::path::Component::Normal(_)));
+    if escapes {
+        return Err(AppError::InvalidInput(format!(
+            "Cannot download '{}': its key is not a safe relative path",
+            key
+        )));
+    }
+    Ok(root.join(relative))
+}
+
+/// Stream one object into `path`, via a `.part` file so a half-written file is
+/// never mistaken for a finished one. Returns the bytes written.
+async fn download_object_to_file(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+    path: &std::path::Path,
+) -> Result<u64, String> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create directory for {}: {}", key, e))?;
+    }
+
+    let response = client
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download {}: {:?}", key, e))?;
+
+    let mut part_path = path.as_os_str().to_owned();
+    part_path.push(".part");
+    let part_path = PathBuf::from(part_path);
+
+    let mut file = tokio::fs::File::create(&part_path)
+        .await
+        .map_err(|e| format!("Failed to create file for {}: {}", key, e))?;
+    let mut body = response.body.into_async_read();
+    let bytes = tokio::io::copy(&mut body, &mut file)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", key, e))?;
+    drop(file);
+
+    tokio::fs::rename(&part_path, path)
+        .await
+        .map_err(|e| format!("Failed to move {} into place: {}", key, e))?;
+    Ok(bytes)
+}
+
+/// Build a ZIP from a fully downloaded folder tree
+fn zip_directory_tree(
+    root: &std::path::Path,
+    prefix: &str,
+    objects: &[FolderObject],
+    zip_path: &std::path::Path,
+) -> Result<(), AppError> {
+    let zip_file = std::fs::File::create(zip_path)
+        .map_err(|e| AppError::InvalidInput(format!("Failed to create ZIP file: {}", e)))?;
+    let mut zip = zip::ZipWriter::new(zip_file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .compression_level(Some(6));
+
+    for object in objects {
+        let relative_path = object.key.strip_prefix(prefix).unwrap_or(&object.key);
+        let mut source = std::fs::File::open(folder_object_path(root, prefix, &object.key)?)
+            .map_err(|e| {
+                AppError::InvalidInput(format!("Failed to read {}: {}", relative_path, e))
+            })?;
+        zip.start_file(relative_path, options).map_err(|e| {
+            AppError::InvalidInput(format!(
+                "Failed to start file in ZIP {}: {:?}",
+                relative_path, e
+            ))
+        })?;
+        std::io::copy(&mut source, &mut zip).map_err(|e| {
+            AppError::InvalidInput(format!("Failed to write to ZIP {}: {:?}", relative_path, e))
+        })?;
+    }
+
+    zip.finish()
+        .map_err(|e| AppError::InvalidInput(format!("Failed to finalize ZIP: {}", e)))?;
+    Ok(())
+}
+
+/// Parameters needed to append failed files to a folder download's ZIP
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FolderDownloadRetryParams {
+    prefix: String,
+    zip_path: String,
+}
+
+/// Download one object into a ZIP archive, returning the bytes written
+async fn add_object_to_zip<W: Write + std::io::Seek>(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    prefix: &str,
+    object_key: &str,
+    zip: &mut zip::ZipWriter<W>,
+    options: zip::write::SimpleFileOptions,
+) -> Result<u64, String> {
+    // Get the object from S3
+    let response = client
+        .get_object()
+        .bucket(bucket)
+        .key(object_key)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download {}: {:?}", object_key, e))?;
+
+    let body = response
+        .body
+        .collect()
+        .await
+        .map_err(|e| format!("Failed to read body for {}: {:?}", object_key, e))?
+        .into_bytes();
+
+    // Calculate path within ZIP (strip the prefix)
+    let relative_path = object_key.strip_prefix(prefix).unwrap_or(object_key);
+
+    // Add file to ZIP
+    zip.start_file(relative_path, options)
+        .map_err(|e| format!("Failed to start file in ZIP {}: {:?}", relative_path, e))?;
+    zip.write_all(&body)
+        .map_err(|e| format!("Failed to write to ZIP {}: {:?}", relative_path, e))?;
+
+    Ok(body.len() as u64)
+}
+
+/// Outcome of `retry_failed_items`, in the shape of the original operation's result
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "operation", content = "result", rename_all = "snake_case")]
+pub enum RetryFailedItemsResult {
+    Copy(CopyMoveResult),
+    Delete(DeleteResult),
+    /// Files that failed again stay stored under `operation_batch_id`
+    DownloadFolder(DownloadCompleted),
+}
+
+/// Get the S3 client for an account
+async fn account_client(
+    credentials: &CredentialsManager,
+    s3_clients: &S3ClientManager,
+    account_id: &str,
+) -> Result<Arc<aws_sdk_s3::Client>, AppError> {
+    let account = credentials.get_account(account_id)?;
+    let secret = credentials.get_secret_key(account_id)?;
+
+    s3_clients
+        .get_or_create_client(
+            account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await
+}
+
+/// Re-attempt only the items that failed in an earlier copy, delete or folder
+/// download. Items that fail again stay stored under the same batch id.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn retry_failed_items(
+    app: AppHandle,
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    listing_cache: State<'_, ListingCache>,
+    operation_batch_id: String,
+) -> Result<RetryFailedItemsResult, AppError> {
+    let start_time = Instant::now();
+    let batch = db
+        .get_failed_batch(&operation_batch_id)?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("No failed items for batch {}", operation_batch_id))
+        })?;
+
+    let client = account_client(&credentials, &s3_clients, &batch.account_id).await?;
+    let account_id = batch.account_id.as_str();
+    let bucket = batch.bucket.as_str();
+
+    match batch.operation {
+        BatchOperation::Copy => {
+            let params: CopyRetryParams = serde_json::from_str(&batch.params)
+                .map_err(|e| AppError::Storage(format!("Invalid batch parameters: {}", e)))?;
+
+            // Items without a destination are folders that failed to list
+            let (mut pairs, mut folders) = (Vec::new(), Vec::new());
+            for item in batch.items {
+                match item.dest_key {
+                    Some(dest_key) => pairs.push((item.key, dest_key)),
+                    None => folders.push(item.key),
                 }
             }
-        } else {
-            // For single files
-            let file_name = source_key.split('/').last().unwrap_or(source_key);
-            let dest_key = format!("{}{}", destination_prefix, file_name);
-
-            let result = if same_account {
-                // Same account: use S3 copy
-                let copy_source = format!(
-                    "{}/{}",
-                    source_bucket,
-                    urlencoding::encode(source_key)
-                );
-                dest_client
-                    .copy_object()
-                    .bucket(&dest_bucket)
-                    .key(&dest_key)
-                    .copy_source(&copy_source)
-                    .send()
-                    .await
-                    .map(|_| ())
-                    .map_err(|e| format!("{:?}", e))
-            } else {
-                // Different accounts: download and upload
-                copy_via_download_upload(
-                    &source_client,
+            let (folder_pairs, errors) =
+                expand_copy_sources(&client, bucket, &folders, &params.destination_prefix).await;
+            pairs.extend(folder_pairs);
+
+            let never_cancelled = AtomicBool::new(false);
+            let retry = RetryPolicy::from_settings(&db.get_app_settings()?);
+            let dest_account_id = params.dest_account_id.as_deref().unwrap_or(account_id);
+            let dest_bucket = params.dest_bucket.as_deref().unwrap_or(bucket);
+            let mut result = if params.dest_bucket.is_some() {
+                let dest_client = account_client(&credentials, &s3_clients, dest_account_id).await?;
+                copy_pairs_across_buckets(
+                    &app,
+                    &client,
+                    bucket,
                     &dest_client,
-                    &source_bucket,
-                    &dest_bucket,
-                    source_key,
-                    &dest_key,
+                    dest_bucket,
+                    dest_account_id == account_id,
+                    &pairs,
+                    params.delete_source,
+                    params.verify_before_delete,
+                    None,
+                    &never_cancelled,
+                    errors,
+                    retry,
+                )
+                .await
+            } else {
+                copy_pairs_in_bucket(
+                    &app,
+                    &client,
+                    bucket,
+                    &pairs,
+                    params.source_version_id.as_deref(),
+                    params.delete_source,
+                    params.verify_before_delete,
+                    None,
+                    &never_cancelled,
+                    errors,
+                    retry,
                 )
                 .await
             };
 
-            match result {
-                Ok(_) => {
-                    objects_copied += 1;
+            let dest_keys: Vec<String> = pairs.iter().map(|(_, dest)| dest.clone()).collect();
+            cache::invalidate_for(
+                &listing_cache,
+                &db,
+                dest_account_id,
+                dest_bucket,
+                &[],
+                &dest_keys,
+            );
+            if !result.deleted_keys.is_empty() {
+                cache::invalidate_for(
+                    &listing_cache,
+                    &db,
+                    account_id,
+                    bucket,
+                    &[],
+                    &result.deleted_keys,
+                );
+            }
 
-                    // Delete source if moving
-                    if delete_source {
-                        match source_client
-                            .delete_object()
-                            .bucket(&source_bucket)
-                            .key(source_key)
-                            .send()
-                            .await
-                        {
-                            Ok(_) => objects_deleted += 1,
-                            Err(e) => errors.push(CopyMoveError {
-                                source_key: source_key.clone(),
-                                error: format!("Failed to delete: {:?}", e),
-                            }),
-                        }
-                    }
-                }
-                Err(e) => {
-                    errors.push(CopyMoveError {
-                        source_key: source_key.clone(),
-                        error: format!("Failed to copy: {}", e),
+            let operation_type = if params.delete_source {
+                OperationType::Move
+            } else {
+                OperationType::Copy
+            };
+            let _ = db.log_completed_operation(
+                account_id,
+                bucket,
+                operation_type,
+                None,
+                Some(&params.destination_prefix),
+                None,
+                start_time.elapsed().as_millis() as i64,
+                (!result.errors.is_empty()).then_some("Copy/move failed"),
+            );
+
+            result.operation_batch_id = save_copy_failures(
+                &db,
+                Some(operation_batch_id),
+                account_id,
+                bucket,
+                &params,
+                &pairs,
+                &result.errors,
+            );
+            Ok(RetryFailedItemsResult::Copy(result))
+        }
+        BatchOperation::Delete => {
+            let keys: Vec<String> = batch.items.into_iter().map(|item| item.key).collect();
+            let mut result = delete_or_trash_keys(
+                &client,
+                &db,
+                account_id,
+                bucket,
+                keys.clone(),
+                start_time,
+                |_| {},
+            )
+            .await?;
+
+            let trash_prefixes = if result.trashed > 0 {
+                vec![db.get_app_settings()?.trash_prefix()]
+            } else {
+                vec![]
+            };
+            cache::invalidate_for(&listing_cache, &db, account_id, bucket, &trash_prefixes, &keys);
+            result.operation_batch_id = save_delete_failures(
+                &db,
+                Some(operation_batch_id),
+                account_id,
+                bucket,
+                &result.errors,
+            );
+            Ok(RetryFailedItemsResult::Delete(result))
+        }
+        BatchOperation::DownloadFolder => {
+            let params: FolderDownloadRetryParams = serde_json::from_str(&batch.params)
+                .map_err(|e| AppError::Storage(format!("Invalid batch parameters: {}", e)))?;
+
+            let zip_file = std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(&params.zip_path)
+                .map_err(|e| AppError::InvalidInput(format!("Failed to open ZIP file: {}", e)))?;
+            let mut zip = zip::ZipWriter::new_append(zip_file)
+                .map_err(|e| AppError::InvalidInput(format!("Failed to open ZIP file: {}", e)))?;
+
+            let options = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated)
+                .compression_level(Some(6));
+
+            let mut errors: Vec<FailedItem> = Vec::new();
+            for item in batch.items {
+                let added =
+                    add_object_to_zip(&client, bucket, &params.prefix, &item.key, &mut zip, options)
+                        .await;
+                if let Err(e) = added {
+                    errors.push(FailedItem {
+                        key: item.key,
+                        dest_key: None,
+                        error: e,
                     });
                 }
             }
+
+            zip.finish()
+                .map_err(|e| AppError::InvalidInput(format!("Failed to finalize ZIP: {}", e)))?;
+
+            let remaining_batch_id = save_failures(
+                &db,
+                &operation_batch_id,
+                BatchOperation::DownloadFolder,
+                account_id,
+                bucket,
+                &batch.params,
+                &errors,
+            );
+            Ok(RetryFailedItemsResult::DownloadFolder(DownloadCompleted {
+                download_id: operation_batch_id,
+                key: params.prefix,
+                path: params.zip_path,
+                operation_batch_id: remaining_batch_id,
+            }))
         }
     }
+}
 
-    let duration_ms = start_time.elapsed().as_millis() as i64;
+/// Outcome of `replay_operation`, in the shape of the replayed command's result
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "operation", content = "result", rename_all = "snake_case")]
+pub enum ReplayOperationResult {
+    Copy(CopyMoveResult),
+    /// Key of the folder marker
+    CreateFolder(String),
+    /// Path of the downloaded file
+    Download(String),
+    Delete(DeleteResult),
+}
 
-    // Log copy/move operations to history (for both source and dest buckets)
-    let operation_type = if delete_source {
-        OperationType::Move
-    } else {
-        OperationType::Copy
+/// Run a past operation from the history again. Copies, folder creations and
+/// downloads can be replayed; a download needs a `destination` folder, since
+/// the original one isn't recorded. Replaying a delete removes the object
+/// again and is refused unless `confirm` is set. Other operations (moves,
+/// renames, uploads, ...) can't be reproduced from their record and are
+/// rejected. The replay is logged as a new operation.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn replay_operation(
+    app: AppHandle,
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    copy_state: State<'_, CopyState>,
+    transfer_state: State<'_, TransferState>,
+    listing_cache: State<'_, ListingCache>,
+    operation_id: i64,
+    destination: Option<String>,
+    transfer_id: Option<String>,
+    confirm: Option<bool>,
+) -> Result<ReplayOperationResult, AppError> {
+    let operation = db
+        .get_operation(operation_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Operation {} not found", operation_id)))?;
+
+    if matches!(
+        operation.status,
+        OperationStatus::Pending | OperationStatus::InProgress
+    ) {
+        return Err(AppError::InvalidInput(format!(
+            "Operation {} is still in progress",
+            operation_id
+        )));
+    }
+    let source_key = operation.source_key.ok_or_else(|| {
+        AppError::InvalidInput(format!("Operation {} has no recorded key", operation_id))
+    })?;
+    let account_id = operation.account_id;
+    let bucket = operation.bucket;
+
+    match operation.operation {
+        OperationType::Copy => {
+            if operation
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get("restore"))
+                .is_some()
+            {
+                return Err(AppError::InvalidInput(
+                    "Version restores can't be replayed; restore the version again instead".into(),
+                ));
+            }
+            let destination = operation
+                .metadata
+                .and_then(|m| serde_json::from_value::<CopyDestinationRecord>(m).ok())
+                .ok_or_else(|| {
+                    AppError::InvalidInput(
+                        "This copy was recorded without its destination and can't be replayed"
+                            .into(),
+                    )
+                })?;
+
+            let result =
+                if destination.dest_account_id == account_id && destination.dest_bucket == bucket {
+                    copy_objects(
+                        app,
+                        credentials,
+                        s3_clients,
+                        db,
+                        copy_state,
+                        listing_cache,
+                        account_id,
+                        bucket,
+                        vec![source_key],
+                        destination.destination_prefix,
+                        false,
+                        None,
+                        None,
+                        None,
+                    )
+                    .await?
+                } else {
+                    copy_objects_across_buckets(
+                        app,
+                        credentials,
+                        s3_clients,
+                        db,
+                        listing_cache,
+                        copy_state,
+                        account_id,
+                        bucket,
+                        destination.dest_account_id,
+                        destination.dest_bucket,
+                        vec![source_key],
+                        destination.destination_prefix,
+                        false,
+                        None,
+                        None,
+                    )
+                    .await?
+                };
+            Ok(ReplayOperationResult::Copy(result))
+        }
+        OperationType::CreateFolder => {
+            let trimmed = source_key.trim_end_matches('/');
+            let (prefix, folder_name) = match trimmed.rsplit_once('/') {
+                Some((parent, name)) => (format!("{}/", parent), name.to_string()),
+                None => (String::new(), trimmed.to_string()),
+            };
+            let key = create_folder(
+                credentials,
+                s3_clients,
+                db,
+                listing_cache,
+                account_id,
+                bucket,
+                prefix,
+                folder_name,
+            )
+            .await?;
+            Ok(ReplayOperationResult::CreateFolder(key))
+        }
+        OperationType::Download => {
+            let destination = destination.ok_or_else(|| {
+                AppError::InvalidInput("Replaying a download needs a destination folder".into())
+            })?;
+            let path = download_object(
+                app,
+                credentials,
+                s3_clients,
+                db,
+                transfer_state,
+                account_id,
+                bucket,
+                source_key,
+                destination,
+                transfer_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            )
+            .await?;
+            Ok(ReplayOperationResult::Download(path))
+        }
+        OperationType::Delete => {
+            if !confirm.unwrap_or(false) {
+                return Err(AppError::InvalidInput(format!(
+                    "Replaying this delete removes '{}' again; confirm to proceed",
+                    source_key
+                )));
+            }
+            let result = delete_objects(
+                credentials,
+                s3_clients,
+                db,
+                listing_cache,
+                account_id,
+                bucket,
+                vec![source_key],
+            )
+            .await?;
+            Ok(ReplayOperationResult::Delete(result))
+        }
+        other => Err(AppError::InvalidInput(format!(
+            "'{}' operations can't be replayed",
+            other
+        ))),
+    }
+}
+
+/// Largest page S3 returns for ListObjectVersions
+const MAX_VERSIONS_PAGE: i32 = 1000;
+
+/// List one page of versions of a specific object, or of every object under a
+/// prefix when `as_prefix` is set. Pass the returned markers back to continue.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_object_versions(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    account_id: String,
+    bucket: String,
+    key: String,
+    key_marker: Option<String>,
+    version_id_marker: Option<String>,
+    max_keys: Option<i32>,
+    include_delete_markers: Option<bool>,
+    as_prefix: Option<bool>,
+) -> Result<ListVersionsResponse, AppError> {
+    let include_delete_markers = include_delete_markers.unwrap_or(true);
+    let as_prefix = as_prefix.unwrap_or(false);
+    let max_keys = max_keys.unwrap_or(100).clamp(1, MAX_VERSIONS_PAGE);
+
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    // Check if versioning is enabled
+    let versioning_enabled = match client.get_bucket_versioning().bucket(&bucket).send().await {
+        Ok(resp) => matches!(
+            resp.status(),
+            Some(aws_sdk_s3::types::BucketVersioningStatus::Enabled)
+        ),
+        Err(_) => false, // R2 may not support this, treat as no versioning
     };
 
-    for source_key in &source_keys {
-        let has_error = errors.iter().any(|e| &e.source_key == source_key);
-        // Log for source bucket
-        let _ = db.log_completed_operation(
-            &source_account_id,
-            &source_bucket,
-            operation_type.clone(),
-            Some(source_key),
-            Some(&format!("{}/{}", dest_bucket, destination_prefix)),
-            None,
-            duration_ms / source_keys.len().max(1) as i64,
-            if has_error { Some("Copy/move failed") } else { None },
-        );
+    // Build the list_object_versions request
+    let mut request = client
+        .list_object_versions()
+        .bucket(&bucket)
+        .prefix(&key)
+        .max_keys(max_keys);
+
+    if let Some(km) = key_marker {
+        request = request.key_marker(km);
     }
 
-    Ok(CopyMoveResult {
-        objects_copied,
-        objects_deleted,
-        errors,
-    })
-}
+    if let Some(vim) = version_id_marker {
+        request = request.version_id_marker(vim);
+    }
 
-/// Helper function to copy an object by downloading from source and uploading to destination
-async fn copy_via_download_upload(
-    source_client: &aws_sdk_s3::Client,
-    dest_client: &aws_sdk_s3::Client,
-    source_bucket: &str,
-    dest_bucket: &str,
-    source_key: &str,
-    dest_key: &str,
-) -> Result<(), String> {
-    // Download from source
-    let response = source_client
-        .get_object()
-        .bucket(source_bucket)
-        .key(source_key)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to download: {:?}", e))?;
+    let response = match request.send().await {
+        Ok(r) => r,
+        // An unsupported operation (e.g. R2) means there are no versions to show
+        Err(e) => match AppError::from(e) {
+            AppError::NotImplemented(_) => {
+                return Ok(ListVersionsResponse {
+                    key,
+                    versions: vec![],
+                    key_marker: None,
+                    version_id_marker: None,
+                    is_truncated: false,
+                    versioning_enabled: false,
+                });
+            }
+            e => return Err(e),
+        },
+    };
 
-    let content_type = response
-        .content_type()
-        .map(|s| s.to_string())
-        .unwrap_or_else(|| "application/octet-stream".to_string());
+    let mut versions: Vec<ObjectVersionInfo> = Vec::new();
 
-    let body = response
-        .body
-        .collect()
-        .await
-        .map_err(|e| format!("Failed to read body: {:?}", e))?;
+    // Without prefix mode, keys that merely start with `key` are skipped
+    let wanted = |k: &str| as_prefix || k == key;
 
-    // Upload to destination
-    dest_client
-        .put_object()
-        .bucket(dest_bucket)
-        .key(dest_key)
-        .body(aws_sdk_s3::primitives::ByteStream::from(body.into_bytes()))
-        .content_type(&content_type)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to upload: {:?}", e))?;
+    // Process actual versions
+    for version in response.versions() {
+        if let Some(version_key) = version.key().filter(|k| wanted(k)) {
+            versions.push(ObjectVersionInfo {
+                key: version_key.to_string(),
+                version_id: version.version_id().unwrap_or("null").to_string(),
+                is_latest: version.is_latest().unwrap_or(false),
+                is_delete_marker: false,
+                last_modified: version.last_modified().map(|d| d.to_string()),
+                size: version.size(),
+                etag: version.e_tag().map(|e| e.trim_matches('"').to_string()),
+                storage_class: version.storage_class().map(|s| s.as_str().to_string()),
+            });
+        }
+    }
 
-    Ok(())
-}
+    // Process delete markers
+    for marker in response
+        .delete_markers()
+        .iter()
+        .filter(|_| include_delete_markers)
+    {
+        if let Some(marker_key) = marker.key().filter(|k| wanted(k)) {
+            versions.push(ObjectVersionInfo {
+                key: marker_key.to_string(),
+                version_id: marker.version_id().unwrap_or("null").to_string(),
+                is_latest: marker.is_latest().unwrap_or(false),
+                is_delete_marker: true,
+                last_modified: marker.last_modified().map(|d| d.to_string()),
+                size: None,
+                etag: None,
+                storage_class: None,
+            });
+        }
+    }
 
-// Folder download event types
-#[derive(Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct FolderDownloadProgress {
-    pub download_id: String,
-    pub files_processed: usize,
-    pub total_files: usize,
-    pub bytes_downloaded: u64,
+    // Group by key, newest version first within each key
+    versions.sort_by(|a, b| {
+        a.key
+            .cmp(&b.key)
+            .then_with(|| b.last_modified.as_ref().cmp(&a.last_modified.as_ref()))
+    });
+
+    Ok(ListVersionsResponse {
+        key,
+        versions,
+        key_marker: response.next_key_marker().map(|s| s.to_string()),
+        version_id_marker: response.next_version_id_marker().map(|s| s.to_string()),
+        is_truncated: response.is_truncated().unwrap_or(false),
+        versioning_enabled,
+    })
 }
 
-/// Update object metadata using copy-in-place with REPLACE directive
+/// Restore a previous version, either over the key itself (`Overwrite`) or
+/// into `destination_key` (`CopyTo`), which must not already exist
 #[tauri::command(rename_all = "camelCase")]
-pub async fn update_object_metadata(
+pub async fn restore_object_version(
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
+    listing_cache: State<'_, ListingCache>,
+    db: State<'_, DbManager>,
     account_id: String,
     bucket: String,
     key: String,
-    content_type: Option<String>,
-    cache_control: Option<String>,
-    content_disposition: Option<String>,
-    content_encoding: Option<String>,
-    custom_metadata: Option<std::collections::HashMap<String, String>>,
-) -> Result<ObjectMetadata, AppError> {
+    version_id: String,
+    mode: Option<RestoreVersionMode>,
+    destination_key: Option<String>,
+) -> Result<RestoreVersionResult, AppError> {
+    let start_time = Instant::now();
+    let mode = mode.unwrap_or_default();
+    let destination_key = match mode {
+        RestoreVersionMode::Overwrite => key.clone(),
+        RestoreVersionMode::CopyTo => match destination_key {
+            Some(dest) if !dest.is_empty() && dest != key => dest,
+            _ => {
+                return Err(AppError::InvalidInput(
+                    "Restoring as a copy needs a destination key different from the source"
+                        .to_string(),
+                ))
+            }
+        },
+    };
+
     let account = credentials.get_account(&account_id)?;
     let secret = credentials.get_secret_key(&account_id)?;
 
@@ -1853,108 +6995,238 @@ pub async fn update_object_metadata(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    // First, get current metadata to preserve any fields not being updated
-    let current = client.head_object().bucket(&bucket).key(&key).send().await?;
+    // Make sure the version exists before copying anything
+    let head = client
+        .head_object()
+        .bucket(&bucket)
+        .key(&key)
+        .version_id(&version_id)
+        .send()
+        .await
+        .map_err(|e| {
+            if e.as_service_error().map(|se| se.is_not_found()) == Some(true) {
+                AppError::NotFound(format!("Version {} of '{}' does not exist", version_id, key))
+            } else {
+                AppError::from(e)
+            }
+        })?;
 
-    // Use copy-in-place with REPLACE metadata directive
-    let copy_source = format!("{}/{}", bucket, urlencoding::encode(&key));
+    if mode == RestoreVersionMode::CopyTo
+        && destination_exists(&client, &bucket, &destination_key).await?
+    {
+        return Err(AppError::InvalidInput(format!(
+            "'{}' already exists; choose another destination key",
+            destination_key
+        )));
+    }
 
-    let mut copy_request = client
+    let copy_source = copy_source(&bucket, &key, Some(&version_id));
+
+    let result = client
         .copy_object()
         .bucket(&bucket)
-        .key(&key)
+        .key(&destination_key)
         .copy_source(&copy_source)
-        .metadata_directive(aws_sdk_s3::types::MetadataDirective::Replace);
+        .send()
+        .await
+        .map_err(AppError::from);
 
-    // Set content type (use provided or keep existing)
-    if let Some(ct) = content_type {
-        copy_request = copy_request.content_type(ct);
-    } else if let Some(ct) = current.content_type() {
-        copy_request = copy_request.content_type(ct);
+    let status = if result.is_ok() {
+        OperationStatus::Completed
+    } else {
+        OperationStatus::Failed
+    };
+    let logged = db.log_operation(&NewOperation {
+        account_id: account_id.clone(),
+        bucket: bucket.clone(),
+        operation: OperationType::Copy,
+        source_key: Some(key.clone()),
+        dest_key: Some(destination_key.clone()),
+        size: head.content_length(),
+        status: status.clone(),
+        metadata: Some(serde_json::json!({
+            "restore": true,
+            "mode": mode,
+            "sourceVersionId": version_id,
+        })),
+    });
+    if let Ok(operation_id) = logged {
+        let error = result.as_ref().err().map(|e| e.to_string());
+        let _ = db.update_operation_status(
+            operation_id,
+            status,
+            Some(start_time.elapsed().as_millis() as i64),
+            error.as_deref(),
+        );
     }
+    let response = result?;
 
-    // Set cache control
-    if let Some(cc) = cache_control {
-        if !cc.is_empty() {
-            copy_request = copy_request.cache_control(cc);
+    cache::invalidate_for(
+        &listing_cache,
+        &db,
+        &account_id,
+        &bucket,
+        &[],
+        std::slice::from_ref(&destination_key),
+    );
+
+    Ok(RestoreVersionResult {
+        key,
+        restored_version_id: version_id,
+        destination_key,
+        new_version_id: response.version_id().map(|s| s.to_string()),
+    })
+}
+
+// Purge types
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgePrefixResult {
+    pub prefix: String,
+    /// Object versions and delete markers removed
+    pub objects_deleted: usize,
+    pub delete_markers_deleted: usize,
+    pub bytes_reclaimed: i64,
+    pub errors: Vec<DeleteError>,
+}
+
+/// A single version (or delete marker) queued for permanent deletion
+struct PurgeEntry {
+    key: String,
+    version_id: Option<String>,
+    size: i64,
+    is_delete_marker: bool,
+}
+
+/// Collect every version and delete marker under a prefix. Falls back to a plain
+/// listing for providers that don't implement version listing (e.g. R2).
+async fn collect_purge_entries(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    prefix: &str,
+) -> Result<Vec<PurgeEntry>, AppError> {
+    let mut entries = Vec::new();
+    let mut key_marker: Option<String> = None;
+    let mut version_id_marker: Option<String> = None;
+
+    loop {
+        let mut request = client.list_object_versions().bucket(bucket).prefix(prefix);
+
+        if let Some(km) = &key_marker {
+            request = request.key_marker(km);
+        }
+        if let Some(vim) = &version_id_marker {
+            request = request.version_id_marker(vim);
         }
-    } else if let Some(cc) = current.cache_control() {
-        copy_request = copy_request.cache_control(cc);
-    }
 
-    // Set content disposition
-    if let Some(cd) = content_disposition {
-        if !cd.is_empty() {
-            copy_request = copy_request.content_disposition(cd);
+        let response = match request.send().await {
+            Ok(r) => r,
+            Err(e) => match AppError::from(e) {
+                AppError::NotImplemented(_) if entries.is_empty() => {
+                    return collect_unversioned_purge_entries(client, bucket, prefix).await;
+                }
+                e => return Err(e),
+            },
+        };
+
+        for version in response.versions() {
+            if let Some(key) = version.key() {
+                entries.push(PurgeEntry {
+                    key: key.to_string(),
+                    version_id: version.version_id().map(|s| s.to_string()),
+                    size: version.size().unwrap_or(0),
+                    is_delete_marker: false,
+                });
+            }
         }
-    } else if let Some(cd) = current.content_disposition() {
-        copy_request = copy_request.content_disposition(cd);
-    }
 
-    // Set content encoding
-    if let Some(ce) = content_encoding {
-        if !ce.is_empty() {
-            copy_request = copy_request.content_encoding(ce);
+        for marker in response.delete_markers() {
+            if let Some(key) = marker.key() {
+                entries.push(PurgeEntry {
+                    key: key.to_string(),
+                    version_id: marker.version_id().map(|s| s.to_string()),
+                    size: 0,
+                    is_delete_marker: true,
+                });
+            }
         }
-    } else if let Some(ce) = current.content_encoding() {
-        copy_request = copy_request.content_encoding(ce);
-    }
 
-    // Set custom metadata
-    if let Some(meta) = custom_metadata {
-        copy_request = copy_request.set_metadata(Some(meta));
-    } else if let Some(meta) = current.metadata() {
-        let meta_map: std::collections::HashMap<String, String> = meta
-            .iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect();
-        copy_request = copy_request.set_metadata(Some(meta_map));
+        if response.is_truncated() == Some(true) {
+            key_marker = response.next_key_marker().map(|s| s.to_string());
+            version_id_marker = response.next_version_id_marker().map(|s| s.to_string());
+        } else {
+            break;
+        }
     }
 
-    // Execute the copy
-    copy_request
-        .send()
-        .await
-        .map_err(|e| AppError::S3(format!("Failed to update metadata: {:?}", e)))?;
+    Ok(entries)
+}
 
-    // Fetch and return the updated metadata
-    let updated = client.head_object().bucket(&bucket).key(&key).send().await?;
+async fn collect_unversioned_purge_entries(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    prefix: &str,
+) -> Result<Vec<PurgeEntry>, AppError> {
+    let mut entries = Vec::new();
+    let mut continuation_token: Option<String> = None;
 
-    let metadata = updated.metadata().map(|m| {
-        m.iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect::<std::collections::HashMap<String, String>>()
-    });
+    loop {
+        let mut request = client.list_objects_v2().bucket(bucket).prefix(prefix);
 
-    Ok(ObjectMetadata {
-        key: key.clone(),
-        size: updated.content_length().unwrap_or(0),
-        content_type: updated.content_type().map(|s| s.to_string()),
-        last_modified: updated.last_modified().map(|d| d.to_string()),
-        etag: updated.e_tag().map(|e| e.trim_matches('"').to_string()),
-        storage_class: updated.storage_class().map(|s| s.as_str().to_string()),
-        content_encoding: updated.content_encoding().map(|s| s.to_string()),
-        cache_control: updated.cache_control().map(|s| s.to_string()),
-        version_id: updated.version_id().map(|s| s.to_string()),
-        metadata,
-    })
+        if let Some(token) = &continuation_token {
+            request = request.continuation_token(token);
+        }
+
+        let response = request.send().await?;
+
+        for obj in response.contents() {
+            if let Some(key) = obj.key() {
+                entries.push(PurgeEntry {
+                    key: key.to_string(),
+                    version_id: None,
+                    size: obj.size().unwrap_or(0),
+                    is_delete_marker: false,
+                });
+            }
+        }
+
+        if response.is_truncated() == Some(true) {
+            continuation_token = response.next_continuation_token().map(|s| s.to_string());
+        } else {
+            break;
+        }
+    }
+
+    Ok(entries)
 }
 
-/// Download a folder as a ZIP file
+/// Permanently delete every version and delete marker under a prefix.
+/// This cannot be undone, so `confirm_permanent` must be set explicitly.
 #[tauri::command(rename_all = "camelCase")]
-pub async fn download_folder(
-    app: AppHandle,
+pub async fn purge_prefix(
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    listing_cache: State<'_, ListingCache>,
     account_id: String,
     bucket: String,
     prefix: String,
-    destination: String,
-    download_id: String,
-) -> Result<String, AppError> {
+    confirm_permanent: bool,
+) -> Result<PurgePrefixResult, AppError> {
+    if !confirm_permanent {
+        return Err(AppError::InvalidInput(
+            "Purging permanently deletes all versions; confirmation is required".into(),
+        ));
+    }
+    if prefix.is_empty() {
+        return Err(AppError::InvalidInput("Prefix cannot be empty".into()));
+    }
+
+    let start_time = Instant::now();
     let account = credentials.get_account(&account_id)?;
     let secret = credentials.get_secret_key(&account_id)?;
 
@@ -1966,145 +7238,242 @@ pub async fn download_folder(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    // List all objects with this prefix
-    let mut all_objects: Vec<(String, i64)> = Vec::new();
-    let mut continuation_token: Option<String> = None;
+    let entries = collect_purge_entries(&client, &bucket, &prefix).await?;
 
-    loop {
-        let mut request = client.list_objects_v2().bucket(&bucket).prefix(&prefix);
+    let mut objects_deleted = 0;
+    let mut delete_markers_deleted = 0;
+    let mut bytes_reclaimed: i64 = 0;
+    let mut errors: Vec<DeleteError> = Vec::new();
 
-        if let Some(token) = &continuation_token {
-            request = request.continuation_token(token);
-        }
+    // S3 delete_objects can handle up to 1000 objects per call
+    for chunk in entries.chunks(1000) {
+        let objects_to_delete: Vec<ObjectIdentifier> = chunk
+            .iter()
+            .filter_map(|entry| {
+                ObjectIdentifier::builder()
+                    .key(&entry.key)
+                    .set_version_id(entry.version_id.clone())
+                    .build()
+                    .ok()
+            })
+            .collect();
 
-        let response = request.send().await?;
+        let delete = aws_sdk_s3::types::Delete::builder()
+            .set_objects(Some(objects_to_delete))
+            .build()
+            .map_err(|e| AppError::S3(format!("Failed to build delete request: {:?}", e)))?;
 
-        for obj in response.contents() {
-            if let Some(key) = obj.key() {
-                // Skip folder markers (keys ending with /)
-                if !key.ends_with('/') {
-                    all_objects.push((key.to_string(), obj.size().unwrap_or(0)));
+        let response = match client
+            .delete_objects()
+            .bucket(&bucket)
+            .delete(delete)
+            .send()
+            .await
+        {
+            Ok(r) => r,
+            Err(e) => {
+                let error = format!("Failed to delete batch: {:?}", e);
+                errors.extend(chunk.iter().map(|entry| DeleteError {
+                    key: entry.key.clone(),
+                    error: error.clone(),
+                }));
+                continue;
+            }
+        };
+
+        for deleted in response.deleted() {
+            let matched = chunk.iter().find(|entry| {
+                Some(entry.key.as_str()) == deleted.key()
+                    && entry.version_id.as_deref() == deleted.version_id()
+            });
+            objects_deleted += 1;
+            if let Some(entry) = matched {
+                bytes_reclaimed += entry.size;
+                if entry.is_delete_marker {
+                    delete_markers_deleted += 1;
                 }
             }
         }
 
-        if response.is_truncated() == Some(true) {
-            continuation_token = response.next_continuation_token().map(|s| s.to_string());
-        } else {
-            break;
+        for err in response.errors() {
+            errors.push(DeleteError {
+                key: err.key().unwrap_or_default().to_string(),
+                error: err.message().unwrap_or_default().to_string(),
+            });
         }
     }
 
-    if all_objects.is_empty() {
-        return Err(AppError::InvalidInput("Folder is empty".into()));
-    }
-
-    let total_files = all_objects.len();
-
-    // Create ZIP file name from folder name
-    let folder_name = prefix
-        .trim_end_matches('/')
-        .split('/')
-        .last()
-        .unwrap_or("folder");
-    let zip_filename = format!("{}.zip", folder_name);
-    let zip_path = PathBuf::from(&destination).join(&zip_filename);
+    let duration_ms = start_time.elapsed().as_millis() as i64;
+    let error_msg = if errors.is_empty() {
+        None
+    } else {
+        Some(format!("{} versions failed to delete", errors.len()))
+    };
+    cache::invalidate_for(&listing_cache, &db, &account_id, &bucket, &[prefix.clone()], &[]);
 
-    // Create the ZIP file
-    let zip_file = std::fs::File::create(&zip_path)
-        .map_err(|e| AppError::InvalidInput(format!("Failed to create ZIP file: {}", e)))?;
-    let mut zip = zip::ZipWriter::new(zip_file);
+    let _ = db.log_completed_operation(
+        &account_id,
+        &bucket,
+        OperationType::Purge,
+        Some(&prefix),
+        None,
+        Some(bytes_reclaimed),
+        duration_ms,
+        error_msg.as_deref(),
+    );
 
-    let options = zip::write::SimpleFileOptions::default()
-        .compression_method(zip::CompressionMethod::Deflated)
-        .compression_level(Some(6));
+    Ok(PurgePrefixResult {
+        prefix,
+        objects_deleted,
+        delete_markers_deleted,
+        bytes_reclaimed,
+        errors,
+    })
+}
 
-    let mut files_processed = 0usize;
-    let mut bytes_downloaded = 0u64;
+// Object tagging types
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectTag {
+    pub key: String,
+    pub value: String,
+}
 
-    for (object_key, _size) in &all_objects {
-        // Get the object from S3
-        let response = match client.get_object().bucket(&bucket).key(object_key).send().await {
-            Ok(r) => r,
-            Err(e) => {
-                // Log error but continue with other files
-                log::warn!("Failed to download {}: {:?}", object_key, e);
-                continue;
-            }
-        };
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectTagsResponse {
+    pub object_key: String,
+    pub tags: Vec<ObjectTag>,
+}
 
-        let body = match response.body.collect().await {
-            Ok(b) => b.into_bytes(),
-            Err(e) => {
-                log::warn!("Failed to read body for {}: {:?}", object_key, e);
-                continue;
-            }
-        };
+/// Get tags for an object
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_object_tagging(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    account_id: String,
+    bucket: String,
+    key: String,
+) -> Result<ObjectTagsResponse, AppError> {
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
 
-        bytes_downloaded += body.len() as u64;
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
 
-        // Calculate path within ZIP (strip the prefix)
-        let relative_path = object_key.strip_prefix(&prefix).unwrap_or(object_key);
+    let response = match client
+        .get_object_tagging()
+        .bucket(&bucket)
+        .key(&key)
+        .send()
+        .await
+    {
+        Ok(r) => r,
+        // Providers without tagging support have no tags to show
+        Err(e) => match AppError::from(e) {
+            AppError::NotImplemented(_) => {
+                return Ok(ObjectTagsResponse {
+                    object_key: key,
+                    tags: vec![],
+                });
+            }
+            e => return Err(e),
+        },
+    };
 
-        // Add file to ZIP
-        if let Err(e) = zip.start_file(relative_path, options) {
-            log::warn!("Failed to start file in ZIP {}: {:?}", relative_path, e);
-            continue;
-        }
+    let tags: Vec<ObjectTag> = response
+        .tag_set()
+        .iter()
+        .map(|tag| ObjectTag {
+            key: tag.key().to_string(),
+            value: tag.value().to_string(),
+        })
+        .collect();
 
-        if let Err(e) = zip.write_all(&body) {
-            log::warn!("Failed to write to ZIP {}: {:?}", relative_path, e);
-            continue;
-        }
+    Ok(ObjectTagsResponse {
+        object_key: key,
+        tags,
+    })
+}
 
-        files_processed += 1;
+/// Set tags for an object (replaces all existing tags)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn put_object_tagging(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    account_id: String,
+    bucket: String,
+    key: String,
+    tags: Vec<ObjectTag>,
+) -> Result<ObjectTagsResponse, AppError> {
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
 
-        // Emit progress
-        let _ = app.emit(
-            "folder-download-progress",
-            FolderDownloadProgress {
-                download_id: download_id.clone(),
-                files_processed,
-                total_files,
-                bytes_downloaded,
-            },
-        );
-    }
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
 
-    // Finalize ZIP
-    zip.finish()
-        .map_err(|e| AppError::InvalidInput(format!("Failed to finalize ZIP: {}", e)))?;
+    // Build the tag set
+    let s3_tags: Vec<aws_sdk_s3::types::Tag> = tags
+        .iter()
+        .filter_map(|tag| {
+            aws_sdk_s3::types::Tag::builder()
+                .key(&tag.key)
+                .value(&tag.value)
+                .build()
+                .ok()
+        })
+        .collect();
 
-    let final_path = zip_path.to_string_lossy().to_string();
+    let tagging = aws_sdk_s3::types::Tagging::builder()
+        .set_tag_set(Some(s3_tags))
+        .build()
+        .map_err(|e| AppError::InvalidInput(format!("Failed to build tagging: {:?}", e)))?;
 
-    // Emit completed
-    let _ = app.emit(
-        "download-completed",
-        DownloadCompleted {
-            download_id,
-            key: prefix,
-            path: final_path.clone(),
-        },
-    );
+    client
+        .put_object_tagging()
+        .bucket(&bucket)
+        .key(&key)
+        .tagging(tagging)
+        .send()
+        .await?;
 
-    Ok(final_path)
+    Ok(ObjectTagsResponse {
+        object_key: key,
+        tags,
+    })
 }
 
-/// List all versions of a specific object
+/// Delete all tags from an object
 #[tauri::command(rename_all = "camelCase")]
-pub async fn list_object_versions(
+pub async fn delete_object_tagging(
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
     account_id: String,
     bucket: String,
     key: String,
-    key_marker: Option<String>,
-    version_id_marker: Option<String>,
-    max_keys: Option<i32>,
-) -> Result<ListVersionsResponse, AppError> {
+) -> Result<(), AppError> {
     let account = credentials.get_account(&account_id)?;
     let secret = credentials.get_secret_key(&account_id)?;
 
@@ -2116,107 +7485,249 @@ pub async fn list_object_versions(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    // Check if versioning is enabled
-    let versioning_enabled = match client.get_bucket_versioning().bucket(&bucket).send().await {
-        Ok(resp) => matches!(
-            resp.status(),
-            Some(aws_sdk_s3::types::BucketVersioningStatus::Enabled)
-        ),
-        Err(_) => false, // R2 may not support this, treat as no versioning
-    };
-
-    // Build the list_object_versions request
-    let mut request = client
-        .list_object_versions()
+    client
+        .delete_object_tagging()
         .bucket(&bucket)
-        .prefix(&key)
-        .max_keys(max_keys.unwrap_or(100));
+        .key(&key)
+        .send()
+        .await?;
 
-    if let Some(km) = key_marker {
-        request = request.key_marker(km);
-    }
+    Ok(())
+}
 
-    if let Some(vim) = version_id_marker {
-        request = request.version_id_marker(vim);
+/// Objects retagged at once by the bulk tagging commands
+const BULK_TAGGING_CONCURRENCY: usize = 8;
+/// S3 rejects tag sets larger than this
+const MAX_OBJECT_TAGS: usize = 10;
+
+/// How `apply_tags_to_prefix` combines new tags with an object's existing ones
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagApplyMode {
+    /// Replace each object's tag set with the given tags
+    Replace,
+    /// Add the given tags, overwriting values of matching keys and keeping the rest
+    #[default]
+    Merge,
+}
+
+/// Progress event for bulk tagging
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaggingProgress {
+    pub operation_id: String,
+    pub processed: usize,
+    pub total: usize,
+    pub current_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaggingError {
+    pub key: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkTaggingResult {
+    pub total: usize,
+    pub updated: usize,
+    /// Objects whose tags already matched, so nothing was written
+    pub unchanged: usize,
+    pub errors: Vec<TaggingError>,
+}
+
+/// Read an object's current tags
+async fn read_object_tags(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+    retry: RetryPolicy,
+) -> Result<Vec<ObjectTag>, String> {
+    let response = with_retry(retry, || {
+        client.get_object_tagging().bucket(bucket).key(key).send()
+    })
+    .await
+    .map_err(|e| format!("Failed to get tags: {:?}", e))?;
+
+    Ok(response
+        .tag_set()
+        .iter()
+        .map(|tag| ObjectTag {
+            key: tag.key().to_string(),
+            value: tag.value().to_string(),
+        })
+        .collect())
+}
+
+/// Write an object's tag set; an empty set deletes its tags
+async fn write_object_tags(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+    tags: &[ObjectTag],
+    retry: RetryPolicy,
+) -> Result<(), String> {
+    if tags.is_empty() {
+        with_retry(retry, || {
+            client.delete_object_tagging().bucket(bucket).key(key).send()
+        })
+        .await
+        .map_err(|e| format!("Failed to delete tags: {:?}", e))?;
+        return Ok(());
+    }
+    if tags.len() > MAX_OBJECT_TAGS {
+        return Err(format!(
+            "Objects can have at most {} tags ({} requested)",
+            MAX_OBJECT_TAGS,
+            tags.len()
+        ));
     }
 
-    let response = match request.send().await {
-        Ok(r) => r,
-        Err(e) => {
-            // Check if it's an unsupported operation (e.g., R2)
-            let err_str = format!("{:?}", e);
-            if err_str.contains("NotImplemented") || err_str.contains("not supported") {
-                return Ok(ListVersionsResponse {
-                    key,
-                    versions: vec![],
-                    key_marker: None,
-                    version_id_marker: None,
-                    is_truncated: false,
-                    versioning_enabled: false,
-                });
-            }
-            return Err(AppError::S3(err_str));
-        }
-    };
+    let s3_tags: Vec<aws_sdk_s3::types::Tag> = tags
+        .iter()
+        .filter_map(|tag| {
+            aws_sdk_s3::types::Tag::builder()
+                .key(&tag.key)
+                .value(&tag.value)
+                .build()
+                .ok()
+        })
+        .collect();
+    let tagging = aws_sdk_s3::types::Tagging::builder()
+        .set_tag_set(Some(s3_tags))
+        .build()
+        .map_err(|e| format!("Failed to build tagging: {:?}", e))?;
 
-    let mut versions: Vec<ObjectVersionInfo> = Vec::new();
+    with_retry(retry, || {
+        client
+            .put_object_tagging()
+            .bucket(bucket)
+            .key(key)
+            .tagging(tagging.clone())
+            .send()
+    })
+    .await
+    .map_err(|e| format!("Failed to set tags: {:?}", e))?;
 
-    // Process actual versions (filter by exact key match)
-    for version in response.versions() {
-        if version.key().map_or(false, |k| k == key) {
-            versions.push(ObjectVersionInfo {
-                version_id: version.version_id().unwrap_or("null").to_string(),
-                is_latest: version.is_latest().unwrap_or(false),
-                is_delete_marker: false,
-                last_modified: version.last_modified().map(|d| d.to_string()),
-                size: version.size(),
-                etag: version.e_tag().map(|e| e.trim_matches('"').to_string()),
-                storage_class: version.storage_class().map(|s| s.as_str().to_string()),
-            });
+    Ok(())
+}
+
+/// Retag every object under a prefix (optionally filtered), emitting
+/// `tagging-progress` events. `update` maps an object's current tags to its new
+/// tags, or `None` to leave it alone; current tags are only read when
+/// `read_current` is set, otherwise `update` receives an empty list.
+async fn retag_prefix<F>(
+    app: &AppHandle,
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    prefix: &str,
+    filter: &ObjectFilter,
+    operation_id: &str,
+    retry: RetryPolicy,
+    read_current: bool,
+    update: F,
+) -> Result<BulkTaggingResult, AppError>
+where
+    F: Fn(Vec<ObjectTag>) -> Option<Vec<ObjectTag>>,
+{
+    let (matches, _) = list_matching_objects(client, bucket, prefix, filter, None, None).await?;
+    let keys: Vec<String> = matches
+        .into_iter()
+        .map(|m| m.key)
+        .filter(|k| !k.ends_with('/'))
+        .collect();
+    let total = keys.len();
+
+    let update = &update;
+    let mut results = stream::iter(keys)
+        .map(|key| async move {
+            let current = if read_current {
+                match read_object_tags(client, bucket, &key, retry).await {
+                    Ok(tags) => tags,
+                    Err(e) => return (key, Err(e)),
+                }
+            } else {
+                Vec::new()
+            };
+
+            let outcome = match update(current) {
+                Some(tags) => write_object_tags(client, bucket, &key, &tags, retry)
+                    .await
+                    .map(|_| true),
+                None => Ok(false),
+            };
+            (key, outcome)
+        })
+        .buffer_unordered(BULK_TAGGING_CONCURRENCY);
+
+    let mut result = BulkTaggingResult {
+        total,
+        updated: 0,
+        unchanged: 0,
+        errors: Vec::new(),
+    };
+    let mut processed = 0;
+    let mut progress = ProgressThrottle::new();
+    while let Some((key, outcome)) = results.next().await {
+        processed += 1;
+        match outcome {
+            Ok(true) => result.updated += 1,
+            Ok(false) => result.unchanged += 1,
+            Err(error) => result.errors.push(TaggingError {
+                key: key.clone(),
+                error,
+            }),
         }
-    }
 
-    // Process delete markers (filter by exact key match)
-    for marker in response.delete_markers() {
-        if marker.key().map_or(false, |k| k == key) {
-            versions.push(ObjectVersionInfo {
-                version_id: marker.version_id().unwrap_or("null").to_string(),
-                is_latest: marker.is_latest().unwrap_or(false),
-                is_delete_marker: true,
-                last_modified: marker.last_modified().map(|d| d.to_string()),
-                size: None,
-                etag: None,
-                storage_class: None,
-            });
+        if progress.ready(processed == total) {
+            let _ = app.emit(
+                "tagging-progress",
+                TaggingProgress {
+                    operation_id: operation_id.to_string(),
+                    processed,
+                    total,
+                    current_key: Some(key),
+                },
+            );
         }
     }
 
-    // Sort by last_modified descending (newest first)
-    versions.sort_by(|a, b| b.last_modified.as_ref().cmp(&a.last_modified.as_ref()));
-
-    Ok(ListVersionsResponse {
-        key,
-        versions,
-        key_marker: response.next_key_marker().map(|s| s.to_string()),
-        version_id_marker: response.next_version_id_marker().map(|s| s.to_string()),
-        is_truncated: response.is_truncated().unwrap_or(false),
-        versioning_enabled,
-    })
+    Ok(result)
 }
 
-/// Restore a previous version by copying it to become the new current version
+/// Tag every object under a prefix. In merge mode (the default) existing tags
+/// are kept and only the given keys are added or overwritten.
 #[tauri::command(rename_all = "camelCase")]
-pub async fn restore_object_version(
+pub async fn apply_tags_to_prefix(
+    app: AppHandle,
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
     account_id: String,
     bucket: String,
-    key: String,
-    version_id: String,
-) -> Result<RestoreVersionResult, AppError> {
+    prefix: String,
+    filter: Option<ObjectFilter>,
+    tags: Vec<ObjectTag>,
+    mode: Option<TagApplyMode>,
+    operation_id: String,
+) -> Result<BulkTaggingResult, AppError> {
+    if tags.is_empty() {
+        return Err(AppError::InvalidInput("No tags to apply".into()));
+    }
+    if tags.len() > MAX_OBJECT_TAGS {
+        return Err(AppError::InvalidInput(format!(
+            "Objects can have at most {} tags",
+            MAX_OBJECT_TAGS
+        )));
+    }
+    let mode = mode.unwrap_or_default();
+
     let account = credentials.get_account(&account_id)?;
     let secret = credentials.get_secret_key(&account_id)?;
 
@@ -2228,57 +7739,61 @@ pub async fn restore_object_version(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    // Copy the specified version to the same key (creates a new current version)
-    let copy_source = format!(
-        "{}/{}?versionId={}",
-        bucket,
-        urlencoding::encode(&key),
-        urlencoding::encode(&version_id)
-    );
-
-    let response = client
-        .copy_object()
-        .bucket(&bucket)
-        .key(&key)
-        .copy_source(&copy_source)
-        .send()
-        .await
-        .map_err(|e| AppError::S3(format!("Failed to restore version: {:?}", e)))?;
-
-    Ok(RestoreVersionResult {
-        key,
-        restored_version_id: version_id,
-        new_version_id: response.version_id().map(|s| s.to_string()),
-    })
-}
-
-// Object tagging types
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ObjectTag {
-    pub key: String,
-    pub value: String,
-}
+    let retry = RetryPolicy::from_settings(&db.get_app_settings()?);
+    retag_prefix(
+        &app,
+        &client,
+        &bucket,
+        &prefix,
+        &filter.unwrap_or_default(),
+        &operation_id,
+        retry,
+        mode == TagApplyMode::Merge,
+        |current| {
+            if mode == TagApplyMode::Replace {
+                return Some(tags.clone());
+            }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ObjectTagsResponse {
-    pub object_key: String,
-    pub tags: Vec<ObjectTag>,
+            let mut merged = current.clone();
+            for tag in &tags {
+                match merged.iter_mut().find(|t| t.key == tag.key) {
+                    Some(existing) => existing.value = tag.value.clone(),
+                    None => merged.push(tag.clone()),
+                }
+            }
+            let changed = merged.len() != current.len()
+                || merged
+                    .iter()
+                    .zip(&current)
+                    .any(|(a, b)| a.key != b.key || a.value != b.value);
+            changed.then_some(merged)
+        },
+    )
+    .await
 }
 
-/// Get tags for an object
+/// Remove the given tag keys from every object under a prefix, keeping their other tags
 #[tauri::command(rename_all = "camelCase")]
-pub async fn get_object_tagging(
+pub async fn remove_tags_from_prefix(
+    app: AppHandle,
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
     account_id: String,
     bucket: String,
-    key: String,
-) -> Result<ObjectTagsResponse, AppError> {
+    prefix: String,
+    filter: Option<ObjectFilter>,
+    tag_keys: Vec<String>,
+    operation_id: String,
+) -> Result<BulkTaggingResult, AppError> {
+    if tag_keys.is_empty() {
+        return Err(AppError::InvalidInput("No tags to remove".into()));
+    }
+
     let account = credentials.get_account(&account_id)?;
     let secret = credentials.get_secret_key(&account_id)?;
 
@@ -2290,110 +7805,205 @@ pub async fn get_object_tagging(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    let response = match client
-        .get_object_tagging()
-        .bucket(&bucket)
-        .key(&key)
-        .send()
-        .await
-    {
-        Ok(r) => r,
-        Err(e) => {
-            // Check if it's an unsupported operation (e.g., some providers)
-            let err_str = format!("{:?}", e);
-            if err_str.contains("NotImplemented") || err_str.contains("not supported") {
-                return Ok(ObjectTagsResponse {
-                    object_key: key,
-                    tags: vec![],
-                });
+    let retry = RetryPolicy::from_settings(&db.get_app_settings()?);
+    retag_prefix(
+        &app,
+        &client,
+        &bucket,
+        &prefix,
+        &filter.unwrap_or_default(),
+        &operation_id,
+        retry,
+        true,
+        |current| {
+            let before = current.len();
+            let remaining: Vec<ObjectTag> = current
+                .into_iter()
+                .filter(|t| !tag_keys.contains(&t.key))
+                .collect();
+            (remaining.len() != before).then_some(remaining)
+        },
+    )
+    .await
+}
+
+/// Digest algorithms for on-demand object hashing
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum HashAlgorithm {
+    Md5,
+    Sha1,
+    Sha256,
+    Crc32c,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    fn parse(value: &str) -> Result<Self, AppError> {
+        match value.to_ascii_lowercase().as_str() {
+            "md5" => Ok(HashAlgorithm::Md5),
+            "sha1" => Ok(HashAlgorithm::Sha1),
+            "sha256" => Ok(HashAlgorithm::Sha256),
+            "crc32c" => Ok(HashAlgorithm::Crc32c),
+            "blake3" => Ok(HashAlgorithm::Blake3),
+            other => Err(AppError::InvalidInput(format!(
+                "Unsupported hash algorithm: {} (expected md5, sha1, sha256, crc32c or blake3)",
+                other
+            ))),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            HashAlgorithm::Md5 => "md5",
+            HashAlgorithm::Sha1 => "sha1",
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Crc32c => "crc32c",
+            HashAlgorithm::Blake3 => "blake3",
+        }
+    }
+}
+
+/// Incremental hasher for any `HashAlgorithm`
+enum ObjectHasher {
+    Md5(md5::Md5),
+    Sha256(sha2::Sha256),
+    Blake3(Box<blake3::Hasher>),
+    /// SHA-1 and CRC32C, which the project only gets through the SDK's checksum crate
+    Checksum(Box<dyn aws_smithy_checksums::http::HttpChecksum>),
+}
+
+impl ObjectHasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        use sha2::Digest;
+
+        match algorithm {
+            HashAlgorithm::Md5 => ObjectHasher::Md5(md5::Md5::new()),
+            HashAlgorithm::Sha256 => ObjectHasher::Sha256(sha2::Sha256::new()),
+            HashAlgorithm::Blake3 => ObjectHasher::Blake3(Box::new(blake3::Hasher::new())),
+            HashAlgorithm::Sha1 => ObjectHasher::Checksum(
+                aws_smithy_checksums::ChecksumAlgorithm::Sha1.into_impl(),
+            ),
+            HashAlgorithm::Crc32c => ObjectHasher::Checksum(
+                aws_smithy_checksums::ChecksumAlgorithm::Crc32c.into_impl(),
+            ),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        use sha2::Digest;
+
+        match self {
+            ObjectHasher::Md5(hasher) => hasher.update(data),
+            ObjectHasher::Sha256(hasher) => hasher.update(data),
+            ObjectHasher::Blake3(hasher) => {
+                hasher.update(data);
             }
-            return Err(AppError::S3(err_str));
+            ObjectHasher::Checksum(hasher) => hasher.update(data),
         }
-    };
+    }
 
-    let tags: Vec<ObjectTag> = response
-        .tag_set()
-        .iter()
-        .map(|tag| ObjectTag {
-            key: tag.key().to_string(),
-            value: tag.value().to_string(),
-        })
-        .collect();
+    /// Lowercase hex digest
+    fn finalize_hex(self) -> String {
+        use sha2::Digest;
 
-    Ok(ObjectTagsResponse {
-        object_key: key,
-        tags,
-    })
+        match self {
+            ObjectHasher::Md5(hasher) => hex::encode(hasher.finalize()),
+            ObjectHasher::Sha256(hasher) => hex::encode(hasher.finalize()),
+            ObjectHasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+            ObjectHasher::Checksum(hasher) => hex::encode(hasher.finalize()),
+        }
+    }
 }
 
-/// Set tags for an object (replaces all existing tags)
-#[tauri::command(rename_all = "camelCase")]
-pub async fn put_object_tagging(
-    credentials: State<'_, CredentialsManager>,
-    s3_clients: State<'_, S3ClientManager>,
-    account_id: String,
-    bucket: String,
-    key: String,
-    tags: Vec<ObjectTag>,
-) -> Result<ObjectTagsResponse, AppError> {
-    let account = credentials.get_account(&account_id)?;
-    let secret = credentials.get_secret_key(&account_id)?;
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectHash {
+    pub algorithm: String,
+    /// Lowercase hex digest
+    pub hash: String,
+    pub size: u64,
+}
 
-    let client = s3_clients
-        .get_or_create_client(
-            &account_id,
-            &account.endpoint,
-            &account.access_key_id,
-            &secret,
-            account.provider_type,
-            account.region.as_deref(),
-        )
-        .await?;
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashProgress {
+    pub operation_id: String,
+    pub bytes_hashed: u64,
+    pub total_bytes: u64,
+}
 
-    // Build the tag set
-    let s3_tags: Vec<aws_sdk_s3::types::Tag> = tags
-        .iter()
-        .filter_map(|tag| {
-            aws_sdk_s3::types::Tag::builder()
-                .key(&tag.key)
-                .value(&tag.value)
-                .build()
-                .ok()
-        })
-        .collect();
+/// Stream an object through a hasher without buffering it. With an
+/// `operation_id`, `hash-progress` events are emitted while reading.
+pub(crate) async fn hash_object(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+    algorithm: HashAlgorithm,
+    progress: Option<(&AppHandle, &str)>,
+    retry: RetryPolicy,
+) -> Result<ObjectHash, AppError> {
+    // Only the request is retried; a body that fails mid-stream fails the hash
+    let response = with_retry(retry, || client.get_object().bucket(bucket).key(key).send()).await?;
+    let total_bytes = response.content_length().unwrap_or(0).max(0) as u64;
+    let mut body = response.body.into_async_read();
 
-    let tagging = aws_sdk_s3::types::Tagging::builder()
-        .set_tag_set(Some(s3_tags))
-        .build()
-        .map_err(|e| AppError::InvalidInput(format!("Failed to build tagging: {:?}", e)))?;
+    let mut hasher = ObjectHasher::new(algorithm);
+    let mut buffer = vec![0u8; DOWNLOAD_CHUNK_SIZE];
+    let mut bytes_hashed: u64 = 0;
+    let mut throttle = ProgressThrottle::new();
 
-    client
-        .put_object_tagging()
-        .bucket(&bucket)
-        .key(&key)
-        .tagging(tagging)
-        .send()
-        .await
-        .map_err(|e| AppError::S3(format!("Failed to set tags: {:?}", e)))?;
+    loop {
+        let bytes_read = body
+            .read(&mut buffer)
+            .await
+            .map_err(|e| AppError::S3(format!("Failed to read body: {}", e)))?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+        bytes_hashed += bytes_read as u64;
 
-    Ok(ObjectTagsResponse {
-        object_key: key,
-        tags,
+        if let Some((app, operation_id)) = progress {
+            if throttle.ready(bytes_hashed >= total_bytes) {
+                let _ = app.emit(
+                    "hash-progress",
+                    HashProgress {
+                        operation_id: operation_id.to_string(),
+                        bytes_hashed,
+                        total_bytes,
+                    },
+                );
+            }
+        }
+    }
+
+    Ok(ObjectHash {
+        algorithm: algorithm.as_str().to_string(),
+        hash: hasher.finalize_hex(),
+        size: bytes_hashed,
     })
 }
 
-/// Delete all tags from an object
+/// Compute a single object's digest on demand (md5, sha1, sha256, crc32c or
+/// blake3) to check it against a known checksum
 #[tauri::command(rename_all = "camelCase")]
-pub async fn delete_object_tagging(
+pub async fn compute_object_hash(
+    app: AppHandle,
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
     account_id: String,
     bucket: String,
     key: String,
-) -> Result<(), AppError> {
+    algorithm: String,
+    operation_id: Option<String>,
+) -> Result<ObjectHash, AppError> {
+    let algorithm = HashAlgorithm::parse(&algorithm)?;
+
     let account = credentials.get_account(&account_id)?;
     let secret = credentials.get_secret_key(&account_id)?;
 
@@ -2405,16 +8015,218 @@ pub async fn delete_object_tagging(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
-    client
-        .delete_object_tagging()
-        .bucket(&bucket)
-        .key(&key)
-        .send()
+    hash_object(
+        &client,
+        &bucket,
+        &key,
+        algorithm,
+        operation_id.as_deref().map(|id| (&app, id)),
+        RetryPolicy::from_settings(&db.get_app_settings()?),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trash_batch_time_ignores_the_batch_suffix() {
+        let prefix = ".trash/";
+        assert_eq!(
+            trash_batch_time(".trash/20240102T030405Z-1a2b3c4d/docs/a.txt", prefix),
+            Some("20240102T030405Z")
+        );
+        // Batches from before the suffix was added
+        assert_eq!(
+            trash_batch_time(".trash/20240102T030405Z/docs/a.txt", prefix),
+            Some("20240102T030405Z")
+        );
+        assert_eq!(trash_batch_time("docs/a.txt", prefix), None);
+    }
+
+    #[test]
+    fn original_key_is_recovered_from_suffixed_batches() {
+        assert_eq!(
+            original_key_from_trash(".trash/20240102T030405Z-1a2b3c4d/docs/a-b.txt", ".trash/"),
+            Some("docs/a-b.txt".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn overlapping_delete_selection_covers_each_object_once() {
+        let folders: HashMap<&str, Vec<&str>> = HashMap::from([
+            (
+                "photos/",
+                vec!["photos/", "photos/a.jpg", "photos/2024/b.jpg", "photos/2024/c.jpg"],
+            ),
+            ("photos/2024/", vec!["photos/2024/b.jpg", "photos/2024/c.jpg"]),
+        ]);
+        let selection: Vec<String> = ["photos/", "photos/a.jpg", "photos/2024/", "docs/readme.md"]
+            .iter()
+            .map(|k| k.to_string())
+            .collect();
+
+        let expanded = expand_delete_selection(&selection, |prefix| {
+            let keys = folders[prefix.as_str()].iter().map(|k| k.to_string()).collect();
+            std::future::ready(Ok(keys))
+        })
         .await
-        .map_err(|e| AppError::S3(format!("Failed to delete tags: {:?}", e)))?;
+        .unwrap();
+
+        assert_eq!(
+            expanded,
+            [
+                "photos/",
+                "photos/a.jpg",
+                "photos/2024/b.jpg",
+                "photos/2024/c.jpg",
+                "docs/readme.md"
+            ]
+        );
+    }
 
-    Ok(())
+    /// Serve `keys` in pages of at most 1000, like ListObjectsV2, using the
+    /// offset of the next page as the continuation token
+    fn paged(
+        keys: &[String],
+        continuation_token: Option<String>,
+    ) -> Result<(Vec<String>, Option<String>), AppError> {
+        let start: usize = continuation_token.map_or(0, |t| t.parse().unwrap());
+        let end = (start + 1000).min(keys.len());
+        let next_token = (end < keys.len()).then(|| end.to_string());
+        Ok((keys[start..end].to_vec(), next_token))
+    }
+
+    #[tokio::test]
+    async fn folder_keys_are_collected_across_pages() {
+        let mut keys = vec!["photos/".to_string()];
+        keys.extend((0..2500).map(|i| format!("photos/2024/img-{:04}.jpg", i)));
+
+        let mut pages = 0;
+        let collected = collect_paginated_keys(|token| {
+            pages += 1;
+            std::future::ready(paged(&keys, token))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(pages, 3);
+        assert_eq!(collected, keys);
+    }
+
+    #[tokio::test]
+    async fn folder_key_collection_stops_on_a_page_error() {
+        let mut pages = 0;
+        let result = collect_paginated_keys(|token| {
+            pages += 1;
+            std::future::ready(match token {
+                None => Ok((vec!["a/1".to_string()], Some("next".to_string()))),
+                Some(_) => Err(AppError::S3("listing failed".into())),
+            })
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(pages, 2);
+    }
+
+    #[test]
+    fn copy_source_encodes_spaces_and_unicode() {
+        assert_eq!(
+            copy_source("bucket", "my file.txt", None),
+            "bucket/my%20file.txt"
+        );
+        assert_eq!(
+            copy_source("bucket", "photos/été.jpg", None),
+            "bucket/photos/%C3%A9t%C3%A9.jpg"
+        );
+    }
+
+    #[test]
+    fn copy_source_encodes_plus_and_percent() {
+        assert_eq!(copy_source("bucket", "a+b.txt", None), "bucket/a%2Bb.txt");
+        assert_eq!(copy_source("bucket", "100%.txt", None), "bucket/100%25.txt");
+    }
+
+    #[test]
+    fn copy_source_keeps_slashes_between_segments() {
+        assert_eq!(
+            copy_source("bucket", "a/b c/d/e.txt", None),
+            "bucket/a/b%20c/d/e.txt"
+        );
+        assert_eq!(copy_source("bucket", "folder/", None), "bucket/folder/");
+    }
+
+    #[test]
+    fn copy_source_appends_encoded_version_id() {
+        assert_eq!(
+            copy_source("bucket", "a b.txt", Some("v1+x/y")),
+            "bucket/a%20b.txt?versionId=v1%2Bx%2Fy"
+        );
+    }
+
+    /// Client and bucket for tests against a real S3-compatible endpoint, set
+    /// through `BUCKET_SCOUT_TEST_{ENDPOINT,ACCESS_KEY,SECRET_KEY,BUCKET}` and
+    /// optionally `BUCKET_SCOUT_TEST_REGION`
+    fn live_test_target() -> Option<(aws_sdk_s3::Client, String)> {
+        let var = |name: &str| std::env::var(format!("BUCKET_SCOUT_TEST_{}", name)).ok();
+        let credentials = aws_sdk_s3::config::Credentials::new(
+            var("ACCESS_KEY")?,
+            var("SECRET_KEY")?,
+            None,
+            None,
+            "bucket-scout-test",
+        );
+        let config = aws_sdk_s3::Config::builder()
+            .behavior_version(aws_sdk_s3::config::BehaviorVersion::latest())
+            .endpoint_url(var("ENDPOINT")?)
+            .region(aws_sdk_s3::config::Region::new(
+                var("REGION").unwrap_or_else(|| "us-east-1".to_string()),
+            ))
+            .credentials_provider(credentials)
+            .force_path_style(true)
+            .build();
+        Some((aws_sdk_s3::Client::from_conf(config), var("BUCKET")?))
+    }
+
+    #[tokio::test]
+    #[ignore = "needs an S3 endpoint in BUCKET_SCOUT_TEST_*"]
+    async fn touch_advances_last_modified_and_keeps_content() {
+        let (client, bucket) = live_test_target().expect("BUCKET_SCOUT_TEST_* is not set");
+        let key = format!("bucket-scout-test/touch-{}.txt", uuid::Uuid::new_v4());
+        let content: &[u8] = b"touched but unchanged";
+
+        client
+            .put_object()
+            .bucket(&bucket)
+            .key(&key)
+            .body(aws_sdk_s3::primitives::ByteStream::from_static(content))
+            .send()
+            .await
+            .unwrap();
+        let before = client.head_object().bucket(&bucket).key(&key).send().await.unwrap();
+
+        // Last-modified times have one-second resolution
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        let touched = copy_in_place(&client, &bucket, &key, &before, None).await;
+
+        let after = client.head_object().bucket(&bucket).key(&key).send().await;
+        let body = match client.get_object().bucket(&bucket).key(&key).send().await {
+            Ok(response) => response.body.collect().await.ok().map(|b| b.into_bytes()),
+            Err(_) => None,
+        };
+        let _ = client.delete_object().bucket(&bucket).key(&key).send().await;
+
+        touched.unwrap();
+        let after = after.unwrap();
+        assert!(after.last_modified().unwrap().secs() > before.last_modified().unwrap().secs());
+        assert_eq!(after.e_tag(), before.e_tag());
+        assert_eq!(after.content_type(), before.content_type());
+        assert_eq!(body.as_deref(), Some(content));
+    }
 }
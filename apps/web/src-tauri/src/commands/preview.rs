@@ -1,28 +1,54 @@
-use crate::credentials::CredentialsManager;
+use crate::cache::PublicUrlCache;
+use crate::commands::objects::{encode_key_path, presign_get_url};
+use crate::credentials::{Account, CredentialsManager};
+use crate::db::settings::PreviewSettings;
+use crate::db::thumbnails::CachedThumbnail;
+use crate::db::DbManager;
 use crate::error::AppError;
-use crate::s3::client::S3ClientManager;
+use crate::s3::client::{ClientOptions, S3ClientManager};
+use futures::stream::{self, StreamExt};
 use image::ImageFormat;
-use serde::Serialize;
+use serde::de::{Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io::Cursor;
+use std::time::Duration;
 use tauri::State;
+use tokio_util::io::SyncIoBridge;
 
-const MAX_PREVIEW_SIZE: i64 = 5 * 1024 * 1024; // 5MB default limit
-const MAX_TEXT_PREVIEW_SIZE: i64 = 1024 * 1024; // 1MB for text
-const MAX_PDF_SIZE: i64 = 20 * 1024 * 1024; // 20MB for PDFs
-const MAX_THUMBNAIL_SOURCE_SIZE: i64 = 10 * 1024 * 1024; // 10MB max source for thumbnails
 const DEFAULT_THUMBNAIL_SIZE: u32 = 200;
+const THUMBNAIL_BATCH_CONCURRENCY: usize = 6;
+const PUBLIC_URL_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+const PREVIEW_URL_EXPIRY_SECS: u64 = 15 * 60; // 15 minutes
+const JSON_TREE_SAMPLE_SIZE: usize = 64 * 1024; // 64KB
+const JSON_TREE_MAX_CHILDREN: usize = 200;
+const IMAGE_INFO_PREFIX_SIZE: i64 = 256 * 1024; // 256KB usually covers headers and EXIF
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type")]
 pub enum PreviewContent {
     Text { content: String, truncated: bool },
+    /// Rendered, sanitized HTML alongside the raw source
+    Markdown {
+        content: String,
+        html: String,
+        truncated: bool,
+    },
     Image {
         base64: String,
         #[serde(rename = "mimeType")]
         mime_type: String,
     },
     Json { content: serde_json::Value },
+    /// Structural summary of a JSON document too large to parse into memory,
+    /// with the start of the document re-indented
+    JsonTree {
+        summary: JsonTreeSummary,
+        sample: String,
+    },
     Pdf { base64: String },
+    /// Served directly from the account's public base URL instead of being embedded
+    PublicUrl { url: String },
     Unsupported { message: String },
 }
 
@@ -34,6 +60,48 @@ pub struct PreviewData {
     pub data: PreviewContent,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonKind {
+    Object,
+    Array,
+    String,
+    Number,
+    Boolean,
+    Null,
+}
+
+/// Shallow shape of a JSON value: its kind and, for objects and arrays, the entry count
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonShape {
+    pub kind: JsonKind,
+    pub length: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonTreeEntry {
+    /// Member name, or the element index for arrays
+    pub key: String,
+    #[serde(flatten)]
+    pub shape: JsonShape,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonTreeSummary {
+    #[serde(flatten)]
+    pub root: JsonShape,
+    /// Distinct kinds of the elements when the root is an array
+    pub item_kinds: Vec<JsonKind>,
+    /// Top-level members or leading array elements, one level deep
+    pub children: Vec<JsonTreeEntry>,
+    pub children_truncated: bool,
+    /// False when the document is malformed; counts then cover only the valid part
+    pub complete: bool,
+}
+
 fn get_content_type_from_extension(key: &str) -> Option<&'static str> {
     let ext = key.rsplit('.').next()?.to_lowercase();
     match ext.as_str() {
@@ -45,6 +113,10 @@ fn get_content_type_from_extension(key: &str) -> Option<&'static str> {
         "svg" => Some("image/svg+xml"),
         "ico" => Some("image/x-icon"),
         "bmp" => Some("image/bmp"),
+        "tif" | "tiff" => Some("image/tiff"),
+        "avif" => Some("image/avif"),
+        "heic" => Some("image/heic"),
+        "heif" => Some("image/heif"),
 
         // Text
         "txt" => Some("text/plain"),
@@ -72,6 +144,14 @@ fn get_content_type_from_extension(key: &str) -> Option<&'static str> {
         // PDF
         "pdf" => Some("application/pdf"),
 
+        // Video and audio
+        "mp4" => Some("video/mp4"),
+        "webm" => Some("video/webm"),
+        "mov" => Some("video/quicktime"),
+        "mp3" => Some("audio/mpeg"),
+        "wav" => Some("audio/wav"),
+        "ogg" => Some("audio/ogg"),
+
         _ => None,
     }
 }
@@ -90,19 +170,108 @@ fn is_text_content_type(content_type: &str) -> bool {
         || content_type == "application/toml"
 }
 
+fn is_markdown(content_type: &str, key: &str) -> bool {
+    let lower_key = key.to_lowercase();
+    content_type.starts_with("text/markdown")
+        || content_type.starts_with("text/x-markdown")
+        || lower_key.ends_with(".md")
+        || lower_key.ends_with(".markdown")
+}
+
+/// Render Markdown to HTML and sanitize it (scripts, event handlers and
+/// tags outside ammonia's allowlist are stripped)
+fn render_markdown(source: &str) -> String {
+    let options = pulldown_cmark::Options::ENABLE_TABLES
+        | pulldown_cmark::Options::ENABLE_STRIKETHROUGH
+        | pulldown_cmark::Options::ENABLE_TASKLISTS
+        | pulldown_cmark::Options::ENABLE_FOOTNOTES;
+    let parser = pulldown_cmark::Parser::new_ext(source, options);
+
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, parser);
+    ammonia::clean(&html)
+}
+
 fn is_image_content_type(content_type: &str) -> bool {
     content_type.starts_with("image/")
 }
 
+fn is_heif_content_type(content_type: &str) -> bool {
+    matches!(content_type, "image/heic" | "image/heif")
+}
+
+/// Formats the webview can't be relied on to display (HEIC outside Safari,
+/// TIFF almost anywhere, AVIF on older WebKit). Previews of these are
+/// converted to JPEG, and a public URL to the original isn't used.
+fn needs_transcoding(content_type: &str) -> bool {
+    is_heif_content_type(content_type) || matches!(content_type, "image/avif" | "image/tiff")
+}
+
+/// Decode an image with the `image` crate, falling back to libheif for HEIC
+/// when built with the `heif` feature. `None` if it can't be decoded.
+fn decode_image(bytes: &[u8], content_type: &str) -> Option<image::DynamicImage> {
+    image::load_from_memory(bytes).ok().or_else(|| {
+        if is_heif_content_type(content_type) {
+            decode_heif(bytes)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(feature = "heif")]
+fn decode_heif(bytes: &[u8]) -> Option<image::DynamicImage> {
+    use libheif_rs::{ColorSpace, HeifContext, LibHeif, RgbChroma};
+
+    let context = HeifContext::read_from_bytes(bytes).ok()?;
+    let handle = context.primary_image_handle().ok()?;
+    let decoded = LibHeif::new()
+        .decode(&handle, ColorSpace::Rgb(RgbChroma::Rgb), None)
+        .ok()?;
+    let plane = decoded.planes().interleaved?;
+
+    // Rows can be padded past the pixel data; copy out just the pixels
+    let row_len = plane.width as usize * 3;
+    let mut pixels = Vec::with_capacity(row_len * plane.height as usize);
+    for row in plane.data.chunks(plane.stride).take(plane.height as usize) {
+        pixels.extend_from_slice(row.get(..row_len)?);
+    }
+    image::RgbImage::from_raw(plane.width, plane.height, pixels).map(image::DynamicImage::ImageRgb8)
+}
+
+#[cfg(not(feature = "heif"))]
+fn decode_heif(_bytes: &[u8]) -> Option<image::DynamicImage> {
+    None
+}
+
+/// Re-encode an image as JPEG, dropping any alpha channel
+fn encode_jpeg(img: &image::DynamicImage) -> Option<Vec<u8>> {
+    let mut output = Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgb8(img.to_rgb8())
+        .write_to(&mut output, ImageFormat::Jpeg)
+        .ok()?;
+    Some(output.into_inner())
+}
+
+/// Types the webview loads through element `src` attributes, which aren't subject to CORS
+fn is_media_content_type(content_type: &str) -> bool {
+    is_image_content_type(content_type)
+        || content_type.starts_with("video/")
+        || content_type.starts_with("audio/")
+}
+
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_preview(
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    public_url_cache: State<'_, PublicUrlCache>,
     account_id: String,
     bucket: String,
     key: String,
     max_size: Option<i64>,
 ) -> Result<PreviewData, AppError> {
+    let limits = db.get_preview_settings()?;
     let account = credentials.get_account(&account_id)?;
     let secret = credentials.get_secret_key(&account_id)?;
 
@@ -114,25 +283,46 @@ pub async fn get_preview(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
+    build_preview(
+        &client,
+        &limits,
+        &bucket,
+        &key,
+        max_size,
+        PublicOrigin::for_account(&account, &public_url_cache).as_ref(),
+    )
+    .await
+}
+
+/// Fetch and encode an object for inline preview, honoring the configured size limits
+async fn build_preview(
+    client: &aws_sdk_s3::Client,
+    limits: &PreviewSettings,
+    bucket: &str,
+    key: &str,
+    max_size: Option<i64>,
+    public: Option<&PublicOrigin<'_>>,
+) -> Result<PreviewData, AppError> {
     // First, get metadata to check size and content type
     let head = client
         .head_object()
-        .bucket(&bucket)
-        .key(&key)
+        .bucket(bucket)
+        .key(key)
         .send()
         .await?;
 
     let size = head.content_length().unwrap_or(0);
-    let max_allowed = max_size.unwrap_or(MAX_PREVIEW_SIZE);
+    let max_allowed = max_size.unwrap_or(limits.max_preview_size);
 
     // Determine content type
     let content_type = head
         .content_type()
         .map(|s| s.to_string())
-        .or_else(|| get_content_type_from_extension(&key).map(|s| s.to_string()))
+        .or_else(|| get_content_type_from_extension(key).map(|s| s.to_string()))
         .unwrap_or_else(|| "application/octet-stream".to_string());
 
     // Check if we can preview this type
@@ -149,6 +339,22 @@ pub async fn get_preview(
         });
     }
 
+    // Images and PDFs are loaded straight from a public domain when one is configured,
+    // which also lifts the size limits since nothing passes through the app
+    if let Some(public) = public {
+        if (is_image_content_type(&content_type) && !needs_transcoding(&content_type))
+            || is_pdf_content_type(&content_type)
+        {
+            if let Some(url) = public.url_for(key).await {
+                return Ok(PreviewData {
+                    content_type,
+                    size,
+                    data: PreviewContent::PublicUrl { url },
+                });
+            }
+        }
+    }
+
     // Check size limits for images
     if is_image_content_type(&content_type) && size > max_allowed {
         return Ok(PreviewData {
@@ -161,7 +367,7 @@ pub async fn get_preview(
     }
 
     // Check size limits for PDFs
-    if is_pdf_content_type(&content_type) && size > MAX_PDF_SIZE {
+    if is_pdf_content_type(&content_type) && size > limits.max_pdf_size {
         return Ok(PreviewData {
             content_type,
             size,
@@ -173,7 +379,7 @@ pub async fn get_preview(
 
     // Handle PDF preview
     if is_pdf_content_type(&content_type) {
-        let response = client.get_object().bucket(&bucket).key(&key).send().await?;
+        let response = client.get_object().bucket(bucket).key(key).send().await?;
         let body = response
             .body
             .collect()
@@ -191,11 +397,22 @@ pub async fn get_preview(
         });
     }
 
+    let text_limit = limits.max_text_preview_size.min(max_allowed);
+
+    // JSON beyond the text limit is summarized while streaming rather than parsed
+    if content_type == "application/json" && size > text_limit {
+        let data = build_json_tree(client, bucket, key).await?;
+        return Ok(PreviewData {
+            content_type,
+            size,
+            data,
+        });
+    }
+
     // Fetch the object content
-    let mut get_request = client.get_object().bucket(&bucket).key(&key);
+    let mut get_request = client.get_object().bucket(bucket).key(key);
 
     // For text files, limit the range if too large
-    let text_limit = MAX_TEXT_PREVIEW_SIZE.min(max_allowed);
     let truncated = if is_text_content_type(&content_type) && size > text_limit {
         get_request = get_request.range(format!("bytes=0-{}", text_limit - 1));
         true
@@ -214,10 +431,22 @@ pub async fn get_preview(
     // Process based on content type
     let data = if is_image_content_type(&content_type) {
         use base64::Engine;
-        let base64 = base64::engine::general_purpose::STANDARD.encode(&bytes);
-        PreviewContent::Image {
-            base64,
-            mime_type: content_type.clone(),
+        // Convert formats the webview may not show; if decoding fails, send the
+        // original and let the webview try
+        let jpeg = if needs_transcoding(&content_type) {
+            decode_image(&bytes, &content_type).and_then(|img| encode_jpeg(&img))
+        } else {
+            None
+        };
+        match jpeg {
+            Some(jpeg) => PreviewContent::Image {
+                base64: base64::engine::general_purpose::STANDARD.encode(jpeg),
+                mime_type: "image/jpeg".to_string(),
+            },
+            None => PreviewContent::Image {
+                base64: base64::engine::general_purpose::STANDARD.encode(&bytes),
+                mime_type: content_type.clone(),
+            },
         }
     } else if content_type == "application/json" {
         // Try to parse as JSON
@@ -229,6 +458,14 @@ pub async fn get_preview(
                 PreviewContent::Text { content, truncated }
             }
         }
+    } else if is_markdown(&content_type, key) {
+        let content = String::from_utf8_lossy(&bytes).to_string();
+        let html = render_markdown(&content);
+        PreviewContent::Markdown {
+            content,
+            html,
+            truncated,
+        }
     } else {
         // Text content
         let content = String::from_utf8_lossy(&bytes).to_string();
@@ -242,6 +479,579 @@ pub async fn get_preview(
     })
 }
 
+/// Stream a JSON object through a summarizing deserializer, keeping only the
+/// first bytes as a sample. Memory stays bounded regardless of the object size.
+async fn build_json_tree(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+) -> Result<PreviewContent, AppError> {
+    let response = client.get_object().bucket(bucket).key(key).send().await?;
+    // The bridge has to be created on the runtime and then used from a blocking thread
+    let reader = SyncIoBridge::new(Box::pin(response.body.into_async_read()));
+
+    let (summary, prefix) = tokio::task::spawn_blocking(move || {
+        let mut capture = PrefixCapture {
+            inner: reader,
+            prefix: Vec::new(),
+        };
+        let summary = summarize_json(std::io::BufReader::with_capacity(
+            JSON_TREE_SAMPLE_SIZE,
+            &mut capture,
+        ));
+        (summary, capture.prefix)
+    })
+    .await
+    .map_err(|e| AppError::S3(format!("Failed to read body: {}", e)))?;
+
+    let text = String::from_utf8_lossy(&prefix);
+
+    // Nothing parsed at all: show the start as plain text, like small invalid JSON
+    if !summary.complete && summary.root.length.is_none() {
+        return Ok(PreviewContent::Text {
+            content: text.to_string(),
+            truncated: true,
+        });
+    }
+
+    Ok(PreviewContent::JsonTree {
+        summary,
+        sample: pretty_print_json_prefix(&text),
+    })
+}
+
+/// Reader adapter that keeps a copy of the first `JSON_TREE_SAMPLE_SIZE` bytes read
+struct PrefixCapture<R> {
+    inner: R,
+    prefix: Vec<u8>,
+}
+
+impl<R: std::io::Read> std::io::Read for PrefixCapture<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        let wanted = JSON_TREE_SAMPLE_SIZE.saturating_sub(self.prefix.len());
+        self.prefix.extend_from_slice(&buf[..read.min(wanted)]);
+        Ok(read)
+    }
+}
+
+fn summarize_json<R: std::io::Read>(reader: R) -> JsonTreeSummary {
+    let mut summary = JsonTreeSummary {
+        root: JsonShape {
+            kind: JsonKind::Null,
+            length: None,
+        },
+        item_kinds: Vec::new(),
+        children: Vec::new(),
+        children_truncated: false,
+        complete: false,
+    };
+
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let parsed = deserializer
+        .deserialize_any(SummaryVisitor(&mut summary))
+        .and_then(|_| deserializer.end());
+    summary.complete = parsed.is_ok();
+    summary
+}
+
+/// Records the root and its direct children into the summary as they stream by,
+/// so a parse error part-way through still leaves a usable partial summary
+struct SummaryVisitor<'a>(&'a mut JsonTreeSummary);
+
+impl SummaryVisitor<'_> {
+    fn push_child(&mut self, key: String, shape: JsonShape) {
+        if self.0.children.len() < JSON_TREE_MAX_CHILDREN {
+            self.0.children.push(JsonTreeEntry { key, shape });
+        } else {
+            self.0.children_truncated = true;
+        }
+    }
+
+    fn scalar<E>(self, kind: JsonKind) -> Result<(), E> {
+        self.0.root = JsonShape { kind, length: None };
+        Ok(())
+    }
+}
+
+impl<'de> Visitor<'de> for SummaryVisitor<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a JSON value")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<(), A::Error> {
+        self.0.root = JsonShape {
+            kind: JsonKind::Object,
+            length: Some(0),
+        };
+        while let Some(key) = map.next_key::<String>()? {
+            let shape: JsonShape = map.next_value()?;
+            self.0.root.length = self.0.root.length.map(|n| n + 1);
+            self.push_child(key, shape);
+        }
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<(), A::Error> {
+        self.0.root = JsonShape {
+            kind: JsonKind::Array,
+            length: Some(0),
+        };
+        let mut index = 0u64;
+        while let Some(shape) = seq.next_element::<JsonShape>()? {
+            self.0.root.length = Some(index + 1);
+            if !self.0.item_kinds.contains(&shape.kind) {
+                self.0.item_kinds.push(shape.kind);
+            }
+            self.push_child(index.to_string(), shape);
+            index += 1;
+        }
+        Ok(())
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<(), E> {
+        self.scalar(JsonKind::Boolean)
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<(), E> {
+        self.scalar(JsonKind::Number)
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<(), E> {
+        self.scalar(JsonKind::Number)
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<(), E> {
+        self.scalar(JsonKind::Number)
+    }
+
+    fn visit_str<E>(self, _: &str) -> Result<(), E> {
+        self.scalar(JsonKind::String)
+    }
+
+    fn visit_unit<E>(self) -> Result<(), E> {
+        self.scalar(JsonKind::Null)
+    }
+}
+
+/// Deserializes only the shape of a value; nested content is skipped without allocation
+impl<'de> Deserialize<'de> for JsonShape {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ShapeVisitor;
+
+        impl<'de> Visitor<'de> for ShapeVisitor {
+            type Value = JsonShape;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a JSON value")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<JsonShape, A::Error> {
+                let mut length = 0;
+                while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {
+                    length += 1;
+                }
+                Ok(JsonShape {
+                    kind: JsonKind::Object,
+                    length: Some(length),
+                })
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<JsonShape, A::Error> {
+                let mut length = 0;
+                while seq.next_element::<IgnoredAny>()?.is_some() {
+                    length += 1;
+                }
+                Ok(JsonShape {
+                    kind: JsonKind::Array,
+                    length: Some(length),
+                })
+            }
+
+            fn visit_bool<E>(self, _: bool) -> Result<JsonShape, E> {
+                Ok(JsonShape::scalar(JsonKind::Boolean))
+            }
+
+            fn visit_i64<E>(self, _: i64) -> Result<JsonShape, E> {
+                Ok(JsonShape::scalar(JsonKind::Number))
+            }
+
+            fn visit_u64<E>(self, _: u64) -> Result<JsonShape, E> {
+                Ok(JsonShape::scalar(JsonKind::Number))
+            }
+
+            fn visit_f64<E>(self, _: f64) -> Result<JsonShape, E> {
+                Ok(JsonShape::scalar(JsonKind::Number))
+            }
+
+            fn visit_str<E>(self, _: &str) -> Result<JsonShape, E> {
+                Ok(JsonShape::scalar(JsonKind::String))
+            }
+
+            fn visit_unit<E>(self) -> Result<JsonShape, E> {
+                Ok(JsonShape::scalar(JsonKind::Null))
+            }
+        }
+
+        deserializer.deserialize_any(ShapeVisitor)
+    }
+}
+
+impl JsonShape {
+    fn scalar(kind: JsonKind) -> Self {
+        Self { kind, length: None }
+    }
+}
+
+/// Re-indent the start of a JSON document. Works on truncated input since it
+/// only tracks strings and nesting instead of parsing.
+fn pretty_print_json_prefix(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = text.chars().peekable();
+
+    let newline = |out: &mut String, depth: usize| {
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+    };
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '{' | '[' => {
+                out.push(c);
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                // Keep empty containers on one line
+                let close = if c == '{' { '}' } else { ']' };
+                if let Some(close) = chars.next_if_eq(&close) {
+                    out.push(close);
+                } else {
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut out, depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                newline(&mut out, depth);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// URL of an object under a public base URL, with each key segment percent-encoded
+fn public_object_url(base_url: &str, key: &str) -> String {
+    format!("{}/{}", base_url.trim_end_matches('/'), encode_key_path(key))
+}
+
+/// Check that a public URL actually serves the object (2xx to an anonymous HEAD,
+/// after redirects), so a misconfigured domain or private bucket doesn't produce
+/// broken links. Goes through the account's proxy like its S3 requests do.
+async fn is_publicly_readable(url: &str, options: &ClientOptions) -> bool {
+    let mut builder = reqwest::Client::builder()
+        .timeout(PUBLIC_URL_CHECK_TIMEOUT)
+        .user_agent("bucket-scout");
+    if let Some(proxy) = options.proxy_for(url) {
+        let no_proxy = options.no_proxy.as_deref().and_then(reqwest::NoProxy::from_string);
+        match reqwest::Proxy::all(proxy) {
+            Ok(proxy) => builder = builder.proxy(proxy.no_proxy(no_proxy)),
+            Err(e) => {
+                log::warn!("Invalid proxy URL '{}': {}", proxy, e);
+                return false;
+            }
+        }
+    }
+
+    let Ok(http) = builder.build() else {
+        return false;
+    };
+    match http.head(url).send().await {
+        Ok(response) => response.status().is_success(),
+        Err(e) => {
+            log::debug!("Public URL check failed for {}: {}", url, e);
+            false
+        }
+    }
+}
+
+/// An account's public base URL, with what's needed to check it serves objects
+struct PublicOrigin<'a> {
+    account_id: &'a str,
+    base_url: &'a str,
+    options: ClientOptions,
+    cache: &'a PublicUrlCache,
+}
+
+impl<'a> PublicOrigin<'a> {
+    fn for_account(account: &'a Account, cache: &'a PublicUrlCache) -> Option<Self> {
+        Some(Self {
+            account_id: &account.id,
+            base_url: account.public_base_url.as_deref()?,
+            options: account.client_options(),
+            cache,
+        })
+    }
+
+    /// Public URL of `key` if the base URL serves objects anonymously. The
+    /// first object checked decides for the base URL until the verdict expires.
+    async fn url_for(&self, key: &str) -> Option<String> {
+        let url = public_object_url(self.base_url, key);
+        let readable = match self.cache.get(self.account_id, self.base_url) {
+            Some(readable) => readable,
+            None => {
+                let readable = is_publicly_readable(&url, &self.options).await;
+                self.cache.insert(self.account_id, self.base_url, readable);
+                readable
+            }
+        };
+        readable.then_some(url)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum PreviewSource {
+    /// Short-lived presigned GET URL the frontend loads directly
+    Url {
+        url: String,
+        #[serde(rename = "contentType")]
+        content_type: String,
+        size: i64,
+        #[serde(rename = "expiresAt")]
+        expires_at: String,
+    },
+    /// The object can't be fetched directly (e.g. blocked by CORS), so it is inlined
+    Inline { preview: PreviewData },
+}
+
+/// Whether the bucket's CORS rules let the webview fetch objects with GET
+async fn cors_allows_get(client: &aws_sdk_s3::Client, bucket: &str) -> bool {
+    let response = match client.get_bucket_cors().bucket(bucket).send().await {
+        Ok(resp) => resp,
+        // Includes NoSuchCORSConfiguration: no rules means cross-origin fetches fail
+        Err(_) => return false,
+    };
+
+    response.cors_rules().iter().any(|rule| {
+        rule.allowed_methods().iter().any(|m| m.eq_ignore_ascii_case("GET"))
+            && rule.allowed_origins().iter().any(|origin| {
+                origin == "*" || origin.contains("tauri://") || origin.contains("tauri.localhost")
+            })
+    })
+}
+
+/// Get a presigned URL for previewing an object without sending its bytes over IPC.
+/// Images, video and audio always get a URL. Other types (e.g. PDFs, text) are read
+/// with `fetch`, so they only get a URL when the bucket's CORS rules allow it;
+/// otherwise this falls back to the same inline content as `get_preview`.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_preview_url(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    public_url_cache: State<'_, PublicUrlCache>,
+    account_id: String,
+    bucket: String,
+    key: String,
+) -> Result<PreviewSource, AppError> {
+    let limits = db.get_preview_settings()?;
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    let head = client
+        .head_object()
+        .bucket(&bucket)
+        .key(&key)
+        .send()
+        .await?;
+
+    let size = head.content_length().unwrap_or(0);
+    let content_type = head
+        .content_type()
+        .map(|s| s.to_string())
+        .or_else(|| get_content_type_from_extension(&key).map(|s| s.to_string()))
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+
+    let direct = is_media_content_type(&content_type)
+        || ((is_pdf_content_type(&content_type) || is_text_content_type(&content_type))
+            && cors_allows_get(&client, &bucket).await);
+
+    if !direct {
+        let preview = build_preview(
+            &client,
+            &limits,
+            &bucket,
+            &key,
+            None,
+            PublicOrigin::for_account(&account, &public_url_cache).as_ref(),
+        )
+        .await?;
+        return Ok(PreviewSource::Inline { preview });
+    }
+
+    let url = presign_get_url(&client, &bucket, &key, PREVIEW_URL_EXPIRY_SECS).await?;
+    let expires_at =
+        chrono::Utc::now() + chrono::Duration::seconds(PREVIEW_URL_EXPIRY_SECS as i64);
+
+    Ok(PreviewSource::Url {
+        url,
+        content_type,
+        size,
+        expires_at: expires_at.to_rfc3339(),
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextRange {
+    pub content: String,
+    /// Byte offset the content actually starts at (snapped to a character boundary)
+    pub offset: u64,
+    /// Byte offset to request next for "load more"
+    pub next_offset: u64,
+    pub total_size: u64,
+    pub eof: bool,
+}
+
+fn is_utf8_continuation(byte: u8) -> bool {
+    byte & 0b1100_0000 == 0b1000_0000
+}
+
+/// Read a slice of a text object for incremental "load more" previews.
+/// The range is snapped to UTF-8 character boundaries: leading continuation bytes are
+/// skipped, and a character cut off at the end is completed rather than split, so
+/// consecutive calls using `next_offset` never break a multibyte character.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_text_range(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    account_id: String,
+    bucket: String,
+    key: String,
+    offset: u64,
+    length: Option<u64>,
+) -> Result<TextRange, AppError> {
+    let limits = db.get_preview_settings()?;
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    let head = client
+        .head_object()
+        .bucket(&bucket)
+        .key(&key)
+        .send()
+        .await?;
+    let total_size = head.content_length().unwrap_or(0).max(0) as u64;
+
+    let length = length
+        .unwrap_or(limits.max_text_preview_size as u64)
+        .clamp(1, limits.max_text_preview_size as u64);
+
+    if offset >= total_size {
+        return Ok(TextRange {
+            content: String::new(),
+            offset: total_size,
+            next_offset: total_size,
+            total_size,
+            eof: true,
+        });
+    }
+
+    // Fetch up to 3 extra bytes so a character cut off at the end can be completed
+    let end = (offset + length + 3).min(total_size) - 1;
+    let response = client
+        .get_object()
+        .bucket(&bucket)
+        .key(&key)
+        .range(format!("bytes={}-{}", offset, end))
+        .send()
+        .await?;
+    let body = response
+        .body
+        .collect()
+        .await
+        .map_err(|e| AppError::S3(format!("Failed to read body: {}", e)))?;
+    let bytes = body.into_bytes();
+
+    // Skip the tail of a character that started before the requested offset
+    let start = bytes
+        .iter()
+        .take(3)
+        .take_while(|b| is_utf8_continuation(**b))
+        .count();
+
+    // Don't split a multibyte character at the end of the range
+    // (at most 3 bytes past `length`, since that's all that was fetched)
+    let mut stop = (length as usize).min(bytes.len()).max(start);
+    while stop < bytes.len() && is_utf8_continuation(bytes[stop]) {
+        stop += 1;
+    }
+
+    let content = String::from_utf8_lossy(&bytes[start..stop]).to_string();
+    let next_offset = offset + stop as u64;
+
+    Ok(TextRange {
+        content,
+        offset: offset + start as u64,
+        next_offset,
+        total_size,
+        eof: next_offset >= total_size,
+    })
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ThumbnailData {
@@ -249,17 +1059,23 @@ pub struct ThumbnailData {
     pub mime_type: String,
     pub width: u32,
     pub height: u32,
+    /// Set when the image is served from the account's public base URL; `base64`
+    /// is then empty and the dimensions are 0 since the image wasn't decoded
+    pub url: Option<String>,
 }
 
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_thumbnail(
     credentials: State<'_, CredentialsManager>,
     s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    public_url_cache: State<'_, PublicUrlCache>,
     account_id: String,
     bucket: String,
     key: String,
     size: Option<u32>,
 ) -> Result<Option<ThumbnailData>, AppError> {
+    let limits = db.get_preview_settings()?;
     let account = credentials.get_account(&account_id)?;
     let secret = credentials.get_secret_key(&account_id)?;
 
@@ -271,14 +1087,41 @@ pub async fn get_thumbnail(
             &secret,
             account.provider_type,
             account.region.as_deref(),
+            &account.client_options(),
         )
         .await?;
 
+    let thumb_size = size.unwrap_or(DEFAULT_THUMBNAIL_SIZE);
+    build_thumbnail(
+        &client,
+        &db,
+        &limits,
+        &account_id,
+        PublicOrigin::for_account(&account, &public_url_cache).as_ref(),
+        &bucket,
+        &key,
+        thumb_size,
+    )
+    .await
+}
+
+/// Generate (or load from the cache) a JPEG thumbnail.
+/// Returns `None` for non-images, oversized sources and undecodable content.
+async fn build_thumbnail(
+    client: &aws_sdk_s3::Client,
+    db: &DbManager,
+    limits: &PreviewSettings,
+    account_id: &str,
+    public: Option<&PublicOrigin<'_>>,
+    bucket: &str,
+    key: &str,
+    thumb_size: u32,
+) -> Result<Option<ThumbnailData>, AppError> {
     // First, get metadata to check if this is an image and its size
     let head = client
         .head_object()
-        .bucket(&bucket)
-        .key(&key)
+        .bucket(bucket)
+        .key(key)
         .send()
         .await?;
 
@@ -288,7 +1131,7 @@ pub async fn get_thumbnail(
     let content_type = head
         .content_type()
         .map(|s| s.to_string())
-        .or_else(|| get_content_type_from_extension(&key).map(|s| s.to_string()))
+        .or_else(|| get_content_type_from_extension(key).map(|s| s.to_string()))
         .unwrap_or_else(|| "application/octet-stream".to_string());
 
     // Only process images
@@ -296,21 +1139,52 @@ pub async fn get_thumbnail(
         return Ok(None);
     }
 
+    // With a public domain the webview can load (and scale) the original directly
+    if let Some(public) = public.filter(|_| !needs_transcoding(&content_type)) {
+        if let Some(url) = public.url_for(key).await {
+            return Ok(Some(ThumbnailData {
+                base64: String::new(),
+                mime_type: content_type,
+                width: 0,
+                height: 0,
+                url: Some(url),
+            }));
+        }
+    }
+
     // Skip SVG - we can't resize them with image crate
     if content_type == "image/svg+xml" {
         return Ok(None);
     }
 
     // Check if source is too large
-    if file_size > MAX_THUMBNAIL_SOURCE_SIZE {
+    if file_size > limits.max_thumbnail_source_size {
         return Ok(None);
     }
 
+    // Serve from the local cache while the source object is unchanged
+    let etag = head.e_tag().map(|e| e.trim_matches('"').to_string());
+    if let Some(etag) = &etag {
+        match db.get_cached_thumbnail(account_id, bucket, key, thumb_size, etag, file_size) {
+            Ok(Some(cached)) => {
+                return Ok(Some(ThumbnailData {
+                    base64: cached.base64,
+                    mime_type: "image/jpeg".to_string(),
+                    width: cached.width,
+                    height: cached.height,
+                    url: None,
+                }))
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("Failed to read thumbnail cache: {}", e),
+        }
+    }
+
     // Fetch the image
     let response = client
         .get_object()
-        .bucket(&bucket)
-        .key(&key)
+        .bucket(bucket)
+        .key(key)
         .send()
         .await?;
 
@@ -321,31 +1195,351 @@ pub async fn get_thumbnail(
         .map_err(|e| AppError::S3(format!("Failed to read body: {}", e)))?;
     let bytes = body.into_bytes();
 
-    // Decode the image
-    let img = match image::load_from_memory(&bytes) {
-        Ok(img) => img,
-        Err(_) => return Ok(None), // Can't decode, skip thumbnail
+    let Some(cached) = render_thumbnail(&bytes, &content_type, thumb_size)? else {
+        return Ok(None); // Can't decode, skip thumbnail
     };
 
-    let thumb_size = size.unwrap_or(DEFAULT_THUMBNAIL_SIZE);
+    if let Some(etag) = &etag {
+        if let Err(e) = db.save_cached_thumbnail(
+            account_id,
+            bucket,
+            key,
+            thumb_size,
+            etag,
+            file_size,
+            &cached,
+        ) {
+            log::warn!("Failed to cache thumbnail: {}", e);
+        }
+    }
+
+    Ok(Some(ThumbnailData {
+        base64: cached.base64,
+        mime_type: "image/jpeg".to_string(),
+        width: cached.width,
+        height: cached.height,
+        url: None,
+    }))
+}
+
+/// Decode image bytes and scale them into a base64 JPEG thumbnail.
+/// `None` when the content can't be decoded in this build.
+fn render_thumbnail(
+    bytes: &[u8],
+    content_type: &str,
+    thumb_size: u32,
+) -> Result<Option<CachedThumbnail>, AppError> {
+    let Some(img) = decode_image(bytes, content_type) else {
+        return Ok(None);
+    };
 
     // Resize to thumbnail
     let thumbnail = img.thumbnail(thumb_size, thumb_size);
     let (width, height) = (thumbnail.width(), thumbnail.height());
 
-    // Encode as JPEG for smaller size
+    // Encode as JPEG for smaller size (JPEG has no alpha channel)
     let mut output = Cursor::new(Vec::new());
-    thumbnail
+    image::DynamicImage::ImageRgb8(thumbnail.to_rgb8())
         .write_to(&mut output, ImageFormat::Jpeg)
         .map_err(|e| AppError::S3(format!("Failed to encode thumbnail: {}", e)))?;
 
     use base64::Engine;
-    let base64 = base64::engine::general_purpose::STANDARD.encode(output.into_inner());
+    Ok(Some(CachedThumbnail {
+        base64: base64::engine::general_purpose::STANDARD.encode(output.into_inner()),
+        width,
+        height,
+    }))
+}
 
-    Ok(Some(ThumbnailData {
-        base64,
-        mime_type: "image/jpeg".to_string(),
+/// Generate thumbnails for a page of keys with bounded concurrency.
+/// Keys that can't be thumbnailed (or fail) map to `None`.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_thumbnails_batch(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    public_url_cache: State<'_, PublicUrlCache>,
+    account_id: String,
+    bucket: String,
+    keys: Vec<String>,
+    size: Option<u32>,
+) -> Result<HashMap<String, Option<ThumbnailData>>, AppError> {
+    let limits = db.get_preview_settings()?;
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    let thumb_size = size.unwrap_or(DEFAULT_THUMBNAIL_SIZE);
+    let client = &client;
+    let db: &DbManager = &db;
+    let limits = &limits;
+    let account_id = &account_id;
+    let public = PublicOrigin::for_account(&account, &public_url_cache);
+    let public = public.as_ref();
+    let bucket = &bucket;
+
+    let thumbnails = stream::iter(keys)
+        .map(|key| async move {
+            let thumbnail = match build_thumbnail(
+                client,
+                db,
+                limits,
+                account_id,
+                public,
+                bucket,
+                &key,
+                thumb_size,
+            )
+            .await
+            {
+                Ok(thumbnail) => thumbnail,
+                Err(e) => {
+                    log::warn!("Failed to generate thumbnail for {}: {}", key, e);
+                    None
+                }
+            };
+            (key, thumbnail)
+        })
+        .buffer_unordered(THUMBNAIL_BATCH_CONCURRENCY)
+        .collect()
+        .await;
+
+    Ok(thumbnails)
+}
+
+/// Remove cached thumbnails for one account, or for all accounts when omitted.
+/// Returns the number of thumbnails removed.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn clear_thumbnail_cache(
+    db: State<'_, DbManager>,
+    account_id: Option<String>,
+) -> Result<usize, AppError> {
+    db.clear_thumbnail_cache(account_id.as_deref())
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageInfo {
+    pub width: u32,
+    pub height: u32,
+    pub format: String,
+    pub exif: Option<ExifInfo>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExifInfo {
+    pub camera_make: Option<String>,
+    pub camera_model: Option<String>,
+    pub lens_model: Option<String>,
+    /// EXIF orientation (1-8), where 1 means no rotation
+    pub orientation: Option<u32>,
+    pub date_taken: Option<String>,
+    pub exposure_time: Option<String>,
+    pub f_number: Option<String>,
+    pub iso: Option<u32>,
+    pub focal_length: Option<String>,
+    pub gps: Option<GpsCoordinates>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GpsCoordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub altitude: Option<f64>,
+}
+
+fn exif_string(exif: &exif::Exif, tag: exif::Tag) -> Option<String> {
+    let field = exif.get_field(tag, exif::In::PRIMARY)?;
+    let value = match &field.value {
+        // Display quotes ASCII values, so read them directly
+        exif::Value::Ascii(parts) => parts
+            .first()
+            .map(|p| String::from_utf8_lossy(p).trim().to_string())?,
+        _ => field.display_value().with_unit(exif).to_string(),
+    };
+    (!value.is_empty()).then_some(value)
+}
+
+fn exif_uint(exif: &exif::Exif, tag: exif::Tag) -> Option<u32> {
+    exif.get_field(tag, exif::In::PRIMARY)?.value.get_uint(0)
+}
+
+/// Convert a degrees/minutes/seconds GPS field and its N/S/E/W reference to decimal degrees
+fn exif_gps_degrees(exif: &exif::Exif, tag: exif::Tag, ref_tag: exif::Tag) -> Option<f64> {
+    let field = exif.get_field(tag, exif::In::PRIMARY)?;
+    let degrees = match &field.value {
+        exif::Value::Rational(parts) if parts.len() >= 3 => {
+            parts[0].to_f64() + parts[1].to_f64() / 60.0 + parts[2].to_f64() / 3600.0
+        }
+        _ => return None,
+    };
+    let negative = matches!(exif_string(exif, ref_tag).as_deref(), Some("S") | Some("W"));
+    Some(if negative { -degrees } else { degrees })
+}
+
+fn read_exif(bytes: &[u8]) -> Option<ExifInfo> {
+    let exif = exif::Reader::new()
+        .read_from_container(&mut Cursor::new(bytes))
+        .ok()?;
+
+    let gps = match (
+        exif_gps_degrees(&exif, exif::Tag::GPSLatitude, exif::Tag::GPSLatitudeRef),
+        exif_gps_degrees(&exif, exif::Tag::GPSLongitude, exif::Tag::GPSLongitudeRef),
+    ) {
+        (Some(latitude), Some(longitude)) => Some(GpsCoordinates {
+            latitude,
+            longitude,
+            altitude: exif
+                .get_field(exif::Tag::GPSAltitude, exif::In::PRIMARY)
+                .and_then(|f| match &f.value {
+                    exif::Value::Rational(parts) => parts.first().map(|r| r.to_f64()),
+                    _ => None,
+                }),
+        }),
+        _ => None,
+    };
+
+    Some(ExifInfo {
+        camera_make: exif_string(&exif, exif::Tag::Make),
+        camera_model: exif_string(&exif, exif::Tag::Model),
+        lens_model: exif_string(&exif, exif::Tag::LensModel),
+        orientation: exif_uint(&exif, exif::Tag::Orientation),
+        date_taken: exif_string(&exif, exif::Tag::DateTimeOriginal),
+        exposure_time: exif_string(&exif, exif::Tag::ExposureTime),
+        f_number: exif_string(&exif, exif::Tag::FNumber),
+        iso: exif_uint(&exif, exif::Tag::PhotographicSensitivity),
+        focal_length: exif_string(&exif, exif::Tag::FocalLength),
+        gps,
+    })
+}
+
+/// Decode dimensions and format from the start of an image file
+fn read_image_header(bytes: &[u8]) -> Option<(u32, u32, ImageFormat)> {
+    let reader = image::ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()?;
+    let format = reader.format()?;
+    let (width, height) = reader.into_dimensions().ok()?;
+    Some((width, height, format))
+}
+
+/// Get dimensions, format and selected EXIF fields for an image.
+/// Only a ranged prefix of the file is fetched; the whole file is read only when the
+/// header isn't within the prefix and the image is small enough to thumbnail.
+/// Returns `None` for non-images or content that can't be decoded.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_image_info(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    db: State<'_, DbManager>,
+    account_id: String,
+    bucket: String,
+    key: String,
+) -> Result<Option<ImageInfo>, AppError> {
+    let limits = db.get_preview_settings()?;
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+
+    let client = s3_clients
+        .get_or_create_client(
+            &account_id,
+            &account.endpoint,
+            &account.access_key_id,
+            &secret,
+            account.provider_type,
+            account.region.as_deref(),
+            &account.client_options(),
+        )
+        .await?;
+
+    let head = client
+        .head_object()
+        .bucket(&bucket)
+        .key(&key)
+        .send()
+        .await?;
+
+    let file_size = head.content_length().unwrap_or(0);
+
+    let content_type = head
+        .content_type()
+        .map(|s| s.to_string())
+        .or_else(|| get_content_type_from_extension(&key).map(|s| s.to_string()))
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+
+    if !is_image_content_type(&content_type) || content_type == "image/svg+xml" {
+        return Ok(None);
+    }
+
+    let mut request = client.get_object().bucket(&bucket).key(&key);
+    if file_size > IMAGE_INFO_PREFIX_SIZE {
+        request = request.range(format!("bytes=0-{}", IMAGE_INFO_PREFIX_SIZE - 1));
+    }
+    let response = request.send().await?;
+    let mut bytes = response
+        .body
+        .collect()
+        .await
+        .map_err(|e| AppError::S3(format!("Failed to read body: {}", e)))?
+        .into_bytes();
+
+    let mut header = read_image_header(&bytes);
+    if header.is_none()
+        && file_size > IMAGE_INFO_PREFIX_SIZE
+        && file_size <= limits.max_thumbnail_source_size
+    {
+        let response = client.get_object().bucket(&bucket).key(&key).send().await?;
+        bytes = response
+            .body
+            .collect()
+            .await
+            .map_err(|e| AppError::S3(format!("Failed to read body: {}", e)))?
+            .into_bytes();
+        header = read_image_header(&bytes);
+    }
+
+    Ok(header.map(|(width, height, format)| ImageInfo {
         width,
         height,
+        format: format!("{:?}", format).to_lowercase(),
+        exif: read_exif(&bytes),
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tiff_thumbnail_decodes() {
+        let img = image::DynamicImage::ImageRgb8(image::RgbImage::new(400, 200));
+        let mut tiff = Cursor::new(Vec::new());
+        img.write_to(&mut tiff, ImageFormat::Tiff).unwrap();
+
+        let thumb = render_thumbnail(tiff.get_ref(), "image/tiff", 100)
+            .unwrap()
+            .expect("TIFF should decode");
+        assert_eq!((thumb.width, thumb.height), (100, 50));
+    }
+
+    #[cfg(not(feature = "heif"))]
+    #[test]
+    fn heic_thumbnail_skipped_without_heif_feature() {
+        // Just the `ftyp` box of a HEIC file; nothing here can decode it
+        let heic = b"\x00\x00\x00\x18ftypheic\x00\x00\x00\x00mif1heic";
+        assert!(render_thumbnail(heic, "image/heic", 100).unwrap().is_none());
+    }
+}
@@ -0,0 +1,60 @@
+use tauri::State;
+
+use crate::db::settings::{AppSettings, PreviewSettings, UploadSettings};
+use crate::db::DbManager;
+use crate::error::{AppError, Result};
+use crate::s3::retry::MAX_BULK_RETRIES;
+
+/// Get application settings
+#[tauri::command]
+pub async fn get_app_settings(db: State<'_, DbManager>) -> Result<AppSettings> {
+    db.get_app_settings()
+}
+
+/// Replace application settings
+#[tauri::command]
+pub async fn update_app_settings(
+    db: State<'_, DbManager>,
+    settings: AppSettings,
+) -> Result<AppSettings> {
+    if settings.bulk_max_retries > MAX_BULK_RETRIES {
+        return Err(AppError::InvalidInput(format!(
+            "Bulk retries cannot exceed {}",
+            MAX_BULK_RETRIES
+        )));
+    }
+    db.save_app_settings(&settings)?;
+    Ok(settings)
+}
+
+/// Get preview and thumbnail size limits
+#[tauri::command]
+pub async fn get_preview_settings(db: State<'_, DbManager>) -> Result<PreviewSettings> {
+    db.get_preview_settings()
+}
+
+/// Replace preview and thumbnail size limits
+#[tauri::command]
+pub async fn set_preview_settings(
+    db: State<'_, DbManager>,
+    settings: PreviewSettings,
+) -> Result<PreviewSettings> {
+    db.save_preview_settings(&settings)?;
+    Ok(settings)
+}
+
+/// Get multipart upload part size and threshold
+#[tauri::command]
+pub async fn get_upload_settings(db: State<'_, DbManager>) -> Result<UploadSettings> {
+    db.get_upload_settings()
+}
+
+/// Replace multipart upload part size and threshold
+#[tauri::command]
+pub async fn set_upload_settings(
+    db: State<'_, DbManager>,
+    settings: UploadSettings,
+) -> Result<UploadSettings> {
+    db.save_upload_settings(&settings)?;
+    Ok(settings)
+}
@@ -1,16 +1,22 @@
+use crate::commands::buckets::bucket_status_from_error;
 use crate::credentials::CredentialsManager;
 use crate::db::sync::{
-    ChangeType, DetectedChange, NewSyncPair, SyncDirection, SyncPair, SyncPairStatus, SyncPreview,
-    SyncSession,
+    ChangeType, DetectedChange, NewSyncPair, SyncDirection, SyncFileAction, SyncFileStatus,
+    SyncPair, SyncPairStatus, SyncPhaseTimings, SyncPreview, SyncSession, SyncSessionFile,
 };
 use crate::db::DbManager;
 use crate::error::AppError;
-use crate::s3::client::{extract_region_from_redirect_error, is_redirect_error, S3ClientManager};
+use crate::progress::ProgressThrottle;
+use crate::s3::client::{
+    extract_region_from_redirect_error, is_redirect_error, ClientOptions, S3ClientManager,
+};
+use md5::{Digest, Md5};
 use serde::Serialize;
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::RwLock;
 
@@ -51,6 +57,15 @@ pub struct SyncCompleteEvent {
     pub files_downloaded: i64,
     pub files_deleted_local: i64,
     pub files_deleted_remote: i64,
+    pub phase_timings: SyncPhaseTimings,
+}
+
+/// Result of a sync pair pre-flight check
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPairValidation {
+    /// Whether anything is stored under the remote prefix yet
+    pub remote_has_objects: bool,
 }
 
 /// Error event for sync
@@ -75,6 +90,10 @@ pub async fn create_sync_pair(
     remote_prefix: String,
     sync_direction: String,
     delete_propagation: bool,
+    follow_symlinks: Option<bool>,
+    prune_empty_dirs: Option<bool>,
+    max_file_size_bytes: Option<i64>,
+    verify: Option<bool>,
 ) -> Result<SyncPair, AppError> {
     // Validate local path exists
     let path = Path::new(&local_path);
@@ -91,7 +110,14 @@ pub async fn create_sync_pair(
         )));
     }
 
+    if max_file_size_bytes.map_or(false, |max| max <= 0) {
+        return Err(AppError::InvalidInput(
+            "Max file size must be greater than zero".to_string(),
+        ));
+    }
+
     let direction = SyncDirection::try_from(sync_direction.as_str())?;
+    let remote_prefix = remote_prefix.trim_end_matches('/').to_string();
 
     let pair_id = db.create_sync_pair(&NewSyncPair {
         name,
@@ -101,12 +127,69 @@ pub async fn create_sync_pair(
         remote_prefix,
         sync_direction: direction,
         delete_propagation,
+        follow_symlinks: follow_symlinks.unwrap_or(true),
+        prune_empty_dirs: prune_empty_dirs.unwrap_or(false),
+        max_file_size_bytes,
+        verify: verify.unwrap_or(false),
     })?;
 
     db.get_sync_pair(pair_id)?
         .ok_or_else(|| AppError::Storage("Failed to retrieve created sync pair".to_string()))
 }
 
+/// Check that a pair's bucket and remote prefix can be listed before the pair
+/// is created, so access problems surface here rather than in the first sync
+#[tauri::command(rename_all = "camelCase")]
+pub async fn validate_sync_pair(
+    credentials: State<'_, CredentialsManager>,
+    s3_clients: State<'_, S3ClientManager>,
+    account_id: String,
+    bucket: String,
+    remote_prefix: String,
+) -> Result<SyncPairValidation, AppError> {
+    let account = credentials.get_account(&account_id)?;
+    let secret = credentials.get_secret_key(&account_id)?;
+    // Fails with a clear error if the bucket is missing, forbidden or in another region
+    let client = get_bucket_client(
+        &s3_clients,
+        &account_id,
+        &bucket,
+        &account.endpoint,
+        &account.access_key_id,
+        &secret,
+        account.provider_type,
+        account.region.as_deref(),
+        &account.client_options(),
+    )
+    .await?;
+
+    // HeadBucket can succeed while listing is still denied, so list the prefix itself
+    let response = client
+        .list_objects_v2()
+        .bucket(&bucket)
+        .prefix(remote_list_prefix(&remote_prefix))
+        .max_keys(1)
+        .send()
+        .await
+        .map_err(|e| {
+            if e.raw_response()
+                .map_or(false, |r| r.status().as_u16() == 403)
+            {
+                return AppError::AccessDenied(format!(
+                    "Listing '{}' in bucket '{}' is denied for this account",
+                    remote_prefix, bucket
+                ));
+            }
+            bucket_status_from_error(&bucket, &e)
+                .and_then(|status| status.to_error())
+                .unwrap_or_else(|| e.into())
+        })?;
+
+    Ok(SyncPairValidation {
+        remote_has_objects: !response.contents().is_empty(),
+    })
+}
+
 /// Get a sync pair by ID
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_sync_pair(
@@ -158,6 +241,7 @@ pub async fn preview_sync(
         &secret,
         account.provider_type,
         account.region.as_deref(),
+        &account.client_options(),
     )
     .await?;
 
@@ -175,6 +259,7 @@ pub async fn preview_sync(
         to_download: Vec::new(),
         to_delete_local: Vec::new(),
         to_delete_remote: Vec::new(),
+        skipped_too_large: Vec::new(),
     };
 
     match pair.sync_direction {
@@ -238,6 +323,17 @@ pub async fn preview_sync(
         }
     }
 
+    preview.to_upload = without_oversized(
+        std::mem::take(&mut preview.to_upload),
+        pair.max_file_size_bytes,
+        &mut preview.skipped_too_large,
+    );
+    preview.to_download = without_oversized(
+        std::mem::take(&mut preview.to_download),
+        pair.max_file_size_bytes,
+        &mut preview.skipped_too_large,
+    );
+
     Ok(preview)
 }
 
@@ -294,6 +390,7 @@ pub async fn start_sync(
         &secret,
         account.provider_type,
         account.region.as_deref(),
+        &account.client_options(),
     )
     .await?;
 
@@ -376,8 +473,65 @@ pub async fn get_sync_sessions(
     db.get_sync_sessions(pair_id, limit.unwrap_or(20))
 }
 
+/// Get the per-file log of a sync session
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_sync_session_files(
+    db: State<'_, DbManager>,
+    session_id: i64,
+) -> Result<Vec<SyncSessionFile>, AppError> {
+    db.get_sync_session_files(session_id)
+}
+
 // ==================== Helper Functions ====================
 
+/// Add a file to the session's log. A failure to log is only warned about so
+/// it never aborts the sync itself.
+fn log_session_file(
+    db: &DbManager,
+    session_id: i64,
+    relative_path: &str,
+    action: SyncFileAction,
+    size: Option<i64>,
+    status: SyncFileStatus,
+    error: Option<&str>,
+) {
+    if let Err(e) = db.log_sync_session_file(session_id, relative_path, action, size, status, error)
+    {
+        log::warn!("Failed to log sync file '{}': {}", relative_path, e);
+    }
+}
+
+/// Log the outcome of a file transfer or deletion and pass it through
+fn log_file_result(
+    db: &DbManager,
+    session_id: i64,
+    relative_path: &str,
+    action: SyncFileAction,
+    result: Result<Option<i64>, AppError>,
+) -> Result<Option<i64>, AppError> {
+    match &result {
+        Ok(size) => log_session_file(
+            db,
+            session_id,
+            relative_path,
+            action,
+            *size,
+            SyncFileStatus::Completed,
+            None,
+        ),
+        Err(e) => log_session_file(
+            db,
+            session_id,
+            relative_path,
+            action,
+            None,
+            SyncFileStatus::Failed,
+            Some(&e.to_string()),
+        ),
+    }
+    result
+}
+
 /// Get an S3 client for a bucket, handling region detection via redirect errors
 /// This tries to access the bucket and if it gets a PermanentRedirect, extracts the
 /// correct region and creates a new client
@@ -390,6 +544,7 @@ async fn get_bucket_client(
     secret_access_key: &str,
     provider_type: crate::provider::ProviderType,
     region: Option<&str>,
+    options: &ClientOptions,
 ) -> Result<Arc<aws_sdk_s3::Client>, AppError> {
     // First, try to get or create the bucket-specific client
     let client = s3_clients
@@ -401,6 +556,7 @@ async fn get_bucket_client(
             secret_access_key,
             provider_type,
             region,
+            options,
         )
         .await?;
 
@@ -422,8 +578,10 @@ async fn get_bucket_client(
                     return Ok(new_client);
                 }
             }
-            // If we couldn't extract region or it's a different error, return the original error
-            Err(AppError::S3(error_str))
+            // Report a missing or forbidden bucket plainly; anything else as the raw error
+            Err(bucket_status_from_error(bucket, &e)
+                .and_then(|status| status.to_error())
+                .unwrap_or_else(|| e.into()))
         }
     }
 }
@@ -437,7 +595,7 @@ async fn scan_current_state(
     _pair_id: i64,
 ) -> Result<(HashMap<String, DetectedChange>, HashMap<String, DetectedChange>), AppError> {
     // Scan local files
-    let local_current = scan_local_files(&pair.local_path)?;
+    let local_current = scan_local_files(&pair.local_path, pair.follow_symlinks)?;
 
     // Scan remote files
     let remote_current = scan_remote_files(client, &pair.bucket, &pair.remote_prefix).await?;
@@ -445,8 +603,17 @@ async fn scan_current_state(
     Ok((local_current, remote_current))
 }
 
-/// Scan local directory for files
-fn scan_local_files(base_path: &str) -> Result<HashMap<String, DetectedChange>, AppError> {
+/// Scan local directory for files.
+///
+/// Symlinks are skipped unless `follow_symlinks` is set, in which case a
+/// linked file is synced under the link's path and a linked directory is
+/// scanned as if it were a real one. A directory that links back to one of
+/// its own ancestors is a cycle and is skipped, as are broken links. Sockets,
+/// FIFOs and devices can't be uploaded and are skipped with a warning.
+fn scan_local_files(
+    base_path: &str,
+    follow_symlinks: bool,
+) -> Result<HashMap<String, DetectedChange>, AppError> {
     let mut files = HashMap::new();
     let base = Path::new(base_path);
 
@@ -461,6 +628,8 @@ fn scan_local_files(base_path: &str) -> Result<HashMap<String, DetectedChange>,
     fn scan_dir(
         base: &Path,
         current: &Path,
+        follow_symlinks: bool,
+        ancestors: &mut Vec<std::path::PathBuf>,
         files: &mut HashMap<String, DetectedChange>,
     ) -> Result<(), AppError> {
         let entries = std::fs::read_dir(current)
@@ -471,18 +640,50 @@ fn scan_local_files(base_path: &str) -> Result<HashMap<String, DetectedChange>,
                 entry.map_err(|e| AppError::Storage(format!("Failed to read entry: {}", e)))?;
             let path = entry.path();
 
-            if path.is_dir() {
-                scan_dir(base, &path, files)?;
-            } else if path.is_file() {
+            // file_type() describes the entry itself, without following symlinks
+            let file_type = entry
+                .file_type()
+                .map_err(|e| AppError::Storage(format!("Failed to get file type: {}", e)))?;
+            let metadata = if file_type.is_symlink() {
+                if !follow_symlinks {
+                    log::debug!("Skipping symlink '{}'", path.display());
+                    continue;
+                }
+                match std::fs::metadata(&path) {
+                    Ok(metadata) => metadata,
+                    Err(e) => {
+                        log::warn!("Skipping broken symlink '{}': {}", path.display(), e);
+                        continue;
+                    }
+                }
+            } else {
+                entry
+                    .metadata()
+                    .map_err(|e| AppError::Storage(format!("Failed to get metadata: {}", e)))?
+            };
+
+            if metadata.is_dir() {
+                let canonical = std::fs::canonicalize(&path).map_err(|e| {
+                    AppError::Storage(format!(
+                        "Failed to resolve directory '{}': {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+                if ancestors.contains(&canonical) {
+                    log::warn!("Skipping symlink cycle at '{}'", path.display());
+                    continue;
+                }
+                ancestors.push(canonical);
+                scan_dir(base, &path, follow_symlinks, ancestors, files)?;
+                ancestors.pop();
+            } else if metadata.is_file() {
                 let relative = path
                     .strip_prefix(base)
                     .map_err(|e| AppError::Storage(format!("Failed to get relative path: {}", e)))?
                     .to_string_lossy()
                     .to_string();
 
-                let metadata = std::fs::metadata(&path)
-                    .map_err(|e| AppError::Storage(format!("Failed to get metadata: {}", e)))?;
-
                 let mtime = metadata
                     .modified()
                     .ok()
@@ -499,16 +700,113 @@ fn scan_local_files(base_path: &str) -> Result<HashMap<String, DetectedChange>,
                         hash: None, // We don't compute hash during scan for performance
                     },
                 );
+            } else {
+                log::warn!("Skipping special file '{}'", path.display());
             }
         }
 
         Ok(())
     }
 
-    scan_dir(base, base, &mut files)?;
+    let root = std::fs::canonicalize(base).map_err(|e| {
+        AppError::Storage(format!("Failed to resolve local folder '{}': {}", base_path, e))
+    })?;
+    scan_dir(base, base, follow_symlinks, &mut vec![root], &mut files)?;
     Ok(files)
 }
 
+/// Check an uploaded object against the local file it came from: the sizes
+/// must match, and so must the ETag and MD5 unless the object is encrypted in
+/// a way (SSE-KMS, SSE-C) that makes its ETag something other than the MD5
+async fn verify_upload(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+    size: i64,
+    md5: &str,
+) -> Result<(), AppError> {
+    let head = client.head_object().bucket(bucket).key(key).send().await?;
+
+    let remote_size = head.content_length().unwrap_or(0);
+    if remote_size != size {
+        return Err(AppError::S3(format!(
+            "Verification failed: uploaded {} bytes but the object is {} bytes",
+            size, remote_size
+        )));
+    }
+
+    let etag_is_md5 = head.sse_customer_algorithm().is_none()
+        && !head
+            .server_side_encryption()
+            .map_or(false, |sse| sse.as_str().starts_with("aws:kms"));
+    let etag = head.e_tag().unwrap_or_default().trim_matches('"');
+    if etag_is_md5 && !etag.eq_ignore_ascii_case(md5) {
+        return Err(AppError::S3(format!(
+            "Verification failed: object ETag {} does not match the file's MD5 {}",
+            etag, md5
+        )));
+    }
+
+    Ok(())
+}
+
+/// Drop changes larger than `max_size` from `changes`, moving them to `skipped`
+fn without_oversized(
+    changes: Vec<DetectedChange>,
+    max_size: Option<i64>,
+    skipped: &mut Vec<DetectedChange>,
+) -> Vec<DetectedChange> {
+    let Some(max_size) = max_size else {
+        return changes;
+    };
+    let (oversized, kept): (Vec<_>, Vec<_>) = changes
+        .into_iter()
+        .partition(|change| change.size.map_or(false, |size| size > max_size));
+    skipped.extend(oversized);
+    kept
+}
+
+/// Remove directories under `root` that deletions left empty, along with any
+/// parents that become empty in turn. `remove_dir` only succeeds on an empty
+/// directory, so a directory still holding anything (including files the sync
+/// never tracks, like hidden or special files) is kept, and so are its parents.
+/// The root itself is never removed.
+fn prune_empty_dirs(root: &Path, dirs: std::collections::HashSet<std::path::PathBuf>) {
+    let mut dirs: Vec<_> = dirs.into_iter().collect();
+    // Deepest first, so children go before the parents they may have kept non-empty
+    dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+
+    for dir in dirs {
+        let mut current = dir.as_path();
+        while current != root && current.starts_with(root) {
+            match std::fs::remove_dir(current) {
+                Ok(()) => log::debug!("Removed empty directory '{}'", current.display()),
+                // Not empty, already gone via a deeper entry, or not removable
+                Err(_) => break,
+            }
+            match current.parent() {
+                Some(parent) => current = parent,
+                None => break,
+            }
+        }
+    }
+}
+
+/// The S3 prefix holding a pair's files: the remote prefix with any trailing
+/// slashes replaced by exactly one, or empty for the bucket root. `"foo"` and
+/// `"foo/"` both give `"foo/"`, so `foo/bar/baz.txt` is `bar/baz.txt` either way.
+fn remote_list_prefix(remote_prefix: &str) -> String {
+    match remote_prefix.trim_end_matches('/') {
+        "" => String::new(),
+        trimmed => format!("{}/", trimmed),
+    }
+}
+
+/// Object key for a file synced under `remote_prefix`
+fn remote_key(remote_prefix: &str, relative_path: &str) -> String {
+    format!("{}{}", remote_list_prefix(remote_prefix), relative_path)
+}
+
 /// Scan remote S3 prefix for files
 async fn scan_remote_files(
     client: &aws_sdk_s3::Client,
@@ -517,14 +815,13 @@ async fn scan_remote_files(
 ) -> Result<HashMap<String, DetectedChange>, AppError> {
     let mut files = HashMap::new();
     let mut continuation_token: Option<String> = None;
-
-    let prefix_len = if prefix.is_empty() { 0 } else { prefix.len() + 1 }; // +1 for trailing /
+    let list_prefix = remote_list_prefix(prefix);
 
     loop {
         let mut request = client.list_objects_v2().bucket(bucket);
 
-        if !prefix.is_empty() {
-            request = request.prefix(format!("{}/", prefix));
+        if !list_prefix.is_empty() {
+            request = request.prefix(&list_prefix);
         }
 
         if let Some(token) = &continuation_token {
@@ -541,11 +838,11 @@ async fn scan_remote_files(
                 }
 
                 // Get relative path (strip prefix and any leading slashes)
-                let relative = if prefix_len > 0 && key.len() > prefix_len {
-                    key[prefix_len..].trim_start_matches('/').to_string()
-                } else {
-                    key.trim_start_matches('/').to_string()
-                };
+                let relative = key
+                    .strip_prefix(list_prefix.as_str())
+                    .unwrap_or(key)
+                    .trim_start_matches('/')
+                    .to_string();
 
                 let mtime = obj
                     .last_modified()
@@ -661,8 +958,10 @@ async fn run_sync(
     );
 
     // Scan current state
+    let listing_started = Instant::now();
     let (local_current, remote_current) =
         scan_current_state(app, client, db, pair, pair_id).await?;
+    let listing_ms = listing_started.elapsed().as_millis() as i64;
 
     if cancel_flag.load(Ordering::Relaxed) {
         return Ok(());
@@ -767,6 +1066,29 @@ async fn run_sync(
         return Ok(());
     }
 
+    // Oversized files are left untracked, so they come up (and are skipped) again next sync
+    let mut too_large = Vec::new();
+    let to_upload = without_oversized(to_upload, pair.max_file_size_bytes, &mut too_large);
+    let to_download = without_oversized(to_download, pair.max_file_size_bytes, &mut too_large);
+    if let Some(max) = pair.max_file_size_bytes {
+        let reason = format!("Larger than the pair's {} byte limit", max);
+        let action = match pair.sync_direction {
+            SyncDirection::UploadOnly => SyncFileAction::Upload,
+            SyncDirection::DownloadOnly => SyncFileAction::Download,
+        };
+        for change in &too_large {
+            log_session_file(
+                db,
+                session_id,
+                &change.relative_path,
+                action,
+                change.size,
+                SyncFileStatus::Skipped,
+                Some(&reason),
+            );
+        }
+    }
+
     // Ensure base local directory exists for download operations
     if !to_download.is_empty() {
         tokio::fs::create_dir_all(&pair.local_path)
@@ -788,6 +1110,9 @@ async fn run_sync(
     let mut files_downloaded = 0i64;
     let mut files_deleted_local = 0i64;
     let mut files_deleted_remote = 0i64;
+    let mut progress = ProgressThrottle::new();
+    let transfer_started = Instant::now();
+    let mut hashing = Duration::ZERO;
 
     // Upload files
     for change in &to_upload {
@@ -795,43 +1120,65 @@ async fn run_sync(
             return Ok(());
         }
 
-        let _ = app.emit(
-            "sync-progress",
-            SyncProgressEvent {
-                pair_id,
-                session_id,
-                phase: "uploading".to_string(),
-                current_file: Some(change.relative_path.clone()),
-                files_processed: processed,
-                total_files: total_ops as i64,
-                bytes_transferred,
-            },
-        );
+        if progress.ready(false) {
+            let _ = app.emit(
+                "sync-progress",
+                SyncProgressEvent {
+                    pair_id,
+                    session_id,
+                    phase: "uploading".to_string(),
+                    current_file: Some(change.relative_path.clone()),
+                    files_processed: processed,
+                    total_files: total_ops as i64,
+                    bytes_transferred,
+                },
+            );
+        }
 
         // Strip leading slash from relative path to prevent it from becoming an absolute path
         let relative = change.relative_path.trim_start_matches('/');
         let local_path = Path::new(&pair.local_path).join(relative);
-        let remote_key = if pair.remote_prefix.is_empty() {
-            relative.to_string()
-        } else {
-            format!("{}/{}", pair.remote_prefix, relative)
-        };
+        let remote_key = remote_key(&pair.remote_prefix, relative);
 
-        // Read file content
-        let content = tokio::fs::read(&local_path)
-            .await
-            .map_err(|e| AppError::Storage(format!("Failed to read file '{}': {}", local_path.display(), e)))?;
+        let result = async {
+            // Read file content
+            let content = tokio::fs::read(&local_path).await.map_err(|e| {
+                AppError::Storage(format!(
+                    "Failed to read file '{}': {}",
+                    local_path.display(),
+                    e
+                ))
+            })?;
 
-        let size = content.len() as i64;
+            let size = content.len() as i64;
+            let hashing_started = Instant::now();
+            let md5 = pair.verify.then(|| hex::encode(Md5::digest(&content)));
+            hashing += hashing_started.elapsed();
+
+            // Upload to S3
+            client
+                .put_object()
+                .bucket(&pair.bucket)
+                .key(&remote_key)
+                .body(content.into())
+                .send()
+                .await?;
+
+            if let Some(md5) = md5 {
+                verify_upload(client, &pair.bucket, &remote_key, size, &md5).await?;
+            }
 
-        // Upload to S3
-        client
-            .put_object()
-            .bucket(&pair.bucket)
-            .key(&remote_key)
-            .body(content.into())
-            .send()
-            .await?;
+            Ok(Some(size))
+        }
+        .await;
+        let size = log_file_result(
+            db,
+            session_id,
+            &change.relative_path,
+            SyncFileAction::Upload,
+            result,
+        )?
+        .unwrap_or(0);
 
         bytes_transferred += size;
         files_uploaded += 1;
@@ -853,34 +1200,49 @@ async fn run_sync(
         db.save_remote_file_state(pair_id, &change.relative_path, size, None, None, None)?;
     }
 
-    // Download files
-    for change in &to_download {
-        if cancel_flag.load(Ordering::Relaxed) {
-            return Ok(());
-        }
-
+    // Always report where the phase ended, even inside the throttle window
+    if !to_upload.is_empty() {
+        progress.ready(true);
         let _ = app.emit(
             "sync-progress",
             SyncProgressEvent {
                 pair_id,
                 session_id,
-                phase: "downloading".to_string(),
-                current_file: Some(change.relative_path.clone()),
+                phase: "uploading".to_string(),
+                current_file: None,
                 files_processed: processed,
                 total_files: total_ops as i64,
                 bytes_transferred,
             },
         );
+    }
+
+    // Download files
+    for change in &to_download {
+        if cancel_flag.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        if progress.ready(false) {
+            let _ = app.emit(
+                "sync-progress",
+                SyncProgressEvent {
+                    pair_id,
+                    session_id,
+                    phase: "downloading".to_string(),
+                    current_file: Some(change.relative_path.clone()),
+                    files_processed: processed,
+                    total_files: total_ops as i64,
+                    bytes_transferred,
+                },
+            );
+        }
 
         // Strip leading slash from relative path to prevent it from becoming an absolute path
         let relative = change.relative_path.trim_start_matches('/');
         let local_path = Path::new(&pair.local_path).join(relative);
 
-        let remote_key = if pair.remote_prefix.is_empty() {
-            relative.to_string()
-        } else {
-            format!("{}/{}", pair.remote_prefix, relative)
-        };
+        let remote_key = remote_key(&pair.remote_prefix, relative);
 
         // Download from S3
         let response = match client
@@ -892,43 +1254,106 @@ async fn run_sync(
         {
             Ok(resp) => resp,
             Err(e) => {
-                // Check if it's a NoSuchKey error - file may have been deleted since scan
-                let err_str = format!("{:?}", e);
-                if err_str.contains("NoSuchKey") {
-                    // File no longer exists in S3, skip it
+                let error = AppError::from(e);
+                // The object may have been deleted since the scan; skip it
+                if let AppError::NoSuchKey(_) = error {
+                    log_session_file(
+                        db,
+                        session_id,
+                        &change.relative_path,
+                        SyncFileAction::Download,
+                        None,
+                        SyncFileStatus::Skipped,
+                        Some("Object no longer exists"),
+                    );
                     processed += 1;
                     continue;
                 }
-                return Err(e.into());
+                log_session_file(
+                    db,
+                    session_id,
+                    &change.relative_path,
+                    SyncFileAction::Download,
+                    None,
+                    SyncFileStatus::Failed,
+                    Some(&error.to_string()),
+                );
+                return Err(error);
             }
         };
 
-        let content = response
-            .body
-            .collect()
-            .await
-            .map_err(|e| AppError::S3(format!("Failed to read body: {:?}", e)))?
-            .into_bytes();
-
-        let size = content.len() as i64;
+        let expected_size = response.content_length();
+        let result = async {
+            let content = response
+                .body
+                .collect()
+                .await
+                .map_err(|e| AppError::S3(format!("Failed to read body: {}", e)))?
+                .into_bytes();
+
+            let size = content.len() as i64;
+
+            // Ensure parent directory exists
+            if let Some(parent) = local_path.parent() {
+                tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                    AppError::Storage(format!(
+                        "Failed to create directory '{}': {}",
+                        parent.display(),
+                        e
+                    ))
+                })?;
+            }
 
-        // Ensure parent directory exists
-        if let Some(parent) = local_path.parent() {
-            tokio::fs::create_dir_all(parent)
+            // Write to local file
+            tokio::fs::write(&local_path, content)
                 .await
-                .map_err(|e| AppError::Storage(format!("Failed to create directory '{}': {}", parent.display(), e)))?;
-        }
+                .map_err(|e| AppError::Storage(format!("Failed to write file: {}", e)))?;
+
+            if pair.verify {
+                let written = tokio::fs::metadata(&local_path)
+                    .await
+                    .map(|m| m.len() as i64)
+                    .map_err(|e| AppError::Storage(format!("Failed to verify file: {}", e)))?;
+                if expected_size.map_or(false, |expected| expected != written) {
+                    return Err(AppError::Storage(format!(
+                        "Verification failed: wrote {} bytes but the object is {} bytes",
+                        written,
+                        expected_size.unwrap_or(0)
+                    )));
+                }
+            }
 
-        // Write to local file
-        tokio::fs::write(&local_path, content)
-            .await
-            .map_err(|e| AppError::Storage(format!("Failed to write file: {}", e)))?;
+            Ok(Some(size))
+        }
+        .await;
+        let size = log_file_result(
+            db,
+            session_id,
+            &change.relative_path,
+            SyncFileAction::Download,
+            result,
+        )?
+        .unwrap_or(0);
 
         bytes_transferred += size;
         files_downloaded += 1;
         processed += 1;
 
-        // Update tracked state
+        // Give the file the object's last-modified time so the next scan doesn't
+        // see it as locally changed. Failing to set it only costs a re-upload check.
+        if let Some(remote_mtime) = change.mtime {
+            let file_time = filetime::FileTime::from_unix_time(
+                remote_mtime.div_euclid(1000),
+                (remote_mtime.rem_euclid(1000) * 1_000_000) as u32,
+            );
+            if let Err(e) = filetime::set_file_mtime(&local_path, file_time) {
+                log::warn!("Failed to set mtime of '{}': {}", local_path.display(), e);
+            }
+        }
+
+        // Update tracked state. Read the mtime back rather than storing the remote
+        // value: filesystems round it differently (FAT to 2s, others to 1s or finer),
+        // and the next scan compares against what the filesystem reports, in ms.
         let mtime = std::fs::metadata(&local_path)
             .ok()
             .and_then(|m| m.modified().ok())
@@ -948,7 +1373,25 @@ async fn run_sync(
         )?;
     }
 
+    // Always report where the phase ended, even inside the throttle window
+    if !to_download.is_empty() {
+        progress.ready(true);
+        let _ = app.emit(
+            "sync-progress",
+            SyncProgressEvent {
+                pair_id,
+                session_id,
+                phase: "downloading".to_string(),
+                current_file: None,
+                files_processed: processed,
+                total_files: total_ops as i64,
+                bytes_transferred,
+            },
+        );
+    }
+
     // Delete local files
+    let mut emptied_dirs = std::collections::HashSet::new();
     for change in &to_delete_local {
         if cancel_flag.load(Ordering::Relaxed) {
             return Ok(());
@@ -958,10 +1401,23 @@ async fn run_sync(
         let relative = change.relative_path.trim_start_matches('/');
         let local_path = Path::new(&pair.local_path).join(relative);
 
-        if local_path.exists() {
+        let result = if local_path.exists() {
             tokio::fs::remove_file(&local_path)
                 .await
-                .map_err(|e| AppError::Storage(format!("Failed to delete file: {}", e)))?;
+                .map(|_| change.size)
+                .map_err(|e| AppError::Storage(format!("Failed to delete file: {}", e)))
+        } else {
+            Ok(change.size)
+        };
+        log_file_result(
+            db,
+            session_id,
+            &change.relative_path,
+            SyncFileAction::DeleteLocal,
+            result,
+        )?;
+        if let Some(parent) = local_path.parent() {
+            emptied_dirs.insert(parent.to_path_buf());
         }
 
         // Mark both local and remote as deleted since they're now in sync (both deleted)
@@ -971,6 +1427,10 @@ async fn run_sync(
         processed += 1;
     }
 
+    if pair.prune_empty_dirs {
+        prune_empty_dirs(Path::new(&pair.local_path), emptied_dirs);
+    }
+
     // Delete remote files
     for change in &to_delete_remote {
         if cancel_flag.load(Ordering::Relaxed) {
@@ -979,18 +1439,23 @@ async fn run_sync(
 
         // Strip leading slash from relative path
         let relative = change.relative_path.trim_start_matches('/');
-        let remote_key = if pair.remote_prefix.is_empty() {
-            relative.to_string()
-        } else {
-            format!("{}/{}", pair.remote_prefix, relative)
-        };
+        let remote_key = remote_key(&pair.remote_prefix, relative);
 
-        client
+        let result = client
             .delete_object()
             .bucket(&pair.bucket)
             .key(&remote_key)
             .send()
-            .await?;
+            .await
+            .map(|_| change.size)
+            .map_err(AppError::from);
+        log_file_result(
+            db,
+            session_id,
+            &change.relative_path,
+            SyncFileAction::DeleteRemote,
+            result,
+        )?;
 
         // Mark both local and remote as deleted since they're now in sync (both deleted)
         db.mark_local_file_deleted(pair_id, &change.relative_path)?;
@@ -1005,12 +1470,30 @@ async fn run_sync(
         // Local file was deleted but we're not propagating to remote
         // Mark local as deleted so we don't keep detecting it
         db.mark_local_file_deleted(pair_id, &change.relative_path)?;
+        log_session_file(
+            db,
+            session_id,
+            &change.relative_path,
+            SyncFileAction::DeleteRemote,
+            change.size,
+            SyncFileStatus::Skipped,
+            Some("Delete propagation is disabled"),
+        );
     }
 
     for change in &skipped_remote_deletions {
         // Remote file was deleted but we're not propagating to local
         // Mark remote as deleted so we don't keep detecting it
         db.mark_remote_file_deleted(pair_id, &change.relative_path)?;
+        log_session_file(
+            db,
+            session_id,
+            &change.relative_path,
+            SyncFileAction::DeleteLocal,
+            change.size,
+            SyncFileStatus::Skipped,
+            Some("Delete propagation is disabled"),
+        );
     }
 
     // Update session with final stats
@@ -1023,8 +1506,17 @@ async fn run_sync(
         bytes_transferred,
     )?;
 
+    let phase_timings = SyncPhaseTimings {
+        listing_ms,
+        hashing_ms: hashing.as_millis() as i64,
+        transferring_ms: transfer_started
+            .elapsed()
+            .saturating_sub(hashing)
+            .as_millis() as i64,
+    };
+
     // Complete
-    db.complete_sync_session(session_id)?;
+    db.complete_sync_session(session_id, &phase_timings)?;
     db.mark_sync_completed(pair_id)?;
 
     let _ = app.emit(
@@ -1036,8 +1528,84 @@ async fn run_sync(
             files_downloaded,
             files_deleted_local,
             files_deleted_remote,
+            phase_timings,
         },
     );
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh, empty directory under the system temp dir
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("bucket-scout-{}-{}", name, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn scan_follows_symlinks_without_looping() {
+        let root = temp_dir("symlink-loop");
+        std::fs::create_dir(root.join("docs")).unwrap();
+        std::fs::write(root.join("docs/a.txt"), b"a").unwrap();
+        std::fs::write(root.join("b.txt"), b"b").unwrap();
+        // docs/back -> root is a cycle; linked.txt -> b.txt is a plain file link
+        std::os::unix::fs::symlink(&root, root.join("docs/back")).unwrap();
+        std::os::unix::fs::symlink(root.join("b.txt"), root.join("linked.txt")).unwrap();
+
+        let followed = scan_local_files(root.to_str().unwrap(), true).unwrap();
+        let mut paths: Vec<_> = followed.keys().cloned().collect();
+        paths.sort();
+        assert_eq!(paths, ["b.txt", "docs/a.txt", "linked.txt"]);
+
+        let skipped = scan_local_files(root.to_str().unwrap(), false).unwrap();
+        let mut paths: Vec<_> = skipped.keys().cloned().collect();
+        paths.sort();
+        assert_eq!(paths, ["b.txt", "docs/a.txt"]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn remote_list_prefix_normalizes_trailing_slashes() {
+        assert_eq!(remote_list_prefix(""), "");
+        assert_eq!(remote_list_prefix("/"), "");
+        assert_eq!(remote_list_prefix("foo"), "foo/");
+        assert_eq!(remote_list_prefix("foo/"), "foo/");
+        assert_eq!(remote_list_prefix("foo//"), "foo/");
+        assert_eq!(remote_list_prefix("foo/bar"), "foo/bar/");
+    }
+
+    #[test]
+    fn remote_key_joins_prefix_and_relative_path() {
+        assert_eq!(remote_key("", "bar/baz.txt"), "bar/baz.txt");
+        assert_eq!(remote_key("foo", "bar/baz.txt"), "foo/bar/baz.txt");
+        assert_eq!(remote_key("foo/", "bar/baz.txt"), "foo/bar/baz.txt");
+    }
+
+    #[test]
+    fn prune_removes_nested_empty_dirs_but_keeps_root_and_non_empty_dirs() {
+        let root = temp_dir("prune");
+        std::fs::create_dir_all(root.join("a/b/c")).unwrap();
+        std::fs::create_dir_all(root.join("keep/empty")).unwrap();
+        std::fs::write(root.join("keep/file.txt"), b"kept").unwrap();
+
+        // Parents of deleted files, as collected by a sync run
+        let dirs = [root.join("a/b/c"), root.join("keep/empty"), root.clone()]
+            .into_iter()
+            .collect();
+        prune_empty_dirs(&root, dirs);
+
+        assert!(!root.join("a").exists());
+        assert!(!root.join("keep/empty").exists());
+        assert!(root.join("keep/file.txt").exists());
+        assert!(root.exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
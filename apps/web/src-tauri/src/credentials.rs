@@ -1,14 +1,51 @@
 use crate::error::{AppError, Result};
 use crate::provider::ProviderType;
+use crate::s3::client::ClientOptions;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use keyring::Entry;
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::RwLock;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 const SERVICE_NAME: &str = "com.bucketscout.credentials";
 const ACCOUNTS_KEY: &str = "accounts_metadata";
 
+/// File name of the encrypted credential store (inside the app data directory)
+const ENCRYPTED_STORE_FILE: &str = "credentials.enc";
+const ENCRYPTED_STORE_VERSION: u32 = 1;
+const PBKDF2_ROUNDS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+
+/// Default lifetime of a cached secret before it is re-read from the backend
+const DEFAULT_SECRET_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Where secrets and account metadata are persisted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialBackend {
+    /// OS keychain / secret service (default)
+    Keyring,
+    /// AES-GCM encrypted file, keyed from a user passphrase
+    EncryptedFile,
+}
+
+/// Current credential backend state, reported to the UI
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialBackendStatus {
+    pub backend: CredentialBackend,
+    pub keyring_available: bool,
+    pub store_exists: bool,
+    pub unlocked: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Account {
@@ -20,11 +57,34 @@ pub struct Account {
     // Provider-specific fields
     pub cloudflare_account_id: Option<String>, // R2 only
     pub region: Option<String>,                // AWS S3
+    /// When the secret key was last set (ms since epoch), used to report key age
+    pub secret_updated_at: Option<i64>,
+    // Client settings (fall back to defaults when unset)
+    pub operation_timeout_ms: Option<u64>,
+    pub connect_timeout_ms: Option<u64>,
+    pub http_proxy: Option<String>,
+    pub https_proxy: Option<String>,
+    pub no_proxy: Option<String>,
+    /// Public base URL (e.g. an R2 custom domain) objects can be fetched from directly
+    pub public_base_url: Option<String>,
     // Legacy field for backwards compatibility during migration
     #[serde(skip_serializing)]
     pub account_id: Option<String>,
 }
 
+impl Account {
+    /// HTTP client settings for this account
+    pub fn client_options(&self) -> ClientOptions {
+        ClientOptions {
+            operation_timeout_ms: self.operation_timeout_ms,
+            connect_timeout_ms: self.connect_timeout_ms,
+            http_proxy: self.http_proxy.clone(),
+            https_proxy: self.https_proxy.clone(),
+            no_proxy: self.no_proxy.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct AccountsMetadata {
     accounts: HashMap<String, AccountMetadata>,
@@ -41,6 +101,20 @@ struct AccountMetadata {
     // Provider-specific fields
     cloudflare_account_id: Option<String>,
     region: Option<String>,
+    #[serde(default)]
+    secret_updated_at: Option<i64>,
+    #[serde(default)]
+    operation_timeout_ms: Option<u64>,
+    #[serde(default)]
+    connect_timeout_ms: Option<u64>,
+    #[serde(default)]
+    http_proxy: Option<String>,
+    #[serde(default)]
+    https_proxy: Option<String>,
+    #[serde(default)]
+    no_proxy: Option<String>,
+    #[serde(default)]
+    public_base_url: Option<String>,
     // Legacy field for migration
     #[serde(rename = "account_id")]
     legacy_account_id: Option<String>,
@@ -54,26 +128,422 @@ impl Default for AccountsMetadata {
     }
 }
 
+/// On-disk layout of the encrypted credential store
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptedStoreFile {
+    version: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Unlocked, in-memory view of the encrypted credential store
+struct FileStore {
+    key: [u8; 32],
+    salt: Vec<u8>,
+    entries: HashMap<String, String>,
+}
+
+impl FileStore {
+    fn derive_key(passphrase: &str, salt: &[u8]) -> [u8; 32] {
+        let mut key = [0u8; 32];
+        pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+        key
+    }
+
+    /// Create an empty store with a fresh salt
+    fn create(passphrase: &str) -> Self {
+        let mut salt = vec![0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let key = Self::derive_key(passphrase, &salt);
+        Self {
+            key,
+            salt,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Read and decrypt the store at `path`
+    fn open(path: &PathBuf, passphrase: &str) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| AppError::Storage(format!("Failed to read credential store: {}", e)))?;
+        let file: EncryptedStoreFile = serde_json::from_str(&raw)
+            .map_err(|e| AppError::Storage(format!("Failed to parse credential store: {}", e)))?;
+
+        if file.version != ENCRYPTED_STORE_VERSION {
+            return Err(AppError::Storage(format!(
+                "Unsupported credential store version: {}",
+                file.version
+            )));
+        }
+
+        let decode = |value: &str| {
+            BASE64
+                .decode(value)
+                .map_err(|e| AppError::Storage(format!("Corrupted credential store: {}", e)))
+        };
+        let salt = decode(&file.salt)?;
+        let nonce = decode(&file.nonce)?;
+        let ciphertext = decode(&file.ciphertext)?;
+
+        let key = Self::derive_key(passphrase, &salt);
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+            .map_err(|_| {
+                AppError::Credential(
+                    "Incorrect passphrase or corrupted credential store".to_string(),
+                )
+            })?;
+
+        let entries: HashMap<String, String> = serde_json::from_slice(&plaintext)
+            .map_err(|e| AppError::Storage(format!("Failed to parse credential store: {}", e)))?;
+
+        Ok(Self { key, salt, entries })
+    }
+
+    /// Encrypt and write the store to `path` with a fresh nonce
+    fn persist(&self, path: &PathBuf) -> Result<()> {
+        let plaintext = serde_json::to_vec(&self.entries)
+            .map_err(|e| AppError::Storage(format!("Failed to serialize credentials: {}", e)))?;
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.key));
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_ref())
+            .map_err(|_| AppError::Credential("Failed to encrypt credentials".to_string()))?;
+
+        let file = EncryptedStoreFile {
+            version: ENCRYPTED_STORE_VERSION,
+            salt: BASE64.encode(&self.salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        };
+        let json = serde_json::to_string(&file).map_err(|e| {
+            AppError::Storage(format!("Failed to serialize credential store: {}", e))
+        })?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // Write to a temp file first so a crash never leaves a truncated store behind
+        let tmp_path = path.with_extension("enc.tmp");
+        std::fs::write(&tmp_path, json)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&tmp_path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        std::fs::rename(&tmp_path, path)?;
+
+        Ok(())
+    }
+}
+
 pub struct CredentialsManager {
     metadata_cache: RwLock<Option<AccountsMetadata>>,
+    backend: RwLock<CredentialBackend>,
+    file_store: RwLock<Option<FileStore>>,
+    keyring_available: bool,
+    /// Short-lived cache of secrets: account_id -> (secret, fetched_at)
+    secret_cache: RwLock<HashMap<String, (String, Instant)>>,
+    secret_cache_ttl: RwLock<Duration>,
 }
 
 impl CredentialsManager {
     pub fn new() -> Self {
+        let keyring_available = Self::probe_keyring();
+        let store_exists = get_encrypted_store_path()
+            .map(|p| p.exists())
+            .unwrap_or(false);
+
+        // An existing encrypted store is the persisted "use file backend" setting.
+        // Otherwise fall back to it only when the keyring can't be used at all.
+        let backend = if store_exists || !keyring_available {
+            if !keyring_available {
+                log::warn!("OS keyring unavailable, using encrypted file credential backend");
+            }
+            CredentialBackend::EncryptedFile
+        } else {
+            CredentialBackend::Keyring
+        };
+
         Self {
             metadata_cache: RwLock::new(None),
+            backend: RwLock::new(backend),
+            file_store: RwLock::new(None),
+            keyring_available,
+            secret_cache: RwLock::new(HashMap::new()),
+            secret_cache_ttl: RwLock::new(DEFAULT_SECRET_CACHE_TTL),
+        }
+    }
+
+    /// Check whether the OS keyring can actually be reached
+    fn probe_keyring() -> bool {
+        match Entry::new(SERVICE_NAME, ACCOUNTS_KEY) {
+            Ok(entry) => match entry.get_password() {
+                Ok(_) | Err(keyring::Error::NoEntry) => true,
+                Err(e) => {
+                    log::warn!("Keyring probe failed: {}", e);
+                    false
+                }
+            },
+            Err(e) => {
+                log::warn!("Keyring init failed: {}", e);
+                false
+            }
+        }
+    }
+
+    fn secret_key_name(account_id: &str) -> String {
+        format!("secret_{}", account_id)
+    }
+
+    fn active_backend(&self) -> CredentialBackend {
+        self.backend
+            .read()
+            .map(|b| *b)
+            .unwrap_or(CredentialBackend::Keyring)
+    }
+
+    fn locked_error() -> AppError {
+        AppError::Credential(
+            "Credential store is locked. Enter your passphrase to unlock it.".to_string(),
+        )
+    }
+
+    // ==================== Backend dispatch ====================
+
+    fn keyring_get(name: &str) -> Result<Option<String>> {
+        let entry =
+            Entry::new(SERVICE_NAME, name).map_err(|e| AppError::Credential(e.to_string()))?;
+        match entry.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(AppError::Credential(e.to_string())),
+        }
+    }
+
+    fn keyring_set(name: &str, value: &str) -> Result<()> {
+        let entry =
+            Entry::new(SERVICE_NAME, name).map_err(|e| AppError::Credential(e.to_string()))?;
+        entry
+            .set_password(value)
+            .map_err(|e| AppError::Credential(e.to_string()))
+    }
+
+    fn keyring_delete(name: &str) -> Result<()> {
+        let entry =
+            Entry::new(SERVICE_NAME, name).map_err(|e| AppError::Credential(e.to_string()))?;
+        match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(AppError::Credential(e.to_string())),
+        }
+    }
+
+    fn get_entry(&self, name: &str) -> Result<Option<String>> {
+        match self.active_backend() {
+            CredentialBackend::Keyring => Self::keyring_get(name),
+            CredentialBackend::EncryptedFile => {
+                let store = self.file_store.read().map_err(|_| {
+                    AppError::Credential("Credential store lock poisoned".to_string())
+                })?;
+                let store = store.as_ref().ok_or_else(Self::locked_error)?;
+                Ok(store.entries.get(name).cloned())
+            }
+        }
+    }
+
+    fn set_entry(&self, name: &str, value: &str) -> Result<()> {
+        match self.active_backend() {
+            CredentialBackend::Keyring => Self::keyring_set(name, value),
+            CredentialBackend::EncryptedFile => {
+                let mut store = self.file_store.write().map_err(|_| {
+                    AppError::Credential("Credential store lock poisoned".to_string())
+                })?;
+                let store = store.as_mut().ok_or_else(Self::locked_error)?;
+                store.entries.insert(name.to_string(), value.to_string());
+                store.persist(&get_encrypted_store_path()?)
+            }
+        }
+    }
+
+    fn delete_entry(&self, name: &str) -> Result<()> {
+        match self.active_backend() {
+            CredentialBackend::Keyring => Self::keyring_delete(name),
+            CredentialBackend::EncryptedFile => {
+                let mut store = self.file_store.write().map_err(|_| {
+                    AppError::Credential("Credential store lock poisoned".to_string())
+                })?;
+                let store = store.as_mut().ok_or_else(Self::locked_error)?;
+                if store.entries.remove(name).is_some() {
+                    store.persist(&get_encrypted_store_path()?)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    // ==================== Backend management ====================
+
+    pub fn backend_status(&self) -> Result<CredentialBackendStatus> {
+        let unlocked = self.file_store.read().map(|s| s.is_some()).unwrap_or(false);
+
+        Ok(CredentialBackendStatus {
+            backend: self.active_backend(),
+            keyring_available: self.keyring_available,
+            store_exists: get_encrypted_store_path()?.exists(),
+            unlocked,
+        })
+    }
+
+    /// Unlock (or create, if it doesn't exist yet) the encrypted file store
+    pub fn unlock_file_store(&self, passphrase: &str) -> Result<()> {
+        if passphrase.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Passphrase cannot be empty".to_string(),
+            ));
+        }
+
+        let path = get_encrypted_store_path()?;
+        let store = if path.exists() {
+            FileStore::open(&path, passphrase)?
+        } else {
+            let store = FileStore::create(passphrase);
+            store.persist(&path)?;
+            store
+        };
+
+        if let Ok(mut guard) = self.file_store.write() {
+            *guard = Some(store);
+        }
+        self.invalidate_metadata_cache();
+
+        Ok(())
+    }
+
+    /// Switch the active backend, migrating all stored entries into the new one.
+    /// Switching to the encrypted file requires a passphrase; switching back to the
+    /// keyring requires the file store to be unlocked.
+    pub fn set_backend(&self, backend: CredentialBackend, passphrase: Option<&str>) -> Result<()> {
+        let current = self.active_backend();
+        if current == backend {
+            if backend == CredentialBackend::EncryptedFile {
+                if let Some(passphrase) = passphrase {
+                    self.unlock_file_store(passphrase)?;
+                }
+            }
+            return Ok(());
+        }
+
+        // Collect everything from the current backend before switching
+        let metadata = self.load_metadata()?;
+        let mut entries: HashMap<String, String> = HashMap::new();
+        if let Some(json) = self.get_entry(ACCOUNTS_KEY)? {
+            entries.insert(ACCOUNTS_KEY.to_string(), json);
+        }
+        for id in metadata.accounts.keys() {
+            let name = Self::secret_key_name(id);
+            if let Some(secret) = self.get_entry(&name)? {
+                entries.insert(name, secret);
+            }
+        }
+
+        let path = get_encrypted_store_path()?;
+        match backend {
+            CredentialBackend::EncryptedFile => {
+                let passphrase = passphrase.filter(|p| !p.is_empty()).ok_or_else(|| {
+                    AppError::InvalidInput(
+                        "A passphrase is required for the encrypted file backend".to_string(),
+                    )
+                })?;
+
+                let mut store = FileStore::create(passphrase);
+                store.entries = entries.clone();
+                store.persist(&path)?;
+
+                // Clear keyring copies now that the file store holds them
+                for name in entries.keys() {
+                    if let Err(e) = Self::keyring_delete(name) {
+                        log::warn!("Failed to remove '{}' from keyring: {}", name, e);
+                    }
+                }
+
+                if let Ok(mut guard) = self.file_store.write() {
+                    *guard = Some(store);
+                }
+            }
+            CredentialBackend::Keyring => {
+                if !self.keyring_available {
+                    return Err(AppError::Credential(
+                        "OS keyring is not available on this system".to_string(),
+                    ));
+                }
+
+                for (name, value) in &entries {
+                    Self::keyring_set(name, value)?;
+                }
+
+                std::fs::remove_file(&path).map_err(|e| {
+                    AppError::Storage(format!("Failed to remove credential store: {}", e))
+                })?;
+
+                if let Ok(mut guard) = self.file_store.write() {
+                    *guard = None;
+                }
+            }
+        }
+
+        if let Ok(mut guard) = self.backend.write() {
+            *guard = backend;
+        }
+        self.invalidate_metadata_cache();
+
+        log::info!("Credential backend switched to {:?}", backend);
+        Ok(())
+    }
+
+    fn invalidate_metadata_cache(&self) {
+        if let Ok(mut cache) = self.metadata_cache.write() {
+            *cache = None;
+        }
+        self.clear_secret_cache();
+    }
+
+    // ==================== Secret cache ====================
+
+    /// Set how long secrets stay cached in memory. Zero disables caching.
+    pub fn set_secret_cache_ttl(&self, ttl: Duration) {
+        if let Ok(mut current) = self.secret_cache_ttl.write() {
+            *current = ttl;
+        }
+        if ttl.is_zero() {
+            self.clear_secret_cache();
         }
     }
 
-    fn get_metadata_entry() -> Result<Entry> {
-        Entry::new(SERVICE_NAME, ACCOUNTS_KEY).map_err(|e| AppError::Credential(e.to_string()))
+    pub fn secret_cache_ttl(&self) -> Duration {
+        self.secret_cache_ttl
+            .read()
+            .map(|ttl| *ttl)
+            .unwrap_or(DEFAULT_SECRET_CACHE_TTL)
     }
 
-    fn get_secret_entry(account_id: &str) -> Result<Entry> {
-        let key = format!("secret_{}", account_id);
-        Entry::new(SERVICE_NAME, &key).map_err(|e| AppError::Credential(e.to_string()))
+    pub fn clear_secret_cache(&self) {
+        if let Ok(mut cache) = self.secret_cache.write() {
+            cache.clear();
+        }
     }
 
+    fn evict_cached_secret(&self, account_id: &str) {
+        if let Ok(mut cache) = self.secret_cache.write() {
+            cache.remove(account_id);
+        }
+    }
+
+    // ==================== Accounts ====================
+
     fn load_metadata(&self) -> Result<AccountsMetadata> {
         // Check cache first
         if let Ok(cache) = self.metadata_cache.read() {
@@ -82,12 +552,10 @@ impl CredentialsManager {
             }
         }
 
-        let entry = Self::get_metadata_entry()?;
-        let metadata = match entry.get_password() {
-            Ok(json) => serde_json::from_str(&json)
+        let metadata = match self.get_entry(ACCOUNTS_KEY)? {
+            Some(json) => serde_json::from_str(&json)
                 .map_err(|e| AppError::Storage(format!("Failed to parse metadata: {}", e)))?,
-            Err(keyring::Error::NoEntry) => AccountsMetadata::default(),
-            Err(e) => return Err(AppError::Credential(e.to_string())),
+            None => AccountsMetadata::default(),
         };
 
         // Update cache
@@ -99,12 +567,9 @@ impl CredentialsManager {
     }
 
     fn save_metadata(&self, metadata: &AccountsMetadata) -> Result<()> {
-        let entry = Self::get_metadata_entry()?;
         let json = serde_json::to_string(metadata)
             .map_err(|e| AppError::Storage(format!("Failed to serialize metadata: {}", e)))?;
-        entry
-            .set_password(&json)
-            .map_err(|e| AppError::Credential(e.to_string()))?;
+        self.set_entry(ACCOUNTS_KEY, &json)?;
 
         // Update cache
         if let Ok(mut cache) = self.metadata_cache.write() {
@@ -123,14 +588,18 @@ impl CredentialsManager {
         provider_type: ProviderType,
         cloudflare_account_id: Option<String>,
         region: Option<String>,
+        operation_timeout_ms: Option<u64>,
+        connect_timeout_ms: Option<u64>,
+        http_proxy: Option<String>,
+        https_proxy: Option<String>,
+        no_proxy: Option<String>,
+        public_base_url: Option<String>,
     ) -> Result<Account> {
         let id = Uuid::new_v4().to_string();
+        let secret_updated_at = Some(chrono::Utc::now().timestamp_millis());
 
-        // Store the secret key in keychain
-        let secret_entry = Self::get_secret_entry(&id)?;
-        secret_entry
-            .set_password(&secret_access_key)
-            .map_err(|e| AppError::Credential(e.to_string()))?;
+        // Store the secret key in the active backend
+        self.set_entry(&Self::secret_key_name(&id), &secret_access_key)?;
 
         // Store metadata
         let mut metadata = self.load_metadata()?;
@@ -143,6 +612,13 @@ impl CredentialsManager {
                 provider_type,
                 cloudflare_account_id: cloudflare_account_id.clone(),
                 region: region.clone(),
+                secret_updated_at,
+                operation_timeout_ms,
+                connect_timeout_ms,
+                http_proxy: http_proxy.clone(),
+                https_proxy: https_proxy.clone(),
+                no_proxy: no_proxy.clone(),
+                public_base_url: public_base_url.clone(),
                 legacy_account_id: None,
             },
         );
@@ -156,6 +632,13 @@ impl CredentialsManager {
             provider_type,
             cloudflare_account_id,
             region,
+            secret_updated_at,
+            operation_timeout_ms,
+            connect_timeout_ms,
+            http_proxy,
+            https_proxy,
+            no_proxy,
+            public_base_url,
             account_id: None,
         })
     }
@@ -196,22 +679,52 @@ impl CredentialsManager {
             provider_type: meta.provider_type,
             cloudflare_account_id,
             region: meta.region,
+            secret_updated_at: meta.secret_updated_at,
+            operation_timeout_ms: meta.operation_timeout_ms,
+            connect_timeout_ms: meta.connect_timeout_ms,
+            http_proxy: meta.http_proxy,
+            https_proxy: meta.https_proxy,
+            no_proxy: meta.no_proxy,
+            public_base_url: meta.public_base_url,
             account_id: meta.legacy_account_id, // Keep for API compatibility
         }
     }
 
     pub fn get_secret_key(&self, account_id: &str) -> Result<String> {
-        let entry = Self::get_secret_entry(account_id)?;
-        entry
-            .get_password()
-            .map_err(|e| AppError::Credential(format!("Failed to get secret key: {}", e)))
+        let ttl = self.secret_cache_ttl();
+
+        // Serve from cache while fresh to avoid a keyring round-trip per command
+        if !ttl.is_zero() {
+            if let Ok(cache) = self.secret_cache.read() {
+                if let Some((secret, fetched_at)) = cache.get(account_id) {
+                    if fetched_at.elapsed() < ttl {
+                        return Ok(secret.clone());
+                    }
+                }
+            }
+        }
+
+        let secret = self
+            .get_entry(&Self::secret_key_name(account_id))?
+            .ok_or_else(|| {
+                AppError::Credential(
+                    "Failed to get secret key: no matching entry found".to_string(),
+                )
+            })?;
+
+        if !ttl.is_zero() {
+            if let Ok(mut cache) = self.secret_cache.write() {
+                cache.insert(account_id.to_string(), (secret.clone(), Instant::now()));
+            }
+        }
+
+        Ok(secret)
     }
 
     pub fn remove_account(&self, id: &str) -> Result<()> {
         // Remove secret key
-        if let Ok(entry) = Self::get_secret_entry(id) {
-            let _ = entry.delete_credential(); // Ignore if doesn't exist
-        }
+        let _ = self.delete_entry(&Self::secret_key_name(id)); // Ignore if doesn't exist
+        self.evict_cached_secret(id);
 
         // Remove from metadata
         let mut metadata = self.load_metadata()?;
@@ -231,6 +744,12 @@ impl CredentialsManager {
         provider_type: Option<ProviderType>,
         cloudflare_account_id: Option<String>,
         region: Option<String>,
+        operation_timeout_ms: Option<u64>,
+        connect_timeout_ms: Option<u64>,
+        http_proxy: Option<String>,
+        https_proxy: Option<String>,
+        no_proxy: Option<String>,
+        public_base_url: Option<String>,
     ) -> Result<Account> {
         let mut metadata = self.load_metadata()?;
         let meta = metadata
@@ -256,16 +775,36 @@ impl CredentialsManager {
         if region.is_some() {
             meta.region = region;
         }
+        // Zero clears a timeout back to the default
+        if let Some(operation_timeout_ms) = operation_timeout_ms {
+            meta.operation_timeout_ms = Some(operation_timeout_ms).filter(|ms| *ms > 0);
+        }
+        if let Some(connect_timeout_ms) = connect_timeout_ms {
+            meta.connect_timeout_ms = Some(connect_timeout_ms).filter(|ms| *ms > 0);
+        }
+        // An empty string clears a proxy setting
+        if let Some(http_proxy) = http_proxy {
+            meta.http_proxy = Some(http_proxy).filter(|p| !p.is_empty());
+        }
+        if let Some(https_proxy) = https_proxy {
+            meta.https_proxy = Some(https_proxy).filter(|p| !p.is_empty());
+        }
+        if let Some(no_proxy) = no_proxy {
+            meta.no_proxy = Some(no_proxy).filter(|p| !p.is_empty());
+        }
+        // An empty string also clears the public base URL
+        if let Some(public_base_url) = public_base_url {
+            meta.public_base_url = Some(public_base_url).filter(|u| !u.is_empty());
+        }
 
         // Update secret if provided
         if let Some(secret) = secret_access_key {
-            let entry = Self::get_secret_entry(id)?;
-            entry
-                .set_password(&secret)
-                .map_err(|e| AppError::Credential(e.to_string()))?;
+            self.set_entry(&Self::secret_key_name(id), &secret)?;
+            meta.secret_updated_at = Some(chrono::Utc::now().timestamp_millis());
         }
 
         self.save_metadata(&metadata)?;
+        self.evict_cached_secret(id);
 
         let meta = metadata.accounts.get(id).unwrap();
         Ok(Self::metadata_to_account(id.to_string(), meta.clone()))
@@ -277,3 +816,11 @@ impl Default for CredentialsManager {
         Self::new()
     }
 }
+
+/// Get the encrypted credential store path for the current platform
+fn get_encrypted_store_path() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir()
+        .ok_or_else(|| AppError::Storage("Could not determine data directory".to_string()))?;
+
+    Ok(data_dir.join("bucketscout").join(ENCRYPTED_STORE_FILE))
+}
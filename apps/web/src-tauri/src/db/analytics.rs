@@ -0,0 +1,255 @@
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+
+use super::DbManager;
+use crate::error::{AppError, Result};
+
+/// How long a cached analytics listing is reused before the prefix is listed again
+const ANALYTICS_CACHE_TTL_SECS: i64 = 15 * 60;
+
+/// One object from a cached analytics listing
+#[derive(Debug, Clone)]
+pub struct AnalyticsCachedObject {
+    pub key: String,
+    pub size: i64,
+    pub last_modified: Option<String>,
+    pub storage_class: Option<String>,
+}
+
+/// A fresh cached listing that covers a requested prefix
+#[derive(Debug, Clone)]
+pub struct AnalyticsCacheEntry {
+    pub id: i64,
+    /// The prefix that was listed; the requested prefix is inside it
+    pub prefix: String,
+    pub cached_at: i64,
+}
+
+/// Size of one storage class in a whole-bucket analytics scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageClassTotal {
+    pub storage_class: String,
+    pub size: i64,
+    pub object_count: i64,
+}
+
+/// Storage class breakdown of a bucket's latest whole-bucket analytics scan
+#[derive(Debug, Clone)]
+pub struct BucketStorageClasses {
+    pub bucket: String,
+    pub storage_classes: Vec<StorageClassTotal>,
+    pub calculated_at: i64,
+}
+
+impl DbManager {
+    /// Replace the cached listing of a prefix, dropping any expired listings
+    pub fn save_analytics_cache(
+        &self,
+        account_id: &str,
+        bucket: &str,
+        prefix: &str,
+        objects: &[AnalyticsCachedObject],
+    ) -> Result<()> {
+        let mut conn = self.get_conn()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Storage(format!("Failed to start transaction: {}", e)))?;
+
+        let now = chrono::Utc::now().timestamp();
+
+        // Objects of the old entries go with them (ON DELETE CASCADE)
+        tx.execute(
+            r#"
+            DELETE FROM analytics_cache
+            WHERE (account_id = ?1 AND bucket = ?2 AND prefix = ?3) OR cached_at < ?4
+            "#,
+            params![account_id, bucket, prefix, now - ANALYTICS_CACHE_TTL_SECS],
+        )
+        .map_err(|e| AppError::Storage(format!("Failed to clear analytics cache: {}", e)))?;
+
+        tx.execute(
+            r#"
+            INSERT INTO analytics_cache (account_id, bucket, prefix, object_count, cached_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+            params![
+                account_id,
+                bucket,
+                prefix,
+                objects.len() as i64,
+                now
+            ],
+        )
+        .map_err(|e| AppError::Storage(format!("Failed to insert analytics cache: {}", e)))?;
+        let cache_id = tx.last_insert_rowid();
+
+        {
+            let mut stmt = tx
+                .prepare(
+                    r#"
+                INSERT INTO analytics_cache_objects (cache_id, key, size, last_modified, storage_class)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                "#,
+                )
+                .map_err(|e| AppError::Storage(format!("Failed to prepare insert: {}", e)))?;
+            for object in objects {
+                stmt.execute(params![
+                    cache_id,
+                    object.key,
+                    object.size,
+                    object.last_modified,
+                    object.storage_class
+                ])
+                .map_err(|e| {
+                    AppError::Storage(format!("Failed to insert analytics object: {}", e))
+                })?;
+            }
+        }
+
+        tx.commit()
+            .map_err(|e| AppError::Storage(format!("Failed to commit analytics cache: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Find the closest fresh cached listing whose prefix contains `prefix`
+    pub fn find_analytics_cache(
+        &self,
+        account_id: &str,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<Option<AnalyticsCacheEntry>> {
+        let conn = self.get_conn()?;
+        let oldest = chrono::Utc::now().timestamp() - ANALYTICS_CACHE_TTL_SECS;
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+            SELECT id, prefix, cached_at FROM analytics_cache
+            WHERE account_id = ?1 AND bucket = ?2 AND cached_at >= ?3
+              AND substr(?4, 1, length(prefix)) = prefix
+            ORDER BY length(prefix) DESC
+            LIMIT 1
+            "#,
+            )
+            .map_err(|e| AppError::Storage(format!("Failed to prepare query: {}", e)))?;
+
+        let result = stmt.query_row(params![account_id, bucket, oldest, prefix], |row| {
+            Ok(AnalyticsCacheEntry {
+                id: row.get(0)?,
+                prefix: row.get(1)?,
+                cached_at: row.get(2)?,
+            })
+        });
+
+        match result {
+            Ok(entry) => Ok(Some(entry)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(AppError::Storage(format!(
+                "Failed to look up analytics cache: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Objects of a cached listing whose keys start with `prefix`
+    pub fn get_analytics_cache_objects(
+        &self,
+        cache_id: i64,
+        prefix: &str,
+    ) -> Result<Vec<AnalyticsCachedObject>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+            SELECT key, size, last_modified, storage_class FROM analytics_cache_objects
+            WHERE cache_id = ?1 AND substr(key, 1, length(?2)) = ?2
+            "#,
+            )
+            .map_err(|e| AppError::Storage(format!("Failed to prepare query: {}", e)))?;
+
+        let objects = stmt
+            .query_map(params![cache_id, prefix], |row| {
+                Ok(AnalyticsCachedObject {
+                    key: row.get(0)?,
+                    size: row.get(1)?,
+                    last_modified: row.get(2)?,
+                    storage_class: row.get(3)?,
+                })
+            })
+            .map_err(|e| AppError::Storage(format!("Failed to get analytics objects: {}", e)))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Storage(format!("Failed to read analytics object: {}", e)))?;
+
+        Ok(objects)
+    }
+
+    /// Record the storage class breakdown of a whole-bucket scan, replacing the
+    /// bucket's previous one. Unlike the listing cache it never expires and is
+    /// kept for buckets of any size.
+    pub fn save_storage_class_totals(
+        &self,
+        account_id: &str,
+        bucket: &str,
+        totals: &[StorageClassTotal],
+    ) -> Result<()> {
+        let conn = self.get_conn()?;
+        let storage_classes = serde_json::to_string(totals).map_err(|e| {
+            AppError::Storage(format!("Failed to serialize storage class totals: {}", e))
+        })?;
+
+        conn.execute(
+            r#"
+            INSERT OR REPLACE INTO analytics_storage_classes
+                (account_id, bucket, storage_classes, calculated_at)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+            params![
+                account_id,
+                bucket,
+                storage_classes,
+                chrono::Utc::now().timestamp()
+            ],
+        )
+        .map_err(|e| AppError::Storage(format!("Failed to save storage class totals: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Storage class breakdowns from the latest whole-bucket analytics scan of
+    /// each bucket (or just `bucket`) in an account, however old they are
+    pub fn get_storage_class_totals(
+        &self,
+        account_id: &str,
+        bucket: Option<&str>,
+    ) -> Result<Vec<BucketStorageClasses>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+            SELECT bucket, storage_classes, calculated_at FROM analytics_storage_classes
+            WHERE account_id = ?1 AND (?2 IS NULL OR bucket = ?2)
+            ORDER BY bucket
+            "#,
+            )
+            .map_err(|e| AppError::Storage(format!("Failed to prepare query: {}", e)))?;
+
+        let totals = stmt
+            .query_map(params![account_id, bucket], |row| {
+                let storage_classes: String = row.get(1)?;
+                Ok(BucketStorageClasses {
+                    bucket: row.get(0)?,
+                    storage_classes: serde_json::from_str(&storage_classes).unwrap_or_default(),
+                    calculated_at: row.get(2)?,
+                })
+            })
+            .map_err(|e| AppError::Storage(format!("Failed to get storage class totals: {}", e)))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Storage(format!("Failed to read storage class totals: {}", e)))?;
+
+        Ok(totals)
+    }
+}
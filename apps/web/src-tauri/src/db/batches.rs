@@ -0,0 +1,186 @@
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+
+use super::DbManager;
+use crate::error::{AppError, Result};
+
+/// Batch operations whose failed items can be retried
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchOperation {
+    Copy,
+    Delete,
+    DownloadFolder,
+}
+
+impl std::fmt::Display for BatchOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BatchOperation::Copy => write!(f, "copy"),
+            BatchOperation::Delete => write!(f, "delete"),
+            BatchOperation::DownloadFolder => write!(f, "download_folder"),
+        }
+    }
+}
+
+impl TryFrom<&str> for BatchOperation {
+    type Error = AppError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        match value {
+            "copy" => Ok(BatchOperation::Copy),
+            "delete" => Ok(BatchOperation::Delete),
+            "download_folder" => Ok(BatchOperation::DownloadFolder),
+            _ => Err(AppError::InvalidInput(format!(
+                "Unknown batch operation: {}",
+                value
+            ))),
+        }
+    }
+}
+
+/// A single item that failed within a batch
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedItem {
+    pub key: String,
+    /// Destination key for copies
+    pub dest_key: Option<String>,
+    pub error: String,
+}
+
+/// Failed items of one batch operation, with what's needed to re-run them
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedBatch {
+    pub batch_id: String,
+    pub operation: BatchOperation,
+    pub account_id: String,
+    pub bucket: String,
+    /// Operation-specific parameters as JSON
+    pub params: String,
+    pub items: Vec<FailedItem>,
+}
+
+impl DbManager {
+    /// Replace the stored failures for a batch. An empty item list clears the batch.
+    pub fn save_failed_batch(
+        &self,
+        batch_id: &str,
+        operation: BatchOperation,
+        account_id: &str,
+        bucket: &str,
+        params: &str,
+        items: &[FailedItem],
+    ) -> Result<()> {
+        let mut conn = self.get_conn()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Storage(format!("Failed to start transaction: {}", e)))?;
+
+        tx.execute(
+            "DELETE FROM failed_batches WHERE batch_id = ?1",
+            params![batch_id],
+        )
+        .map_err(|e| AppError::Storage(format!("Failed to clear failed batch: {}", e)))?;
+
+        if !items.is_empty() {
+            tx.execute(
+                r#"
+                INSERT INTO failed_batches (batch_id, operation, account_id, bucket, params, updated_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                "#,
+                params![
+                    batch_id,
+                    operation.to_string(),
+                    account_id,
+                    bucket,
+                    params,
+                    chrono::Utc::now().timestamp()
+                ],
+            )
+            .map_err(|e| AppError::Storage(format!("Failed to insert failed batch: {}", e)))?;
+
+            for item in items {
+                tx.execute(
+                    r#"
+                    INSERT INTO failed_batch_items (batch_id, key, dest_key, error)
+                    VALUES (?1, ?2, ?3, ?4)
+                    "#,
+                    params![batch_id, item.key, item.dest_key, item.error],
+                )
+                .map_err(|e| {
+                    AppError::Storage(format!("Failed to insert failed batch item: {}", e))
+                })?;
+            }
+        }
+
+        tx.commit()
+            .map_err(|e| AppError::Storage(format!("Failed to commit failed batch: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Load a batch and its failed items
+    pub fn get_failed_batch(&self, batch_id: &str) -> Result<Option<FailedBatch>> {
+        let conn = self.get_conn()?;
+
+        let result = conn.query_row(
+            r#"
+            SELECT operation, account_id, bucket, params
+            FROM failed_batches WHERE batch_id = ?1
+            "#,
+            params![batch_id],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            },
+        );
+
+        let (operation, account_id, bucket, params) = match result {
+            Ok(batch) => batch,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+            Err(e) => {
+                return Err(AppError::Storage(format!(
+                    "Failed to get failed batch: {}",
+                    e
+                )))
+            }
+        };
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT key, dest_key, error
+                FROM failed_batch_items WHERE batch_id = ?1
+                ORDER BY id
+                "#,
+            )
+            .map_err(|e| AppError::Storage(format!("Failed to prepare query: {}", e)))?;
+
+        let items = stmt
+            .query_map(params![batch_id], |row| {
+                Ok(FailedItem {
+                    key: row.get(0)?,
+                    dest_key: row.get(1)?,
+                    error: row.get(2)?,
+                })
+            })
+            .map_err(|e| AppError::Storage(format!("Failed to query failed items: {}", e)))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Storage(format!("Failed to read failed items: {}", e)))?;
+
+        Ok(Some(FailedBatch {
+            batch_id: batch_id.to_string(),
+            operation: BatchOperation::try_from(operation.as_str())?,
+            account_id,
+            bucket,
+            params,
+            items,
+        }))
+    }
+}
@@ -1,4 +1,4 @@
-use rusqlite::params;
+use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 
 use super::DbManager;
@@ -12,6 +12,11 @@ pub enum HashType {
     Etag,
     /// Accurate mode: uses SHA-256 hash of file content
     Sha256,
+    /// Accurate mode: uses BLAKE3 hash of file content, several times faster than SHA-256
+    Blake3,
+    /// Fast mode that also compares multipart uploads made with our part size,
+    /// computing composite MD5s where plain and multipart ETags need bridging
+    EtagComposite,
 }
 
 impl std::fmt::Display for HashType {
@@ -19,6 +24,8 @@ impl std::fmt::Display for HashType {
         match self {
             HashType::Etag => write!(f, "etag"),
             HashType::Sha256 => write!(f, "sha256"),
+            HashType::Blake3 => write!(f, "blake3"),
+            HashType::EtagComposite => write!(f, "etag_composite"),
         }
     }
 }
@@ -30,6 +37,8 @@ impl TryFrom<&str> for HashType {
         match value {
             "etag" => Ok(HashType::Etag),
             "sha256" => Ok(HashType::Sha256),
+            "blake3" => Ok(HashType::Blake3),
+            "etag_composite" => Ok(HashType::EtagComposite),
             _ => Err(AppError::InvalidInput(format!(
                 "Unknown hash type: {}",
                 value
@@ -93,6 +102,51 @@ pub struct DuplicateScan {
     pub duplicate_files: i64,
     pub reclaimable_bytes: i64,
     pub error_message: Option<String>,
+    /// Folder levels below the prefix that were scanned (None = unlimited)
+    pub max_depth: Option<i64>,
+    pub filters: ScanFilters,
+    /// Time spent listing objects (None for older scans)
+    pub listing_ms: Option<i64>,
+    /// Time spent hashing candidates (None until the scan completes)
+    pub hashing_ms: Option<i64>,
+    /// Every (bucket, prefix) scanned; a single entry unless the scan spans buckets
+    pub targets: Vec<ScanTarget>,
+}
+
+/// A bucket and prefix covered by a duplicate scan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanTarget {
+    pub bucket: String,
+    #[serde(default)]
+    pub prefix: String,
+}
+
+/// An object found by a scan, identified across buckets
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanObject {
+    pub bucket: String,
+    pub key: String,
+}
+
+/// Key filters applied while listing a duplicate scan
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ScanFilters {
+    /// Only scan these extensions (lowercase, without the dot); empty scans all
+    pub include_extensions: Vec<String>,
+    pub exclude_extensions: Vec<String>,
+    /// Globs (`*`, `?`) matched against the file name, or the whole key when they contain '/'
+    pub exclude_patterns: Vec<String>,
+}
+
+impl ScanFilters {
+    pub fn is_empty(&self) -> bool {
+        self.include_extensions.is_empty()
+            && self.exclude_extensions.is_empty()
+            && self.exclude_patterns.is_empty()
+    }
 }
 
 /// Duplicate group - files that share the same hash
@@ -105,6 +159,8 @@ pub struct DuplicateGroup {
     pub hash_type: HashType,
     pub file_size: i64,
     pub file_count: i64,
+    /// Matched by ETag only, without knowing how the ETag was computed
+    pub heuristic: bool,
     pub files: Vec<DuplicateFile>,
 }
 
@@ -114,6 +170,7 @@ pub struct DuplicateGroup {
 pub struct DuplicateFile {
     pub id: i64,
     pub group_id: i64,
+    pub bucket: String,
     pub key: String,
     pub etag: Option<String>,
     pub last_modified: Option<i64>,
@@ -139,13 +196,16 @@ pub struct ScanSummary {
 #[derive(Debug, Clone)]
 pub struct NewScan {
     pub account_id: String,
-    pub bucket: String,
-    pub prefix: String,
+    /// At least one target; the first is recorded as the scan's bucket and prefix
+    pub targets: Vec<ScanTarget>,
+    pub max_depth: Option<i64>,
+    pub filters: ScanFilters,
 }
 
 /// File info collected during scan (before grouping)
 #[derive(Debug, Clone)]
 pub struct ScannedFile {
+    pub bucket: String,
     pub key: String,
     pub size: i64,
     pub etag: Option<String>,
@@ -159,13 +219,39 @@ impl DbManager {
     pub fn create_scan(&self, scan: &NewScan) -> Result<i64> {
         let conn = self.get_conn()?;
         let now = chrono::Utc::now().timestamp();
+        let primary = scan
+            .targets
+            .first()
+            .ok_or_else(|| AppError::InvalidInput("A scan needs at least one bucket".into()))?;
+        let filters = if scan.filters.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&scan.filters).map_err(|e| {
+                AppError::Storage(format!("Failed to serialize scan filters: {}", e))
+            })?)
+        };
+        let targets = if scan.targets.len() > 1 {
+            Some(serde_json::to_string(&scan.targets).map_err(|e| {
+                AppError::Storage(format!("Failed to serialize scan targets: {}", e))
+            })?)
+        } else {
+            None
+        };
 
         conn.execute(
             r#"
-            INSERT INTO duplicate_scans (account_id, bucket, prefix, started_at, status)
-            VALUES (?1, ?2, ?3, ?4, 'running')
+            INSERT INTO duplicate_scans (account_id, bucket, prefix, started_at, status, max_depth, filters, targets)
+            VALUES (?1, ?2, ?3, ?4, 'running', ?5, ?6, ?7)
             "#,
-            params![scan.account_id, scan.bucket, scan.prefix, now],
+            params![
+                scan.account_id,
+                primary.bucket,
+                primary.prefix,
+                now,
+                scan.max_depth,
+                filters,
+                targets
+            ],
         )
         .map_err(|e| AppError::Storage(format!("Failed to create scan: {}", e)))?;
 
@@ -190,6 +276,19 @@ impl DbManager {
         Ok(())
     }
 
+    /// Record how long the listing phase took
+    pub fn set_scan_listing_time(&self, scan_id: i64, listing_ms: i64) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "UPDATE duplicate_scans SET listing_ms = ?1 WHERE id = ?2",
+            params![listing_ms, scan_id],
+        )
+        .map_err(|e| AppError::Storage(format!("Failed to update scan timing: {}", e)))?;
+
+        Ok(())
+    }
+
     /// Complete a scan with results
     pub fn complete_scan(
         &self,
@@ -197,6 +296,7 @@ impl DbManager {
         duplicate_groups: i64,
         duplicate_files: i64,
         reclaimable_bytes: i64,
+        hashing_ms: i64,
     ) -> Result<()> {
         let conn = self.get_conn()?;
         let now = chrono::Utc::now().timestamp();
@@ -205,10 +305,18 @@ impl DbManager {
             r#"
             UPDATE duplicate_scans
             SET completed_at = ?1, status = 'completed',
-                duplicate_groups = ?2, duplicate_files = ?3, reclaimable_bytes = ?4
-            WHERE id = ?5
+                duplicate_groups = ?2, duplicate_files = ?3, reclaimable_bytes = ?4,
+                hashing_ms = ?5
+            WHERE id = ?6
             "#,
-            params![now, duplicate_groups, duplicate_files, reclaimable_bytes, scan_id],
+            params![
+                now,
+                duplicate_groups,
+                duplicate_files,
+                reclaimable_bytes,
+                hashing_ms,
+                scan_id
+            ],
         )
         .map_err(|e| AppError::Storage(format!("Failed to complete scan: {}", e)))?;
 
@@ -259,18 +367,31 @@ impl DbManager {
             r#"
             SELECT id, account_id, bucket, prefix, started_at, completed_at, status,
                    total_files, total_size, duplicate_groups, duplicate_files,
-                   reclaimable_bytes, error_message
+                   reclaimable_bytes, error_message, max_depth, filters,
+                   listing_ms, hashing_ms, targets
             FROM duplicate_scans
             WHERE id = ?1
             "#,
             params![scan_id],
             |row| {
                 let status_str: String = row.get("status")?;
+                let filters: Option<String> = row.get("filters")?;
+                let targets: Option<String> = row.get("targets")?;
+                let bucket: String = row.get("bucket")?;
+                let prefix: String = row.get("prefix")?;
+                let targets = targets
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_else(|| {
+                        vec![ScanTarget {
+                            bucket: bucket.clone(),
+                            prefix: prefix.clone(),
+                        }]
+                    });
                 Ok(DuplicateScan {
                     id: row.get("id")?,
                     account_id: row.get("account_id")?,
-                    bucket: row.get("bucket")?,
-                    prefix: row.get("prefix")?,
+                    bucket,
+                    prefix,
                     started_at: row.get("started_at")?,
                     completed_at: row.get("completed_at")?,
                     status: ScanStatus::try_from(status_str.as_str())
@@ -281,6 +402,13 @@ impl DbManager {
                     duplicate_files: row.get("duplicate_files")?,
                     reclaimable_bytes: row.get("reclaimable_bytes")?,
                     error_message: row.get("error_message")?,
+                    max_depth: row.get("max_depth")?,
+                    filters: filters
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default(),
+                    listing_ms: row.get("listing_ms")?,
+                    hashing_ms: row.get("hashing_ms")?,
+                    targets,
                 })
             },
         );
@@ -369,6 +497,7 @@ impl DbManager {
         content_hash: &str,
         hash_type: HashType,
         file_size: i64,
+        heuristic: bool,
         files: &[ScannedFile],
     ) -> Result<i64> {
         let conn = self.get_conn()?;
@@ -376,15 +505,16 @@ impl DbManager {
         // Insert group
         conn.execute(
             r#"
-            INSERT INTO duplicate_groups (scan_id, content_hash, hash_type, file_size, file_count)
-            VALUES (?1, ?2, ?3, ?4, ?5)
+            INSERT INTO duplicate_groups (scan_id, content_hash, hash_type, file_size, file_count, heuristic)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
             "#,
             params![
                 scan_id,
                 content_hash,
                 hash_type.to_string(),
                 file_size,
-                files.len() as i64
+                files.len() as i64,
+                heuristic
             ],
         )
         .map_err(|e| AppError::Storage(format!("Failed to insert duplicate group: {}", e)))?;
@@ -395,11 +525,12 @@ impl DbManager {
         for file in files {
             conn.execute(
                 r#"
-                INSERT INTO duplicate_files (group_id, key, etag, last_modified, storage_class)
-                VALUES (?1, ?2, ?3, ?4, ?5)
+                INSERT INTO duplicate_files (group_id, bucket, key, etag, last_modified, storage_class)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
                 "#,
                 params![
                     group_id,
+                    file.bucket,
                     file.key,
                     file.etag,
                     file.last_modified,
@@ -419,7 +550,7 @@ impl DbManager {
         let mut stmt = conn
             .prepare(
                 r#"
-            SELECT id, scan_id, content_hash, hash_type, file_size, file_count
+            SELECT id, scan_id, content_hash, hash_type, file_size, file_count, heuristic
             FROM duplicate_groups
             WHERE scan_id = ?1
             ORDER BY file_size * file_count DESC
@@ -427,7 +558,7 @@ impl DbManager {
             )
             .map_err(|e| AppError::Storage(format!("Failed to prepare query: {}", e)))?;
 
-        let groups: Vec<(i64, i64, String, String, i64, i64)> = stmt
+        let groups: Vec<(i64, i64, String, String, i64, i64, bool)> = stmt
             .query_map(params![scan_id], |row| {
                 Ok((
                     row.get(0)?,
@@ -436,6 +567,7 @@ impl DbManager {
                     row.get(3)?,
                     row.get(4)?,
                     row.get(5)?,
+                    row.get(6)?,
                 ))
             })
             .map_err(|e| AppError::Storage(format!("Failed to get groups: {}", e)))?
@@ -443,7 +575,7 @@ impl DbManager {
             .collect();
 
         let mut result = Vec::new();
-        for (id, scan_id, content_hash, hash_type_str, file_size, file_count) in groups {
+        for (id, scan_id, content_hash, hash_type_str, file_size, file_count, heuristic) in groups {
             let files = self.get_duplicate_files(id)?;
             result.push(DuplicateGroup {
                 id,
@@ -452,6 +584,7 @@ impl DbManager {
                 hash_type: HashType::try_from(hash_type_str.as_str()).unwrap_or(HashType::Etag),
                 file_size,
                 file_count,
+                heuristic,
                 files,
             });
         }
@@ -459,17 +592,21 @@ impl DbManager {
         Ok(result)
     }
 
-    /// Get files in a duplicate group
+    /// Get files in a duplicate group. Rows saved before files recorded their
+    /// bucket fall back to the scan's bucket.
     fn get_duplicate_files(&self, group_id: i64) -> Result<Vec<DuplicateFile>> {
         let conn = self.get_conn()?;
 
         let mut stmt = conn
             .prepare(
                 r#"
-            SELECT id, group_id, key, etag, last_modified, storage_class
-            FROM duplicate_files
-            WHERE group_id = ?1
-            ORDER BY key ASC
+            SELECT df.id, df.group_id, COALESCE(df.bucket, ds.bucket) AS bucket, df.key,
+                   df.etag, df.last_modified, df.storage_class
+            FROM duplicate_files df
+            JOIN duplicate_groups dg ON dg.id = df.group_id
+            JOIN duplicate_scans ds ON ds.id = dg.scan_id
+            WHERE df.group_id = ?1
+            ORDER BY bucket ASC, df.key ASC
             "#,
             )
             .map_err(|e| AppError::Storage(format!("Failed to prepare query: {}", e)))?;
@@ -479,6 +616,7 @@ impl DbManager {
                 Ok(DuplicateFile {
                     id: row.get("id")?,
                     group_id: row.get("group_id")?,
+                    bucket: row.get("bucket")?,
                     key: row.get("key")?,
                     etag: row.get("etag")?,
                     last_modified: row.get("last_modified")?,
@@ -503,7 +641,7 @@ impl DbManager {
     }
 
     /// Remove files from duplicate groups after deletion
-    pub fn remove_deleted_files(&self, scan_id: i64, deleted_keys: &[String]) -> Result<()> {
+    pub fn remove_deleted_files(&self, scan_id: i64, deleted: &[ScanObject]) -> Result<()> {
         let conn = self.get_conn()?;
 
         // Get all group IDs for this scan
@@ -518,10 +656,15 @@ impl DbManager {
             .collect();
 
         // Delete the files from all groups
-        for key in deleted_keys {
+        for object in deleted {
             conn.execute(
-                "DELETE FROM duplicate_files WHERE key = ?1 AND group_id IN (SELECT id FROM duplicate_groups WHERE scan_id = ?2)",
-                params![key, scan_id],
+                r#"
+                DELETE FROM duplicate_files
+                WHERE key = ?1
+                  AND COALESCE(bucket, (SELECT bucket FROM duplicate_scans WHERE id = ?3)) = ?2
+                  AND group_id IN (SELECT id FROM duplicate_groups WHERE scan_id = ?3)
+                "#,
+                params![object.key, object.bucket, scan_id],
             )
             .map_err(|e| AppError::Storage(format!("Failed to delete file record: {}", e)))?;
         }
@@ -547,32 +690,182 @@ impl DbManager {
             .map_err(|e| AppError::Storage(format!("Failed to delete empty group: {}", e)))?;
         }
 
-        // Update scan stats
-        let stats: (i64, i64, i64) = conn
+        refresh_scan_stats(&conn, scan_id)
+    }
+
+    /// Get a single duplicate group with its files
+    pub fn get_duplicate_group(&self, group_id: i64) -> Result<Option<DuplicateGroup>> {
+        let conn = self.get_conn()?;
+
+        let result = conn.query_row(
+            r#"
+            SELECT id, scan_id, content_hash, hash_type, file_size, file_count, heuristic
+            FROM duplicate_groups
+            WHERE id = ?1
+            "#,
+            params![group_id],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, i64>(4)?,
+                    row.get::<_, i64>(5)?,
+                    row.get::<_, bool>(6)?,
+                ))
+            },
+        );
+
+        let (id, scan_id, content_hash, hash_type_str, file_size, file_count, heuristic) =
+            match result {
+                Ok(row) => row,
+                Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+                Err(e) => {
+                    return Err(AppError::Storage(format!(
+                        "Failed to get duplicate group: {}",
+                        e
+                    )))
+                }
+            };
+
+        let files = self.get_duplicate_files(id)?;
+        Ok(Some(DuplicateGroup {
+            id,
+            scan_id,
+            content_hash,
+            hash_type: HashType::try_from(hash_type_str.as_str()).unwrap_or(HashType::Etag),
+            file_size,
+            file_count,
+            heuristic,
+            files,
+        }))
+    }
+
+    /// Replace a group with content-verified sub-groups (SHA-256 hex hash and the
+    /// files sharing it). Sub-groups with fewer than two files are dropped, and a
+    /// sub-group whose hash already exists in the scan is merged into that group.
+    /// Returns the IDs of the resulting groups.
+    pub fn split_duplicate_group(
+        &self,
+        group_id: i64,
+        subgroups: &[(String, Vec<DuplicateFile>)],
+    ) -> Result<Vec<i64>> {
+        let mut conn = self.get_conn()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Storage(format!("Failed to start transaction: {}", e)))?;
+
+        let (scan_id, file_size): (i64, i64) = tx
             .query_row(
+                "SELECT scan_id, file_size FROM duplicate_groups WHERE id = ?1",
+                params![group_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|e| AppError::Storage(format!("Failed to get duplicate group: {}", e)))?;
+
+        // Files are removed along with the group (ON DELETE CASCADE)
+        tx.execute("DELETE FROM duplicate_groups WHERE id = ?1", params![group_id])
+            .map_err(|e| AppError::Storage(format!("Failed to delete duplicate group: {}", e)))?;
+
+        let mut group_ids = Vec::new();
+        for (hash, files) in subgroups.iter().filter(|(_, files)| files.len() > 1) {
+            let existing = tx.query_row(
+                "SELECT id FROM duplicate_groups WHERE scan_id = ?1 AND content_hash = ?2",
+                params![scan_id, hash],
+                |row| row.get::<_, i64>(0),
+            );
+            let target_id = match existing {
+                Ok(id) => id,
+                Err(rusqlite::Error::QueryReturnedNoRows) => {
+                    tx.execute(
+                        r#"
+                        INSERT INTO duplicate_groups (scan_id, content_hash, hash_type, file_size, file_count, heuristic)
+                        VALUES (?1, ?2, ?3, ?4, 0, 0)
+                        "#,
+                        params![scan_id, hash, HashType::Sha256.to_string(), file_size],
+                    )
+                    .map_err(|e| {
+                        AppError::Storage(format!("Failed to insert duplicate group: {}", e))
+                    })?;
+                    tx.last_insert_rowid()
+                }
+                Err(e) => {
+                    return Err(AppError::Storage(format!(
+                        "Failed to look up duplicate group: {}",
+                        e
+                    )))
+                }
+            };
+
+            for file in files {
+                tx.execute(
+                    r#"
+                    INSERT INTO duplicate_files (group_id, bucket, key, etag, last_modified, storage_class)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                    "#,
+                    params![
+                        target_id,
+                        file.bucket,
+                        file.key,
+                        file.etag,
+                        file.last_modified,
+                        file.storage_class
+                    ],
+                )
+                .map_err(|e| {
+                    AppError::Storage(format!("Failed to insert duplicate file: {}", e))
+                })?;
+            }
+
+            tx.execute(
                 r#"
-                SELECT
-                    COUNT(DISTINCT dg.id) as groups,
-                    COALESCE(SUM(dg.file_count), 0) as files,
-                    COALESCE(SUM(dg.file_size * (dg.file_count - 1)), 0) as reclaimable
-                FROM duplicate_groups dg
-                WHERE dg.scan_id = ?1
+                UPDATE duplicate_groups
+                SET file_count = (SELECT COUNT(*) FROM duplicate_files WHERE group_id = ?1)
+                WHERE id = ?1
                 "#,
-                params![scan_id],
-                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+                params![target_id],
             )
-            .unwrap_or((0, 0, 0));
+            .map_err(|e| AppError::Storage(format!("Failed to update file count: {}", e)))?;
 
-        conn.execute(
+            group_ids.push(target_id);
+        }
+
+        refresh_scan_stats(&tx, scan_id)?;
+
+        tx.commit()
+            .map_err(|e| AppError::Storage(format!("Failed to commit group split: {}", e)))?;
+
+        Ok(group_ids)
+    }
+}
+
+/// Recompute a scan's duplicate totals from its remaining groups
+fn refresh_scan_stats(conn: &Connection, scan_id: i64) -> Result<()> {
+    let stats: (i64, i64, i64) = conn
+        .query_row(
             r#"
-            UPDATE duplicate_scans
-            SET duplicate_groups = ?1, duplicate_files = ?2, reclaimable_bytes = ?3
-            WHERE id = ?4
+            SELECT
+                COUNT(DISTINCT dg.id) as groups,
+                COALESCE(SUM(dg.file_count), 0) as files,
+                COALESCE(SUM(dg.file_size * (dg.file_count - 1)), 0) as reclaimable
+            FROM duplicate_groups dg
+            WHERE dg.scan_id = ?1
             "#,
-            params![stats.0, stats.1, stats.2, scan_id],
+            params![scan_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
         )
-        .map_err(|e| AppError::Storage(format!("Failed to update scan stats: {}", e)))?;
-
-        Ok(())
-    }
+        .unwrap_or((0, 0, 0));
+
+    conn.execute(
+        r#"
+        UPDATE duplicate_scans
+        SET duplicate_groups = ?1, duplicate_files = ?2, reclaimable_bytes = ?3
+        WHERE id = ?4
+        "#,
+        params![stats.0, stats.1, stats.2, scan_id],
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to update scan stats: {}", e)))?;
+
+    Ok(())
 }
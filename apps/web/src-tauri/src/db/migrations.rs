@@ -3,7 +3,7 @@ use rusqlite::Connection;
 use crate::error::{AppError, Result};
 
 /// Current schema version
-const SCHEMA_VERSION: i32 = 1;
+const SCHEMA_VERSION: i32 = 18;
 
 /// Run database migrations
 pub fn run_migrations(conn: &Connection) -> Result<()> {
@@ -21,6 +21,74 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
         migrate_v1(conn)?;
     }
 
+    if current_version < 2 {
+        migrate_v2(conn)?;
+    }
+
+    if current_version < 3 {
+        migrate_v3(conn)?;
+    }
+
+    if current_version < 4 {
+        migrate_v4(conn)?;
+    }
+
+    if current_version < 5 {
+        migrate_v5(conn)?;
+    }
+
+    if current_version < 6 {
+        migrate_v6(conn)?;
+    }
+
+    if current_version < 7 {
+        migrate_v7(conn)?;
+    }
+
+    if current_version < 8 {
+        migrate_v8(conn)?;
+    }
+
+    if current_version < 9 {
+        migrate_v9(conn)?;
+    }
+
+    if current_version < 10 {
+        migrate_v10(conn)?;
+    }
+
+    if current_version < 11 {
+        migrate_v11(conn)?;
+    }
+
+    if current_version < 12 {
+        migrate_v12(conn)?;
+    }
+
+    if current_version < 13 {
+        migrate_v13(conn)?;
+    }
+
+    if current_version < 14 {
+        migrate_v14(conn)?;
+    }
+
+    if current_version < 15 {
+        migrate_v15(conn)?;
+    }
+
+    if current_version < 16 {
+        migrate_v16(conn)?;
+    }
+
+    if current_version < 17 {
+        migrate_v17(conn)?;
+    }
+
+    if current_version < 18 {
+        migrate_v18(conn)?;
+    }
+
     // Set the current schema version
     conn.pragma_update(None, "user_version", SCHEMA_VERSION)
         .map_err(|e| AppError::Storage(format!("Failed to update schema version: {}", e)))?;
@@ -215,3 +283,353 @@ fn migrate_v1(conn: &Connection) -> Result<()> {
     log::info!("Migration v1 completed successfully");
     Ok(())
 }
+
+/// Migration v2: Flag duplicate groups matched by ETag heuristics
+fn migrate_v2(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v2: Duplicate group heuristic flag");
+
+    conn.execute_batch(
+        r#"
+        ALTER TABLE duplicate_groups ADD COLUMN heuristic INTEGER NOT NULL DEFAULT 0;
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v2: {}", e)))?;
+
+    log::info!("Migration v2 completed successfully");
+    Ok(())
+}
+
+/// Migration v3: Key-value app settings
+fn migrate_v3(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v3: App settings");
+
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS app_settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v3: {}", e)))?;
+
+    log::info!("Migration v3 completed successfully");
+    Ok(())
+}
+
+/// Migration v4: Failed items of batch operations, kept for retry
+fn migrate_v4(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v4: Failed batch items");
+
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS failed_batches (
+            batch_id TEXT PRIMARY KEY,
+            operation TEXT NOT NULL,
+            account_id TEXT NOT NULL,
+            bucket TEXT NOT NULL,
+            -- Operation-specific parameters as JSON
+            params TEXT NOT NULL DEFAULT '{}',
+            updated_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+        );
+
+        CREATE TABLE IF NOT EXISTS failed_batch_items (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            batch_id TEXT NOT NULL REFERENCES failed_batches(batch_id) ON DELETE CASCADE,
+            key TEXT NOT NULL,
+            dest_key TEXT,
+            error TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_failed_items_batch ON failed_batch_items(batch_id);
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v4: {}", e)))?;
+
+    log::info!("Migration v4 completed successfully");
+    Ok(())
+}
+
+/// Migration v5: Thumbnail cache
+fn migrate_v5(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v5: Thumbnail cache");
+
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS thumbnail_cache (
+            account_id TEXT NOT NULL,
+            bucket TEXT NOT NULL,
+            key TEXT NOT NULL,
+            thumb_size INTEGER NOT NULL,
+            -- Source object version; a mismatch means the cached entry is stale
+            etag TEXT NOT NULL,
+            object_size INTEGER NOT NULL,
+            base64 TEXT NOT NULL,
+            width INTEGER NOT NULL,
+            height INTEGER NOT NULL,
+            bytes INTEGER NOT NULL,
+            last_accessed INTEGER NOT NULL,
+            PRIMARY KEY (account_id, bucket, key, thumb_size)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_thumbnail_cache_accessed ON thumbnail_cache(last_accessed);
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v5: {}", e)))?;
+
+    log::info!("Migration v5 completed successfully");
+    Ok(())
+}
+
+/// Migration v6: Depth limit for duplicate scans
+fn migrate_v6(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v6: Duplicate scan depth limit");
+
+    conn.execute_batch(
+        r#"
+        ALTER TABLE duplicate_scans ADD COLUMN max_depth INTEGER;
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v6: {}", e)))?;
+
+    log::info!("Migration v6 completed successfully");
+    Ok(())
+}
+
+/// Migration v7: Key filters for duplicate scans
+fn migrate_v7(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v7: Duplicate scan filters");
+
+    conn.execute_batch(
+        r#"
+        -- JSON-encoded ScanFilters; NULL for scans without filters
+        ALTER TABLE duplicate_scans ADD COLUMN filters TEXT;
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v7: {}", e)))?;
+
+    log::info!("Migration v7 completed successfully");
+    Ok(())
+}
+
+/// Migration v8: Shared (presigned) link records
+fn migrate_v8(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v8: Shared links");
+
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS shared_links (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            account_id TEXT NOT NULL,
+            bucket TEXT NOT NULL,
+            key TEXT NOT NULL,
+            url TEXT NOT NULL,
+            label TEXT,
+            created_at INTEGER NOT NULL,
+            expires_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_shared_links_account ON shared_links(account_id, bucket);
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v8: {}", e)))?;
+
+    log::info!("Migration v8 completed successfully");
+    Ok(())
+}
+
+/// Migration v9: Phase timings for duplicate scans
+fn migrate_v9(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v9: Duplicate scan phase timings");
+
+    conn.execute_batch(
+        r#"
+        -- NULL for scans recorded before timings were tracked
+        ALTER TABLE duplicate_scans ADD COLUMN listing_ms INTEGER;
+        ALTER TABLE duplicate_scans ADD COLUMN hashing_ms INTEGER;
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v9: {}", e)))?;
+
+    log::info!("Migration v9 completed successfully");
+    Ok(())
+}
+
+/// Migration v10: Cross-bucket duplicate scans
+fn migrate_v10(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v10: Cross-bucket duplicate scans");
+
+    conn.execute_batch(
+        r#"
+        -- JSON-encoded list of scanned (bucket, prefix) targets; NULL for
+        -- single-bucket scans, whose target is the bucket and prefix columns
+        ALTER TABLE duplicate_scans ADD COLUMN targets TEXT;
+        -- Bucket holding the file; NULL for older rows, which are in the scan's bucket
+        ALTER TABLE duplicate_files ADD COLUMN bucket TEXT;
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v10: {}", e)))?;
+
+    log::info!("Migration v10 completed successfully");
+    Ok(())
+}
+
+/// Migration v11: Per-file log of sync sessions
+fn migrate_v11(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v11: Sync session files");
+
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS sync_session_files (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id INTEGER NOT NULL REFERENCES sync_sessions(id) ON DELETE CASCADE,
+            relative_path TEXT NOT NULL,
+            action TEXT NOT NULL,
+            size INTEGER,
+            status TEXT NOT NULL,
+            error TEXT,
+            recorded_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_sync_session_files_session ON sync_session_files(session_id);
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v11: {}", e)))?;
+
+    log::info!("Migration v11 completed successfully");
+    Ok(())
+}
+
+/// Migration v12: Cached analytics object listings, one per scanned prefix
+fn migrate_v12(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v12: Analytics cache");
+
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS analytics_cache (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            account_id TEXT NOT NULL,
+            bucket TEXT NOT NULL,
+            prefix TEXT NOT NULL,
+            object_count INTEGER NOT NULL,
+            cached_at INTEGER NOT NULL,
+            UNIQUE(account_id, bucket, prefix)
+        );
+
+        CREATE TABLE IF NOT EXISTS analytics_cache_objects (
+            cache_id INTEGER NOT NULL REFERENCES analytics_cache(id) ON DELETE CASCADE,
+            key TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            last_modified TEXT,
+            storage_class TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_analytics_cache_objects_key ON analytics_cache_objects(cache_id, key);
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v12: {}", e)))?;
+
+    log::info!("Migration v12 completed successfully");
+    Ok(())
+}
+
+/// Migration v13: Symlink handling for sync pairs. Existing pairs keep
+/// following symlinks, as every scan did before the option existed.
+fn migrate_v13(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v13: Sync pair symlink option");
+
+    conn.execute_batch(
+        r#"
+        ALTER TABLE sync_pairs ADD COLUMN follow_symlinks INTEGER NOT NULL DEFAULT 1;
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v13: {}", e)))?;
+
+    log::info!("Migration v13 completed successfully");
+    Ok(())
+}
+
+/// Migration v14: Pruning empty local directories after sync deletions
+fn migrate_v14(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v14: Sync pair empty directory pruning");
+
+    conn.execute_batch(
+        r#"
+        ALTER TABLE sync_pairs ADD COLUMN prune_empty_dirs INTEGER NOT NULL DEFAULT 0;
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v14: {}", e)))?;
+
+    log::info!("Migration v14 completed successfully");
+    Ok(())
+}
+
+/// Migration v15: Per-pair file size limit for sync
+fn migrate_v15(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v15: Sync pair max file size");
+
+    conn.execute_batch(
+        r#"
+        -- NULL means no limit
+        ALTER TABLE sync_pairs ADD COLUMN max_file_size_bytes INTEGER;
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v15: {}", e)))?;
+
+    log::info!("Migration v15 completed successfully");
+    Ok(())
+}
+
+/// Migration v16: Post-transfer verification for sync pairs
+fn migrate_v16(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v16: Sync pair transfer verification");
+
+    conn.execute_batch(
+        r#"
+        ALTER TABLE sync_pairs ADD COLUMN verify INTEGER NOT NULL DEFAULT 0;
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v16: {}", e)))?;
+
+    log::info!("Migration v16 completed successfully");
+    Ok(())
+}
+
+/// Migration v17: Per-phase timings for sync sessions
+fn migrate_v17(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v17: Sync session phase timings");
+
+    conn.execute_batch(
+        r#"
+        -- JSON object of phase durations; NULL until the session completes
+        ALTER TABLE sync_sessions ADD COLUMN phase_timings TEXT;
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v17: {}", e)))?;
+
+    log::info!("Migration v17 completed successfully");
+    Ok(())
+}
+
+/// Migration v18: Storage class breakdown of each bucket's latest whole-bucket
+/// analytics scan, kept apart from the expiring listing cache
+fn migrate_v18(conn: &Connection) -> Result<()> {
+    log::info!("Running migration v18: Analytics storage class totals");
+
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS analytics_storage_classes (
+            account_id TEXT NOT NULL,
+            bucket TEXT NOT NULL,
+            -- JSON array of { storageClass, size, objectCount }
+            storage_classes TEXT NOT NULL,
+            calculated_at INTEGER NOT NULL,
+            PRIMARY KEY (account_id, bucket)
+        );
+        "#,
+    )
+    .map_err(|e| AppError::Storage(format!("Failed to run migration v18: {}", e)))?;
+
+    log::info!("Migration v18 completed successfully");
+    Ok(())
+}
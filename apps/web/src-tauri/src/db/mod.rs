@@ -1,7 +1,12 @@
+pub mod analytics;
+pub mod batches;
 pub mod duplicates;
 pub mod migrations;
 pub mod operations;
+pub mod settings;
+pub mod shared_links;
 pub mod sync;
+pub mod thumbnails;
 
 use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
@@ -53,7 +58,14 @@ impl DbManager {
 
         log::info!("Database initialized at {:?}", db_path);
 
-        Ok(Self { pool })
+        let db = Self { pool };
+        match db.fail_interrupted_operations() {
+            Ok(0) => {}
+            Ok(count) => log::info!("Marked {} interrupted operations as failed", count),
+            Err(e) => log::warn!("Failed to close interrupted operations: {}", e),
+        }
+
+        Ok(db)
     }
 
     /// Get a connection from the pool
@@ -4,6 +4,9 @@ use serde::{Deserialize, Serialize};
 use super::DbManager;
 use crate::error::{AppError, Result};
 
+/// Bytes in a megabyte, for the transfer rates in `OperationStats`
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+
 /// Operation types
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -15,6 +18,10 @@ pub enum OperationType {
     Move,
     Rename,
     CreateFolder,
+    CreateObject,
+    Trash,
+    RestoreFromTrash,
+    Purge,
 }
 
 impl std::fmt::Display for OperationType {
@@ -27,6 +34,10 @@ impl std::fmt::Display for OperationType {
             OperationType::Move => write!(f, "move"),
             OperationType::Rename => write!(f, "rename"),
             OperationType::CreateFolder => write!(f, "create_folder"),
+            OperationType::CreateObject => write!(f, "create_object"),
+            OperationType::Trash => write!(f, "trash"),
+            OperationType::RestoreFromTrash => write!(f, "restore_from_trash"),
+            OperationType::Purge => write!(f, "purge"),
         }
     }
 }
@@ -43,6 +54,10 @@ impl TryFrom<&str> for OperationType {
             "move" => Ok(OperationType::Move),
             "rename" => Ok(OperationType::Rename),
             "create_folder" => Ok(OperationType::CreateFolder),
+            "create_object" => Ok(OperationType::CreateObject),
+            "trash" => Ok(OperationType::Trash),
+            "restore_from_trash" => Ok(OperationType::RestoreFromTrash),
+            "purge" => Ok(OperationType::Purge),
             _ => Err(AppError::InvalidInput(format!(
                 "Unknown operation type: {}",
                 value
@@ -187,6 +202,20 @@ pub struct OperationStats {
     pub completed: i64,
     pub failed: i64,
     pub by_type: Vec<TypeCount>,
+    /// Mean rate of completed uploads and downloads, in MB/s (1 MB = 1024 × 1024 bytes)
+    pub avg_mb_per_sec: Option<f64>,
+    /// Fastest completed upload or download, in MB/s
+    pub peak_mb_per_sec: Option<f64>,
+    /// Bytes uploaded and downloaded per UTC day, oldest first
+    pub bytes_per_day: Vec<DailyBytes>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyBytes {
+    /// UTC date as YYYY-MM-DD
+    pub day: String,
+    pub bytes: i64,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -286,6 +315,45 @@ impl DbManager {
         Ok(())
     }
 
+    /// Finish an operation that is still pending or in progress. The update
+    /// only applies to an open row, so a late or repeated call can't overwrite
+    /// an outcome already recorded. Returns whether the row was updated.
+    pub fn finish_operation(
+        &self,
+        id: i64,
+        status: OperationStatus,
+        size: Option<i64>,
+        duration_ms: i64,
+        error: Option<&str>,
+    ) -> Result<bool> {
+        let conn = self.get_conn()?;
+
+        let updated = conn
+            .execute(
+                r#"
+                UPDATE operations
+                SET status = ?1, size = COALESCE(?2, size), duration_ms = ?3, error_message = ?4
+                WHERE id = ?5 AND status IN ('pending', 'in_progress')
+                "#,
+                params![status.to_string(), size, duration_ms, error, id],
+            )
+            .map_err(|e| AppError::Storage(format!("Failed to finish operation: {}", e)))?;
+
+        Ok(updated > 0)
+    }
+
+    /// Mark operations left in progress by a previous run as failed, since no
+    /// transfer survives a restart. Returns the number of operations updated.
+    pub fn fail_interrupted_operations(&self) -> Result<usize> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "UPDATE operations SET status = 'failed', error_message = ?1 WHERE status = 'in_progress'",
+            params!["Interrupted before completing"],
+        )
+        .map_err(|e| AppError::Storage(format!("Failed to close interrupted operations: {}", e)))
+    }
+
     /// Query operations with filters
     pub fn query_operations(&self, filter: &OperationFilter) -> Result<Vec<Operation>> {
         let conn = self.get_conn()?;
@@ -446,12 +514,52 @@ impl DbManager {
             .filter_map(|r| r.ok())
             .collect();
 
+        // Transfer rates; operations without a size or duration can't be rated
+        let transfers_where = format!(
+            "{} AND status = 'completed' AND operation IN ('upload', 'download')",
+            base_where
+        );
+        let (avg_bytes_per_sec, peak_bytes_per_sec): (Option<f64>, Option<f64>) = conn
+            .query_row(
+                &format!(
+                    "SELECT AVG(size * 1000.0 / duration_ms), MAX(size * 1000.0 / duration_ms)
+                     FROM operations WHERE {} AND size > 0 AND duration_ms > 0",
+                    transfers_where
+                ),
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap_or((None, None));
+
+        // Bytes transferred per day
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT date(timestamp, 'unixepoch') AS day, COALESCE(SUM(size), 0)
+                 FROM operations WHERE {} GROUP BY day ORDER BY day",
+                transfers_where
+            ))
+            .map_err(|e| AppError::Storage(format!("Failed to prepare stats query: {}", e)))?;
+
+        let bytes_per_day: Vec<DailyBytes> = stmt
+            .query_map([], |row| {
+                Ok(DailyBytes {
+                    day: row.get(0)?,
+                    bytes: row.get(1)?,
+                })
+            })
+            .map_err(|e| AppError::Storage(format!("Failed to get stats by day: {}", e)))?
+            .filter_map(|r| r.ok())
+            .collect();
+
         Ok(OperationStats {
             total_operations,
             total_bytes,
             completed,
             failed,
             by_type,
+            avg_mb_per_sec: avg_bytes_per_sec.map(|rate| rate / BYTES_PER_MB),
+            peak_mb_per_sec: peak_bytes_per_sec.map(|rate| rate / BYTES_PER_MB),
+            bytes_per_day,
         })
     }
 
@@ -0,0 +1,274 @@
+use rusqlite::params;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::DbManager;
+use crate::error::{AppError, Result};
+
+const APP_SETTINGS_KEY: &str = "app_settings";
+const PREVIEW_SETTINGS_KEY: &str = "preview_settings";
+const UPLOAD_SETTINGS_KEY: &str = "upload_settings";
+
+/// Default prefix soft-deleted objects are moved under
+pub const DEFAULT_TRASH_PREFIX: &str = ".trash/";
+
+/// Application-wide settings persisted in the database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppSettings {
+    /// Move deleted objects to the trash prefix instead of deleting them
+    pub trash_enabled: bool,
+    /// Prefix (ending in '/') under which trashed objects are kept
+    pub trash_prefix: String,
+    /// Times a throttled or failed request in a bulk copy, delete or hash is retried
+    pub bulk_max_retries: u32,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            trash_enabled: false,
+            trash_prefix: DEFAULT_TRASH_PREFIX.to_string(),
+            bulk_max_retries: 3,
+        }
+    }
+}
+
+impl AppSettings {
+    /// Trash prefix normalized to end with a single '/'
+    pub fn trash_prefix(&self) -> String {
+        let prefix = self.trash_prefix.trim().trim_matches('/');
+        if prefix.is_empty() {
+            DEFAULT_TRASH_PREFIX.to_string()
+        } else {
+            format!("{}/", prefix)
+        }
+    }
+}
+
+/// Default size limits for previews and thumbnails
+pub const DEFAULT_MAX_PREVIEW_SIZE: i64 = 5 * 1024 * 1024; // 5MB
+pub const DEFAULT_MAX_TEXT_PREVIEW_SIZE: i64 = 1024 * 1024; // 1MB
+pub const DEFAULT_MAX_PDF_SIZE: i64 = 20 * 1024 * 1024; // 20MB
+pub const DEFAULT_MAX_THUMBNAIL_SOURCE_SIZE: i64 = 10 * 1024 * 1024; // 10MB
+
+/// Upper bound for any preview limit; previews are held in memory and sent over IPC
+const MAX_PREVIEW_LIMIT: i64 = 512 * 1024 * 1024; // 512MB
+
+/// Size limits (in bytes) for previews and thumbnails
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PreviewSettings {
+    /// Largest image that will be previewed
+    pub max_preview_size: i64,
+    /// Text beyond this is truncated (see `get_text_range` for reading further)
+    pub max_text_preview_size: i64,
+    pub max_pdf_size: i64,
+    /// Largest image that will be downloaded to generate a thumbnail
+    pub max_thumbnail_source_size: i64,
+}
+
+impl Default for PreviewSettings {
+    fn default() -> Self {
+        Self {
+            max_preview_size: DEFAULT_MAX_PREVIEW_SIZE,
+            max_text_preview_size: DEFAULT_MAX_TEXT_PREVIEW_SIZE,
+            max_pdf_size: DEFAULT_MAX_PDF_SIZE,
+            max_thumbnail_source_size: DEFAULT_MAX_THUMBNAIL_SOURCE_SIZE,
+        }
+    }
+}
+
+impl PreviewSettings {
+    /// Check that every limit is positive and within `MAX_PREVIEW_LIMIT`
+    pub fn validate(&self) -> Result<()> {
+        let limits = [
+            ("maxPreviewSize", self.max_preview_size),
+            ("maxTextPreviewSize", self.max_text_preview_size),
+            ("maxPdfSize", self.max_pdf_size),
+            ("maxThumbnailSourceSize", self.max_thumbnail_source_size),
+        ];
+        for (name, value) in limits {
+            if value <= 0 || value > MAX_PREVIEW_LIMIT {
+                return Err(AppError::InvalidInput(format!(
+                    "{} must be between 1 and {} bytes",
+                    name, MAX_PREVIEW_LIMIT
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// S3 multipart limits: every part but the last must be at least 5MB, no part
+/// may exceed 5GB, and an upload may have at most 10,000 parts
+pub const MIN_PART_SIZE: u64 = 5 * 1024 * 1024; // 5MB
+pub const MAX_PART_SIZE: u64 = 5 * 1024 * 1024 * 1024; // 5GB
+pub const MAX_PARTS: u64 = 10_000;
+
+/// Granularity automatically scaled part sizes are rounded up to
+const PART_SIZE_STEP: u64 = 1024 * 1024; // 1MB
+
+/// Default multipart part size and the file size above which multipart is used
+pub const DEFAULT_PART_SIZE: u64 = MIN_PART_SIZE;
+pub const DEFAULT_MULTIPART_THRESHOLD: u64 = MIN_PART_SIZE;
+
+/// Multipart upload tuning (in bytes)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UploadSettings {
+    /// Preferred part size; raised automatically for files that would need more than `MAX_PARTS`
+    pub part_size: u64,
+    /// Files larger than this are uploaded in parts
+    pub multipart_threshold: u64,
+}
+
+impl Default for UploadSettings {
+    fn default() -> Self {
+        Self {
+            part_size: DEFAULT_PART_SIZE,
+            multipart_threshold: DEFAULT_MULTIPART_THRESHOLD,
+        }
+    }
+}
+
+impl UploadSettings {
+    /// Check both values against S3's part size limits. The threshold is capped
+    /// at `MAX_PART_SIZE` since that is also the largest single PUT.
+    pub fn validate(&self) -> Result<()> {
+        let values = [
+            ("partSize", self.part_size),
+            ("multipartThreshold", self.multipart_threshold),
+        ];
+        for (name, value) in values {
+            if !(MIN_PART_SIZE..=MAX_PART_SIZE).contains(&value) {
+                return Err(AppError::InvalidInput(format!(
+                    "{} must be between {} and {} bytes",
+                    name, MIN_PART_SIZE, MAX_PART_SIZE
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Part size to use for a file of `total_bytes`: the configured size, or the
+    /// smallest whole number of MB that keeps the upload within `MAX_PARTS`
+    pub fn effective_part_size(&self, total_bytes: u64) -> u64 {
+        let min_for_parts = total_bytes.div_ceil(MAX_PARTS).next_multiple_of(PART_SIZE_STEP);
+        self.part_size.max(min_for_parts)
+    }
+}
+
+impl DbManager {
+    /// Load a JSON settings value, falling back to defaults when missing
+    fn load_settings<T: DeserializeOwned + Default>(&self, key: &str) -> Result<T> {
+        let conn = self.get_conn()?;
+
+        let result = conn.query_row(
+            "SELECT value FROM app_settings WHERE key = ?1",
+            params![key],
+            |row| row.get::<_, String>(0),
+        );
+
+        match result {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| AppError::Storage(format!("Failed to parse settings: {}", e))),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(T::default()),
+            Err(e) => Err(AppError::Storage(format!("Failed to load settings: {}", e))),
+        }
+    }
+
+    /// Persist a JSON settings value
+    fn store_settings<T: Serialize>(&self, key: &str, settings: &T) -> Result<()> {
+        let conn = self.get_conn()?;
+        let json = serde_json::to_string(settings)
+            .map_err(|e| AppError::Storage(format!("Failed to serialize settings: {}", e)))?;
+
+        conn.execute(
+            r#"
+            INSERT INTO app_settings (key, value) VALUES (?1, ?2)
+            ON CONFLICT(key) DO UPDATE SET value = excluded.value
+            "#,
+            params![key, json],
+        )
+        .map_err(|e| AppError::Storage(format!("Failed to save settings: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Load app settings, falling back to defaults for missing fields
+    pub fn get_app_settings(&self) -> Result<AppSettings> {
+        self.load_settings(APP_SETTINGS_KEY)
+    }
+
+    /// Persist app settings
+    pub fn save_app_settings(&self, settings: &AppSettings) -> Result<()> {
+        self.store_settings(APP_SETTINGS_KEY, settings)
+    }
+
+    /// Load preview size limits, falling back to defaults for missing fields
+    pub fn get_preview_settings(&self) -> Result<PreviewSettings> {
+        self.load_settings(PREVIEW_SETTINGS_KEY)
+    }
+
+    /// Persist preview size limits after validating them
+    pub fn save_preview_settings(&self, settings: &PreviewSettings) -> Result<()> {
+        settings.validate()?;
+        self.store_settings(PREVIEW_SETTINGS_KEY, settings)
+    }
+
+    /// Load multipart upload settings, falling back to defaults for missing fields
+    pub fn get_upload_settings(&self) -> Result<UploadSettings> {
+        self.load_settings(UPLOAD_SETTINGS_KEY)
+    }
+
+    /// Persist multipart upload settings after validating them
+    pub fn save_upload_settings(&self, settings: &UploadSettings) -> Result<()> {
+        settings.validate()?;
+        self.store_settings(UPLOAD_SETTINGS_KEY, settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: u64 = 1024 * 1024;
+
+    #[test]
+    fn zero_bytes_use_the_configured_part_size() {
+        let settings = UploadSettings::default();
+        assert_eq!(settings.effective_part_size(0), MIN_PART_SIZE);
+    }
+
+    #[test]
+    fn small_files_keep_the_configured_part_size() {
+        let settings = UploadSettings::default();
+        assert_eq!(settings.effective_part_size(100 * MB), MIN_PART_SIZE);
+
+        let settings = UploadSettings {
+            part_size: 64 * MB,
+            ..UploadSettings::default()
+        };
+        assert_eq!(settings.effective_part_size(100 * MB), 64 * MB);
+    }
+
+    #[test]
+    fn part_size_grows_to_stay_within_the_part_limit() {
+        let settings = UploadSettings::default();
+
+        // Exactly MAX_PARTS parts of the minimum size still fit
+        let at_limit = MAX_PARTS * MIN_PART_SIZE;
+        assert_eq!(settings.effective_part_size(at_limit), MIN_PART_SIZE);
+
+        // One more byte needs the next whole MB
+        assert_eq!(settings.effective_part_size(at_limit + 1), MIN_PART_SIZE + MB);
+
+        for total in [at_limit + 1, 1024 * 1024 * MB, 5 * 1024 * 1024 * MB] {
+            let part_size = settings.effective_part_size(total);
+            assert!(total.div_ceil(part_size) <= MAX_PARTS);
+            assert_eq!(part_size % MB, 0);
+            assert!(part_size <= MAX_PART_SIZE);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use rusqlite::{params, Row};
+use serde::Serialize;
+
+use super::DbManager;
+use crate::error::{AppError, Result};
+
+/// A presigned URL that was handed out. Presigned URLs can't be revoked, so
+/// this is only a local record of what was shared and until when.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedLink {
+    pub id: i64,
+    pub account_id: String,
+    pub bucket: String,
+    pub key: String,
+    pub url: String,
+    pub label: Option<String>,
+    /// Unix timestamps (seconds)
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub expired: bool,
+}
+
+impl SharedLink {
+    fn from_row(row: &Row<'_>) -> rusqlite::Result<Self> {
+        let expires_at: i64 = row.get("expires_at")?;
+        Ok(SharedLink {
+            id: row.get("id")?,
+            account_id: row.get("account_id")?,
+            bucket: row.get("bucket")?,
+            key: row.get("key")?,
+            url: row.get("url")?,
+            label: row.get("label")?,
+            created_at: row.get("created_at")?,
+            expires_at,
+            expired: expires_at <= chrono::Utc::now().timestamp(),
+        })
+    }
+}
+
+/// Input for recording a shared link
+#[derive(Debug, Clone)]
+pub struct NewSharedLink<'a> {
+    pub account_id: &'a str,
+    pub bucket: &'a str,
+    pub key: &'a str,
+    pub url: &'a str,
+    pub label: Option<&'a str>,
+    pub created_at: i64,
+    pub expires_at: i64,
+}
+
+impl DbManager {
+    /// Record a generated presigned URL, returning its id
+    pub fn save_shared_link(&self, link: &NewSharedLink<'_>) -> Result<i64> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            r#"
+            INSERT INTO shared_links (account_id, bucket, key, url, label, created_at, expires_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+            params![
+                link.account_id,
+                link.bucket,
+                link.key,
+                link.url,
+                link.label,
+                link.created_at,
+                link.expires_at
+            ],
+        )
+        .map_err(|e| AppError::Storage(format!("Failed to save shared link: {}", e)))?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// List shared links, newest first, optionally scoped to an account and bucket
+    pub fn list_shared_links(
+        &self,
+        account_id: Option<&str>,
+        bucket: Option<&str>,
+        include_expired: bool,
+    ) -> Result<Vec<SharedLink>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT id, account_id, bucket, key, url, label, created_at, expires_at
+                FROM shared_links
+                WHERE (?1 IS NULL OR account_id = ?1)
+                  AND (?2 IS NULL OR bucket = ?2)
+                  AND (?3 OR expires_at > ?4)
+                ORDER BY created_at DESC, id DESC
+                "#,
+            )
+            .map_err(|e| AppError::Storage(format!("Failed to prepare query: {}", e)))?;
+
+        let links = stmt
+            .query_map(
+                params![
+                    account_id,
+                    bucket,
+                    include_expired,
+                    chrono::Utc::now().timestamp()
+                ],
+                |row| SharedLink::from_row(row),
+            )
+            .map_err(|e| AppError::Storage(format!("Failed to query shared links: {}", e)))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Storage(format!("Failed to read shared links: {}", e)))?;
+
+        Ok(links)
+    }
+
+    /// Remove the local record of a shared link. The URL itself stays valid until it expires.
+    pub fn delete_shared_link(&self, id: i64) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        let deleted = conn
+            .execute("DELETE FROM shared_links WHERE id = ?1", params![id])
+            .map_err(|e| AppError::Storage(format!("Failed to delete shared link: {}", e)))?;
+
+        if deleted == 0 {
+            return Err(AppError::NotFound(format!("Shared link {} not found", id)));
+        }
+        Ok(())
+    }
+}
@@ -118,6 +118,80 @@ impl TryFrom<&str> for SyncSessionStatus {
     }
 }
 
+/// What a sync did to one file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncFileAction {
+    Upload,
+    Download,
+    DeleteLocal,
+    DeleteRemote,
+}
+
+impl std::fmt::Display for SyncFileAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncFileAction::Upload => write!(f, "upload"),
+            SyncFileAction::Download => write!(f, "download"),
+            SyncFileAction::DeleteLocal => write!(f, "delete_local"),
+            SyncFileAction::DeleteRemote => write!(f, "delete_remote"),
+        }
+    }
+}
+
+impl TryFrom<&str> for SyncFileAction {
+    type Error = AppError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        match value {
+            "upload" => Ok(SyncFileAction::Upload),
+            "download" => Ok(SyncFileAction::Download),
+            "delete_local" => Ok(SyncFileAction::DeleteLocal),
+            "delete_remote" => Ok(SyncFileAction::DeleteRemote),
+            _ => Err(AppError::InvalidInput(format!(
+                "Unknown sync file action: {}",
+                value
+            ))),
+        }
+    }
+}
+
+/// Outcome of a sync file action
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncFileStatus {
+    Completed,
+    Failed,
+    /// Not performed, e.g. a deletion while delete propagation is off
+    Skipped,
+}
+
+impl std::fmt::Display for SyncFileStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncFileStatus::Completed => write!(f, "completed"),
+            SyncFileStatus::Failed => write!(f, "failed"),
+            SyncFileStatus::Skipped => write!(f, "skipped"),
+        }
+    }
+}
+
+impl TryFrom<&str> for SyncFileStatus {
+    type Error = AppError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        match value {
+            "completed" => Ok(SyncFileStatus::Completed),
+            "failed" => Ok(SyncFileStatus::Failed),
+            "skipped" => Ok(SyncFileStatus::Skipped),
+            _ => Err(AppError::InvalidInput(format!(
+                "Unknown sync file status: {}",
+                value
+            ))),
+        }
+    }
+}
+
 /// A sync pair configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -130,12 +204,26 @@ pub struct SyncPair {
     pub remote_prefix: String,
     pub sync_direction: SyncDirection,
     pub delete_propagation: bool,
+    /// Scan the targets of symlinks in the local folder instead of skipping them
+    pub follow_symlinks: bool,
+    /// Remove local directories left empty by propagated deletions
+    pub prune_empty_dirs: bool,
+    /// Files larger than this are never transferred; `None` means no limit
+    pub max_file_size_bytes: Option<i64>,
+    /// Check each transferred file against its source afterwards
+    pub verify: bool,
     pub status: SyncPairStatus,
     pub last_sync_at: Option<i64>,
     pub last_error: Option<String>,
     pub created_at: i64,
 }
 
+/// Pairs follow symlinks unless told otherwise, matching scans from before
+/// the option existed
+fn default_follow_symlinks() -> bool {
+    true
+}
+
 /// Input for creating a new sync pair
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -147,6 +235,14 @@ pub struct NewSyncPair {
     pub remote_prefix: String,
     pub sync_direction: SyncDirection,
     pub delete_propagation: bool,
+    #[serde(default = "default_follow_symlinks")]
+    pub follow_symlinks: bool,
+    #[serde(default)]
+    pub prune_empty_dirs: bool,
+    #[serde(default)]
+    pub max_file_size_bytes: Option<i64>,
+    #[serde(default)]
+    pub verify: bool,
 }
 
 /// Tracked file state (local or remote)
@@ -204,6 +300,34 @@ pub struct SyncSession {
     pub files_deleted_remote: i64,
     pub bytes_transferred: i64,
     pub error_message: Option<String>,
+    /// Where the time went (None until the session completes, and for older sessions)
+    pub phase_timings: Option<SyncPhaseTimings>,
+}
+
+/// Time spent in each phase of a sync session, in milliseconds
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPhaseTimings {
+    /// Scanning the local directory and listing the remote prefix
+    pub listing_ms: i64,
+    /// Computing checksums of uploads for verification
+    pub hashing_ms: i64,
+    /// Uploads, downloads and deletions, excluding hashing
+    pub transferring_ms: i64,
+}
+
+/// One file action recorded during a sync session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncSessionFile {
+    pub id: i64,
+    pub session_id: i64,
+    pub relative_path: String,
+    pub action: SyncFileAction,
+    pub size: Option<i64>,
+    pub status: SyncFileStatus,
+    pub error: Option<String>,
+    pub recorded_at: i64,
 }
 
 /// Summary for sync preview (dry-run)
@@ -214,6 +338,8 @@ pub struct SyncPreview {
     pub to_download: Vec<DetectedChange>,
     pub to_delete_local: Vec<DetectedChange>,
     pub to_delete_remote: Vec<DetectedChange>,
+    /// New or changed files left out because they exceed the pair's size limit
+    pub skipped_too_large: Vec<DetectedChange>,
 }
 
 impl DbManager {
@@ -227,8 +353,10 @@ impl DbManager {
         conn.execute(
             r#"
             INSERT INTO sync_pairs (name, local_path, account_id, bucket, remote_prefix,
-                                    sync_direction, delete_propagation, status, created_at)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 'idle', ?8)
+                                    sync_direction, delete_propagation, follow_symlinks,
+                                    prune_empty_dirs, max_file_size_bytes, verify, status,
+                                    created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 'idle', ?12)
             "#,
             params![
                 pair.name,
@@ -238,6 +366,10 @@ impl DbManager {
                 pair.remote_prefix,
                 pair.sync_direction.to_string(),
                 pair.delete_propagation as i32,
+                pair.follow_symlinks as i32,
+                pair.prune_empty_dirs as i32,
+                pair.max_file_size_bytes,
+                pair.verify as i32,
                 now
             ],
         )
@@ -253,8 +385,9 @@ impl DbManager {
         let result = conn.query_row(
             r#"
             SELECT id, name, local_path, account_id, bucket, remote_prefix,
-                   sync_direction, delete_propagation, status, last_sync_at,
-                   last_error, created_at
+                   sync_direction, delete_propagation, follow_symlinks,
+                   prune_empty_dirs, max_file_size_bytes, verify, status,
+                   last_sync_at, last_error, created_at
             FROM sync_pairs
             WHERE id = ?1
             "#,
@@ -263,6 +396,9 @@ impl DbManager {
                 let direction_str: String = row.get("sync_direction")?;
                 let status_str: String = row.get("status")?;
                 let delete_prop: i32 = row.get("delete_propagation")?;
+                let follow_symlinks: i32 = row.get("follow_symlinks")?;
+                let prune_empty_dirs: i32 = row.get("prune_empty_dirs")?;
+                let verify: i32 = row.get("verify")?;
                 Ok(SyncPair {
                     id: row.get("id")?,
                     name: row.get("name")?,
@@ -273,6 +409,10 @@ impl DbManager {
                     sync_direction: SyncDirection::try_from(direction_str.as_str())
                         .unwrap_or(SyncDirection::UploadOnly),
                     delete_propagation: delete_prop != 0,
+                    follow_symlinks: follow_symlinks != 0,
+                    prune_empty_dirs: prune_empty_dirs != 0,
+                    max_file_size_bytes: row.get("max_file_size_bytes")?,
+                    verify: verify != 0,
                     status: SyncPairStatus::try_from(status_str.as_str())
                         .unwrap_or(SyncPairStatus::Idle),
                     last_sync_at: row.get("last_sync_at")?,
@@ -297,8 +437,9 @@ impl DbManager {
             .prepare(
                 r#"
             SELECT id, name, local_path, account_id, bucket, remote_prefix,
-                   sync_direction, delete_propagation, status, last_sync_at,
-                   last_error, created_at
+                   sync_direction, delete_propagation, follow_symlinks,
+                   prune_empty_dirs, max_file_size_bytes, verify, status,
+                   last_sync_at, last_error, created_at
             FROM sync_pairs
             WHERE account_id = ?1
             ORDER BY name ASC
@@ -311,6 +452,9 @@ impl DbManager {
                 let direction_str: String = row.get("sync_direction")?;
                 let status_str: String = row.get("status")?;
                 let delete_prop: i32 = row.get("delete_propagation")?;
+                let follow_symlinks: i32 = row.get("follow_symlinks")?;
+                let prune_empty_dirs: i32 = row.get("prune_empty_dirs")?;
+                let verify: i32 = row.get("verify")?;
                 Ok(SyncPair {
                     id: row.get("id")?,
                     name: row.get("name")?,
@@ -321,6 +465,10 @@ impl DbManager {
                     sync_direction: SyncDirection::try_from(direction_str.as_str())
                         .unwrap_or(SyncDirection::UploadOnly),
                     delete_propagation: delete_prop != 0,
+                    follow_symlinks: follow_symlinks != 0,
+                    prune_empty_dirs: prune_empty_dirs != 0,
+                    max_file_size_bytes: row.get("max_file_size_bytes")?,
+                    verify: verify != 0,
                     status: SyncPairStatus::try_from(status_str.as_str())
                         .unwrap_or(SyncPairStatus::Idle),
                     last_sync_at: row.get("last_sync_at")?,
@@ -632,17 +780,23 @@ impl DbManager {
     }
 
     /// Complete a sync session
-    pub fn complete_sync_session(&self, session_id: i64) -> Result<()> {
+    pub fn complete_sync_session(
+        &self,
+        session_id: i64,
+        phase_timings: &SyncPhaseTimings,
+    ) -> Result<()> {
         let conn = self.get_conn()?;
         let now = chrono::Utc::now().timestamp();
+        let timings_json = serde_json::to_string(phase_timings)
+            .map_err(|e| AppError::Storage(format!("Failed to serialize timings: {}", e)))?;
 
         conn.execute(
             r#"
             UPDATE sync_sessions
-            SET completed_at = ?1, status = 'completed'
-            WHERE id = ?2
+            SET completed_at = ?1, status = 'completed', phase_timings = ?2
+            WHERE id = ?3
             "#,
-            params![now, session_id],
+            params![now, timings_json, session_id],
         )
         .map_err(|e| AppError::Storage(format!("Failed to complete session: {}", e)))?;
 
@@ -676,7 +830,7 @@ impl DbManager {
                 r#"
             SELECT id, sync_pair_id, started_at, completed_at, status,
                    files_uploaded, files_downloaded, files_deleted_local,
-                   files_deleted_remote, bytes_transferred, error_message
+                   files_deleted_remote, bytes_transferred, error_message, phase_timings
             FROM sync_sessions
             WHERE sync_pair_id = ?1
             ORDER BY started_at DESC
@@ -688,6 +842,7 @@ impl DbManager {
         let sessions = stmt
             .query_map(params![pair_id, limit], |row| {
                 let status_str: String = row.get("status")?;
+                let timings_json: Option<String> = row.get("phase_timings")?;
                 Ok(SyncSession {
                     id: row.get("id")?,
                     sync_pair_id: row.get("sync_pair_id")?,
@@ -701,6 +856,8 @@ impl DbManager {
                     files_deleted_remote: row.get("files_deleted_remote")?,
                     bytes_transferred: row.get("bytes_transferred")?,
                     error_message: row.get("error_message")?,
+                    phase_timings: timings_json
+                        .and_then(|json| serde_json::from_str(&json).ok()),
                 })
             })
             .map_err(|e| AppError::Storage(format!("Failed to get sessions: {}", e)))?
@@ -709,4 +866,76 @@ impl DbManager {
 
         Ok(sessions)
     }
+
+    /// Record what a sync session did to one file
+    pub fn log_sync_session_file(
+        &self,
+        session_id: i64,
+        relative_path: &str,
+        action: SyncFileAction,
+        size: Option<i64>,
+        status: SyncFileStatus,
+        error: Option<&str>,
+    ) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = chrono::Utc::now().timestamp();
+
+        conn.execute(
+            r#"
+            INSERT INTO sync_session_files (session_id, relative_path, action, size, status, error, recorded_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+            params![
+                session_id,
+                relative_path,
+                action.to_string(),
+                size,
+                status.to_string(),
+                error,
+                now
+            ],
+        )
+        .map_err(|e| AppError::Storage(format!("Failed to log sync file: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Get the per-file log of a sync session, in the order actions ran
+    pub fn get_sync_session_files(&self, session_id: i64) -> Result<Vec<SyncSessionFile>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+            SELECT id, session_id, relative_path, action, size, status, error, recorded_at
+            FROM sync_session_files
+            WHERE session_id = ?1
+            ORDER BY id ASC
+            "#,
+            )
+            .map_err(|e| AppError::Storage(format!("Failed to prepare query: {}", e)))?;
+
+        let files = stmt
+            .query_map(params![session_id], |row| {
+                let action_str: String = row.get("action")?;
+                let status_str: String = row.get("status")?;
+                Ok(SyncSessionFile {
+                    id: row.get("id")?,
+                    session_id: row.get("session_id")?,
+                    relative_path: row.get("relative_path")?,
+                    action: SyncFileAction::try_from(action_str.as_str())
+                        .unwrap_or(SyncFileAction::Upload),
+                    size: row.get("size")?,
+                    status: SyncFileStatus::try_from(status_str.as_str())
+                        .unwrap_or(SyncFileStatus::Failed),
+                    error: row.get("error")?,
+                    recorded_at: row.get("recorded_at")?,
+                })
+            })
+            .map_err(|e| AppError::Storage(format!("Failed to get sync session files: {}", e)))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(files)
+    }
 }
@@ -0,0 +1,216 @@
+use rusqlite::params;
+
+use super::DbManager;
+use crate::error::{AppError, Result};
+
+/// Total size (of the base64 data) the thumbnail cache may grow to before
+/// least recently used entries are evicted
+const THUMBNAIL_CACHE_MAX_BYTES: i64 = 100 * 1024 * 1024; // 100MB
+
+/// A cached, already-encoded thumbnail
+#[derive(Debug, Clone)]
+pub struct CachedThumbnail {
+    pub base64: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl DbManager {
+    /// Look up a cached thumbnail. Entries whose etag or object size no longer match
+    /// the source object are treated as missing. Hits refresh the LRU timestamp.
+    pub fn get_cached_thumbnail(
+        &self,
+        account_id: &str,
+        bucket: &str,
+        key: &str,
+        thumb_size: u32,
+        etag: &str,
+        object_size: i64,
+    ) -> Result<Option<CachedThumbnail>> {
+        let conn = self.get_conn()?;
+
+        let result = conn.query_row(
+            r#"
+            SELECT base64, width, height FROM thumbnail_cache
+            WHERE account_id = ?1 AND bucket = ?2 AND key = ?3 AND thumb_size = ?4
+              AND etag = ?5 AND object_size = ?6
+            "#,
+            params![account_id, bucket, key, thumb_size, etag, object_size],
+            |row| {
+                Ok(CachedThumbnail {
+                    base64: row.get(0)?,
+                    width: row.get(1)?,
+                    height: row.get(2)?,
+                })
+            },
+        );
+
+        let thumbnail = match result {
+            Ok(thumbnail) => thumbnail,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+            Err(e) => {
+                return Err(AppError::Storage(format!(
+                    "Failed to get cached thumbnail: {}",
+                    e
+                )))
+            }
+        };
+
+        conn.execute(
+            r#"
+            UPDATE thumbnail_cache SET last_accessed = ?5
+            WHERE account_id = ?1 AND bucket = ?2 AND key = ?3 AND thumb_size = ?4
+            "#,
+            params![
+                account_id,
+                bucket,
+                key,
+                thumb_size,
+                chrono::Utc::now().timestamp_millis()
+            ],
+        )
+        .map_err(|e| AppError::Storage(format!("Failed to touch cached thumbnail: {}", e)))?;
+
+        Ok(Some(thumbnail))
+    }
+
+    /// Store a thumbnail, replacing any stale entry for the same object, then evict
+    /// least recently used entries until the cache is back under its size cap
+    pub fn save_cached_thumbnail(
+        &self,
+        account_id: &str,
+        bucket: &str,
+        key: &str,
+        thumb_size: u32,
+        etag: &str,
+        object_size: i64,
+        thumbnail: &CachedThumbnail,
+    ) -> Result<()> {
+        let mut conn = self.get_conn()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Storage(format!("Failed to start transaction: {}", e)))?;
+
+        tx.execute(
+            r#"
+            INSERT INTO thumbnail_cache
+                (account_id, bucket, key, thumb_size, etag, object_size, base64, width, height, bytes, last_accessed)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            ON CONFLICT(account_id, bucket, key, thumb_size) DO UPDATE SET
+                etag = excluded.etag,
+                object_size = excluded.object_size,
+                base64 = excluded.base64,
+                width = excluded.width,
+                height = excluded.height,
+                bytes = excluded.bytes,
+                last_accessed = excluded.last_accessed
+            "#,
+            params![
+                account_id,
+                bucket,
+                key,
+                thumb_size,
+                etag,
+                object_size,
+                thumbnail.base64,
+                thumbnail.width,
+                thumbnail.height,
+                thumbnail.base64.len() as i64,
+                chrono::Utc::now().timestamp_millis()
+            ],
+        )
+        .map_err(|e| AppError::Storage(format!("Failed to cache thumbnail: {}", e)))?;
+
+        let total_bytes: i64 = tx
+            .query_row(
+                "SELECT COALESCE(SUM(bytes), 0) FROM thumbnail_cache",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| AppError::Storage(format!("Failed to size thumbnail cache: {}", e)))?;
+
+        if total_bytes > THUMBNAIL_CACHE_MAX_BYTES {
+            // Delete the oldest entries whose cumulative size covers the excess
+            tx.execute(
+                r#"
+                DELETE FROM thumbnail_cache WHERE rowid IN (
+                    SELECT rowid FROM (
+                        SELECT rowid, SUM(bytes) OVER (
+                            ORDER BY last_accessed, rowid
+                            ROWS BETWEEN UNBOUNDED PRECEDING AND 1 PRECEDING
+                        ) AS freed_before
+                        FROM thumbnail_cache
+                    )
+                    WHERE COALESCE(freed_before, 0) < ?1
+                )
+                "#,
+                params![total_bytes - THUMBNAIL_CACHE_MAX_BYTES],
+            )
+            .map_err(|e| AppError::Storage(format!("Failed to evict thumbnails: {}", e)))?;
+        }
+
+        tx.commit()
+            .map_err(|e| AppError::Storage(format!("Failed to commit thumbnail: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Remove cached thumbnails for specific keys and everything under the given prefixes.
+    /// Returns the number of entries removed.
+    pub fn invalidate_cached_thumbnails(
+        &self,
+        account_id: &str,
+        bucket: &str,
+        prefixes: &[String],
+        keys: &[String],
+    ) -> Result<usize> {
+        let mut conn = self.get_conn()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Storage(format!("Failed to start transaction: {}", e)))?;
+
+        let mut removed = 0;
+        for key in keys {
+            removed += tx
+                .execute(
+                    "DELETE FROM thumbnail_cache WHERE account_id = ?1 AND bucket = ?2 AND key = ?3",
+                    params![account_id, bucket, key],
+                )
+                .map_err(|e| AppError::Storage(format!("Failed to invalidate thumbnail: {}", e)))?;
+        }
+        for prefix in prefixes {
+            // substr avoids escaping LIKE wildcards in the prefix
+            removed += tx
+                .execute(
+                    r#"
+                    DELETE FROM thumbnail_cache
+                    WHERE account_id = ?1 AND bucket = ?2 AND substr(key, 1, length(?3)) = ?3
+                    "#,
+                    params![account_id, bucket, prefix],
+                )
+                .map_err(|e| AppError::Storage(format!("Failed to invalidate thumbnails: {}", e)))?;
+        }
+
+        tx.commit()
+            .map_err(|e| AppError::Storage(format!("Failed to commit thumbnail invalidation: {}", e)))?;
+
+        Ok(removed)
+    }
+
+    /// Remove cached thumbnails, for one account or all of them.
+    /// Returns the number of entries removed.
+    pub fn clear_thumbnail_cache(&self, account_id: Option<&str>) -> Result<usize> {
+        let conn = self.get_conn()?;
+
+        let removed = match account_id {
+            Some(account_id) => conn.execute(
+                "DELETE FROM thumbnail_cache WHERE account_id = ?1",
+                params![account_id],
+            ),
+            None => conn.execute("DELETE FROM thumbnail_cache", []),
+        }
+        .map_err(|e| AppError::Storage(format!("Failed to clear thumbnail cache: {}", e)))?;
+
+        Ok(removed)
+    }
+}
@@ -17,14 +17,70 @@ pub enum AppError {
 
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+
+    #[error("Access denied: {0}")]
+    AccessDenied(String),
+
+    #[error("Bucket not found: {0}")]
+    NoSuchBucket(String),
+
+    #[error("Object not found: {0}")]
+    NoSuchKey(String),
+
+    #[error("Bucket already exists: {0}")]
+    BucketAlreadyExists(String),
+
+    #[error("Request throttled, try again shortly: {0}")]
+    Throttled(String),
+
+    #[error("Object is archived and must be restored first: {0}")]
+    InvalidObjectState(String),
+
+    #[error("Not supported by this provider: {0}")]
+    NotImplemented(String),
+
+    #[error("Already exists: {0}")]
+    Conflict(String),
+
+    #[error("Cancelled")]
+    Cancelled,
 }
 
+impl AppError {
+    /// Name of the variant, so the frontend can tell errors apart without
+    /// parsing messages
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::Credential(_) => "Credential",
+            AppError::S3(_) => "S3",
+            AppError::Storage(_) => "Storage",
+            AppError::NotFound(_) => "NotFound",
+            AppError::InvalidInput(_) => "InvalidInput",
+            AppError::AccessDenied(_) => "AccessDenied",
+            AppError::NoSuchBucket(_) => "NoSuchBucket",
+            AppError::NoSuchKey(_) => "NoSuchKey",
+            AppError::BucketAlreadyExists(_) => "BucketAlreadyExists",
+            AppError::Throttled(_) => "Throttled",
+            AppError::InvalidObjectState(_) => "InvalidObjectState",
+            AppError::NotImplemented(_) => "NotImplemented",
+            AppError::Conflict(_) => "Conflict",
+            AppError::Cancelled => "Cancelled",
+        }
+    }
+}
+
+/// Sent to the frontend as `{ kind, message }`
 impl Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("AppError", 2)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
     }
 }
 
@@ -40,12 +96,44 @@ impl From<aws_sdk_s3::Error> for AppError {
     }
 }
 
+/// Map common S3 error codes to dedicated variants; anything else keeps the
+/// full debug output as an `S3` error. HEAD responses have no body and so no
+/// error code, so their 403 and 404 statuses are mapped as well.
 impl<E> From<aws_sdk_s3::error::SdkError<E>> for AppError
 where
-    E: std::fmt::Debug,
+    E: aws_sdk_s3::error::ProvideErrorMetadata + std::fmt::Debug,
 {
     fn from(err: aws_sdk_s3::error::SdkError<E>) -> Self {
-        AppError::S3(format!("{:?}", err))
+        use aws_sdk_s3::error::ProvideErrorMetadata;
+
+        let status = err.raw_response().map(|r| r.status().as_u16());
+        let message = || match err.message().or(err.code()) {
+            Some(message) => message.to_string(),
+            None => format!("HTTP {}", status.unwrap_or_default()),
+        };
+        match (err.code(), status) {
+            (Some("AccessDenied") | Some("AllAccessDisabled"), _) | (None, Some(403)) => {
+                AppError::AccessDenied(message())
+            }
+            (Some("NoSuchBucket"), _) => AppError::NoSuchBucket(message()),
+            (Some("NoSuchKey"), _) | (None | Some("NotFound"), Some(404)) => {
+                AppError::NoSuchKey(message())
+            }
+            (Some("BucketAlreadyExists") | Some("BucketAlreadyOwnedByYou"), _) => {
+                AppError::BucketAlreadyExists(message())
+            }
+            (
+                Some("SlowDown")
+                | Some("Throttling")
+                | Some("ThrottlingException")
+                | Some("RequestLimitExceeded")
+                | Some("TooManyRequests"),
+                _,
+            ) => AppError::Throttled(message()),
+            (Some("InvalidObjectState"), _) => AppError::InvalidObjectState(message()),
+            (Some("NotImplemented"), _) => AppError::NotImplemented(message()),
+            _ => AppError::S3(format!("{:?}", err)),
+        }
     }
 }
 
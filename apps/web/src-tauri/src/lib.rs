@@ -1,11 +1,18 @@
+mod cache;
 mod commands;
 mod credentials;
 mod db;
 mod error;
+mod progress;
 pub mod provider;
 mod s3;
 
+use cache::{BucketStatusCache, ListingCache, PublicUrlCache};
+use commands::credentials::AccountHealthState;
 use commands::duplicates::ScanState;
+use commands::objects::{
+    CopyState, FilterDeleteState, InventoryExportState, ResolveKeysState, TransferState,
+};
 use commands::sync::SyncState;
 use credentials::CredentialsManager;
 use db::DbManager;
@@ -26,8 +33,17 @@ pub fn run() {
         .manage(CredentialsManager::new())
         .manage(S3ClientManager::new())
         .manage(db_manager)
+        .manage(AccountHealthState::default())
+        .manage(BucketStatusCache::default())
+        .manage(CopyState::default())
+        .manage(FilterDeleteState::default())
+        .manage(InventoryExportState::default())
+        .manage(ListingCache::default())
+        .manage(PublicUrlCache::default())
+        .manage(ResolveKeysState::default())
         .manage(ScanState::default())
         .manage(SyncState::default())
+        .manage(TransferState::default())
         .invoke_handler(tauri::generate_handler![
             // Credentials commands
             commands::credentials::add_account,
@@ -36,14 +52,25 @@ pub fn run() {
             commands::credentials::remove_account,
             commands::credentials::update_account,
             commands::credentials::test_connection,
+            commands::credentials::diagnose_connection,
+            commands::credentials::probe_permissions,
+            commands::credentials::check_account_health,
+            commands::credentials::get_account_health_statuses,
+            commands::credentials::get_credential_backend,
+            commands::credentials::set_credential_backend,
+            commands::credentials::unlock_credential_store,
+            commands::credentials::set_secret_cache_ttl,
+            commands::credentials::set_debug_logging,
             // Bucket commands
             commands::buckets::list_buckets,
+            commands::buckets::list_all_buckets,
             commands::buckets::create_bucket,
             commands::buckets::delete_bucket,
             commands::buckets::get_bucket_config,
             commands::buckets::get_bucket_versioning,
             commands::buckets::put_bucket_versioning,
             commands::buckets::get_bucket_cors,
+            commands::buckets::validate_bucket_cors,
             commands::buckets::put_bucket_cors,
             commands::buckets::delete_bucket_cors,
             commands::buckets::get_bucket_lifecycle,
@@ -51,30 +78,73 @@ pub fn run() {
             commands::buckets::delete_bucket_lifecycle,
             commands::buckets::get_bucket_encryption,
             commands::buckets::get_bucket_logging,
+            commands::buckets::get_bucket_location,
+            commands::buckets::head_bucket,
             // Analytics commands
             commands::analytics::get_bucket_analytics,
+            commands::analytics::get_subfolder_analytics,
+            commands::analytics::estimate_storage_cost,
             // Object commands
             commands::objects::list_objects,
+            commands::objects::invalidate_listing_cache,
             commands::objects::get_object_metadata,
             commands::objects::upload_object,
+            commands::objects::upload_bytes,
             commands::objects::delete_objects,
+            commands::objects::delete_objects_by_filter,
+            commands::objects::restore_from_trash,
+            commands::objects::empty_trash,
+            commands::objects::resolve_keys,
+            commands::objects::cancel_resolve_keys,
+            commands::objects::export_object_inventory,
+            commands::objects::cancel_inventory_export,
             commands::objects::create_folder,
+            commands::objects::create_object,
+            commands::objects::put_object_retention,
+            commands::objects::put_object_legal_hold,
+            commands::objects::compute_object_hash,
             commands::objects::search_objects,
             commands::objects::download_object,
             commands::objects::generate_presigned_url,
+            commands::objects::list_shared_links,
+            commands::objects::delete_shared_link,
+            commands::objects::generate_share_manifest,
             commands::objects::rename_object,
             commands::objects::copy_objects,
             commands::objects::copy_objects_across_buckets,
+            commands::compare::compare_buckets,
+            commands::objects::cancel_copy,
             commands::objects::download_folder,
+            commands::objects::download_folder_tree,
+            commands::objects::cancel_transfer,
+            commands::objects::retry_failed_items,
+            commands::objects::replay_operation,
             commands::objects::update_object_metadata,
+            commands::objects::fix_content_types,
+            commands::objects::touch_object,
             commands::objects::list_object_versions,
             commands::objects::restore_object_version,
+            commands::objects::purge_prefix,
             commands::objects::get_object_tagging,
             commands::objects::put_object_tagging,
             commands::objects::delete_object_tagging,
+            commands::objects::apply_tags_to_prefix,
+            commands::objects::remove_tags_from_prefix,
             // Preview commands
             commands::preview::get_preview,
+            commands::preview::get_preview_url,
             commands::preview::get_thumbnail,
+            commands::preview::get_thumbnails_batch,
+            commands::preview::get_text_range,
+            commands::preview::get_image_info,
+            commands::preview::clear_thumbnail_cache,
+            // Settings commands
+            commands::settings::get_app_settings,
+            commands::settings::update_app_settings,
+            commands::settings::get_preview_settings,
+            commands::settings::set_preview_settings,
+            commands::settings::get_upload_settings,
+            commands::settings::set_upload_settings,
             // History commands
             commands::history::get_operations,
             commands::history::get_operation,
@@ -85,14 +155,18 @@ pub fn run() {
             commands::history::update_operation,
             // Duplicate detection commands
             commands::duplicates::start_duplicate_scan,
+            commands::duplicates::start_cross_bucket_duplicate_scan,
             commands::duplicates::cancel_duplicate_scan,
             commands::duplicates::get_scan,
             commands::duplicates::get_duplicate_groups,
             commands::duplicates::list_scans,
             commands::duplicates::delete_scan,
+            commands::duplicates::preview_duplicate_deletion,
             commands::duplicates::delete_duplicates,
+            commands::duplicates::verify_duplicate_group,
             // Sync commands
             commands::sync::create_sync_pair,
+            commands::sync::validate_sync_pair,
             commands::sync::get_sync_pair,
             commands::sync::list_sync_pairs,
             commands::sync::delete_sync_pair,
@@ -100,6 +174,7 @@ pub fn run() {
             commands::sync::start_sync,
             commands::sync::cancel_sync,
             commands::sync::get_sync_sessions,
+            commands::sync::get_sync_session_files,
         ])
         .setup(|app| {
             if cfg!(debug_assertions) {
@@ -109,6 +184,12 @@ pub fn run() {
                         .build(),
                 )?;
             }
+
+            // Flag accounts whose credentials no longer work
+            tauri::async_runtime::spawn(commands::credentials::check_all_accounts_health(
+                app.handle().clone(),
+            ));
+
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -0,0 +1,72 @@
+use std::time::{Duration, Instant};
+
+/// Default minimum time between progress events for one operation (at most 10 per second)
+pub const DEFAULT_EMIT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Coalesces progress events for a single transfer, scan or sync so the IPC
+/// bridge isn't flooded. Callers keep updating their counters and only emit
+/// when `ready` returns true; the latest values are sent with the next event.
+#[derive(Debug)]
+pub struct ProgressThrottle {
+    interval: Duration,
+    last_emit: Option<Instant>,
+}
+
+impl ProgressThrottle {
+    pub fn new() -> Self {
+        Self::with_interval(DEFAULT_EMIT_INTERVAL)
+    }
+
+    /// Use a custom interval; `Duration::ZERO` emits every update
+    pub fn with_interval(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_emit: None,
+        }
+    }
+
+    /// Whether an event should be emitted now. The first update is always
+    /// emitted, as is any update with `force` set (e.g. the final 100% event).
+    pub fn ready(&mut self, force: bool) -> bool {
+        let due = match self.last_emit {
+            Some(last) => last.elapsed() >= self.interval,
+            None => true,
+        };
+        if due || force {
+            self.last_emit = Some(Instant::now());
+        }
+        due || force
+    }
+}
+
+impl Default for ProgressThrottle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_update_is_emitted_and_later_ones_wait_for_the_interval() {
+        let mut throttle = ProgressThrottle::with_interval(Duration::from_secs(3600));
+        assert!(throttle.ready(false));
+        assert!(!throttle.ready(false));
+    }
+
+    #[test]
+    fn forced_update_is_always_emitted() {
+        let mut throttle = ProgressThrottle::with_interval(Duration::from_secs(3600));
+        assert!(throttle.ready(false));
+        assert!(throttle.ready(true));
+        assert!(!throttle.ready(false));
+    }
+
+    #[test]
+    fn zero_interval_emits_every_update() {
+        let mut throttle = ProgressThrottle::with_interval(Duration::ZERO);
+        assert!((0..5).all(|_| throttle.ready(false)));
+    }
+}
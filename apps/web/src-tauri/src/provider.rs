@@ -36,6 +36,24 @@ impl ProviderType {
             ProviderType::AwsS3 => "Amazon S3",
         }
     }
+
+    /// List price per GB-month (USD) by storage class, used for cost estimates.
+    /// AWS figures are us-east-1 first-tier prices; R2 has no per-region pricing.
+    pub fn default_storage_pricing(&self) -> &'static [(&'static str, f64)] {
+        match self {
+            ProviderType::CloudflareR2 => &[("STANDARD", 0.015), ("STANDARD_IA", 0.01)],
+            ProviderType::AwsS3 => &[
+                ("STANDARD", 0.023),
+                ("INTELLIGENT_TIERING", 0.023),
+                ("STANDARD_IA", 0.0125),
+                ("ONEZONE_IA", 0.01),
+                ("GLACIER_IR", 0.004),
+                ("GLACIER", 0.0036),
+                ("DEEP_ARCHIVE", 0.00099),
+                ("REDUCED_REDUNDANCY", 0.024),
+            ],
+        }
+    }
 }
 
 /// R2 location hints for bucket creation
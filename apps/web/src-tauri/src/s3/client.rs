@@ -1,12 +1,110 @@
 use crate::error::{AppError, Result};
 use crate::provider::ProviderType;
 use aws_credential_types::Credentials;
-use aws_sdk_s3::config::{BehaviorVersion, Region};
+use aws_sdk_s3::config::interceptors::{
+    BeforeDeserializationInterceptorContextRef, BeforeTransmitInterceptorContextRef,
+};
+use aws_sdk_s3::config::timeout::TimeoutConfig;
+use aws_sdk_s3::config::{BehaviorVersion, ConfigBag, Intercept, Region, RuntimeComponents};
+use aws_sdk_s3::error::BoxError;
 use aws_sdk_s3::Client;
+use aws_smithy_http_client::proxy::ProxyConfig;
+use aws_smithy_http_client::tls;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
+/// Default timeout for establishing a connection
+pub const DEFAULT_CONNECT_TIMEOUT_MS: u64 = 10_000;
+
+/// Per-account HTTP settings applied when building a client
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientOptions {
+    /// Limit on a whole S3 operation (including retries). Unset by default,
+    /// since large uploads, downloads and copies can legitimately run for hours.
+    pub operation_timeout_ms: Option<u64>,
+    pub connect_timeout_ms: Option<u64>,
+    /// Proxy for plain-HTTP endpoints (falls back to HTTP_PROXY)
+    pub http_proxy: Option<String>,
+    /// Proxy for HTTPS endpoints (falls back to HTTPS_PROXY)
+    pub https_proxy: Option<String>,
+    /// Comma-separated hosts that bypass the proxy (falls back to NO_PROXY)
+    pub no_proxy: Option<String>,
+}
+
+impl ClientOptions {
+    /// The explicitly configured proxy for a URL, preferring the one for its scheme
+    pub fn proxy_for(&self, url: &str) -> Option<&str> {
+        let proxy = if url.starts_with("http://") {
+            self.http_proxy.as_ref().or(self.https_proxy.as_ref())
+        } else {
+            self.https_proxy.as_ref().or(self.http_proxy.as_ref())
+        };
+        proxy.map(|p| p.trim()).filter(|p| !p.is_empty())
+    }
+
+    /// Build the proxy configuration for an endpoint. Explicit settings win;
+    /// otherwise the standard HTTP_PROXY/HTTPS_PROXY/NO_PROXY env vars are used.
+    fn proxy_config(&self, endpoint: &str) -> Result<ProxyConfig> {
+        let Some(proxy) = self.proxy_for(endpoint) else {
+            return Ok(ProxyConfig::from_env());
+        };
+
+        let mut config = ProxyConfig::all(proxy)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid proxy URL '{}': {}", proxy, e)))?;
+        if let Some(no_proxy) = self.no_proxy.as_deref().filter(|n| !n.trim().is_empty()) {
+            config = config.no_proxy(no_proxy);
+        }
+
+        Ok(config)
+    }
+}
+
+/// Log target used for wire-level request logging
+const WIRE_LOG_TARGET: &str = "s3_wire";
+
+/// Logs method, URL, status and request id of every S3 call.
+/// Never touches headers other than the request id, so auth never leaks.
+#[derive(Debug)]
+struct DebugLoggingInterceptor;
+
+impl Intercept for DebugLoggingInterceptor {
+    fn name(&self) -> &'static str {
+        "DebugLoggingInterceptor"
+    }
+
+    fn read_before_transmit(
+        &self,
+        context: &BeforeTransmitInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> std::result::Result<(), BoxError> {
+        let request = context.request();
+        log::info!(target: WIRE_LOG_TARGET, "--> {} {}", request.method(), request.uri());
+        Ok(())
+    }
+
+    fn read_after_transmit(
+        &self,
+        context: &BeforeDeserializationInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> std::result::Result<(), BoxError> {
+        let response = context.response();
+        let headers = response.headers();
+        log::info!(
+            target: WIRE_LOG_TARGET,
+            "<-- {} x-amz-request-id={} x-amz-id-2={}",
+            response.status().as_u16(),
+            headers.get("x-amz-request-id").unwrap_or("-"),
+            headers.get("x-amz-id-2").unwrap_or("-"),
+        );
+        Ok(())
+    }
+}
+
 /// Cache key for S3 clients - either account-level or bucket-specific
 #[derive(Hash, Eq, PartialEq, Clone)]
 struct ClientCacheKey {
@@ -22,6 +120,8 @@ pub struct S3ClientManager {
     bucket_regions: RwLock<HashMap<(String, String), String>>,
     /// Credentials cache for creating new clients
     credentials_cache: RwLock<HashMap<String, StoredCredentials>>,
+    /// Whether newly created clients get the wire logging interceptor
+    debug_logging: AtomicBool,
 }
 
 struct StoredCredentials {
@@ -30,6 +130,7 @@ struct StoredCredentials {
     secret_access_key: String,
     provider_type: ProviderType,
     default_region: Option<String>,
+    options: ClientOptions,
 }
 
 impl S3ClientManager {
@@ -38,9 +139,20 @@ impl S3ClientManager {
             clients: RwLock::new(HashMap::new()),
             bucket_regions: RwLock::new(HashMap::new()),
             credentials_cache: RwLock::new(HashMap::new()),
+            debug_logging: AtomicBool::new(false),
         }
     }
 
+    /// Toggle wire logging. Cached clients are evicted so new ones pick up the change.
+    pub async fn set_debug_logging(&self, enabled: bool) {
+        self.debug_logging.store(enabled, Ordering::Relaxed);
+        self.clients.write().await.clear();
+    }
+
+    pub fn debug_logging_enabled(&self) -> bool {
+        self.debug_logging.load(Ordering::Relaxed)
+    }
+
     /// Get or create a client for an account (used for account-level operations like list_buckets)
     pub async fn get_or_create_client(
         &self,
@@ -50,6 +162,7 @@ impl S3ClientManager {
         secret_access_key: &str,
         provider_type: ProviderType,
         region: Option<&str>,
+        options: &ClientOptions,
     ) -> Result<Arc<Client>> {
         let cache_key = ClientCacheKey {
             account_id: account_id.to_string(),
@@ -76,13 +189,21 @@ impl S3ClientManager {
                     secret_access_key: secret_access_key.to_string(),
                     provider_type,
                     default_region: region.map(|s| s.to_string()),
+                    options: options.clone(),
                 },
             );
         }
 
         // Create new client
         let client = self
-            .create_client(endpoint, access_key_id, secret_access_key, provider_type, region)
+            .create_client(
+                endpoint,
+                access_key_id,
+                secret_access_key,
+                provider_type,
+                region,
+                options,
+            )
             .await?;
         let client = Arc::new(client);
 
@@ -105,6 +226,7 @@ impl S3ClientManager {
         secret_access_key: &str,
         provider_type: ProviderType,
         region: Option<&str>,
+        options: &ClientOptions,
     ) -> Result<Arc<Client>> {
         // For non-AWS providers, just use the regular client
         if provider_type != ProviderType::AwsS3 {
@@ -116,6 +238,7 @@ impl S3ClientManager {
                     secret_access_key,
                     provider_type,
                     region,
+                    options,
                 )
                 .await;
         }
@@ -156,6 +279,7 @@ impl S3ClientManager {
                     secret_access_key: secret_access_key.to_string(),
                     provider_type,
                     default_region: region.map(|s| s.to_string()),
+                    options: options.clone(),
                 },
             );
         }
@@ -168,6 +292,7 @@ impl S3ClientManager {
                 secret_access_key,
                 provider_type,
                 effective_region,
+                options,
             )
             .await?;
         let client = Arc::new(client);
@@ -184,10 +309,13 @@ impl S3ClientManager {
     /// Store bucket region after detection (called when a redirect error occurs)
     pub async fn cache_bucket_region(&self, account_id: &str, bucket: &str, region: &str) {
         let mut regions = self.bucket_regions.write().await;
-        regions.insert(
+        let previous = regions.insert(
             (account_id.to_string(), bucket.to_string()),
             region.to_string(),
         );
+        if previous.as_deref() == Some(region) {
+            return;
+        }
 
         // Also remove any old cached client for this bucket (it has wrong region)
         let mut clients = self.clients.write().await;
@@ -224,6 +352,7 @@ impl S3ClientManager {
                 &creds.secret_access_key,
                 creds.provider_type,
                 Some(region),
+                &creds.options,
             )
             .await?;
         let client = Arc::new(client);
@@ -249,6 +378,7 @@ impl S3ClientManager {
         secret_access_key: &str,
         provider_type: ProviderType,
         region: Option<&str>,
+        options: &ClientOptions,
     ) -> Result<Client> {
         let credentials = Credentials::new(
             access_key_id,
@@ -261,11 +391,37 @@ impl S3ClientManager {
         // Use provided region or default for the provider
         let region_str = region.unwrap_or(provider_type.default_region());
 
+        // Bound connect time so a dead endpoint fails instead of hanging.
+        // Whole operations are only bounded when the account asks for it.
+        let connect_timeout_ms = options
+            .connect_timeout_ms
+            .unwrap_or(DEFAULT_CONNECT_TIMEOUT_MS);
+        let mut timeout_config =
+            TimeoutConfig::builder().connect_timeout(Duration::from_millis(connect_timeout_ms));
+        if let Some(operation_timeout_ms) = options.operation_timeout_ms {
+            timeout_config =
+                timeout_config.operation_timeout(Duration::from_millis(operation_timeout_ms));
+        }
+
         let mut config_builder = aws_sdk_s3::Config::builder()
             .behavior_version(BehaviorVersion::latest())
             .region(Region::new(region_str.to_string()))
             .credentials_provider(credentials)
-            .force_path_style(provider_type.force_path_style());
+            .force_path_style(provider_type.force_path_style())
+            .timeout_config(timeout_config.build());
+
+        // Build the HTTP client explicitly so proxy settings are honoured
+        let http_client = aws_smithy_http_client::Builder::new()
+            .tls_provider(tls::Provider::Rustls(
+                tls::rustls_provider::CryptoMode::AwsLc,
+            ))
+            .proxy_config(options.proxy_config(endpoint)?)
+            .build_https();
+        config_builder = config_builder.http_client(http_client);
+
+        if self.debug_logging_enabled() {
+            config_builder = config_builder.interceptor(DebugLoggingInterceptor);
+        }
 
         // Only set endpoint for providers that need it (R2, MinIO, etc.)
         // AWS S3 uses the default endpoint based on region
@@ -299,6 +455,7 @@ impl Clone for StoredCredentials {
             secret_access_key: self.secret_access_key.clone(),
             provider_type: self.provider_type,
             default_region: self.default_region.clone(),
+            options: self.options.clone(),
         }
     }
 }
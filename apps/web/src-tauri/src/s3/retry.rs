@@ -0,0 +1,106 @@
+use std::future::Future;
+use std::time::Duration;
+
+use aws_sdk_s3::config::http::HttpResponse;
+use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
+
+use crate::db::settings::AppSettings;
+
+/// Delay before the first retry; doubled on each further attempt
+const BASE_DELAY: Duration = Duration::from_millis(200);
+/// Upper bound for a single backoff delay
+const MAX_DELAY: Duration = Duration::from_secs(10);
+/// Upper bound for the configurable retry count
+pub const MAX_BULK_RETRIES: u32 = 10;
+
+/// Retries for requests made by bulk loops (copy, delete, hashing), on top of
+/// the SDK's own retries, so a throttled provider slows a batch down instead
+/// of failing its items
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::from_settings(&AppSettings::default())
+    }
+}
+
+impl RetryPolicy {
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            max_retries: settings.bulk_max_retries.min(MAX_BULK_RETRIES),
+        }
+    }
+
+    /// Exponential backoff with full jitter: a random delay up to the capped exponential
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let cap = BASE_DELAY
+            .saturating_mul(1u32 << attempt.min(16))
+            .min(MAX_DELAY);
+        cap.mul_f64(fastrand::f64())
+    }
+}
+
+/// Error codes that mean the same request may succeed if sent again. Also used
+/// for the per-key errors of a DeleteObjects response, which carry no status.
+pub fn is_retryable_code(code: &str) -> bool {
+    matches!(
+        code,
+        "SlowDown"
+            | "Throttling"
+            | "ThrottlingException"
+            | "RequestLimitExceeded"
+            | "TooManyRequests"
+            | "RequestTimeout"
+            | "InternalError"
+            | "ServiceUnavailable"
+    )
+}
+
+/// Throttling responses, 5xx errors and transport failures are worth retrying;
+/// anything else (access denied, missing keys) will fail the same way again
+pub fn is_retryable<E: ProvideErrorMetadata>(error: &SdkError<E, HttpResponse>) -> bool {
+    match error {
+        SdkError::TimeoutError(_) | SdkError::DispatchFailure(_) | SdkError::ResponseError(_) => {
+            true
+        }
+        SdkError::ServiceError(service_error) => {
+            let status = service_error.raw().status().as_u16();
+            status == 429 || status >= 500 || error.code().is_some_and(is_retryable_code)
+        }
+        _ => false,
+    }
+}
+
+/// Run `send` until it succeeds, fails with a non-retryable error, or the
+/// policy's retries are used up
+pub async fn with_retry<T, E, F, Fut>(
+    policy: RetryPolicy,
+    mut send: F,
+) -> Result<T, SdkError<E, HttpResponse>>
+where
+    E: ProvideErrorMetadata,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, SdkError<E, HttpResponse>>>,
+{
+    let mut attempt = 0;
+    loop {
+        match send().await {
+            Err(e) if attempt < policy.max_retries && is_retryable(&e) => {
+                let delay = policy.delay(attempt);
+                log::debug!(
+                    "Retrying after {:?} (attempt {} of {}): {:?}",
+                    delay,
+                    attempt + 1,
+                    policy.max_retries,
+                    e.code()
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
@@ -29,6 +29,7 @@ import { VersionHistoryDialog } from "./version-history-dialog";
 import { TagsEditorDialog } from "./tags-editor-dialog";
 import type { ObjectMetadata } from "@/lib/types";
 import { openUrl } from "@/lib/open-url";
+import { errorMessage } from "@/lib/utils";
 
 function formatFileSize(bytes: number): string {
   if (bytes === 0) return "0 B";
@@ -185,7 +186,7 @@ export function PreviewPanel() {
               </div>
               <p className="text-sm font-medium mb-1">Failed to load preview</p>
               <p className="text-xs text-center max-w-[200px] text-muted-foreground/70">
-                {String(error)}
+                {errorMessage(error)}
               </p>
             </div>
           ) : preview ? (
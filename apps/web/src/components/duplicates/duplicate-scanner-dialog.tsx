@@ -60,7 +60,7 @@ import type {
   ScanCompletePayload,
   ScanErrorPayload,
 } from "@/lib/types";
-import { cn } from "@/lib/utils";
+import { cn, errorMessage } from "@/lib/utils";
 
 function formatBytes(bytes: number): string {
   if (bytes === 0) return "0 B";
@@ -258,7 +258,7 @@ export function DuplicateScannerDialog({ open, onOpenChange }: DuplicateScannerD
       setScanError(null);
     },
     onError: (error) => {
-      setScanError(error instanceof Error ? error.message : "Failed to start scan");
+      setScanError(errorMessage(error) || "Failed to start scan");
     },
   });
 
@@ -56,7 +56,7 @@ import type {
   SyncCompletePayload,
   SyncErrorPayload,
 } from "@/lib/types";
-import { cn } from "@/lib/utils";
+import { cn, errorMessage } from "@/lib/utils";
 
 function formatBytes(bytes: number): string {
   if (bytes === 0) return "0 B";
@@ -254,15 +254,7 @@ export function FolderSyncDialog({ open, onOpenChange }: FolderSyncDialogProps)
       setActiveSessionId(sessionId);
     },
     onError: (error) => {
-      const errorMessage =
-        error instanceof Error
-          ? error.message
-          : typeof error === "string"
-            ? error
-            : typeof error === "object" && error !== null && "message" in error
-              ? String((error as { message: unknown }).message)
-              : "Failed to start sync";
-      setSyncError(errorMessage);
+      setSyncError(errorMessage(error) || "Failed to start sync");
       setActivePairId(null);
       setActiveSessionId(null);
       setViewMode("list");
@@ -309,7 +301,7 @@ export function FolderSyncDialog({ open, onOpenChange }: FolderSyncDialogProps)
       setPreview(result);
       setViewMode("preview");
     } catch (error) {
-      setSyncError(error instanceof Error ? error.message : "Failed to preview sync");
+      setSyncError(errorMessage(error) || "Failed to preview sync");
     } finally {
       setPreviewLoading(false);
     }
@@ -11,7 +11,7 @@ import type {
   DownloadFailedPayload,
 } from "@/lib/types";
 import { toast } from "sonner";
-import { parseS3Error } from "@/lib/utils";
+import { errorMessage, parseS3Error } from "@/lib/utils";
 
 export function useDownloadManager() {
   const processingRef = useRef(false);
@@ -99,7 +99,7 @@ export function useDownloadManager() {
       } catch (error) {
         console.error("[download] Download error:", error);
         if (!abortController.signal.aborted) {
-          setStatus(item.id, "failed", String(error));
+          setStatus(item.id, "failed", errorMessage(error));
         }
       } finally {
         abortControllersRef.current.delete(item.id);
@@ -205,7 +205,7 @@ export function useDownloadManager() {
         // Download completed - progress panel shows completion status
       } catch (error) {
         console.error("[download] Folder download error:", error);
-        setStatus(downloadId, "failed", String(error));
+        setStatus(downloadId, "failed", errorMessage(error));
         toast.error("Failed to download folder", {
           description: parseS3Error(error),
         });
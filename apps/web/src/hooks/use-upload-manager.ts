@@ -7,6 +7,7 @@ import { objects } from "@/lib/tauri";
 import { useUploadStore } from "@/lib/upload-store";
 import { useBrowserStore } from "@/lib/store";
 import { queryKeys } from "@/lib/queries";
+import { errorMessage } from "@/lib/utils";
 import type {
   UploadItem,
   UploadProgressPayload,
@@ -136,7 +137,7 @@ export function useUploadManager() {
       } catch (error) {
         console.error("[upload] Upload error:", error);
         if (!abortController.signal.aborted) {
-          setStatus(item.id, "failed", String(error));
+          setStatus(item.id, "failed", errorMessage(error));
         }
       } finally {
         // Clean up temp file (only if we created one)
@@ -158,6 +158,7 @@ export interface CopyMoveResult {
   objectsCopied: number;
   objectsDeleted: number;
   errors: CopyMoveError[];
+  operationBatchId?: string | null; // Pass to retry_failed_items to re-attempt the errors
 }
 
 export interface CopyMoveError {
@@ -247,6 +248,7 @@ export interface UploadFailedPayload {
 export interface DeleteResult {
   deleted: number;
   errors: DeleteError[];
+  operationBatchId?: string | null; // Pass to retry_failed_items to re-attempt the errors
 }
 
 export interface DeleteError {
@@ -288,6 +290,7 @@ export interface DownloadCompletedPayload {
   downloadId: string;
   key: string;
   path: string;
+  operationBatchId?: string | null; // Set when some files of a folder download failed
 }
 
 export interface DownloadFailedPayload {
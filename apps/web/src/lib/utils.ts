@@ -31,11 +31,41 @@ const S3_ERROR_MESSAGES: Record<string, string> = {
   IllegalLocationConstraintException: "The specified region is not valid for this operation.",
 };
 
+/**
+ * Error returned by a backend command
+ */
+export interface AppError {
+  kind: string;
+  message: string;
+}
+
+function isAppError(error: unknown): error is AppError {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    typeof (error as AppError).kind === "string" &&
+    typeof (error as AppError).message === "string"
+  );
+}
+
+/**
+ * Plain message for any error thrown by a backend command or the frontend
+ */
+export function errorMessage(error: unknown): string {
+  if (isAppError(error) || error instanceof Error) {
+    return error.message;
+  }
+  return String(error);
+}
+
 /**
  * Parse an S3 error and return a user-friendly message
  */
 export function parseS3Error(error: unknown): string {
-  const errorStr = String(error);
+  if (isAppError(error) && error.kind in S3_ERROR_MESSAGES) {
+    return S3_ERROR_MESSAGES[error.kind];
+  }
+  const errorStr = errorMessage(error);
 
   // Try to extract S3 error code from the error string
   // Pattern: code: Some("ErrorCode")